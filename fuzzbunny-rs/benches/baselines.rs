@@ -0,0 +1,54 @@
+//! Benchmarks of filtering the catalog against other fuzzy matching crates, as baselines.
+//!
+//! Each crate matches and ranks every line of the catalog for a search, single threaded. The
+//! crates score matches differently, so only their speeds are comparable, not their results.
+//!
+//! ```sh
+//! cargo bench --bench baselines
+//! ```
+
+mod common;
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use fuzzbunny_rs::{fuzzy_filter_with_parallelism, precompute_skips_for_items, Parallelism};
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher as _;
+use nucleo_matcher::pattern::{CaseMatching, Normalization, Pattern};
+
+fn filter_catalog(c: &mut Criterion) {
+    let lines = common::catalog();
+    let targets = precompute_skips_for_items(lines.iter().map(|line| line.as_str()));
+    let sequential = Parallelism::sequential();
+    let skim = SkimMatcherV2::default();
+    let mut nucleo = nucleo_matcher::Matcher::new(nucleo_matcher::Config::DEFAULT);
+
+    let mut group = c.benchmark_group("filter catalog");
+    group.throughput(Throughput::Elements(lines.len() as u64));
+    for search in common::CATALOG_SEARCHES {
+        group.bench_function(BenchmarkId::new("fuzzbunny", search), |b| {
+            b.iter(|| fuzzy_filter_with_parallelism(&targets, black_box(search), &sequential))
+        });
+        group.bench_function(BenchmarkId::new("fuzzy-matcher skim", search), |b| {
+            b.iter(|| {
+                let mut matches: Vec<(i64, &str)> = lines
+                    .iter()
+                    .filter_map(|line| Some((skim.fuzzy_match(line, black_box(search))?, line.as_str())))
+                    .collect();
+                matches.sort_by(|a, b| b.cmp(a));
+                matches
+            })
+        });
+        group.bench_function(BenchmarkId::new("nucleo", search), |b| {
+            b.iter(|| {
+                let pattern = Pattern::parse(black_box(search), CaseMatching::Ignore, Normalization::Smart);
+                pattern.match_list(&lines, &mut nucleo)
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, filter_catalog);
+criterion_main!(benches);
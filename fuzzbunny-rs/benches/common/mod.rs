@@ -0,0 +1,13 @@
+//! The corpus shared by the benchmarks.
+
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+
+/// Searches over the catalog, from prefixes of words to acronyms of whole titles.
+pub const CATALOG_SEARCHES: [&str; 5] = ["oliver", "alice", "mayflo", "declofusa", "audio"];
+
+/// Read the lines of the Project Gutenberg catalog used by the tests, skipping its attribution.
+pub fn catalog() -> Vec<String> {
+    let file = File::open(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/gutenberg-catalog.txt")).unwrap();
+    BufReader::new(file).lines().skip(1).collect::<Result<_, _>>().unwrap()
+}
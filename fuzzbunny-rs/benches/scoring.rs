@@ -0,0 +1,81 @@
+//! Benchmarks of the scoring core, per kind of match.
+//!
+//! ```sh
+//! cargo bench --bench scoring
+//! ```
+
+mod common;
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use fuzzbunny_rs::{fuzzy_filter, fuzzy_score_item, get_target_skips, precompute_skips_for_items, Target};
+
+/// Targets paired with a search that matches them in a particular way, or not at all.
+const CASES: [(&str, &str, &str); 5] = [
+    ("substring", "The Adventures of Sherlock Holmes", "sherlock"),
+    ("quoted substring", "The Adventures of Sherlock Holmes", "\"lock ho"),
+    ("word prefixes", "Declaration of Independence of the United States of America", "declofusa"),
+    ("camel case prefixes", "getElementsByClassName", "gebcn"),
+    ("non-match", "The Adventures of Sherlock Holmes", "moriarty"),
+];
+
+/// Score each case with its skip indices precomputed, and computed on the fly.
+fn score_cases(c: &mut Criterion) {
+    let mut group = c.benchmark_group("fuzzy_score_item");
+    for (name, target, search) in CASES {
+        let precomputed: Target<'_> = (target, Some(get_target_skips(target)));
+        let on_the_fly: Target<'_> = (target, None);
+
+        group.bench_with_input(BenchmarkId::new("precomputed skips", name), &precomputed, |b, target| {
+            b.iter(|| fuzzy_score_item(black_box(target), black_box(search)))
+        });
+        group.bench_with_input(BenchmarkId::new("on the fly skips", name), &on_the_fly, |b, target| {
+            b.iter(|| fuzzy_score_item(black_box(target), black_box(search)))
+        });
+    }
+    group.finish();
+}
+
+/// Score targets of growing length, where the match is at the end of the target.
+fn score_long_targets(c: &mut Criterion) {
+    let mut group = c.benchmark_group("long targets");
+    for words in [10, 100, 1000] {
+        let target = format!("{}the needle", "lorem ipsum dolor sit amet ".repeat(words / 5));
+        let precomputed: Target<'_> = (&target, Some(get_target_skips(&target)));
+
+        group.throughput(Throughput::Bytes(target.len() as u64));
+        for (name, search) in [("substring", "needle"), ("word prefixes", "thnee"), ("non-match", "haystack")] {
+            group.bench_with_input(BenchmarkId::new(name, target.len()), &precomputed, |b, target| {
+                b.iter(|| fuzzy_score_item(black_box(target), black_box(search)))
+            });
+        }
+    }
+    group.finish();
+}
+
+/// Filter the catalog, with skip indices precomputed and computed on the fly.
+fn filter_catalog(c: &mut Criterion) {
+    let lines = common::catalog();
+    let precomputed = precompute_skips_for_items(lines.iter().map(|line| line.as_str()));
+    let on_the_fly: Vec<Target<'_>> = lines.iter().map(|line| (line.as_str(), None)).collect();
+
+    let mut group = c.benchmark_group("fuzzy_filter catalog");
+    group.throughput(Throughput::Elements(lines.len() as u64));
+    for search in common::CATALOG_SEARCHES {
+        group.bench_with_input(BenchmarkId::new("precomputed skips", search), &precomputed, |b, targets| {
+            b.iter(|| fuzzy_filter(targets, black_box(search)))
+        });
+        group.bench_with_input(BenchmarkId::new("on the fly skips", search), &on_the_fly, |b, targets| {
+            b.iter(|| fuzzy_filter(targets, black_box(search)))
+        });
+    }
+    group.finish();
+
+    c.bench_function("precompute_skips_for_items catalog", |b| {
+        b.iter(|| precompute_skips_for_items(lines.iter().map(|line| line.as_str())))
+    });
+}
+
+criterion_group!(benches, score_cases, score_long_targets, filter_catalog);
+criterion_main!(benches);
@@ -0,0 +1,204 @@
+//! An interactive, fzf-like picker over lines read from stdin.
+//!
+//! Type to filter the lines, use the arrow keys to move the selection, and press enter to print
+//! the selected line to stdout (or escape / ctrl-c to cancel):
+//!
+//! ```sh
+//! git branch --all | cargo run --example picker
+//! ```
+//!
+//! Every keystroke starts a new search on a worker thread, streaming its matches back through
+//! [`fuzzy_filter_to_channel`] so the list updates while large inputs are still being scored.
+//! A search that is superseded by the next keystroke is cancelled by dropping its receiver.
+//! Once a search completes, typing more of the same search only rescores its matches.
+//!
+//! The terminal is driven with `stty` and ANSI escapes on `/dev/tty`, so this only runs on Unix.
+//! The terminal handling is shared with the interactive prompt of the `fuzzbunny` CLI.
+
+// Not every part of the terminal is used by the picker
+#[allow(dead_code)]
+#[path = "../src/bin/fuzzbunny/terminal.rs"]
+mod terminal;
+
+use std::fs::File;
+use std::io::{self, BufRead, Read, Write};
+use std::process::ExitCode;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread::{self, Scope};
+use std::time::Duration;
+
+use fuzzbunny_rs::{fuzzy_filter_to_channel, highlight, precompute_skips_for_items, FuzzyFilterResult, Target};
+use terminal::{Key, KeyDecoder, Terminal};
+
+/// The number of matches listed below the prompt.
+const VISIBLE_MATCHES: usize = 10;
+/// The capacity of each search's result channel.
+const CHANNEL_CAPACITY: usize = 1024;
+/// How long to wait for a key before checking for new matches.
+const INPUT_POLL_INTERVAL: Duration = Duration::from_millis(15);
+
+/// A search in progress, or completed once its worker hangs up.
+struct Search<'a> {
+    query: String,
+    receiver: Receiver<FuzzyFilterResult<'a>>,
+    matches: Vec<FuzzyFilterResult<'a>>,
+    complete: bool,
+}
+
+impl<'a> Search<'a> {
+    /// Start scoring a search on a worker thread.
+    ///
+    /// If `narrowed` is given, only those targets are scored rather than every target.
+    fn start<'scope>(
+        scope: &'scope Scope<'scope, '_>,
+        targets: &'scope [Target<'a>],
+        narrowed: Option<Vec<Target<'a>>>,
+        query: String,
+    ) -> Self {
+        let (sender, receiver) = mpsc::sync_channel(CHANNEL_CAPACITY);
+        let worker_query = query.clone();
+        scope.spawn(move || {
+            let targets = narrowed.as_deref().unwrap_or(targets);
+            // Fails once the receiver is dropped, which cancels the rest of the search
+            let _ = fuzzy_filter_to_channel(targets, &worker_query, &sender);
+        });
+        Search { query, receiver, matches: vec![], complete: false }
+    }
+
+    /// Collect the matches streamed so far.
+    ///
+    /// # Returns
+    ///
+    /// Whether there were any new matches, or the search completed.
+    fn collect(&mut self) -> bool {
+        if self.complete {
+            return false
+        }
+        let count = self.matches.len();
+        loop {
+            match self.receiver.try_recv() {
+                Ok(result) => self.matches.push(result),
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    self.complete = true;
+                    return true
+                },
+            }
+        }
+        if self.matches.len() == count {
+            return false
+        }
+        // Matches are streamed in input order, so keep them sorted for display
+        if !self.query.is_empty() {
+            self.matches.sort_by(|a, b| b.cmp(a));
+        }
+        true
+    }
+}
+
+/// Render the prompt and the best matches.
+fn render(tty: &mut File, search: &Search<'_>, total: usize, selected: usize) -> io::Result<()> {
+    let mut frame = String::from("\r\x1b[J");
+    let status = if search.complete { "" } else { " …" };
+    frame.push_str(&format!("> {}  \x1b[2m{}/{}{}\x1b[0m", search.query, search.matches.len(), total, status));
+
+    for (i, result) in search.matches.iter().take(VISIBLE_MATCHES).enumerate() {
+        frame.push_str(if i == selected { "\r\n\x1b[7m▶\x1b[0m " } else { "\r\n  " });
+        frame.push_str(&highlight::to_ansi(result, "1;31"));
+    }
+
+    // Move the cursor back to the end of the prompt
+    let lines = search.matches.len().min(VISIBLE_MATCHES);
+    if lines > 0 {
+        frame.push_str(&format!("\x1b[{lines}A"));
+    }
+    frame.push_str(&format!("\r\x1b[{}C", search.query.chars().count() + 2));
+    tty.write_all(frame.as_bytes())?;
+    tty.flush()
+}
+
+/// Run the picker until a line is selected, or it is cancelled.
+fn pick<'a>(targets: &[Target<'a>]) -> io::Result<Option<&'a str>> {
+    let mut terminal = Terminal::enter()?;
+
+    // Read keys on their own thread, so matches can be rendered while waiting for input
+    let (key_sender, keys) = mpsc::channel();
+    let mut input = terminal.tty.try_clone()?;
+    thread::spawn(move || {
+        let mut decoder = KeyDecoder::default();
+        let mut buffer = [0; 64];
+        while let Ok(len) = input.read(&mut buffer) {
+            for key in decoder.decode(&buffer[..len]) {
+                if key_sender.send(key).is_err() {
+                    return
+                }
+            }
+        }
+    });
+
+    thread::scope(|scope| {
+        let mut search = Search::start(scope, targets, None, String::new());
+        let mut selected = 0;
+        let mut dirty = true;
+
+        loop {
+            dirty |= search.collect();
+            if dirty {
+                render(&mut terminal.tty, &search, targets.len(), selected)?;
+                dirty = false;
+            }
+
+            let key = match keys.recv_timeout(INPUT_POLL_INTERVAL) {
+                Ok(key) => key,
+                Err(mpsc::RecvTimeoutError::Timeout) => continue,
+                Err(mpsc::RecvTimeoutError::Disconnected) => return Ok(None),
+            };
+
+            let mut query = search.query.clone();
+            match key {
+                Key::Char(char) => query.push(char),
+                Key::Backspace => {
+                    query.pop();
+                },
+                Key::Up => selected = selected.saturating_sub(1),
+                Key::Down => selected = (selected + 1).min(search.matches.len().min(VISIBLE_MATCHES).saturating_sub(1)),
+                Key::Enter => return Ok(search.matches.get(selected).map(|result| result.item)),
+                Key::Cancel => return Ok(None),
+            }
+            dirty = true;
+
+            if query != search.query {
+                // The matches of a longer search are a subset of the matches of its prefix, so
+                // a completed search only needs its own matches rescored
+                let narrowed = (search.complete && !search.query.is_empty() && query.starts_with(&search.query))
+                    .then(|| precompute_skips_for_items(search.matches.iter().map(|result| result.item)));
+                // Replacing the search drops its receiver, cancelling it if still in progress
+                search = Search::start(scope, targets, narrowed, query);
+                selected = 0;
+            }
+        }
+    })
+}
+
+fn main() -> ExitCode {
+    let lines = match io::stdin().lock().lines().collect::<io::Result<Vec<String>>>() {
+        Ok(lines) => lines,
+        Err(err) => {
+            eprintln!("error: failed to read stdin: {err}");
+            return ExitCode::from(2)
+        },
+    };
+    let targets = precompute_skips_for_items(lines.iter().map(String::as_str));
+
+    match pick(&targets) {
+        Ok(Some(line)) => {
+            println!("{line}");
+            ExitCode::SUCCESS
+        },
+        Ok(None) => ExitCode::from(1),
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::from(2)
+        },
+    }
+}
@@ -0,0 +1,10 @@
+//! The shared library build of fuzzbunny-rs, exporting its C ABI (see `fuzzbunny_rs::cabi`), and
+//! with the `wasm` feature its WebAssembly bindings.
+//!
+//! fuzzbunny-rs itself is only built as an `rlib`, so it can be used without `std`. This crate
+//! builds it as a `cdylib`, e.g. with `cargo build --release -p fuzzbunny-ffi` for the C ABI, or
+//! `cargo build --release -p fuzzbunny-ffi --target wasm32-unknown-unknown --features wasm` for
+//! the WebAssembly bindings, which are then processed with `wasm-bindgen`.
+
+// Link the library, so its exported functions are included in the shared library
+pub use fuzzbunny_rs::cabi;
@@ -0,0 +1,46 @@
+/* C ABI of fuzzbunny-rs, exported when built as a cdylib with the `cabi` feature. */
+
+#ifndef FUZZBUNNY_H
+#define FUZZBUNNY_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct FuzzbunnyIndex FuzzbunnyIndex;
+typedef struct FuzzbunnyResults FuzzbunnyResults;
+
+/* A matched range of an item, in bytes. */
+typedef struct FuzzbunnyRange {
+    size_t start;
+    size_t len;
+} FuzzbunnyRange;
+
+/* A single match of a search. `ranges` is owned by the result set. */
+typedef struct FuzzbunnyMatch {
+    size_t item_index;
+    uint32_t score;
+    const FuzzbunnyRange *ranges;
+    size_t ranges_len;
+} FuzzbunnyMatch;
+
+/* Copy `count` NUL-terminated UTF-8 strings into a new index. Returns NULL on invalid input. */
+FuzzbunnyIndex *fuzzbunny_index_new(const char *const *items, size_t count);
+void fuzzbunny_index_free(FuzzbunnyIndex *index);
+
+/* Search an index, returning the matches from best to worst. Returns NULL on invalid input. */
+FuzzbunnyResults *fuzzbunny_index_search(const FuzzbunnyIndex *index, const char *search);
+
+size_t fuzzbunny_results_len(const FuzzbunnyResults *results);
+/* Returns NULL if `i` is out of bounds. */
+const FuzzbunnyMatch *fuzzbunny_results_get(const FuzzbunnyResults *results, size_t i);
+void fuzzbunny_results_free(FuzzbunnyResults *results);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* FUZZBUNNY_H */
@@ -0,0 +1,143 @@
+//! Alternate searchable strings for corpus items.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::budget::MemoryAccounting;
+use crate::corpus::CorpusItem;
+use crate::{collect_matches_by, FuzzyFilterResult, ItemId};
+
+/// The aliases of every item in a corpus.
+///
+/// Each distinct alias text has a single entry holding the IDs of every item with that alias,
+/// the same as the texts of the corpus itself.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AliasIndex {
+    /// The distinct alias texts, including tombstones of removed aliases.
+    entries: Vec<CorpusItem>,
+    /// The position of each alias text in `entries`.
+    texts: HashMap<Arc<str>, usize>,
+    /// The positions in `entries` of the aliases of each item.
+    by_item: HashMap<ItemId, Vec<usize>>,
+    /// The memory usage of the derived search data of the aliases, which is never evicted.
+    accounting: MemoryAccounting,
+}
+
+impl AliasIndex {
+    /// Whether no item has any aliases.
+    #[inline]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.by_item.is_empty()
+    }
+
+    /// The memory used by the derived search data of the aliases, in bytes.
+    pub(crate) fn derived_memory(&self) -> usize {
+        self.accounting.used()
+    }
+
+    /// Add an alias to an item.
+    ///
+    /// # Returns
+    ///
+    /// Whether the item didn't already have the alias.
+    pub(crate) fn add(&mut self, id: ItemId, alias: String) -> bool {
+        let position = match self.texts.get(alias.as_str()) {
+            Some(&position) => {
+                if self.entries[position].ids.contains(&id) {
+                    return false
+                }
+                self.entries[position].ids.push(id);
+                position
+            },
+            None => {
+                let entry = CorpusItem::new(id, alias);
+                self.accounting.add(entry.derived.size());
+                let position = self.entries.len();
+                self.texts.insert(Arc::clone(&entry.text), position);
+                self.entries.push(entry);
+                position
+            },
+        };
+        self.by_item.entry(id).or_default().push(position);
+        true
+    }
+
+    /// Remove an alias from an item.
+    ///
+    /// # Returns
+    ///
+    /// Whether the item had the alias.
+    pub(crate) fn remove(&mut self, id: ItemId, alias: &str) -> bool {
+        let Some(&position) = self.texts.get(alias) else { return false };
+        let Some(positions) = self.by_item.get_mut(&id) else { return false };
+        let Some(index) = positions.iter().position(|&item_position| item_position == position) else {
+            return false
+        };
+
+        positions.remove(index);
+        if positions.is_empty() {
+            self.by_item.remove(&id);
+        }
+        self.detach(id, position);
+        true
+    }
+
+    /// Remove every alias of an item.
+    pub(crate) fn remove_item(&mut self, id: ItemId) {
+        for position in self.by_item.remove(&id).unwrap_or_default() {
+            self.detach(id, position);
+        }
+    }
+
+    /// Remove an item from the entry of one of its aliases, leaving a tombstone if no other
+    /// item has the alias.
+    fn detach(&mut self, id: ItemId, position: usize) {
+        let entry = &mut self.entries[position];
+        entry.ids.retain(|&entry_id| entry_id != id);
+        if entry.ids.is_empty() {
+            let text = std::mem::replace(&mut entry.text, Arc::from(""));
+            let derived = std::mem::take(&mut entry.derived);
+            self.texts.remove(&text);
+            self.accounting.release(derived.size());
+        }
+    }
+
+    /// Remove the tombstones of removed aliases.
+    pub(crate) fn compact(&mut self) {
+        let mut new_positions = vec![usize::MAX; self.entries.len()];
+        let mut next = 0;
+        for (position, entry) in self.entries.iter().enumerate() {
+            if !entry.is_tombstone() {
+                new_positions[position] = next;
+                next += 1;
+            }
+        }
+
+        self.entries.retain(|entry| !entry.is_tombstone());
+        for position in self.texts.values_mut().chain(self.by_item.values_mut().flatten()) {
+            *position = new_positions[*position];
+        }
+    }
+
+    /// The aliases of an item, in the order they were added.
+    pub(crate) fn aliases(&self, id: ItemId) -> Vec<&str> {
+        self.by_item
+            .get(&id)
+            .map(|positions| positions.iter().map(|&position| &*self.entries[position].text).collect())
+            .unwrap_or_default()
+    }
+
+    /// Score every alias against a lower case search string.
+    ///
+    /// # Returns
+    ///
+    /// The IDs of the items with each matching alias, along with the match of the alias.
+    pub(crate) fn search(&self, search: &str, parallel: bool) -> Vec<(&[ItemId], FuzzyFilterResult<'_>)> {
+        collect_matches_by(&self.entries, parallel, |entry| {
+            if entry.is_tombstone() {
+                return None
+            }
+            entry.filter(search, &self.accounting).map(|result| (entry.ids.as_slice(), result))
+        })
+    }
+}
@@ -0,0 +1,109 @@
+//! A fast path for autocompleting plain prefix queries.
+
+use crate::pipeline::Derived;
+use crate::normalize::map_ranges;
+use crate::{fuzzy_filter, precompute_skips_for_items, FuzzyFilterResult, Range, Target};
+
+/// An index of items for latency-critical autocompletion (e.g. a search box that completes as
+/// the user types).
+///
+/// The lower case form of every item is kept sorted, so the items starting with a query are
+/// found with a binary search. These are always the best matches for a plain prefix query (one
+/// without whitespace or quotes), as a substring match at the start of an item outscores any
+/// other match, and they all score the same. So if at least `limit` items start with the query,
+/// [`AutocompleteIndex::search`] skips the general fuzzy matcher entirely, and otherwise falls
+/// back to [`fuzzy_filter`]. Either way, the results are the same as the first `limit` results
+/// of `fuzzy_filter`.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::AutocompleteIndex;
+///
+/// let index = AutocompleteIndex::new(["Paris", "Parma", "Perth", "Lima", "Sao Paulo"]);
+///
+/// // Completed from the prefix index
+/// let results = index.search("par", 2);
+/// let items: Vec<&str> = results.iter().map(|res| res.item).collect();
+/// assert_eq!(items, vec!["Paris", "Parma"]);
+///
+/// // Too few items start with "pa", so this falls back to fuzzy matching
+/// let results = index.search("pa", 3);
+/// let items: Vec<&str> = results.iter().map(|res| res.item).collect();
+/// assert_eq!(items, vec!["Paris", "Parma", "Sao Paulo"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct AutocompleteIndex<'a> {
+    /// Every item with its skip indices, in input order, for the fallback path.
+    targets: Vec<Target<'a>>,
+    /// The lower case form of every item (with offsets back to the item, if lower casing changed
+    /// its length), with the item's index in `targets`, sorted by the lower case form.
+    sorted: Vec<(Derived, usize)>,
+}
+
+impl<'a> AutocompleteIndex<'a> {
+    /// Build an index over a set of items.
+    pub fn new(items: impl IntoIterator<Item = &'a str>) -> Self {
+        let targets = precompute_skips_for_items(items);
+        let mut sorted: Vec<(Derived, usize)> =
+            targets.iter().enumerate().map(|(index, (item, _))| (Derived::new(item), index)).collect();
+        sorted.sort_unstable_by(|(a, a_index), (b, b_index)| a.lower.cmp(&b.lower).then(a_index.cmp(b_index)));
+        AutocompleteIndex { targets, sorted }
+    }
+
+    /// The number of indexed items.
+    pub fn len(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// Whether there are no indexed items.
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+
+    /// Whether a search can be completed from the prefix index, rather than by fuzzy matching.
+    fn is_plain_prefix(search: &str) -> bool {
+        !search.is_empty() && !search.starts_with('"') && !search.contains(char::is_whitespace)
+    }
+
+    /// Search the items, returning up to `limit` of the best matches.
+    ///
+    /// # Returns
+    ///
+    /// The first `limit` results [`fuzzy_filter`] would return for the items.
+    pub fn search(&self, search: &str, limit: usize) -> Vec<FuzzyFilterResult<'a>> {
+        let search_lower_cased = search.trim().to_lowercase();
+        if Self::is_plain_prefix(&search_lower_cased) {
+            let key = search_lower_cased.as_str();
+            // Every key starting with the search sorts directly after the search itself
+            let start = self.sorted.partition_point(|(derived, _)| derived.lower.as_str() < key);
+            let len = self.sorted[start..].partition_point(|(derived, _)| derived.lower.starts_with(key));
+            if len >= limit {
+                return self.complete(&self.sorted[start..start + len], key.len(), limit)
+            }
+        }
+
+        let mut results = fuzzy_filter(&self.targets, search);
+        results.truncate(limit);
+        results
+    }
+
+    /// Build the results for the items whose lower case forms start with a search of
+    /// `search_len` bytes, of which there are at least `limit`.
+    fn complete(&self, matches: &[(Derived, usize)], search_len: usize, limit: usize) -> Vec<FuzzyFilterResult<'a>> {
+        // Every match scores the same, so they're ranked by the item text, and then input order
+        let mut ranked: Vec<(&'a str, usize, &Derived)> =
+            matches.iter().map(|(derived, index)| (self.targets[*index].0, *index, derived)).collect();
+        if limit < ranked.len() && limit > 0 {
+            ranked.select_nth_unstable_by_key(limit - 1, |&(item, index, _)| (item, index));
+        }
+        ranked.truncate(limit);
+        ranked.sort_unstable_by_key(|&(item, index, _)| (item, index));
+
+        let score = Range(0, search_len).get_score(false);
+        ranked
+            .into_iter()
+            .map(|(item, _, derived)| FuzzyFilterResult::matched(item, score, map_ranges(vec![Range(0, search_len)], &derived.offsets)))
+            .collect()
+    }
+}
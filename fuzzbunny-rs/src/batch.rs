@@ -0,0 +1,74 @@
+//! Searching the same targets for many searches at once.
+
+use crate::pipeline::Derived;
+use crate::normalize::map_ranges;
+use crate::prelude::*;
+use crate::{
+    collect_indexed_matches_by, instrument, is_lower_case_aligned, is_parallel_workload, map_items, score_prepared,
+    sort_results, FuzzyFilterResult, Target,
+};
+
+/// The lower case form and skip indices of a target, keeping any precomputed skip indices.
+fn derive_target(target: &Target<'_>) -> Derived {
+    if let Some(skips) = &target.1 {
+        let lower = target.0.to_lowercase();
+        if is_lower_case_aligned(target.0, &lower) {
+            return Derived { lower, skips: skips.clone(), offsets: vec![] }
+        }
+    }
+    Derived::new(target.0)
+}
+
+/// Search a slice of [`Target`]s for each of several searches, and return the filtered and
+/// sorted vector of [`FuzzyFilterResult`] of each search, in the order of `searches`.
+///
+/// This returns the same results as calling [`fuzzy_filter`](crate::fuzzy_filter) for each
+/// search, but each target is only lower cased once for every search, and the searches are
+/// scored in parallel as well as the targets, so a batch of many searches (e.g. in an offline
+/// tagging pipeline) keeps every thread busy.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{fuzzy_filter_batch, precompute_skips_for_items};
+///
+/// let targets = precompute_skips_for_items(vec!["pineapple", "apple", "banana"]);
+///
+/// let results = fuzzy_filter_batch(&targets, &["app", "ban", "kiwi"]);
+/// let items: Vec<Vec<&str>> = results.iter().map(|results| results.iter().map(|res| res.item).collect()).collect();
+/// assert_eq!(items, vec![vec!["apple", "pineapple"], vec!["banana"], vec![]]);
+/// ```
+pub fn fuzzy_filter_batch<'a>(items: &[Target<'a>], searches: &[&str]) -> Vec<Vec<FuzzyFilterResult<'a>>> {
+    // Each search with its lower case form
+    let searches_lower_cased: Vec<(&str, String)> = searches.iter().map(|&search| (search, search.trim().to_lowercase())).collect();
+    let total_search_len = searches_lower_cased.iter().map(|(_, lower)| lower.len()).sum();
+    // The work of every search together, as if each target were repeated for every search
+    let parallel = is_parallel_workload(items.len(), |i| items[i].0.len() * searches.len(), total_search_len);
+    let span = instrument::FilterSpan::enter("fuzzy_filter_batch", items.len(), total_search_len, parallel);
+
+    let stage = instrument::Stage::start("prepare");
+    let derived = map_items(items, parallel, derive_target);
+    stage.finish(items.len(), derived.len());
+
+    let stage = instrument::Stage::start("score");
+    let searches_parallel = parallel && searches.len() > 1;
+    let results = map_items(&searches_lower_cased, searches_parallel, |(search, search_lower_cased)| {
+        // Targets are also scored in parallel for long enough searches, nested in the searches
+        let parallel = is_parallel_workload(items.len(), |i| items[i].0.len(), search_lower_cased.len());
+        let mut results = collect_indexed_matches_by(items, parallel, |index, target| {
+            let derived = &derived[index];
+            let string_score = score_prepared(target.0, Some(&derived.lower), Some(&derived.skips), search_lower_cased)?;
+            let ranges = map_ranges(string_score.ranges, &derived.offsets);
+            Some(FuzzyFilterResult::matched(target.0, string_score.score, ranges).with_index(index))
+        });
+        if !search.is_empty() {
+            sort_results(&mut results, parallel);
+        }
+        results
+    });
+    let matches = results.iter().map(Vec::len).sum();
+    stage.finish(items.len() * searches.len(), matches);
+
+    span.finish(matches);
+    results
+}
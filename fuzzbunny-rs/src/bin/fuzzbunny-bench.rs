@@ -0,0 +1,272 @@
+//! Benchmark the search modes of the crate over a corpus file, to choose settings empirically.
+//!
+//! This is only built with the `cli` feature:
+//!
+//! ```sh
+//! cargo run --release --features cli --bin fuzzbunny-bench -- tests/gutenberg-catalog.txt
+//! ```
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::process::ExitCode;
+use std::sync::mpsc::sync_channel;
+use std::task::Poll;
+use std::time::{Duration, Instant};
+
+use fuzzbunny_rs::{fuzzy_filter, fuzzy_filter_to_channel, precompute_skips_for_items, Corpus, ResumableSearch, Target};
+
+const USAGE: &str = "\
+Usage: fuzzbunny-bench [OPTIONS] <CORPUS>
+
+Run a set of queries over a corpus file (one item per line) with each search mode, and report
+the throughput, latency percentiles and memory usage of each.
+
+Options:
+  -q, --queries <FILE>    Read the queries from a file, one per line [default: sampled from the corpus]
+  -i, --iterations <N>    Run the query set N times per mode [default: 3]
+  -m, --mode <MODE>       Only run a mode, and may be repeated [default: every mode]
+  -h, --help              Print this help
+
+Modes:
+  filter         fuzzy_filter with precomputed skip indices
+  filter-lazy    fuzzy_filter computing skip indices on the fly
+  corpus         Corpus::search
+  resumable      ResumableSearch, polled to completion in batches of 4096
+  channel        fuzzy_filter_to_channel, drained on another thread";
+
+/// Every mode, in the order they are run.
+const MODES: [&str; 5] = ["filter", "filter-lazy", "corpus", "resumable", "channel"];
+
+/// The number of queries sampled from the corpus if no query file is given.
+const SAMPLED_QUERIES: usize = 20;
+/// The number of targets polled at a time by the `resumable` mode.
+const RESUMABLE_BATCH_SIZE: usize = 4096;
+
+/// The parsed command line arguments.
+struct Args {
+    corpus: String,
+    queries: Option<String>,
+    iterations: usize,
+    modes: Vec<String>,
+}
+
+impl Args {
+    /// Parse the command line arguments.
+    ///
+    /// # Returns
+    ///
+    /// The arguments, or [`None`] if help was requested.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message describing the invalid argument.
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut corpus = None;
+        let mut queries = None;
+        let mut iterations = 3;
+        let mut modes = vec![];
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => return Ok(None),
+                "-q" | "--queries" => queries = Some(args.next().ok_or("--queries requires a file")?),
+                "-i" | "--iterations" => {
+                    let value = args.next().ok_or("--iterations requires a value")?;
+                    iterations = value.parse().ok().filter(|&n| n > 0).ok_or(format!("invalid iterations '{value}'"))?;
+                },
+                "-m" | "--mode" => {
+                    let mode = args.next().ok_or("--mode requires a value")?;
+                    if !MODES.contains(&mode.as_str()) {
+                        return Err(format!("unknown mode '{mode}'"))
+                    }
+                    modes.push(mode);
+                },
+                _ if arg.starts_with('-') => return Err(format!("unknown option '{arg}'")),
+                _ if corpus.is_none() => corpus = Some(arg),
+                _ => return Err(format!("unexpected argument '{arg}'")),
+            }
+        }
+
+        if modes.is_empty() {
+            modes = MODES.iter().map(|mode| mode.to_string()).collect();
+        }
+        Ok(Some(Args { corpus: corpus.ok_or("missing CORPUS")?, queries, iterations, modes }))
+    }
+}
+
+fn read_lines(path: &str) -> io::Result<Vec<String>> {
+    let lines = BufReader::new(File::open(path)?).lines().collect::<io::Result<Vec<String>>>()?;
+    Ok(lines.into_iter().filter(|line| !line.trim().is_empty()).collect())
+}
+
+/// Sample queries from evenly spaced items of the corpus: the start of the first word, and the
+/// initials of the first two words (e.g. "ali" and "a w" for "Alice in Wonderland").
+fn sample_queries(lines: &[String]) -> Vec<String> {
+    let step = (lines.len() / (SAMPLED_QUERIES / 2)).max(1);
+    let mut queries = vec![];
+    for line in lines.iter().step_by(step).take(SAMPLED_QUERIES / 2) {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        if let Some(word) = words.first() {
+            queries.push(word.chars().take(3).collect::<String>().to_lowercase());
+        }
+        if let [first, second, ..] = words.as_slice() {
+            let initial = |word: &str| word.chars().next().unwrap().to_lowercase().to_string();
+            queries.push(format!("{} {}", initial(first), initial(second)));
+        }
+    }
+    queries
+}
+
+/// The latencies and match counts of every query run with a mode.
+struct Measurements {
+    latencies: Vec<Duration>,
+    matches: usize,
+}
+
+impl Measurements {
+    fn percentile(&self, percentile: usize) -> Duration {
+        let index = (self.latencies.len() * percentile / 100).min(self.latencies.len() - 1);
+        self.latencies[index]
+    }
+}
+
+/// Run every query with a search function, `iterations` times.
+fn measure(queries: &[String], iterations: usize, mut search: impl FnMut(&str) -> usize) -> Measurements {
+    let mut latencies = Vec::with_capacity(queries.len() * iterations);
+    let mut matches = 0;
+    for _ in 0..iterations {
+        for query in queries {
+            let start = Instant::now();
+            matches += search(query);
+            latencies.push(start.elapsed());
+        }
+    }
+    latencies.sort_unstable();
+    Measurements { latencies, matches }
+}
+
+/// The memory used by a set of targets, in bytes.
+fn target_memory(targets: &[Target<'_>]) -> usize {
+    targets
+        .iter()
+        .map(|(text, skips)| text.len() + skips.as_ref().map_or(0, |skips| skips.len() * std::mem::size_of::<usize>()))
+        .sum()
+}
+
+/// Run a mode over the corpus.
+///
+/// # Returns
+///
+/// The measurements, and the memory used by the data the mode searches.
+fn run_mode(mode: &str, lines: &[String], queries: &[String], iterations: usize) -> (Measurements, usize) {
+    let items = || lines.iter().map(String::as_str);
+    match mode {
+        "filter" => {
+            let targets = precompute_skips_for_items(items());
+            (measure(queries, iterations, |query| fuzzy_filter(&targets, query).len()), target_memory(&targets))
+        },
+        "filter-lazy" => {
+            let targets: Vec<Target<'_>> = items().map(|item| (item, None)).collect();
+            (measure(queries, iterations, |query| fuzzy_filter(&targets, query).len()), target_memory(&targets))
+        },
+        "corpus" => {
+            let corpus: Corpus = items().collect();
+            let stats = corpus.stats();
+            (measure(queries, iterations, |query| corpus.search(query).len()), stats.text_bytes + stats.derived_bytes)
+        },
+        "resumable" => {
+            let targets = precompute_skips_for_items(items());
+            let measurements = measure(queries, iterations, |query| {
+                let mut search = ResumableSearch::new(&targets, query, RESUMABLE_BATCH_SIZE);
+                loop {
+                    if let Poll::Ready(results) = search.poll() {
+                        return results.len()
+                    }
+                }
+            });
+            (measurements, target_memory(&targets))
+        },
+        "channel" => {
+            let targets = precompute_skips_for_items(items());
+            let measurements = measure(queries, iterations, |query| {
+                let (sender, receiver) = sync_channel(1024);
+                let targets = &targets;
+                std::thread::scope(|scope| {
+                    // The sender is dropped once every target is scored, ending the iteration
+                    scope.spawn(move || fuzzy_filter_to_channel(targets, query, &sender));
+                    receiver.iter().count()
+                })
+            });
+            (measurements, target_memory(&targets))
+        },
+        _ => unreachable!("modes are validated when parsing arguments"),
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.3}ms", duration.as_secs_f64() * 1000.0)
+}
+
+fn format_bytes(bytes: usize) -> String {
+    format!("{:.1}MiB", bytes as f64 / (1024.0 * 1024.0))
+}
+
+fn run(args: &Args) -> io::Result<()> {
+    let lines = read_lines(&args.corpus)?;
+    let queries = match &args.queries {
+        Some(path) => read_lines(path)?,
+        None => sample_queries(&lines),
+    };
+    if queries.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "no queries to run"))
+    }
+
+    println!("{} items, {} queries, {} iterations\n", lines.len(), queries.len(), args.iterations);
+    println!(
+        "{:<12} {:>12} {:>14} {:>10} {:>10} {:>10} {:>10} {:>10} {:>10}",
+        "mode", "queries/s", "items/s", "p50", "p90", "p99", "max", "matches", "memory"
+    );
+
+    for mode in &args.modes {
+        let (measurements, memory) = run_mode(mode, &lines, &queries, args.iterations);
+        let total: Duration = measurements.latencies.iter().sum();
+        let runs = measurements.latencies.len();
+        let queries_per_sec = runs as f64 / total.as_secs_f64();
+
+        println!(
+            "{:<12} {:>12.1} {:>14.0} {:>10} {:>10} {:>10} {:>10} {:>10.1} {:>10}",
+            mode,
+            queries_per_sec,
+            queries_per_sec * lines.len() as f64,
+            format_duration(measurements.percentile(50)),
+            format_duration(measurements.percentile(90)),
+            format_duration(measurements.percentile(99)),
+            format_duration(*measurements.latencies.last().unwrap()),
+            measurements.matches as f64 / runs as f64,
+            format_bytes(memory),
+        );
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args = match Args::parse(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS
+        },
+        Err(message) => {
+            eprintln!("error: {message}\n\n{USAGE}");
+            return ExitCode::from(2)
+        },
+    };
+
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::from(2)
+        },
+    }
+}
@@ -0,0 +1,280 @@
+//! Filter lines from stdin (or a file) with a fuzzy search, printing the matches with their
+//! scores and highlights, or pick a line with an interactive as-you-type prompt.
+//!
+//! This is only built with the `cli` feature:
+//!
+//! ```sh
+//! cargo install --path . --features cli
+//! git branch --all | fuzzbunny feat
+//! fuzzbunny --file Cargo.lock --interactive serde
+//! ```
+//!
+//! The interactive prompt drives the terminal with `stty` and ANSI escapes on `/dev/tty`, so it
+//! only runs on Unix, but it leaves stdin and stdout free for pipelines.
+
+mod terminal;
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, IsTerminal, Read, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use fuzzbunny_rs::{fuzzy_filter, highlight, precompute_skips_for_items, truncate_highlights, FuzzyFilterResult, Target};
+
+use terminal::{Key, KeyDecoder, Terminal};
+
+const USAGE: &str = "\
+Usage: fuzzbunny [OPTIONS] <QUERY>
+       fuzzbunny [OPTIONS] --interactive [QUERY]
+
+Filter lines from stdin with a fuzzy search, printing the matches from best to worst.
+
+With --interactive, the search is typed in a prompt on the terminal instead, starting from
+QUERY. Use the arrow keys to move the selection, and press enter to print the selected line
+(or escape / ctrl-c to cancel).
+
+Options:
+  -f, --file <PATH>       Read lines from PATH instead of stdin
+  -i, --interactive       Search as you type, and print the selected line
+  -n, --limit <N>         Print at most N matches [interactive default: 10]
+  -s, --no-score          Don't print the score of each match
+      --color <WHEN>      Highlight matches with ANSI colors: auto, always or never [default: auto]
+  -h, --help              Print this help
+
+Exits with status 1 if no lines match, or the interactive prompt is cancelled.";
+
+/// The ANSI style of matched sections, bold red.
+const HIGHLIGHT_STYLE: &str = "1;31";
+/// The number of matches listed below the interactive prompt, unless limited.
+const VISIBLE_MATCHES: usize = 10;
+
+/// The parsed command line arguments.
+struct Args {
+    /// The search, which is only optional for the interactive prompt.
+    query: Option<String>,
+    /// The file to read lines from, or [`None`] for stdin.
+    file: Option<PathBuf>,
+    interactive: bool,
+    limit: Option<usize>,
+    score: bool,
+    color: bool,
+}
+
+impl Args {
+    /// Parse the command line arguments.
+    ///
+    /// # Returns
+    ///
+    /// The arguments, or [`None`] if help was requested.
+    ///
+    /// # Errors
+    ///
+    /// Returns a message describing the invalid argument.
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut query = None;
+        let mut file = None;
+        let mut interactive = false;
+        let mut limit = None;
+        let mut score = true;
+        let mut color = None;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => return Ok(None),
+                "-i" | "--interactive" => interactive = true,
+                "-s" | "--no-score" => score = false,
+                "-f" | "--file" => file = Some(args.next().ok_or("--file requires a value")?.into()),
+                "-n" | "--limit" => {
+                    let value = args.next().ok_or("--limit requires a value")?;
+                    limit = Some(value.parse().map_err(|_| format!("invalid limit '{value}'"))?);
+                },
+                "--color" => {
+                    color = match args.next().as_deref() {
+                        Some("auto") => None,
+                        Some("always") => Some(true),
+                        Some("never") => Some(false),
+                        _ => return Err("--color must be one of auto, always or never".to_string()),
+                    };
+                },
+                "--" => {
+                    query = args.next();
+                    break
+                },
+                _ if arg.starts_with('-') && arg.len() > 1 => return Err(format!("unknown option '{arg}'")),
+                _ if query.is_none() => query = Some(arg),
+                _ => return Err(format!("unexpected argument '{arg}'")),
+            }
+        }
+
+        if query.is_none() && !interactive {
+            return Err("missing QUERY".to_string())
+        }
+
+        Ok(Some(Args {
+            query,
+            file: file.filter(|path: &PathBuf| path.as_os_str() != "-"),
+            interactive,
+            limit,
+            score,
+            color: color.unwrap_or_else(|| io::stdout().is_terminal()),
+        }))
+    }
+}
+
+/// Write a single match, with the matched sections highlighted if `color` is set.
+fn write_match(out: &mut impl Write, result: &FuzzyFilterResult<'_>, args: &Args) -> io::Result<()> {
+    if args.score {
+        write!(out, "{}\t", result.score)?;
+    }
+
+    if args.color {
+        out.write_all(highlight::to_ansi(result, HIGHLIGHT_STYLE).as_bytes())?;
+    } else {
+        out.write_all(result.item.as_bytes())?;
+    }
+    writeln!(out)
+}
+
+/// Read the lines to search, from the file if one was given, or stdin otherwise.
+fn read_lines(args: &Args) -> io::Result<Vec<String>> {
+    match &args.file {
+        Some(path) => {
+            let file = File::open(path).map_err(|err| io::Error::new(err.kind(), format!("{}: {err}", path.display())))?;
+            lossy_lines(BufReader::new(file))
+        },
+        None => lossy_lines(io::stdin().lock()),
+    }
+}
+
+/// Split text into lines like [`BufRead::lines`], replacing invalid UTF-8 rather than failing,
+/// so a single line in another encoding doesn't stop the rest from being searched.
+fn lossy_lines(reader: impl BufRead) -> io::Result<Vec<String>> {
+    reader
+        .split(b'\n')
+        .map(|line| {
+            let line = line?;
+            let line = line.strip_suffix(b"\r").unwrap_or(&line);
+            Ok(String::from_utf8_lossy(line).into_owned())
+        })
+        .collect()
+}
+
+/// Render the prompt and the best matches, with the selected match marked.
+fn render(terminal: &mut Terminal, query: &str, results: &[FuzzyFilterResult<'_>], total: usize, selected: usize, args: &Args) -> io::Result<()> {
+    let mut frame = format!("\r\x1b[J> {query}  \x1b[2m{}/{total}\x1b[0m", results.len());
+
+    // Each match is cut to fit on one line after its marker and score, so the cursor can
+    // move back up to the prompt
+    let score_width = if args.score { 7 } else { 0 };
+    let width = terminal.columns.saturating_sub(2 + score_width + 1);
+    let visible = &results[..results.len().min(args.limit.unwrap_or(VISIBLE_MATCHES))];
+    for (i, result) in visible.iter().enumerate() {
+        frame.push_str(if i == selected { "\r\n\x1b[7m▶\x1b[0m " } else { "\r\n  " });
+        if args.score {
+            frame.push_str(&format!("{:>6} ", result.score));
+        }
+        let truncated = truncate_highlights(result.highlights.as_deref().unwrap_or(&[result.item]), width);
+        let sections: Vec<&str> = truncated.iter().map(AsRef::as_ref).collect();
+        if args.color {
+            frame.push_str(&highlight::to_ansi(sections, HIGHLIGHT_STYLE));
+        } else {
+            frame.push_str(&sections.concat());
+        }
+    }
+
+    // Move the cursor back to the end of the prompt
+    if !visible.is_empty() {
+        frame.push_str(&format!("\x1b[{}A", visible.len()));
+    }
+    frame.push_str(&format!("\r\x1b[{}C", query.chars().count() + 2));
+    terminal.tty.write_all(frame.as_bytes())?;
+    terminal.tty.flush()
+}
+
+/// Run the interactive prompt until a line is selected, or it is cancelled.
+///
+/// Every keystroke searches every line again, so the prompt stays responsive on inputs that
+/// [`fuzzy_filter`] scores in a few milliseconds.
+fn pick<'a>(targets: &Vec<Target<'a>>, args: &Args) -> io::Result<Option<&'a str>> {
+    let mut terminal = Terminal::enter()?;
+    let mut input = terminal.tty.try_clone()?;
+    let mut decoder = KeyDecoder::default();
+    let mut buffer = [0; 64];
+
+    let mut query = args.query.clone().unwrap_or_default();
+    let mut results = fuzzy_filter(targets, &query);
+    let mut selected = 0;
+    render(&mut terminal, &query, &results, targets.len(), selected, args)?;
+    loop {
+        let len = input.read(&mut buffer)?;
+        let keys = decoder.decode(&buffer[..len]);
+        if keys.is_empty() {
+            continue
+        }
+
+        let previous = query.clone();
+        for key in keys {
+            let visible = results.len().min(args.limit.unwrap_or(VISIBLE_MATCHES));
+            match key {
+                Key::Char(char) => query.push(char),
+                Key::Backspace => {
+                    query.pop();
+                },
+                Key::Up => selected = selected.saturating_sub(1),
+                Key::Down => selected = (selected + 1).min(visible.saturating_sub(1)),
+                Key::Enter => return Ok(results.get(selected).map(|result| result.item)),
+                Key::Cancel => return Ok(None),
+            }
+        }
+        if query != previous {
+            results = fuzzy_filter(targets, &query);
+            selected = 0;
+        }
+        render(&mut terminal, &query, &results, targets.len(), selected, args)?;
+    }
+}
+
+fn run(args: &Args) -> io::Result<bool> {
+    let lines = read_lines(args)?;
+    let targets = precompute_skips_for_items(lines.iter().map(String::as_str));
+
+    let mut out = BufWriter::new(io::stdout().lock());
+    if args.interactive {
+        let Some(line) = pick(&targets, args)? else { return Ok(false) };
+        writeln!(out, "{line}")?;
+        out.flush()?;
+        return Ok(true)
+    }
+
+    let results = fuzzy_filter(&targets, args.query.as_deref().unwrap_or_default());
+    for result in results.iter().take(args.limit.unwrap_or(usize::MAX)) {
+        write_match(&mut out, result, args)?;
+    }
+    out.flush()?;
+    Ok(!results.is_empty())
+}
+
+fn main() -> ExitCode {
+    let args = match Args::parse(std::env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS
+        },
+        Err(message) => {
+            eprintln!("error: {message}\n\n{USAGE}");
+            return ExitCode::from(2)
+        },
+    };
+
+    match run(&args) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        // A closed pipe (e.g. piping into `head`) isn't an error
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::from(2)
+        },
+    }
+}
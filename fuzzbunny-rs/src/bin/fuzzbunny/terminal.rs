@@ -0,0 +1,130 @@
+//! Raw mode terminal input and output on `/dev/tty`, shared by the interactive prompt and the
+//! picker example.
+//!
+//! The terminal is driven with `stty` and ANSI escapes, so this only runs on Unix.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+/// The width of the terminal, in cells, if it can't be found.
+const DEFAULT_COLUMNS: usize = 80;
+
+/// A key pressed by the user.
+pub enum Key {
+    Char(char),
+    Backspace,
+    Up,
+    Down,
+    Enter,
+    Cancel,
+}
+
+/// Decodes the raw bytes read from the terminal into [`Key`]s.
+#[derive(Default)]
+pub struct KeyDecoder {
+    pending: Vec<u8>,
+}
+
+impl KeyDecoder {
+    /// Decode the bytes of a single read from the terminal, where an empty read means the read
+    /// timed out without input.
+    pub fn decode(&mut self, bytes: &[u8]) -> Vec<Key> {
+        if bytes.is_empty() {
+            return self.timeout().into_iter().collect()
+        }
+        bytes.iter().filter_map(|&byte| self.push(byte)).collect()
+    }
+
+    fn push(&mut self, byte: u8) -> Option<Key> {
+        self.pending.push(byte);
+        let key = match self.pending.as_slice() {
+            [b'\r' | b'\n'] => Key::Enter,
+            [3 | 7] => Key::Cancel,
+            [8 | 127] => Key::Backspace,
+            // The start of an escape sequence, or a lone escape if nothing follows it
+            [0x1b] | [0x1b, b'[' | b'O'] => return None,
+            [0x1b, b'[' | b'O', b'A'] => Key::Up,
+            [0x1b, b'[' | b'O', b'B'] => Key::Down,
+            // A CSI sequence continues until its final byte
+            [0x1b, b'[', .., last] if !(0x40..=0x7e).contains(last) => return None,
+            // Other sequences (e.g. left, home or delete) and alt+key are ignored
+            [0x1b, ..] => {
+                self.pending.clear();
+                return None
+            },
+            bytes => match std::str::from_utf8(bytes) {
+                Ok(text) => match text.chars().next() {
+                    Some(char) if !char.is_control() => Key::Char(char),
+                    _ => {
+                        self.pending.clear();
+                        return None
+                    },
+                },
+                // Wait for the rest of a multi-byte character
+                Err(err) if err.error_len().is_none() => return None,
+                Err(_) => {
+                    self.pending.clear();
+                    return None
+                },
+            },
+        };
+        self.pending.clear();
+        Some(key)
+    }
+
+    /// Resolve a pending escape once no more bytes follow it, so a lone escape cancels rather
+    /// than waiting for (and swallowing) the next key.
+    fn timeout(&mut self) -> Option<Key> {
+        match self.pending.as_slice() {
+            [0x1b] => {
+                self.pending.clear();
+                Some(Key::Cancel)
+            },
+            // An incomplete escape sequence
+            [0x1b, ..] => {
+                self.pending.clear();
+                None
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Run `stty` on the terminal.
+fn stty(args: &[&str]) -> io::Result<String> {
+    let output = Command::new("stty").args(args).stdin(File::open("/dev/tty")?).stderr(Stdio::inherit()).output()?;
+    if !output.status.success() {
+        return Err(io::Error::other("stty failed"))
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// The terminal in raw mode, restored when dropped.
+pub struct Terminal {
+    pub tty: File,
+    saved_mode: String,
+    /// The width of the terminal, in cells.
+    pub columns: usize,
+}
+
+impl Terminal {
+    pub fn enter() -> io::Result<Self> {
+        // Terminals that don't know their size report 0 columns
+        let columns = stty(&["size"])?.split_whitespace().nth(1).and_then(|columns| columns.parse().ok()).filter(|&columns| columns > 0);
+        let columns = columns.unwrap_or(DEFAULT_COLUMNS);
+        let saved_mode = stty(&["-g"])?;
+        // Reads time out after a tenth of a second without input, to tell a lone escape apart from
+        // the start of an escape sequence
+        stty(&["raw", "-echo", "min", "0", "time", "1"])?;
+        Ok(Terminal { tty: OpenOptions::new().read(true).write(true).open("/dev/tty")?, saved_mode, columns })
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        // Clear the prompt, leaving the cursor where it started
+        let _ = write!(self.tty, "\r\x1b[J");
+        let _ = stty(&[&self.saved_mode]);
+    }
+}
@@ -0,0 +1,57 @@
+//! Boosting of matches by a score supplied for each item.
+
+use crate::prelude::*;
+use crate::{collect_indexed_matches_by, filter_target, instrument, should_parallelise, sort_results, FuzzyFilterResult, Target};
+
+/// Search a slice of [`Target`]s and return a filtered vector of [`FuzzyFilterResult`], sorted
+/// by their scores plus the boost of each item.
+///
+/// `boost` is called with the index and string of every matched target, and returns the score
+/// added to its match (or subtracted, if negative), e.g. so recently or frequently used
+/// commands rank above equally matching ones that haven't been used. Boosts only affect the
+/// order of matches, and not whether a target matches at all, as a negative boost can only
+/// reduce the score of a match to 0.
+///
+/// The [`score`](FuzzyFilterResult::score) of each result includes its boost. As with
+/// [`fuzzy_filter`](crate::fuzzy_filter), the results of an empty search aren't sorted, and
+/// are left in the order of `items`.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{fuzzy_filter_boosted, precompute_skips_for_items};
+///
+/// let commands = vec!["Git: Commit", "Git: Checkout", "Git: Clone"];
+/// let targets = precompute_skips_for_items(commands.clone());
+/// let uses = [0, 0, 12];
+///
+/// let results = fuzzy_filter_boosted(&targets, "git c", |index, _| uses[index] * 50);
+/// assert_eq!(results[0].item, "Git: Clone");
+/// assert_eq!(results.len(), 3);
+/// ```
+pub fn fuzzy_filter_boosted<'a>(
+    items: &[Target<'a>],
+    search: &str,
+    boost: impl Fn(usize, &str) -> i32 + Sync,
+) -> Vec<FuzzyFilterResult<'a>> {
+    let search_lower_cased = search.trim().to_lowercase();
+    let parallel = should_parallelise(items, &search_lower_cased);
+    let span = instrument::FilterSpan::enter("fuzzy_filter_boosted", items.len(), search_lower_cased.len(), parallel);
+
+    let stage = instrument::Stage::start("score");
+    let mut results = collect_indexed_matches_by(items, parallel, |index, target| {
+        let mut result = filter_target(target, &search_lower_cased)?.with_index(index);
+        result.score = result.score.saturating_add_signed(boost(index, target.0));
+        Some(result)
+    });
+    stage.finish(items.len(), results.len());
+
+    if !search.is_empty() {
+        let stage = instrument::Stage::start("sort");
+        sort_results(&mut results, parallel);
+        stage.finish(results.len(), results.len());
+    }
+
+    span.finish(results.len());
+    results
+}
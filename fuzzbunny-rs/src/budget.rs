@@ -0,0 +1,209 @@
+//! Memory accounting and eviction for the derived search data of a corpus.
+
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+
+use crate::pipeline::Derived;
+
+/// The fraction of the budget (in tenths) that eviction reduces memory usage to, so evictions
+/// are batched rather than happening on every insertion.
+const EVICTION_LOW_WATER_TENTHS: usize = 9;
+
+impl Derived {
+    /// The heap memory used by this data, in bytes.
+    ///
+    /// This is based on lengths rather than capacities, so it is stable across clones.
+        pub(crate) fn size(&self) -> usize {
+        self.lower.len() + (self.skips.len() + self.offsets.len()) * core::mem::size_of::<usize>()
+    }
+}
+
+/// A lazily populated, evictable slot of [`Derived`] data, along with its recency.
+///
+/// The data is reference counted, so cloning a slot doesn't copy it, and searches holding it keep
+/// it alive even if it's evicted part-way through.
+#[derive(Debug, Default)]
+pub(crate) struct DerivedSlot {
+    derived: RwLock<Option<Arc<Derived>>>,
+    /// The value of [`MemoryAccounting::clock`] when this slot was last used in a match.
+    last_used: AtomicU64,
+}
+
+impl Clone for DerivedSlot {
+    fn clone(&self) -> Self {
+        DerivedSlot {
+            derived: RwLock::new(self.get()),
+            last_used: AtomicU64::new(self.last_used.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+impl DerivedSlot {
+    /// Create a slot that is already populated.
+    pub(crate) fn new(derived: Derived) -> Self {
+        DerivedSlot { derived: RwLock::new(Some(Arc::new(derived))), last_used: AtomicU64::new(0) }
+    }
+
+    /// The cached data, if it hasn't been evicted.
+    #[inline]
+    pub(crate) fn get(&self) -> Option<Arc<Derived>> {
+        // The slot only ever holds complete data, so a poisoned lock is still valid
+        self.derived.read().unwrap_or_else(|err| err.into_inner()).clone()
+    }
+
+    /// The memory used by the cached data, or 0 if it has been evicted.
+    pub(crate) fn size(&self) -> usize {
+        self.get().map_or(0, |derived| derived.size())
+    }
+
+    /// Mark this slot as used at the current time.
+    #[inline]
+    pub(crate) fn touch(&self, accounting: &MemoryAccounting) {
+        self.last_used.store(accounting.clock.load(Ordering::Relaxed), Ordering::Relaxed);
+    }
+
+    /// Cache recomputed data in this slot, if it fits within the memory budget.
+    ///
+    /// Data that doesn't fit is recorded as missed, so the search can make room for it by
+    /// evicting stale data with [`MemoryAccounting::evict_missed`].
+    pub(crate) fn try_cache(&self, derived: Derived, accounting: &MemoryAccounting) -> Arc<Derived> {
+        let size = derived.size();
+        let derived = Arc::new(derived);
+        if !accounting.try_reserve(size) {
+            accounting.missed.fetch_add(size, Ordering::Relaxed);
+            return derived
+        }
+
+        let mut slot = self.derived.write().unwrap_or_else(|err| err.into_inner());
+        match &*slot {
+            Some(cached) => {
+                // Another thread already cached it
+                accounting.release(size);
+                Arc::clone(cached)
+            },
+            None => {
+                *slot = Some(Arc::clone(&derived));
+                derived
+            },
+        }
+    }
+
+    /// Evict the cached data.
+    ///
+    /// # Returns
+    ///
+    /// The memory freed, in bytes.
+    fn evict(&self) -> usize {
+        let derived = self.derived.write().unwrap_or_else(|err| err.into_inner()).take();
+        derived.map_or(0, |derived| derived.size())
+    }
+}
+
+/// The memory budget and usage of the derived data in a corpus.
+#[derive(Debug, Default)]
+pub(crate) struct MemoryAccounting {
+    /// The maximum memory to use for derived data, in bytes, or [`None`] if unlimited.
+    pub(crate) budget: Option<usize>,
+    /// The memory currently used by derived data, in bytes.
+    used: AtomicUsize,
+    /// A logical clock, advanced once per search, used to track recency.
+    clock: AtomicU64,
+    /// The memory of recomputed data that didn't fit within the budget during the current search,
+    /// in bytes.
+    missed: AtomicUsize,
+}
+
+impl Clone for MemoryAccounting {
+    fn clone(&self) -> Self {
+        MemoryAccounting {
+            budget: self.budget,
+            used: AtomicUsize::new(self.used()),
+            clock: AtomicU64::new(self.clock.load(Ordering::Relaxed)),
+            missed: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl MemoryAccounting {
+    /// The memory currently used by derived data, in bytes.
+    pub(crate) fn used(&self) -> usize {
+        self.used.load(Ordering::Relaxed)
+    }
+
+    /// Reset the memory usage to the total size of the given slots, e.g. after they were copied.
+    pub(crate) fn recount<'s>(&mut self, slots: impl Iterator<Item = &'s DerivedSlot>) {
+        *self.used.get_mut() = slots.map(DerivedSlot::size).sum();
+    }
+
+    /// Advance the logical clock, at the start of a search.
+    pub(crate) fn tick(&self) {
+        self.clock.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record memory that has been allocated regardless of the budget.
+    pub(crate) fn add(&self, size: usize) {
+        self.used.fetch_add(size, Ordering::Relaxed);
+    }
+
+    /// Record memory that has been freed.
+    pub(crate) fn release(&self, size: usize) {
+        self.used.fetch_sub(size, Ordering::Relaxed);
+    }
+
+    /// Reserve memory within the budget.
+    ///
+    /// # Returns
+    ///
+    /// Whether the memory fit within the budget, in which case it has been recorded as used.
+    fn try_reserve(&self, size: usize) -> bool {
+        let Some(budget) = self.budget else {
+            self.add(size);
+            return true
+        };
+
+        self.used
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |used| {
+                (used + size <= budget).then_some(used + size)
+            })
+            .is_ok()
+    }
+
+    /// Evict the least recently used derived data until usage is back under the budget.
+    pub(crate) fn enforce<'s>(&self, slots: impl Iterator<Item = &'s DerivedSlot>) {
+        let Some(budget) = self.budget else { return };
+        if self.used() <= budget {
+            return
+        }
+        self.evict_lru(slots, 0, u64::MAX);
+    }
+
+    /// Make room for the data that didn't fit within the budget during the current search, by
+    /// evicting the least recently used data that wasn't matched by the search, so the next
+    /// search can cache it. Without this, a full budget would keep caching a stale set of items.
+    pub(crate) fn evict_missed<'s>(&self, slots: impl Iterator<Item = &'s DerivedSlot>) {
+        let missed = self.missed.swap(0, Ordering::Relaxed);
+        if missed == 0 || self.budget.is_none() {
+            return
+        }
+        self.evict_lru(slots, missed, self.clock.load(Ordering::Relaxed));
+    }
+
+    /// Evict the least recently used data last used before `clock`, until usage plus `reserve`
+    /// is at the low water mark of the budget.
+    fn evict_lru<'s>(&self, slots: impl Iterator<Item = &'s DerivedSlot>, reserve: usize, clock: u64) {
+        let Some(budget) = self.budget else { return };
+
+        let mut slots: Vec<&DerivedSlot> = slots
+            .filter(|slot| slot.last_used.load(Ordering::Relaxed) < clock && slot.get().is_some())
+            .collect();
+        slots.sort_by_key(|slot| slot.last_used.load(Ordering::Relaxed));
+
+        let low_water = budget / 10 * EVICTION_LOW_WATER_TENTHS;
+        for slot in slots {
+            if self.used() + reserve <= low_water {
+                break
+            }
+            self.release(slot.evict());
+        }
+    }
+}
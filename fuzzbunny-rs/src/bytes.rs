@@ -0,0 +1,126 @@
+//! Matching of byte slices that may not be valid UTF-8.
+
+use core::cmp::Ordering;
+use core::ops::Range as ByteRange;
+
+use crate::normalize::map_ranges;
+use crate::prelude::*;
+use crate::{collect_matches_by, instrument, is_parallel_workload, score_prepared, sort_results, NormalizationPipeline};
+
+/// A line matched by [`fuzzy_filter_bytes`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ByteFilterResult<'a> {
+    /// The matched line.
+    pub item: &'a [u8],
+    /// The score of the match.
+    pub score: u32,
+    /// The matched byte ranges of the line, in order.
+    pub ranges: Vec<ByteRange<usize>>,
+}
+
+impl ByteFilterResult<'_> {
+    /// The line split into alternating unmatched and matched sections, the same as
+    /// [`FuzzyFilterResult::highlights`](crate::FuzzyFilterResult::highlights).
+    pub fn highlights(&self) -> Vec<&[u8]> {
+        let mut last_index = 0;
+        let mut highlights = Vec::with_capacity(self.ranges.len() * 2 + 1);
+        for range in &self.ranges {
+            highlights.push(&self.item[last_index..range.start]);
+            highlights.push(&self.item[range.clone()]);
+            last_index = range.end;
+        }
+        if last_index < self.item.len() {
+            highlights.push(&self.item[last_index..]);
+        }
+        highlights
+    }
+}
+
+impl PartialOrd for ByteFilterResult<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ByteFilterResult<'_> {
+    /// The same order as [`FuzzyFilterResult`](crate::FuzzyFilterResult): by score, and then
+    /// the line with the smaller bytes ranks higher.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score.cmp(&other.score).then_with(|| other.item.cmp(self.item))
+    }
+}
+
+/// Score a line of Latin-1 bytes against a lower case search, with the ranges as byte offsets
+/// into the line.
+fn score_bytes(line: &[u8], search: &str) -> Option<(u32, Vec<ByteRange<usize>>)> {
+    // Every byte is a Latin-1 character, so the line is always valid text once decoded
+    let decoded: String = line.iter().map(|&byte| byte as char).collect();
+    let derived = NormalizationPipeline::default().derive(&decoded, false);
+    let string_score = score_prepared(&derived.lower, Some(&derived.lower), Some(&derived.skips), search)?;
+
+    let ranges = if line.is_ascii() {
+        // ASCII decodes to the same bytes, and lower cases without changing lengths
+        string_score.ranges
+    } else {
+        // The index of the line byte at each byte offset of the decoded line
+        let mut line_indices = vec![0; decoded.len() + 1];
+        for (index, (offset, _)) in decoded.char_indices().enumerate() {
+            line_indices[offset] = index;
+        }
+        line_indices[decoded.len()] = line.len();
+
+        let offsets: Vec<usize> = if derived.offsets.is_empty() {
+            (0..=derived.lower.len()).map(|offset| line_indices[offset]).collect()
+        } else {
+            derived.offsets.iter().map(|&offset| line_indices[offset]).collect()
+        };
+        map_ranges(string_score.ranges, &offsets)
+    };
+
+    Some((string_score.score, ranges.into_iter().map(|range| range.0..range.0 + range.1).collect()))
+}
+
+/// Search a slice of byte lines and return a filtered and sorted vector of
+/// [`ByteFilterResult`].
+///
+/// This matches the same as [`fuzzy_filter`](crate::fuzzy_filter), but each byte of a line is
+/// taken to be a Latin-1 character (which includes ASCII), so lines don't need to be valid UTF-8.
+/// This suits grepping logs and other data that's mostly text but may hold arbitrary bytes.
+/// Characters of the search outside of Latin-1 never match.
+///
+/// The matched ranges are byte offsets into each line.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::fuzzy_filter_bytes;
+///
+/// let log: &[u8] = b"INFO started\nWARN disk \xff\xfe low\nERROR disk failed";
+/// let lines: Vec<&[u8]> = log.split(|&byte| byte == b'\n').collect();
+///
+/// let results = fuzzy_filter_bytes(&lines, "disk");
+/// assert_eq!(results.len(), 2);
+/// assert_eq!(results[0].item, b"WARN disk \xff\xfe low");
+/// assert_eq!(results[0].ranges, vec![5..9]);
+/// ```
+pub fn fuzzy_filter_bytes<'a>(lines: &[&'a [u8]], search: &str) -> Vec<ByteFilterResult<'a>> {
+    let search_lower_cased = search.trim().to_lowercase();
+    let parallel = is_parallel_workload(lines.len(), |i| lines[i].len(), search_lower_cased.len());
+    let span = instrument::FilterSpan::enter("fuzzy_filter_bytes", lines.len(), search_lower_cased.len(), parallel);
+
+    let stage = instrument::Stage::start("score");
+    let mut results = collect_matches_by(lines, parallel, |&line| {
+        let (score, ranges) = score_bytes(line, &search_lower_cased)?;
+        Some(ByteFilterResult { item: line, score, ranges })
+    });
+    stage.finish(lines.len(), results.len());
+
+    if !search.is_empty() {
+        let stage = instrument::Stage::start("sort");
+        sort_results(&mut results, parallel);
+        stage.finish(results.len(), results.len());
+    }
+
+    span.finish(results.len());
+    results
+}
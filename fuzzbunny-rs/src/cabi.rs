@@ -0,0 +1,191 @@
+//! A C ABI for embedding the matcher in non-Rust hosts.
+//!
+//! The shared library is built by the `fuzzbunny-ffi` crate in `ffi/` (e.g. with
+//! `cargo build --release -p fuzzbunny-ffi`), which exports the functions declared in
+//! `include/fuzzbunny.h`. Every object is created and freed through these
+//! functions, so the host never has to know the layout of an index or a result set.
+//!
+//! ```c
+//! const char *items[] = {"apple", "banana", "application"};
+//! FuzzbunnyIndex *index = fuzzbunny_index_new(items, 3);
+//!
+//! FuzzbunnyResults *results = fuzzbunny_index_search(index, "app");
+//! for (size_t i = 0; i < fuzzbunny_results_len(results); i++) {
+//!     const FuzzbunnyMatch *match = fuzzbunny_results_get(results, i);
+//!     printf("%s %u\n", items[match->item_index], match->score);
+//! }
+//!
+//! fuzzbunny_results_free(results);
+//! fuzzbunny_index_free(index);
+//! ```
+
+use std::ffi::{c_char, CStr};
+use std::ptr;
+
+use crate::FuzzyIndex;
+
+/// An index of items, created by [`fuzzbunny_index_new`] and freed by [`fuzzbunny_index_free`].
+pub struct FuzzbunnyIndex {
+    index: FuzzyIndex,
+}
+
+/// A matched range of an item, in bytes.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FuzzbunnyRange {
+    /// The byte offset of the start of the range in the item.
+    pub start: usize,
+    /// The length of the range in bytes.
+    pub len: usize,
+}
+
+/// A single match of a search.
+#[repr(C)]
+#[derive(Debug)]
+pub struct FuzzbunnyMatch {
+    /// The position of the matched item in the array the index was created from.
+    pub item_index: usize,
+    /// The score of the match. Higher is better.
+    pub score: u32,
+    /// The matched ranges of the item, in order. Owned by the result set.
+    pub ranges: *const FuzzbunnyRange,
+    /// The number of ranges in `ranges`.
+    pub ranges_len: usize,
+}
+
+/// The sorted matches of a search, created by [`fuzzbunny_index_search`] and freed by
+/// [`fuzzbunny_results_free`].
+pub struct FuzzbunnyResults {
+    matches: Vec<FuzzbunnyMatch>,
+    /// The ranges that `matches` point into, which never move once the result set is created.
+    _ranges: Vec<Vec<FuzzbunnyRange>>,
+}
+
+/// Borrow a NUL-terminated UTF-8 string, or [`None`] if the pointer is null or the string
+/// isn't valid UTF-8.
+///
+/// # Safety
+///
+/// `string` must be null or point to a NUL-terminated string that outlives `'s`.
+unsafe fn borrow_str<'s>(string: *const c_char) -> Option<&'s str> {
+    if string.is_null() {
+        return None
+    }
+    CStr::from_ptr(string).to_str().ok()
+}
+
+/// Create an index of `count` NUL-terminated UTF-8 strings.
+///
+/// The strings are copied, so they can be freed as soon as this returns. Matches refer to
+/// items by their position in `items`.
+///
+/// # Returns
+///
+/// The new index, or null if `items` is null (and `count` isn't 0), or if any item is null
+/// or isn't valid UTF-8.
+///
+/// # Safety
+///
+/// `items` must be null or point to `count` pointers, each of which must be null or point to
+/// a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn fuzzbunny_index_new(items: *const *const c_char, count: usize) -> *mut FuzzbunnyIndex {
+    if items.is_null() && count > 0 {
+        return ptr::null_mut()
+    }
+
+    let mut index = FuzzyIndex::new();
+    for i in 0..count {
+        let Some(item) = borrow_str(*items.add(i)) else { return ptr::null_mut() };
+        index.insert(item);
+    }
+    Box::into_raw(Box::new(FuzzbunnyIndex { index }))
+}
+
+/// Free an index created by [`fuzzbunny_index_new`]. Does nothing if `index` is null.
+///
+/// # Safety
+///
+/// `index` must be null or an index that hasn't been freed yet. Result sets of the index stay
+/// valid after it's freed.
+#[no_mangle]
+pub unsafe extern "C" fn fuzzbunny_index_free(index: *mut FuzzbunnyIndex) {
+    if !index.is_null() {
+        drop(Box::from_raw(index));
+    }
+}
+
+/// Search an index with a NUL-terminated UTF-8 search string.
+///
+/// The matches are sorted in the same order as [`fuzzy_filter`](crate::fuzzy_filter).
+///
+/// # Returns
+///
+/// The matches, or null if `index` or `search` is null, or if `search` isn't valid UTF-8.
+///
+/// # Safety
+///
+/// `index` must be null or a live index, and `search` must be null or point to a
+/// NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn fuzzbunny_index_search(index: *const FuzzbunnyIndex, search: *const c_char) -> *mut FuzzbunnyResults {
+    let (Some(index), Some(search)) = (index.as_ref(), borrow_str(search)) else { return ptr::null_mut() };
+
+    let results = index.index.search(search);
+    let ranges: Vec<Vec<FuzzbunnyRange>> = results
+        .iter()
+        .map(|res| {
+            let ranges = res.result.ranges.as_deref().unwrap_or_default();
+            ranges.iter().map(|range| FuzzbunnyRange { start: range.0, len: range.1 }).collect()
+        })
+        .collect();
+    let matches = results
+        .iter()
+        .zip(&ranges)
+        .map(|(res, ranges)| FuzzbunnyMatch {
+            // IDs are assigned from 0 in insertion order, and items are never removed
+            item_index: res.id.0 as usize,
+            score: res.result.score,
+            ranges: ranges.as_ptr(),
+            ranges_len: ranges.len(),
+        })
+        .collect();
+    Box::into_raw(Box::new(FuzzbunnyResults { matches, _ranges: ranges }))
+}
+
+/// The number of matches in a result set, or 0 if `results` is null.
+///
+/// # Safety
+///
+/// `results` must be null or a live result set.
+#[no_mangle]
+pub unsafe extern "C" fn fuzzbunny_results_len(results: *const FuzzbunnyResults) -> usize {
+    results.as_ref().map_or(0, |results| results.matches.len())
+}
+
+/// Get a match from a result set, from best to worst.
+///
+/// # Returns
+///
+/// The match at position `i`, which is valid until the result set is freed, or null if
+/// `results` is null or `i` is out of bounds.
+///
+/// # Safety
+///
+/// `results` must be null or a live result set.
+#[no_mangle]
+pub unsafe extern "C" fn fuzzbunny_results_get(results: *const FuzzbunnyResults, i: usize) -> *const FuzzbunnyMatch {
+    results.as_ref().and_then(|results| results.matches.get(i)).map_or(ptr::null(), ptr::from_ref)
+}
+
+/// Free a result set created by [`fuzzbunny_index_search`]. Does nothing if `results` is null.
+///
+/// # Safety
+///
+/// `results` must be null or a result set that hasn't been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn fuzzbunny_results_free(results: *mut FuzzbunnyResults) {
+    if !results.is_null() {
+        drop(Box::from_raw(results));
+    }
+}
@@ -0,0 +1,93 @@
+//! Cancellation of in-flight searches.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::{collect_matches, instrument, is_parallel_workload, map_items, sort_results, FuzzyFilterResult, Target};
+
+/// Number of targets scored between checks of the cancellation flag.
+const CANCEL_CHECK_INTERVAL: usize = 1024;
+
+/// A handle to a search that can be cancelled from another thread.
+///
+/// Keystroke-driven UIs can search with [`SearchHandle::fuzzy_filter`] on a worker thread, and
+/// [`cancel`](SearchHandle::cancel) a clone of the handle as soon as the search is out of date
+/// (e.g. when the user types another character), so the worker is freed up for the next search
+/// instead of finishing one whose results would be thrown away.
+///
+/// The flag is checked every thousand or so targets, so a cancelled search returns promptly even
+/// over a huge corpus. Clones of a handle share the same flag, and a cancelled handle stays
+/// cancelled, so use a new handle for every search.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{precompute_skips_for_items, SearchHandle};
+///
+/// let targets = precompute_skips_for_items(vec!["apple", "banana", "application"]);
+/// let handle = SearchHandle::new();
+///
+/// let results = handle.fuzzy_filter(&targets, "app").unwrap();
+/// assert_eq!(results.len(), 2);
+///
+/// handle.clone().cancel();
+/// assert!(handle.fuzzy_filter(&targets, "app").is_none());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SearchHandle {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl SearchHandle {
+    /// Create a new handle that hasn't been cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancel every search using this handle or one of its clones, including searches that
+    /// haven't started yet.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the handle has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Search a slice of [`Target`]s in the same way as [`fuzzy_filter`](crate::fuzzy_filter),
+    /// unless the handle is cancelled first.
+    ///
+    /// # Returns
+    ///
+    /// The filtered and sorted results, or [`None`] if the handle was cancelled before the
+    /// search finished.
+    pub fn fuzzy_filter<'a>(&self, items: &[Target<'a>], search: &str) -> Option<Vec<FuzzyFilterResult<'a>>> {
+        let search_lower_cased = search.trim().to_lowercase();
+        let parallel = is_parallel_workload(items.len(), |i| items[i].0.len(), search_lower_cased.len());
+        let span = instrument::FilterSpan::enter("cancellable_fuzzy_filter", items.len(), search_lower_cased.len(), parallel);
+
+        // Each chunk checks the flag before it's scored, so every remaining chunk is skipped
+        // once the handle is cancelled
+        let stage = instrument::Stage::start("score");
+        let chunks: Vec<(usize, &[Target<'a>])> = items.chunks(CANCEL_CHECK_INTERVAL).enumerate().collect();
+        let chunk_results = map_items(&chunks, parallel, |&(i, chunk)| {
+            (!self.is_cancelled()).then(|| collect_matches(chunk, i * CANCEL_CHECK_INTERVAL, &search_lower_cased, false))
+        });
+        let mut results: Vec<FuzzyFilterResult<'a>> = chunk_results.into_iter().collect::<Option<Vec<_>>>()?.concat();
+        stage.finish(items.len(), results.len());
+
+        if self.is_cancelled() {
+            return None
+        }
+
+        if !search.is_empty() {
+            let stage = instrument::Stage::start("sort");
+            sort_results(&mut results, parallel);
+            stage.finish(results.len(), results.len());
+        }
+
+        span.finish(results.len());
+        Some(results)
+    }
+}
@@ -0,0 +1,47 @@
+//! Grouping of near-duplicate strings.
+
+use crate::similarity::Prepared;
+
+/// Group strings into clusters of near-duplicates, e.g. to deduplicate user-generated item
+/// names before indexing them.
+///
+/// Two strings are near-duplicates if their [`similarity`](crate::similarity()) is at least
+/// `threshold`. A threshold of around 0.6 groups strings that differ only in case, whitespace or
+/// punctuation between words.
+///
+/// Clusters are built greedily: each string joins the first cluster whose first string it is a
+/// near-duplicate of, or otherwise starts a new cluster.
+///
+/// # Returns
+///
+/// The clusters in the order they were started, each holding its strings in input order. The
+/// first string of each cluster can be used as its canonical form.
+///
+/// # Panics
+///
+/// Panics if `threshold` isn't between 0 (exclusive) and 1 (inclusive).
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::cluster_near_duplicates;
+///
+/// let names = ["Coca-Cola", "coca cola", "Pepsi", "COCA-COLA ", "Pepsi Max"];
+///
+/// let clusters = cluster_near_duplicates(names, 0.6);
+/// assert_eq!(clusters, vec![vec!["Coca-Cola", "coca cola", "COCA-COLA "], vec!["Pepsi"], vec!["Pepsi Max"]]);
+/// ```
+pub fn cluster_near_duplicates<'a>(items: impl IntoIterator<Item = &'a str>, threshold: f64) -> Vec<Vec<&'a str>> {
+    assert!(threshold > 0.0 && threshold <= 1.0, "threshold must be in (0, 1]");
+
+    // The first string of each cluster, and the cluster's strings
+    let mut clusters: Vec<(Prepared<'a>, Vec<&'a str>)> = vec![];
+    for item in items {
+        let prepared = Prepared::new(item);
+        match clusters.iter_mut().find(|(first, _)| first.similarity(&prepared) >= threshold) {
+            Some((_, cluster)) => cluster.push(item),
+            None => clusters.push((prepared, vec![item])),
+        }
+    }
+    clusters.into_iter().map(|(_, cluster)| cluster).collect()
+}
@@ -0,0 +1,192 @@
+//! Targets made of several fields, searched as a single string.
+
+use crate::pipeline::Derived;
+use crate::normalize::map_ranges;
+use crate::prelude::*;
+use crate::{
+    collect_matches_by, instrument, is_parallel_workload, score_prepared, sort_results, FuzzyFilterResult, Range,
+};
+
+/// The separator inserted between the fields of a [`CompositeTarget`].
+const FIELD_SEPARATOR: &str = " ";
+
+/// A target made of several fields (e.g. the name, email and team of a user), concatenated into
+/// a single haystack.
+///
+/// The fields are joined with a space, so each field starts a new word, and a search can match
+/// across fields (e.g. "jo sm" matching the first and last name fields "John" and "Smith"). The
+/// whole haystack is scored in a single pass, but the byte offsets of each field are kept, so
+/// the matched ranges can be attributed back to the fields they fall in. See
+/// [`CompositeFilterResult::fields`].
+///
+/// The lower case form and skip indices of the haystack are precomputed on construction.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{fuzzy_filter_composite, CompositeTarget, FieldRange};
+///
+/// let targets = vec![
+///     CompositeTarget::new(["John Smith", "john@example.com"]),
+///     CompositeTarget::new(["Jane Doe", "jane@example.com"]),
+/// ];
+///
+/// let results = fuzzy_filter_composite(&targets, "smith");
+/// assert_eq!(results.len(), 1);
+/// assert_eq!(results[0].fields, vec![FieldRange { field: 0, start: 5, len: 5 }]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct CompositeTarget {
+    /// The fields joined with [`FIELD_SEPARATOR`].
+    haystack: String,
+    /// The start offset of each field in `haystack`.
+    field_starts: Vec<usize>,
+    /// The lower case form and skip indices of `haystack`.
+    derived: Derived,
+}
+
+/// A range of a match within a single field of a [`CompositeTarget`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FieldRange {
+    /// The index of the field, in the order the fields were given.
+    pub field: usize,
+    /// The byte offset of the start of the range, relative to the start of the field.
+    pub start: usize,
+    /// The length of the range, in bytes.
+    pub len: usize,
+}
+
+/// Filter result for a [`CompositeTarget`], with the matched ranges attributed to its fields.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompositeFilterResult<'t> {
+    /// The matched target.
+    pub target: &'t CompositeTarget,
+    /// The match result against the whole haystack of the target, including the score and
+    /// highlights.
+    pub result: FuzzyFilterResult<'t>,
+    /// The matched ranges within each field, in order. A range spanning several fields is split
+    /// at the field boundaries, leaving out the separators.
+    pub fields: Vec<FieldRange>,
+}
+
+impl<'t> PartialOrd for CompositeFilterResult<'t> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> { Some(self.cmp(other)) }
+}
+impl<'t> Ord for CompositeFilterResult<'t> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.result.cmp(&other.result)
+    }
+}
+
+impl PartialEq for CompositeTarget {
+    fn eq(&self, other: &Self) -> bool {
+        self.haystack == other.haystack && self.field_starts == other.field_starts
+    }
+}
+impl Eq for CompositeTarget {}
+
+impl CompositeTarget {
+    /// Build a target from its fields, in order.
+    pub fn new<S: AsRef<str>>(fields: impl IntoIterator<Item = S>) -> Self {
+        let mut haystack = String::new();
+        let mut field_starts = vec![];
+        for field in fields {
+            if !field_starts.is_empty() {
+                haystack.push_str(FIELD_SEPARATOR);
+            }
+            field_starts.push(haystack.len());
+            haystack.push_str(field.as_ref());
+        }
+
+        let derived = Derived::new(&haystack);
+        CompositeTarget { haystack, field_starts, derived }
+    }
+
+    /// The concatenated fields that are searched.
+    pub fn haystack(&self) -> &str {
+        &self.haystack
+    }
+
+    /// The number of fields.
+    pub fn field_count(&self) -> usize {
+        self.field_starts.len()
+    }
+
+    /// Get the text of a field.
+    pub fn field(&self, field: usize) -> Option<&str> {
+        let start = *self.field_starts.get(field)?;
+        Some(&self.haystack[start..self.field_end(field)])
+    }
+
+    /// The end offset of a field in the haystack.
+    fn field_end(&self, field: usize) -> usize {
+        match self.field_starts.get(field + 1) {
+            Some(next_start) => next_start - FIELD_SEPARATOR.len(),
+            None => self.haystack.len(),
+        }
+    }
+
+    /// Split ranges of the haystack at the field boundaries.
+    fn field_ranges(&self, ranges: &[Range]) -> Vec<FieldRange> {
+        let mut field_ranges = vec![];
+        // Ranges are in order, so the field only ever moves forwards
+        let mut field = 0;
+        for range in ranges {
+            let (start, end) = (range.0, range.end());
+            while field < self.field_starts.len() {
+                let (field_start, field_end) = (self.field_starts[field], self.field_end(field));
+                if field_end > start.max(field_start) && field_start < end {
+                    let overlap_start = field_start.max(start);
+                    field_ranges.push(FieldRange {
+                        field,
+                        start: overlap_start - field_start,
+                        len: field_end.min(end) - overlap_start,
+                    });
+                }
+                if field_end >= end {
+                    break
+                }
+                field += 1;
+            }
+        }
+        field_ranges
+    }
+
+    /// Score this target against a lower case search string.
+    fn filter(&self, search: &str) -> Option<CompositeFilterResult<'_>> {
+        let string_score = score_prepared(&self.derived.lower, Some(&self.derived.lower), Some(&self.derived.skips), search)?;
+        // The ranges are offsets into the lower case form, which can differ in length from the haystack
+        let ranges = map_ranges(string_score.ranges, &self.derived.offsets);
+        let fields = self.field_ranges(&ranges);
+
+        Some(CompositeFilterResult {
+            target: self,
+            result: FuzzyFilterResult::matched(&self.haystack, string_score.score, ranges),
+            fields,
+        })
+    }
+}
+
+/// Search a slice of [`CompositeTarget`]s and return a filtered and sorted vector of
+/// [`CompositeFilterResult`].
+///
+/// This behaves the same as [`fuzzy_filter`](crate::fuzzy_filter) over the haystacks of the
+/// targets, with the matched ranges of each result also attributed to the fields of its target.
+pub fn fuzzy_filter_composite<'t>(targets: &'t [CompositeTarget], search: &str) -> Vec<CompositeFilterResult<'t>> {
+    let search_lower_cased = search.trim().to_lowercase();
+    let parallel = is_parallel_workload(targets.len(), |i| targets[i].haystack.len(), search_lower_cased.len());
+    let span = instrument::FilterSpan::enter("fuzzy_filter_composite", targets.len(), search_lower_cased.len(), parallel);
+
+    let stage = instrument::Stage::start("score");
+    let mut results = collect_matches_by(targets, parallel, |target| target.filter(&search_lower_cased));
+    stage.finish(targets.len(), results.len());
+
+    if !search.is_empty() {
+        let stage = instrument::Stage::start("sort");
+        sort_results(&mut results, parallel);
+        stage.finish(results.len(), results.len());
+    }
+
+    span.finish(results.len());
+    results
+}
@@ -0,0 +1,1107 @@
+//! An owning, mutable collection of searchable items.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, OnceLock};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+
+use crate::alias::AliasIndex;
+use crate::budget::{DerivedSlot, MemoryAccounting};
+use crate::pipeline::Derived;
+use crate::dictionary::Dictionaries;
+use crate::normalize::{map_ranges, Normalization};
+use crate::usage::{RecentSelection, UsageBoost, UsageTracker};
+use crate::{
+    collect_matches_by, get_target_skips, instrument, is_parallel_workload, map_items, score_prepared, sort_results,
+    FuzzyFilterResult,
+};
+
+/// A persistent identifier for an item in a [`Corpus`].
+///
+/// IDs are assigned on insertion and are never reused by the corpus that assigned them.
+///
+/// # Stability
+///
+/// An ID refers to the same item for as long as the item exists, regardless of any other
+/// mutations of the corpus. In particular, an ID is unaffected by:
+///
+/// - [`Corpus::update`], which replaces the text of the item but keeps its ID
+/// - [`Corpus::remove`] and [`Corpus::compact`] of other items, even though the storage of the
+///   remaining items moves
+/// - items sharing identical text, which are still returned as separate results per ID
+/// - eviction of derived search data, see [`Corpus::set_memory_budget`]
+/// - [`Corpus::export`] followed by [`Corpus::import`], with the `serde` feature
+///
+/// UIs can therefore key list rows, selections and caches by ID, and look up the current text
+/// of an item with [`Corpus::get`], rather than relying on the position of a result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ItemId(pub u64);
+
+/// A unique text in a [`Corpus`], along with its derived search data and the IDs of every
+/// item with that text.
+#[derive(Debug, Clone)]
+pub(crate) struct CorpusItem {
+    /// The IDs of the items with this text, in insertion order.
+    pub(crate) ids: Vec<ItemId>,
+    /// The original text of the items.
+    pub(crate) text: Arc<str>,
+    /// The lower case form and skip indices of `text`, which may be evicted.
+    pub(crate) derived: DerivedSlot,
+    /// The normalization profile of the items.
+    pub(crate) normalization: Normalization,
+}
+
+impl CorpusItem {
+    /// Create a new entry for a single item, computing its search data.
+    pub(crate) fn new(id: ItemId, text: String) -> Self {
+        let derived = Derived::new(&text);
+        CorpusItem::with_derived(id, text.into(), derived)
+    }
+
+    /// Create a new entry for a single item, with already computed search data.
+    pub(crate) fn with_derived(id: ItemId, text: Arc<str>, derived: Derived) -> Self {
+        CorpusItem { ids: vec![id], text, derived: DerivedSlot::new(derived), normalization: Normalization::Unicode }
+    }
+
+    /// Create a new entry for a single item with a normalization profile, computing its search
+    /// data.
+    pub(crate) fn with_normalization(id: ItemId, text: String, normalization: Normalization) -> Self {
+        let derived = normalization.derive(&text);
+        CorpusItem { normalization, ..CorpusItem::with_derived(id, text.into(), derived) }
+    }
+
+    /// Compute the search data of this entry.
+    pub(crate) fn derive(&self) -> Derived {
+        self.normalization.derive(&self.text)
+    }
+
+    /// Whether this entry is shared by every item with the same text, rather than belonging to a
+    /// single item with a normalization profile.
+    #[inline]
+    pub(crate) fn is_shared(&self) -> bool {
+        self.normalization == Normalization::Unicode
+    }
+
+    /// Whether every item with this text has been removed, leaving the entry to be reclaimed by
+    /// [`Corpus::compact`].
+    #[inline]
+    pub(crate) fn is_tombstone(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Score this entry against a search string, normalized with the profile of the entry.
+    ///
+    /// If the derived search data has been evicted, it is recomputed, and cached again if it fits
+    /// within the memory budget.
+    #[inline]
+    pub(crate) fn filter(&self, search: &str, accounting: &MemoryAccounting) -> Option<FuzzyFilterResult<'_>> {
+        let derived = match self.derived.get() {
+            Some(derived) => derived,
+            None => self.derived.try_cache(self.derive(), accounting),
+        };
+
+        let match_item = score_prepared(&self.text, Some(&derived.lower), Some(&derived.skips), search)?;
+        self.derived.touch(accounting);
+
+        Some(FuzzyFilterResult::matched(&self.text, match_item.score, map_ranges(match_item.ranges, &derived.offsets)))
+    }
+}
+
+/// A filter result for an item in a [`Corpus`].
+///
+/// The `id` of a match stays valid across later mutations of the corpus (see [`ItemId`]),
+/// whereas `result` borrows the corpus and so must be dropped before it is mutated.
+#[derive(Debug, PartialEq, Eq)]
+pub struct CorpusMatch<'c> {
+    /// The ID of the matched item.
+    pub id: ItemId,
+    /// The match result, including the item text, score and highlights.
+    pub result: FuzzyFilterResult<'c>,
+    /// Whether the matched item is pinned. See [`Corpus::set_pinned`].
+    pub pinned: bool,
+    /// The match of the alias that the item matched through, if it scored higher than the
+    /// item's own text. See [`Corpus::add_alias`].
+    ///
+    /// For a match through an alias, `result` holds the item's own text with the score of the
+    /// alias, and no highlights, as the highlights are for the alias.
+    pub alias: Option<FuzzyFilterResult<'c>>,
+}
+
+impl<'c> PartialOrd for CorpusMatch<'c> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> { Some(self.cmp(other)) }
+}
+impl<'c> Ord for CorpusMatch<'c> {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        // Pinned items rank above all others, and identical items are ranked in insertion order
+        self.pinned
+            .cmp(&other.pinned)
+            .then_with(|| self.result.cmp(&other.result))
+            .then_with(|| other.id.cmp(&self.id))
+    }
+}
+
+/// A summary of the contents and memory usage of a [`Corpus`]. See [`Corpus::stats`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CorpusStats {
+    /// The number of items. See [`Corpus::len`].
+    pub items: usize,
+    /// The number of distinct texts. See [`Corpus::unique_len`].
+    pub unique_texts: usize,
+    /// The number of removed texts that haven't been reclaimed. See [`Corpus::compact`].
+    pub tombstones: usize,
+    /// The total length of the distinct texts, in bytes.
+    pub text_bytes: usize,
+    /// The memory used by derived search data (lower case forms and skip indices), in bytes.
+    pub derived_bytes: usize,
+    /// The number of distinct texts whose derived search data is currently cached.
+    pub cached_texts: usize,
+    /// The average number of skip indices per distinct text.
+    ///
+    /// Each skip index is a word start that search terms can jump to, so texts with many of them
+    /// are the most expensive to score.
+    pub average_skips: f64,
+    /// The ID and length in bytes of the longest item, or [`None`] if the corpus is empty.
+    pub longest: Option<(ItemId, usize)>,
+}
+
+/// An owning collection of searchable items.
+///
+/// Unlike a [`Target`](crate::Target) vector, a corpus owns its strings along with their
+/// precomputed lower case forms and skip indices. Items can be inserted, removed and updated by
+/// [`ItemId`], and only the affected item has its search data recomputed, so long-lived
+/// applications don't have to rebuild everything when a single item changes.
+///
+/// Items with identical text share a single copy of the text and its search data, and are only
+/// scored once per search, so log-like corpora with many repeated lines stay cheap.
+///
+/// Selections of items can be recorded with [`Corpus::record_selection`], boosting the score of
+/// frequently selected items (e.g. the most used commands) with a count that decays over time.
+/// Recent selections can also be blended in per search with [`Corpus::search_with_recency`].
+///
+/// Items can also be pinned with [`Corpus::set_pinned`], so they always rank above every
+/// unpinned item whenever they match.
+///
+/// Items can be tagged with categories using [`Corpus::add_tag`], so searches can be restricted
+/// to a set of tags with [`Corpus::search_tagged`].
+///
+/// Searches can be rewritten with synonyms and stop-words (see [`Corpus::set_dictionaries`]),
+/// which can be swapped at any time without rebuilding anything.
+///
+/// Cloning a corpus is O(1), as clones share their storage until either is mutated. Mutations
+/// are copy-on-write, copying only the affected part of the corpus (e.g. pinning an item only
+/// copies the set of pinned items), and never the texts or their search data themselves. Each UI
+/// tab or window can therefore hold its own clone of a large corpus.
+///
+/// The memory used by the derived search data can be limited with a memory budget (see
+/// [`Corpus::set_memory_budget`]), in which case the data of the least recently matched items
+/// is evicted, and lazily recomputed when next needed.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::Corpus;
+///
+/// let mut corpus = Corpus::new();
+/// let apple = corpus.insert("apple");
+/// corpus.insert("banana");
+/// corpus.update(apple, "application");
+///
+/// let results = corpus.search("app");
+/// assert_eq!(results[0].id, apple);
+/// assert_eq!(results[0].result.item, "application");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Corpus {
+    /// The texts and their search data.
+    pub(crate) storage: Arc<Storage>,
+    /// The ID to assign to the next inserted item.
+    pub(crate) next_id: u64,
+    /// The number of mutations applied to the corpus.
+    pub(crate) version: u64,
+    /// The selection counts used to boost frequently selected items.
+    pub(crate) usage: Arc<UsageTracker>,
+    /// The IDs of the pinned items.
+    pub(crate) pinned: Arc<HashSet<ItemId>>,
+    /// The IDs of the items with each tag.
+    pub(crate) tag_index: Arc<HashMap<String, HashSet<ItemId>>>,
+    /// The alternate searchable texts of the items.
+    pub(crate) aliases: Arc<AliasIndex>,
+    /// The synonyms and stop-words applied to searches.
+    pub(crate) dictionaries: Arc<Dictionaries>,
+    /// The number of times the dictionaries have been replaced.
+    pub(crate) dictionary_generation: u64,
+}
+
+/// A single search to run against a corpus, normalized for each profile as needed.
+struct Query {
+    /// The trimmed search, as given.
+    raw: String,
+    /// The search lower cased with [`Normalization::Unicode`].
+    lower: String,
+    /// The search as normalized by each other profile, only computed if any items use it.
+    normalized: [OnceLock<String>; Normalization::COUNT],
+}
+
+impl Query {
+    fn new(raw: String) -> Self {
+        Query { lower: raw.to_lowercase(), raw, normalized: Default::default() }
+    }
+
+    /// The search normalized for items with a profile.
+    fn for_profile(&self, normalization: Normalization) -> &str {
+        match normalization {
+            Normalization::Unicode => &self.lower,
+            _ => self.normalized[normalization.index()].get_or_init(|| normalization.normalize(&self.raw)),
+        }
+    }
+}
+
+/// The texts of a [`Corpus`] and their search data, shared between clones until mutated.
+#[derive(Debug, Default)]
+pub(crate) struct Storage {
+    /// The unique texts, in order of their first insertion, including tombstones of removed texts.
+    pub(crate) items: Vec<CorpusItem>,
+    /// The position in `items` of the text of each item, by ID.
+    pub(crate) positions: HashMap<ItemId, usize>,
+    /// The position of each unique text in `items`.
+    pub(crate) texts: HashMap<Arc<str>, usize>,
+    /// The memory budget and usage of the derived search data.
+    pub(crate) accounting: MemoryAccounting,
+    /// The number of entries in `items` left behind by removals.
+    pub(crate) tombstones: usize,
+}
+
+impl Clone for Storage {
+    fn clone(&self) -> Self {
+        let items = self.items.clone();
+        // Other clones may be caching derived data concurrently, so count what was actually copied
+        let mut accounting = self.accounting.clone();
+        accounting.recount(items.iter().map(|item| &item.derived));
+
+        Storage {
+            items,
+            positions: self.positions.clone(),
+            texts: self.texts.clone(),
+            accounting,
+            tombstones: self.tombstones,
+        }
+    }
+}
+
+impl Storage {
+    /// Add an entry for a new text with already computed search data.
+    ///
+    /// Unless the entry has a normalization profile, the text must not already be in the storage.
+    /// None of the IDs may be in use.
+    pub(crate) fn push_entry(&mut self, ids: Vec<ItemId>, text: Arc<str>, derived: Derived, normalization: Normalization) {
+        let position = self.items.len();
+        for &id in &ids {
+            self.positions.insert(id, position);
+        }
+
+        let item = CorpusItem { ids, text: Arc::clone(&text), derived: DerivedSlot::new(derived), normalization };
+        self.accounting.add(item.derived.size());
+        if item.is_shared() {
+            self.texts.insert(text, position);
+        }
+        self.items.push(item);
+    }
+
+    /// Add an ID to the entry for a text, creating the entry if needed.
+    ///
+    /// Items with a normalization profile other than [`Normalization::Unicode`] always get an
+    /// entry of their own.
+    fn attach(&mut self, id: ItemId, text: String, normalization: Normalization) {
+        let shared = normalization == Normalization::Unicode;
+        if let Some(&position) = self.texts.get(text.as_str()).filter(|_| shared) {
+            self.items[position].ids.push(id);
+            self.positions.insert(id, position);
+            return
+        }
+
+        let item = CorpusItem::with_normalization(id, text, normalization);
+        self.accounting.add(item.derived.size());
+        item.derived.touch(&self.accounting);
+
+        let position = self.items.len();
+        self.positions.insert(id, position);
+        if shared {
+            self.texts.insert(Arc::clone(&item.text), position);
+        }
+        self.items.push(item);
+        self.enforce_memory_budget();
+    }
+
+    /// Remove an ID from the entry for its text, leaving a tombstone if it has no IDs left.
+    ///
+    /// # Returns
+    ///
+    /// The text of the item, or [`None`] if there is no item with the given ID.
+    fn detach(&mut self, id: ItemId) -> Option<Arc<str>> {
+        let position = self.positions.remove(&id)?;
+        let item = &mut self.items[position];
+        item.ids.retain(|&item_id| item_id != id);
+        if !item.ids.is_empty() {
+            return Some(Arc::clone(&item.text))
+        }
+
+        // Leave a tombstone rather than shifting every following entry, until the next compaction
+        let text = std::mem::replace(&mut item.text, Arc::from(""));
+        let derived = std::mem::take(&mut item.derived);
+        if item.is_shared() {
+            self.texts.remove(&text);
+        }
+        self.accounting.release(derived.size());
+        self.tombstones += 1;
+
+        Some(text)
+    }
+
+    /// Evict derived data until the memory usage is within the budget.
+    fn enforce_memory_budget(&mut self) {
+        self.accounting.enforce(self.items.iter().map(|item| &item.derived));
+    }
+
+    /// Remove the tombstones, moving the remaining entries to be contiguous.
+    fn compact(&mut self) {
+        self.items.retain(|item| !item.is_tombstone());
+        self.items.shrink_to_fit();
+        self.tombstones = 0;
+
+        for (position, item) in self.items.iter().enumerate() {
+            if item.is_shared() {
+                *self.texts.get_mut(&item.text).unwrap() = position;
+            }
+            for id in &item.ids {
+                *self.positions.get_mut(id).unwrap() = position;
+            }
+        }
+        debug_assert_eq!(self.positions.len(), self.items.iter().map(|item| item.ids.len()).sum::<usize>());
+    }
+}
+
+impl Corpus {
+    /// Create a new, empty corpus.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a corpus from a file, with one item per line.
+    ///
+    /// See [`Corpus::from_lines`] for how the lines are processed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if the file can't be opened or read, or isn't valid UTF-8.
+    pub fn from_lines_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = File::open(path)?;
+        Self::from_lines(BufReader::new(file))
+    }
+
+    /// Build a corpus from a reader, with one item per line.
+    ///
+    /// Each line is trimmed of surrounding whitespace, and empty lines are skipped. The search
+    /// data for each item is precomputed as it is read.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if reading fails, or the input isn't valid UTF-8.
+    pub fn from_lines(reader: impl BufRead) -> io::Result<Self> {
+        let mut lines = vec![];
+
+        for line in reader.lines() {
+            let line = line?;
+            let text = line.trim();
+            if text.is_empty() {
+                continue
+            }
+
+            if text.len() == line.len() {
+                lines.push(line);
+            } else {
+                lines.push(text.to_string());
+            }
+        }
+
+        let mut corpus = Corpus::new();
+        corpus.extend(lines);
+        Ok(corpus)
+    }
+
+    /// The number of items in the corpus.
+    pub fn len(&self) -> usize {
+        self.storage.positions.len()
+    }
+
+    /// The number of distinct texts in the corpus.
+    ///
+    /// This is less than [`Corpus::len`] if multiple items share the same text.
+    pub fn unique_len(&self) -> usize {
+        self.storage.items.len() - self.storage.tombstones
+    }
+
+    /// The version of the corpus, incremented by every mutation.
+    ///
+    /// Two views of the same corpus with equal versions hold identical items.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Collect statistics about the contents and memory usage of the corpus.
+    ///
+    /// This is O(n) in the size of the corpus. The skip indices of texts whose derived data has
+    /// been evicted are recomputed, but not cached again.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fuzzbunny_rs::Corpus;
+    ///
+    /// let corpus: Corpus = ["apple", "banana split", "apple"].into_iter().collect();
+    /// let stats = corpus.stats();
+    ///
+    /// assert_eq!(stats.items, 3);
+    /// assert_eq!(stats.unique_texts, 2);
+    /// assert_eq!(stats.text_bytes, 17);
+    /// assert_eq!(stats.longest.map(|(_, len)| len), Some(12));
+    /// ```
+    pub fn stats(&self) -> CorpusStats {
+        let mut stats = CorpusStats {
+            items: self.len(),
+            unique_texts: self.unique_len(),
+            tombstones: self.storage.tombstones,
+            derived_bytes: self.derived_memory(),
+            ..CorpusStats::default()
+        };
+
+        let mut total_skips = 0;
+        for item in self.storage.items.iter().filter(|item| !item.is_tombstone()) {
+            stats.text_bytes += item.text.len();
+            let skips = match item.derived.get() {
+                Some(derived) => {
+                    stats.cached_texts += 1;
+                    derived.skips.len()
+                },
+                None if item.is_shared() => get_target_skips(&item.text).len(),
+                None => item.derive().skips.len(),
+            };
+            // Excluding the trailing skip at the end of the text
+            total_skips += skips - 1;
+
+            if stats.longest.is_none_or(|(_, len)| item.text.len() > len) {
+                stats.longest = Some((item.ids[0], item.text.len()));
+            }
+        }
+
+        if stats.unique_texts > 0 {
+            stats.average_skips = total_skips as f64 / stats.unique_texts as f64;
+        }
+        stats
+    }
+
+    /// Whether the corpus contains no items.
+    pub fn is_empty(&self) -> bool {
+        self.storage.positions.is_empty()
+    }
+
+    /// Insert a new item into the corpus.
+    ///
+    /// If another item already has identical text, the text and its search data are shared.
+    ///
+    /// # Returns
+    ///
+    /// The [`ItemId`] assigned to the new item.
+    pub fn insert(&mut self, text: impl Into<String>) -> ItemId {
+        let id = ItemId(self.next_id);
+        self.next_id += 1;
+        self.version += 1;
+
+        Arc::make_mut(&mut self.storage).attach(id, text.into(), Normalization::Unicode);
+        id
+    }
+
+    /// Insert a new item into the corpus, matched with a specific normalization profile.
+    ///
+    /// Searches are normalized with the profile of each item before matching it, so corpora
+    /// mixing several languages can match each item with the right rules. Items with a profile
+    /// other than [`Normalization::Unicode`] don't share their search data with identical texts.
+    ///
+    /// # Returns
+    ///
+    /// The [`ItemId`] assigned to the new item.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fuzzbunny_rs::{Corpus, Normalization};
+    ///
+    /// let mut corpus = Corpus::new();
+    /// let street = corpus.insert_normalized("Hauptstraße", Normalization::German);
+    /// corpus.insert("Main Street");
+    ///
+    /// let results = corpus.search("strasse");
+    /// assert_eq!(results.len(), 1);
+    /// assert_eq!(results[0].id, street);
+    /// assert_eq!(results[0].result.highlights, Some(vec!["Haupt", "straße"]));
+    /// ```
+    pub fn insert_normalized(&mut self, text: impl Into<String>, normalization: Normalization) -> ItemId {
+        let id = ItemId(self.next_id);
+        self.next_id += 1;
+        self.version += 1;
+
+        Arc::make_mut(&mut self.storage).attach(id, text.into(), normalization);
+        id
+    }
+
+    /// The normalization profile of an item. See [`Corpus::insert_normalized`].
+    pub fn normalization(&self, id: ItemId) -> Option<Normalization> {
+        self.storage.positions.get(&id).map(|&position| self.storage.items[position].normalization)
+    }
+
+    /// Insert many new items into the corpus at once.
+    ///
+    /// This is much faster than inserting the items one at a time when loading large numbers of
+    /// items, as storage is reserved once, and the search data of the new texts is computed in
+    /// parallel (if the `rayon` feature is enabled).
+    ///
+    /// # Returns
+    ///
+    /// The [`ItemId`]s assigned to the new items, in the same order as `items`.
+    pub fn extend<S: Into<String>>(&mut self, items: impl IntoIterator<Item = S>) -> Vec<ItemId> {
+        let texts: Vec<String> = items.into_iter().map(Into::into).collect();
+        let first_id = self.next_id;
+        self.next_id += texts.len() as u64;
+        self.version += 1;
+
+        let storage = Arc::make_mut(&mut self.storage);
+
+        // Find the texts that aren't in the corpus yet, de-duplicating within the batch
+        let mut new_positions: HashMap<&str, usize> = HashMap::new();
+        let mut new_texts: Vec<&str> = vec![];
+        for text in &texts {
+            if !storage.texts.contains_key(text.as_str()) && !new_positions.contains_key(text.as_str()) {
+                new_positions.insert(text, storage.items.len() + new_texts.len());
+                new_texts.push(text);
+            }
+        }
+
+        let parallel = is_parallel_workload(new_texts.len(), |i| new_texts[i].len(), 0);
+        let mut derived = map_items(&new_texts, parallel, |text| Derived::new(text)).into_iter();
+
+        storage.items.reserve(new_texts.len());
+        storage.texts.reserve(new_texts.len());
+        storage.positions.reserve(texts.len());
+
+        let mut ids = Vec::with_capacity(texts.len());
+        for (i, text) in texts.iter().enumerate() {
+            let id = ItemId(first_id + i as u64);
+            ids.push(id);
+
+            let position = match storage.texts.get(text.as_str()) {
+                Some(&position) => position,
+                None => new_positions[text.as_str()],
+            };
+            storage.positions.insert(id, position);
+
+            if position < storage.items.len() {
+                storage.items[position].ids.push(id);
+                continue
+            }
+
+            let text: Arc<str> = text.as_str().into();
+            let item = CorpusItem::with_derived(id, Arc::clone(&text), derived.next().unwrap());
+            storage.accounting.add(item.derived.size());
+            item.derived.touch(&storage.accounting);
+            storage.texts.insert(text, position);
+            storage.items.push(item);
+        }
+
+        storage.enforce_memory_budget();
+        ids
+    }
+
+    /// Insert an item with a specific ID, replacing any existing item with that ID.
+    ///
+    /// IDs assigned by later insertions will always be greater than `id`.
+    pub(crate) fn insert_with_id(&mut self, id: ItemId, text: String, normalization: Normalization) {
+        self.next_id = self.next_id.max(id.0 + 1);
+        self.version += 1;
+
+        let storage = Arc::make_mut(&mut self.storage);
+        storage.detach(id);
+        storage.attach(id, text, normalization);
+    }
+
+    /// Limit the memory used by the derived search data (lower case forms and skip indices), in
+    /// bytes. [`None`] removes the limit.
+    ///
+    /// When the budget is exceeded, the data of the least recently matched items is evicted.
+    /// Evicted data is recomputed whenever it is needed by a search, and cached again if it fits
+    /// within the budget, so a smaller budget trades search speed for memory. A search that
+    /// recomputes data that doesn't fit evicts the data of items it didn't match, so the cache
+    /// follows the items that are being matched.
+    pub fn set_memory_budget(&mut self, budget: Option<usize>) {
+        let storage = Arc::make_mut(&mut self.storage);
+        storage.accounting.budget = budget;
+        storage.enforce_memory_budget();
+    }
+
+    /// The memory budget for derived search data, in bytes. See [`Corpus::set_memory_budget`].
+    pub fn memory_budget(&self) -> Option<usize> {
+        self.storage.accounting.budget
+    }
+
+    /// The memory currently used by derived search data, in bytes.
+    pub fn derived_memory(&self) -> usize {
+        self.storage.accounting.used() + self.aliases.derived_memory()
+    }
+
+    /// Remove an item from the corpus.
+    ///
+    /// Removal is O(1) in the size of the corpus, as the storage of removed items is only marked
+    /// as unused. It is reclaimed by [`Corpus::compact`].
+    ///
+    /// # Returns
+    ///
+    /// The text of the removed item, or [`None`] if there is no item with the given ID.
+    pub fn remove(&mut self, id: ItemId) -> Option<String> {
+        if !self.contains(id) {
+            return None
+        }
+        let text = Arc::make_mut(&mut self.storage).detach(id)?;
+        if self.usage.tracks(id) {
+            Arc::make_mut(&mut self.usage).forget(id);
+        }
+        if self.pinned.contains(&id) {
+            Arc::make_mut(&mut self.pinned).remove(&id);
+        }
+        if !self.aliases.aliases(id).is_empty() {
+            Arc::make_mut(&mut self.aliases).remove_item(id);
+        }
+        if self.tag_index.values().any(|ids| ids.contains(&id)) {
+            Arc::make_mut(&mut self.tag_index).retain(|_, ids| {
+                ids.remove(&id);
+                !ids.is_empty()
+            });
+        }
+        self.version += 1;
+        Some(text.to_string())
+    }
+
+    /// Reclaim the storage left behind by removed items.
+    ///
+    /// The remaining items are moved to be contiguous again, keeping their order and IDs. This is
+    /// O(n) in the size of the corpus, so is best done when the application is idle, e.g. once
+    /// [`Corpus::tombstone_count`] grows large.
+    pub fn compact(&mut self) {
+        if self.storage.tombstones > 0 {
+            Arc::make_mut(&mut self.storage).compact();
+        }
+        if !self.aliases.is_empty() {
+            Arc::make_mut(&mut self.aliases).compact();
+        }
+    }
+
+    /// The number of removed texts whose storage hasn't been reclaimed by [`Corpus::compact`].
+    pub fn tombstone_count(&self) -> usize {
+        self.storage.tombstones
+    }
+
+    /// Replace the text of an item, recomputing the search data for that item only.
+    ///
+    /// The item keeps its normalization profile.
+    ///
+    /// # Returns
+    ///
+    /// The previous text of the item, or [`None`] if there is no item with the given ID.
+    pub fn update(&mut self, id: ItemId, text: impl Into<String>) -> Option<String> {
+        if !self.contains(id) {
+            return None
+        }
+        let normalization = self.normalization(id)?;
+        let storage = Arc::make_mut(&mut self.storage);
+        let old_text = storage.detach(id)?;
+        storage.attach(id, text.into(), normalization);
+        self.version += 1;
+        Some(old_text.to_string())
+    }
+
+    /// Record that an item was selected by the user.
+    ///
+    /// Matches of frequently selected items are boosted in search results, without affecting
+    /// whether an item matches at all. See [`UsageBoost`] for how the boost decays over time.
+    ///
+    /// # Returns
+    ///
+    /// Whether there is an item with the given ID.
+    pub fn record_selection(&mut self, id: ItemId) -> bool {
+        if !self.contains(id) {
+            return false
+        }
+        Arc::make_mut(&mut self.usage).record(id);
+        true
+    }
+
+    /// Configure how recorded selections boost item scores.
+    pub fn set_usage_boost(&mut self, config: UsageBoost) {
+        Arc::make_mut(&mut self.usage).config = config;
+    }
+
+    /// The decayed selection count of an item. See [`Corpus::record_selection`].
+    pub fn selection_count(&self, id: ItemId) -> f32 {
+        self.usage.decayed_count(id)
+    }
+
+    /// Pin or unpin an item.
+    ///
+    /// Whenever a pinned item matches a search, it is ranked above every unpinned match.
+    /// Pinned matches are still ranked by score amongst themselves.
+    ///
+    /// # Returns
+    ///
+    /// Whether there is an item with the given ID.
+    pub fn set_pinned(&mut self, id: ItemId, pinned: bool) -> bool {
+        if !self.contains(id) {
+            return false
+        }
+        if pinned != self.pinned.contains(&id) {
+            let pins = Arc::make_mut(&mut self.pinned);
+            if pinned {
+                pins.insert(id);
+            } else {
+                pins.remove(&id);
+            }
+        }
+        self.version += 1;
+        true
+    }
+
+    /// Whether an item is pinned. See [`Corpus::set_pinned`].
+    pub fn is_pinned(&self, id: ItemId) -> bool {
+        self.pinned.contains(&id)
+    }
+
+    /// Tag an item with a category.
+    ///
+    /// # Returns
+    ///
+    /// Whether there is an item with the given ID.
+    pub fn add_tag(&mut self, id: ItemId, tag: impl Into<String>) -> bool {
+        if !self.contains(id) {
+            return false
+        }
+        Arc::make_mut(&mut self.tag_index).entry(tag.into()).or_default().insert(id);
+        self.version += 1;
+        true
+    }
+
+    /// Remove a tag from an item.
+    ///
+    /// # Returns
+    ///
+    /// Whether the item had the tag.
+    pub fn remove_tag(&mut self, id: ItemId, tag: &str) -> bool {
+        if !self.tag_index.get(tag).is_some_and(|ids| ids.contains(&id)) {
+            return false
+        }
+
+        let tag_index = Arc::make_mut(&mut self.tag_index);
+        let ids = tag_index.get_mut(tag).unwrap();
+        ids.remove(&id);
+        if ids.is_empty() {
+            tag_index.remove(tag);
+        }
+        self.version += 1;
+        true
+    }
+
+    /// The tags of an item, in no particular order.
+    pub fn tags(&self, id: ItemId) -> Vec<&str> {
+        self.tag_index
+            .iter()
+            .filter(|(_, ids)| ids.contains(&id))
+            .map(|(tag, _)| tag.as_str())
+            .collect()
+    }
+
+    /// Add an alternate searchable text to an item, e.g. "prefs" and "options" for "Settings".
+    ///
+    /// A search that matches an alias returns the item itself, only once no matter how many of
+    /// its texts match, with the best match taken. See [`CorpusMatch::alias`].
+    ///
+    /// # Returns
+    ///
+    /// Whether there is an item with the given ID, that didn't already have the alias.
+    pub fn add_alias(&mut self, id: ItemId, alias: impl Into<String>) -> bool {
+        if !self.contains(id) || !Arc::make_mut(&mut self.aliases).add(id, alias.into()) {
+            return false
+        }
+        self.version += 1;
+        true
+    }
+
+    /// Remove an alias from an item.
+    ///
+    /// # Returns
+    ///
+    /// Whether the item had the alias.
+    pub fn remove_alias(&mut self, id: ItemId, alias: &str) -> bool {
+        if !self.aliases.aliases(id).contains(&alias) {
+            return false
+        }
+        Arc::make_mut(&mut self.aliases).remove(id, alias);
+        self.version += 1;
+        true
+    }
+
+    /// The aliases of an item, in the order they were added.
+    pub fn aliases(&self, id: ItemId) -> Vec<&str> {
+        self.aliases.aliases(id)
+    }
+
+    /// Replace the synonym and stop-word dictionaries applied to searches.
+    ///
+    /// Only searches are rewritten by the dictionaries, so swapping them is O(1), and doesn't
+    /// recompute the search data of any items. Each search uses the dictionaries as they were
+    /// when it started, so with a [`SharedCorpus`](crate::SharedCorpus), searches of snapshots
+    /// taken before a swap keep using the old dictionaries.
+    ///
+    /// # Returns
+    ///
+    /// The new dictionary generation. See [`Corpus::dictionary_generation`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fuzzbunny_rs::{Corpus, Dictionaries};
+    ///
+    /// let mut corpus: Corpus = ["Television", "Radio"].into_iter().collect();
+    /// assert!(corpus.search("tv").is_empty());
+    ///
+    /// let mut dictionaries = Dictionaries::new();
+    /// dictionaries.add_synonyms(["tv", "television"]);
+    /// corpus.set_dictionaries(dictionaries);
+    ///
+    /// assert_eq!(corpus.search("tv")[0].result.item, "Television");
+    /// ```
+    pub fn set_dictionaries(&mut self, dictionaries: impl Into<Arc<Dictionaries>>) -> u64 {
+        self.dictionaries = dictionaries.into();
+        self.dictionary_generation += 1;
+        self.version += 1;
+        self.dictionary_generation
+    }
+
+    /// The synonym and stop-word dictionaries applied to searches.
+    pub fn dictionaries(&self) -> &Arc<Dictionaries> {
+        &self.dictionaries
+    }
+
+    /// The number of times the dictionaries have been replaced by [`Corpus::set_dictionaries`].
+    ///
+    /// Comparing the generation of a snapshot against that of the current corpus tells whether
+    /// its results were produced with outdated dictionaries.
+    pub fn dictionary_generation(&self) -> u64 {
+        self.dictionary_generation
+    }
+
+    /// Get the text of an item.
+    pub fn get(&self, id: ItemId) -> Option<&str> {
+        self.storage.positions.get(&id).map(|&position| &*self.storage.items[position].text)
+    }
+
+    /// Whether the corpus contains an item with the given ID.
+    pub fn contains(&self, id: ItemId) -> bool {
+        self.storage.positions.contains_key(&id)
+    }
+
+    /// Iterate over the IDs and text of every item.
+    ///
+    /// Items are ordered by the first insertion of their text, and then by insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (ItemId, &str)> + '_ {
+        self.storage.items.iter().flat_map(|item| item.ids.iter().map(|&id| (id, &*item.text)))
+    }
+
+    /// Search the corpus and return the sorted matches.
+    ///
+    /// This behaves the same as [`fuzzy_filter`](crate::fuzzy_filter), with every result
+    /// also carrying the [`ItemId`] of the matched item. Items with identical text are scored
+    /// once, and returned as a separate result for each ID.
+    pub fn search(&self, search: &str) -> Vec<CorpusMatch<'_>> {
+        self.search_filtered(search, None, None)
+    }
+
+    /// Search the corpus, boosting recently selected items, and return the sorted matches.
+    ///
+    /// Each recent selection boosts its item as if that many more selections had been recorded
+    /// with [`Corpus::record_selection`], subject to the same [`UsageBoost`] configuration, so
+    /// "frecency" ranking can be computed per query without mutating the corpus. Selections of
+    /// the same item add up. See [`Corpus::search`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fuzzbunny_rs::{Corpus, RecentSelection};
+    ///
+    /// let mut corpus = Corpus::new();
+    /// let commit = corpus.insert("git commit");
+    /// let checkout = corpus.insert("git checkout");
+    /// assert_eq!(corpus.search("git c")[0].id, checkout);
+    ///
+    /// let recent = [RecentSelection { id: commit, weight: 1.0 }];
+    /// let results = corpus.search_with_recency("git c", &recent);
+    /// assert_eq!(results[0].id, commit);
+    /// ```
+    pub fn search_with_recency(&self, search: &str, recent: &[RecentSelection]) -> Vec<CorpusMatch<'_>> {
+        let mut weights: HashMap<ItemId, f32> = HashMap::new();
+        for selection in recent.iter().filter(|selection| selection.weight > 0.0) {
+            *weights.entry(selection.id).or_default() += selection.weight;
+        }
+        self.search_filtered(search, None, Some(&weights))
+    }
+
+    /// Search only the items with at least one of the given tags, and return the sorted matches.
+    ///
+    /// Items without any of the tags are skipped before scoring, so restricting a search to a
+    /// small category is much cheaper than searching the whole corpus. See [`Corpus::search`].
+    pub fn search_tagged(&self, search: &str, tags: &[&str]) -> Vec<CorpusMatch<'_>> {
+        let allowed: HashSet<ItemId> = tags
+            .iter()
+            .filter_map(|tag| self.tag_index.get(*tag))
+            .flatten()
+            .copied()
+            .collect();
+        self.search_filtered(search, Some(&allowed), None)
+    }
+
+    /// Add the matches of item aliases to the matches of the items themselves, keeping only the
+    /// best match of each item.
+    fn merge_alias_matches<'c>(
+        &'c self,
+        results: &mut Vec<CorpusMatch<'c>>,
+        search: &str,
+        allowed: Option<&HashSet<ItemId>>,
+        parallel: bool,
+    ) {
+        let stage = instrument::Stage::start("aliases");
+        let mut result_positions: HashMap<ItemId, usize> =
+            results.iter().enumerate().map(|(position, res)| (res.id, position)).collect();
+
+        for (ids, alias) in self.aliases.search(search, parallel) {
+            for &id in ids.iter().filter(|id| allowed.is_none_or(|allowed| allowed.contains(id))) {
+                match result_positions.get(&id) {
+                    Some(&position) => {
+                        let res = &mut results[position];
+                        let best_score = res.alias.as_ref().map_or(res.result.score, |best| best.score);
+                        if alias.score > best_score {
+                            res.result.score = alias.score;
+                            res.result.highlights = None;
+                            res.result.ranges = None;
+                            res.alias = Some(alias.clone());
+                        }
+                    },
+                    None => {
+                        let item = &*self.storage.items[self.storage.positions[&id]].text;
+                        let result = FuzzyFilterResult { item, score: alias.score, highlights: None, ranges: None, index: None };
+                        result_positions.insert(id, results.len());
+                        results.push(CorpusMatch { id, result, pinned: false, alias: Some(alias.clone()) });
+                    },
+                }
+            }
+        }
+        stage.finish(results.len(), results.len());
+    }
+
+    /// Search the corpus, optionally restricted to a set of item IDs, and with the items of
+    /// `recent` boosted by their number of recent selections.
+    pub(crate) fn search_filtered<'c>(
+        &'c self,
+        search: &str,
+        allowed: Option<&HashSet<ItemId>>,
+        recent: Option<&HashMap<ItemId, f32>>,
+    ) -> Vec<CorpusMatch<'c>> {
+        // Synonyms and stop-words rewrite the search into one or more queries
+        let queries: Vec<Query> = self.dictionaries.expand(search).into_iter().map(Query::new).collect();
+        let search_lower_cased = &queries[0].lower;
+
+        // Only score the entries that hold at least one allowed item
+        let candidates: Option<Vec<usize>> = allowed.map(|allowed| {
+            let mut positions: Vec<usize> = allowed.iter().map(|id| self.storage.positions[id]).collect();
+            positions.sort_unstable();
+            positions.dedup();
+            positions
+        });
+        let count = candidates.as_ref().map_or(self.storage.items.len(), Vec::len);
+        let item_at = |i: usize| &self.storage.items[candidates.as_ref().map_or(i, |positions| positions[i])];
+
+        let parallel = is_parallel_workload(count, |i| item_at(i).text.len(), search_lower_cased.len());
+        let span = instrument::FilterSpan::enter("corpus_search", count, search_lower_cased.len(), parallel);
+
+        self.storage.accounting.tick();
+        let stage = instrument::Stage::start("score");
+        let score = |item: &'c CorpusItem| {
+            if item.is_tombstone() {
+                return None
+            }
+            // With synonyms, an item matches with the best of the rewritten searches
+            let result = queries
+                .iter()
+                .filter_map(|query| item.filter(query.for_profile(item.normalization), &self.storage.accounting))
+                .reduce(|best, result| if result.score > best.score { result } else { best })?;
+            Some((item.ids.as_slice(), result))
+        };
+        let scored = match &candidates {
+            Some(positions) => collect_matches_by(positions, parallel, |&position| score(&self.storage.items[position])),
+            None => collect_matches_by(&self.storage.items, parallel, score),
+        };
+        stage.finish(count, scored.len());
+        self.storage.accounting.evict_missed(self.storage.items.iter().map(|item| &item.derived));
+
+        let mut results = Vec::with_capacity(scored.len());
+        for (ids, result) in scored {
+            if let ([id], None) = (ids, allowed) {
+                results.push(CorpusMatch { id: *id, result, pinned: false, alias: None });
+            } else {
+                let ids = ids.iter().filter(|id| allowed.is_none_or(|allowed| allowed.contains(id)));
+                results.extend(ids.map(|&id| CorpusMatch { id, result: result.clone(), pinned: false, alias: None }));
+            }
+        }
+        // Every item already matches an empty search, so aliases can't add anything
+        if !self.aliases.is_empty() && !search_lower_cased.is_empty() {
+            for query in &queries {
+                self.merge_alias_matches(&mut results, &query.lower, allowed, parallel);
+            }
+        }
+        for res in &mut results {
+            let recent = recent.and_then(|recent| recent.get(&res.id)).copied().unwrap_or(0.0);
+            res.result.score += self.usage.boost(res.id, recent);
+            res.pinned = self.pinned.contains(&res.id);
+        }
+
+        if !search.is_empty() {
+            let stage = instrument::Stage::start("sort");
+            sort_results(&mut results, parallel);
+            stage.finish(results.len(), results.len());
+        } else if !self.pinned.is_empty() {
+            // Unsorted results stay in corpus order, other than pinned items coming first
+            results.sort_by_key(|res| !res.pinned);
+        }
+
+        span.finish(results.len());
+        results
+    }
+}
+
+impl<S: Into<String>> FromIterator<S> for Corpus {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        let mut corpus = Corpus::new();
+        corpus.extend(iter);
+        corpus
+    }
+}
+
+impl<S: Into<String>> Extend<S> for Corpus {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, iter: I) {
+        Corpus::extend(self, iter);
+    }
+}
@@ -0,0 +1,111 @@
+//! Synonym and stop-word dictionaries applied to searches.
+
+use std::collections::{HashMap, HashSet};
+
+/// Synonyms and stop-words used to rewrite searches of a [`Corpus`](crate::Corpus).
+///
+/// Dictionaries only affect searches, and not the search data of the items, so they can be
+/// swapped on a live corpus at any time with [`Corpus::set_dictionaries`] without rebuilding
+/// anything.
+///
+/// Words are compared case-insensitively, and only whole words of a search (separated by
+/// whitespace) are rewritten. Quoted searches are never rewritten.
+///
+/// [`Corpus::set_dictionaries`]: crate::Corpus::set_dictionaries
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::Dictionaries;
+///
+/// let mut dictionaries = Dictionaries::new();
+/// dictionaries.add_synonyms(["colour", "color"]);
+/// dictionaries.add_stop_word("the");
+///
+/// assert_eq!(dictionaries.expand("The Colour red"), vec!["Colour red", "color red"]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Dictionaries {
+    /// The synonyms of each lower case word.
+    synonyms: HashMap<String, Vec<String>>,
+    /// The lower case stop-words.
+    stop_words: HashSet<String>,
+}
+
+impl Dictionaries {
+    /// Create empty dictionaries, which leave every search unchanged.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether the dictionaries hold no synonyms or stop-words.
+    pub fn is_empty(&self) -> bool {
+        self.synonyms.is_empty() && self.stop_words.is_empty()
+    }
+
+    /// Add a group of words that are all synonyms of each other, e.g. `["tv", "television"]`.
+    ///
+    /// A synonym may be a phrase of multiple words, but only single words are ever looked up, so
+    /// a phrase is only ever substituted into a search, and not replaced itself.
+    pub fn add_synonyms<S: Into<String>>(&mut self, words: impl IntoIterator<Item = S>) {
+        let words: Vec<String> = words.into_iter().map(|word| word.into().to_lowercase()).collect();
+        for word in &words {
+            let synonyms = self.synonyms.entry(word.clone()).or_default();
+            for synonym in &words {
+                if synonym != word && !synonyms.contains(synonym) {
+                    synonyms.push(synonym.clone());
+                }
+            }
+        }
+    }
+
+    /// The synonyms of a word, in the order they were added.
+    pub fn synonyms(&self, word: &str) -> &[String] {
+        self.synonyms.get(&word.to_lowercase()).map_or(&[], Vec::as_slice)
+    }
+
+    /// Add a word that is dropped from searches, e.g. "the".
+    ///
+    /// A search made only of stop-words is left unchanged, so the stop-words can still be found.
+    pub fn add_stop_word(&mut self, word: impl Into<String>) {
+        self.stop_words.insert(word.into().to_lowercase());
+    }
+
+    /// Whether a word is a stop-word.
+    pub fn is_stop_word(&self, word: &str) -> bool {
+        self.stop_words.contains(&word.to_lowercase())
+    }
+
+    /// Rewrite a search with the dictionaries.
+    ///
+    /// # Returns
+    ///
+    /// The searches to run, starting with the search without its stop-words, followed by a
+    /// search for each synonym of each of its words. An item matching any of the searches
+    /// matches, with the best score of them. The case of the search is otherwise preserved, so it
+    /// can still be normalized per item (see [`Normalization`](crate::Normalization)).
+    pub fn expand(&self, search: &str) -> Vec<String> {
+        let search = search.trim();
+        if self.is_empty() || search.starts_with('"') {
+            return vec![search.to_string()]
+        }
+
+        let mut words: Vec<&str> = search.split_whitespace().filter(|word| !self.is_stop_word(word)).collect();
+        if words.is_empty() {
+            words = search.split_whitespace().collect();
+        }
+
+        let mut searches = vec![words.join(" ")];
+        for (i, word) in words.iter().enumerate() {
+            for synonym in self.synonyms(word) {
+                let mut replaced = words.clone();
+                replaced[i] = synonym;
+                let replaced = replaced.join(" ");
+                if !searches.contains(&replaced) {
+                    searches.push(replaced);
+                }
+            }
+        }
+        searches
+    }
+}
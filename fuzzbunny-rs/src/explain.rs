@@ -0,0 +1,144 @@
+//! Human-readable explanations of how targets were scored.
+
+use core::cmp::Ordering;
+use core::fmt::Write;
+
+use crate::prelude::*;
+use crate::{score_prepared, FuzzyFilterResult, Range, SCORE_CONTIGUOUS, SCORE_PREFIX, SCORE_START_STR};
+
+/// How a target matched a search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MatchKind {
+    /// The search is a substring of the target.
+    Substring,
+    /// The search matched the starts of words in the target.
+    Fuzzy,
+}
+
+/// The score of a target, broken down into its bonuses.
+#[derive(Debug)]
+struct ScoreBreakdown {
+    kind: MatchKind,
+    /// The matched sections of the target.
+    sections: Vec<String>,
+    /// The bonus for contiguous matches, which grows with the square of each range's length.
+    contiguity: u32,
+    /// The bonus for matching at the start of the target.
+    start: u32,
+    /// The bonus for ranges starting at word prefixes, which is larger the earlier they are.
+    prefix: u32,
+}
+
+impl ScoreBreakdown {
+    fn score(&self) -> u32 {
+        self.contiguity + self.start + self.prefix
+    }
+}
+
+/// Score a target against a lower case search, breaking down its score.
+///
+/// This mirrors how [`score_prepared`] scores each matched range.
+fn breakdown(target: &str, search: &str) -> Option<ScoreBreakdown> {
+    let string_score = score_prepared(target, None, None, search)?;
+    let lower = target.to_lowercase();
+
+    let unquoted = match search.strip_prefix('"') {
+        Some(quoted) => quoted.strip_suffix('"').unwrap_or(quoted),
+        None => search,
+    };
+    let kind = if lower.contains(unquoted) { MatchKind::Substring } else { MatchKind::Fuzzy };
+
+    let mut breakdown = ScoreBreakdown { kind, sections: vec![], contiguity: 0, start: 0, prefix: 0 };
+    for &Range(start, len) in &string_score.ranges {
+        breakdown.sections.push(target[start..start + len].to_string());
+        breakdown.contiguity += SCORE_CONTIGUOUS * (len * len) as u32;
+
+        if start == 0 {
+            breakdown.start += SCORE_START_STR;
+        } else if kind == MatchKind::Fuzzy || target[..start].chars().next_back().is_some_and(|char| !char.is_alphanumeric()) {
+            // Fuzzy ranges always start at a word, whereas substrings may not
+            breakdown.prefix += SCORE_PREFIX - start as u32;
+        }
+    }
+    debug_assert_eq!(breakdown.score(), string_score.score);
+    Some(breakdown)
+}
+
+fn describe(out: &mut String, target: &str, breakdown: &ScoreBreakdown) {
+    let kind = match breakdown.kind {
+        MatchKind::Substring => "substring",
+        MatchKind::Fuzzy => "fuzzy",
+    };
+    let _ = writeln!(
+        out,
+        "  {target:?}: {kind} match on {:?}, score {} (contiguity {} + start {} + word prefix {})",
+        breakdown.sections,
+        breakdown.score(),
+        breakdown.contiguity,
+        breakdown.start,
+        breakdown.prefix,
+    );
+}
+
+/// Explain why one target ranks above another for a search, e.g. to diagnose a reported
+/// "wrong" ranking.
+///
+/// # Returns
+///
+/// A human-readable, multi-line comparison of the two scorings: how each target matched, and
+/// which bonuses differed between them and by how much. Targets are ranked the same as by
+/// [`fuzzy_filter`](crate::fuzzy_filter), so equal scores are explained by the tie-break on the
+/// target text.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::explain_pair;
+///
+/// let explanation = explain_pair("app", "Apple", "Pineapple");
+/// assert!(explanation.starts_with("\"Apple\" ranks above \"Pineapple\""));
+/// assert!(explanation.contains("start: +1000"));
+/// ```
+pub fn explain_pair(query: &str, a: &str, b: &str) -> String {
+    let search = query.trim().to_lowercase();
+    let mut out = String::new();
+
+    let (a_breakdown, b_breakdown) = match (breakdown(a, &search), breakdown(b, &search)) {
+        (Some(a_breakdown), Some(b_breakdown)) => (a_breakdown, b_breakdown),
+        (Some(_), None) => return format!("{a:?} ranks above {b:?} for {query:?}, as {b:?} doesn't match"),
+        (None, Some(_)) => return format!("{b:?} ranks above {a:?} for {query:?}, as {a:?} doesn't match"),
+        (None, None) => return format!("neither {a:?} nor {b:?} match {query:?}"),
+    };
+
+    let result = |item, breakdown: &ScoreBreakdown| FuzzyFilterResult { item, score: breakdown.score(), highlights: None, ranges: None, index: None };
+    let ((above, above_breakdown), (below, below_breakdown)) =
+        match result(a, &a_breakdown).cmp(&result(b, &b_breakdown)) {
+            Ordering::Less => ((b, &b_breakdown), (a, &a_breakdown)),
+            _ => ((a, &a_breakdown), (b, &b_breakdown)),
+        };
+
+    let _ = writeln!(
+        out,
+        "{above:?} ranks above {below:?} for {query:?} ({} vs {})",
+        above_breakdown.score(),
+        below_breakdown.score(),
+    );
+    describe(&mut out, above, above_breakdown);
+    describe(&mut out, below, below_breakdown);
+
+    if above_breakdown.score() == below_breakdown.score() {
+        let _ = write!(out, "The scores are equal, so {above:?} ranks first as it sorts first alphabetically");
+        return out
+    }
+
+    let _ = write!(out, "Differences:");
+    let bonuses = [
+        ("contiguity", above_breakdown.contiguity, below_breakdown.contiguity),
+        ("start", above_breakdown.start, below_breakdown.start),
+        ("word prefix", above_breakdown.prefix, below_breakdown.prefix),
+    ];
+    for (name, above_bonus, below_bonus) in bonuses.into_iter().filter(|(_, above, below)| above != below) {
+        let _ = write!(out, "\n  {name}: {:+} ({above_bonus} vs {below_bonus})", above_bonus as i64 - below_bonus as i64);
+    }
+    out
+}
@@ -0,0 +1,203 @@
+//! Import and export of whole corpora in a portable format.
+
+use std::io::{self, ErrorKind};
+#[cfg(any(feature = "json", feature = "msgpack"))]
+use std::io::{Read, Write};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Corpus, ItemId, Normalization, UsageBoost};
+
+/// The version of the [`CorpusExport`] format.
+const EXPORT_FORMAT: u32 = 1;
+
+/// A single item of a [`CorpusExport`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedItem {
+    /// The ID of the item, which is preserved on import.
+    pub id: u64,
+    /// The text of the item.
+    pub text: String,
+    /// The normalization profile of the item. See [`Corpus::insert_normalized`].
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub normalization: Normalization,
+    /// The aliases of the item, in the order they were added. See [`Corpus::add_alias`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    /// The tags of the item, in sorted order. See [`Corpus::add_tag`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Whether the item is pinned. See [`Corpus::set_pinned`].
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pinned: bool,
+    /// The decayed selection count of the item. See [`Corpus::selection_count`].
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub selections: f32,
+}
+
+fn is_default(normalization: &Normalization) -> bool {
+    *normalization == Normalization::default()
+}
+
+fn is_zero(count: &f32) -> bool {
+    *count == 0.0
+}
+
+/// The portable form of a whole [`Corpus`], created by [`Corpus::export`].
+///
+/// This is only available with the `serde` feature.
+///
+/// # Format
+///
+/// The document can be serialized with any serde format. JSON (with the `json` feature) and
+/// MessagePack (with the `msgpack` feature) are supported directly by [`Corpus::to_json`] and
+/// [`Corpus::to_msgpack`]. In JSON, a document looks like:
+///
+/// ```json
+/// {
+///   "format": 1,
+///   "next_id": 3,
+///   "usage_boost": { "score_per_selection": 100, "half_life": 50, "max_boost": 1000 },
+///   "items": [
+///     { "id": 0, "text": "Slay the dragon", "tags": ["quest"], "pinned": true, "selections": 2.5 },
+///     { "id": 1, "text": "Settings", "aliases": ["prefs", "options"] },
+///     { "id": 2, "text": "Audio settings" }
+///   ]
+/// }
+/// ```
+///
+/// `normalization` (one of `"unicode"`, `"german"` or `"turkish"`), `aliases`, `tags`, `pinned`
+/// and `selections` may be omitted, defaulting to Unicode normalization, no aliases or tags,
+/// unpinned and never selected. `selections` is the decayed selection count of the item at the time of export.
+/// Derived search data isn't exported, and is recomputed on import.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CorpusExport {
+    /// The version of the format, currently `1`.
+    pub format: u32,
+    /// The ID that the corpus will assign to the next inserted item.
+    pub next_id: u64,
+    /// The configuration of the selection boost. See [`Corpus::set_usage_boost`].
+    pub usage_boost: UsageBoost,
+    /// The items, in order of their IDs.
+    pub items: Vec<ExportedItem>,
+}
+
+/// Create an [`io::Error`] for invalid imported data.
+fn invalid_data(err: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, err)
+}
+
+impl Corpus {
+    /// Export the items of the corpus, along with their IDs, aliases, tags, pins and selection
+    /// counts.
+    pub fn export(&self) -> CorpusExport {
+        let mut items: Vec<ExportedItem> = self
+            .iter()
+            .map(|(id, text)| {
+                let mut tags: Vec<String> = self.tags(id).into_iter().map(str::to_string).collect();
+                tags.sort_unstable();
+                ExportedItem {
+                    id: id.0,
+                    text: text.to_string(),
+                    normalization: self.normalization(id).unwrap_or_default(),
+                    aliases: self.aliases(id).into_iter().map(str::to_string).collect(),
+                    tags,
+                    pinned: self.is_pinned(id),
+                    selections: self.selection_count(id),
+                }
+            })
+            .collect();
+        items.sort_unstable_by_key(|item| item.id);
+
+        CorpusExport {
+            format: EXPORT_FORMAT,
+            next_id: self.next_id,
+            usage_boost: self.usage.config,
+            items,
+        }
+    }
+
+    /// Build a corpus from exported data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] of kind [`ErrorKind::InvalidData`] if the format version isn't
+    /// supported, or multiple items have the same ID.
+    pub fn import(data: CorpusExport) -> io::Result<Self> {
+        if data.format != EXPORT_FORMAT {
+            return Err(invalid_data(format!("unsupported corpus format {}", data.format)))
+        }
+
+        let mut corpus = Corpus::new();
+        corpus.set_usage_boost(data.usage_boost);
+        for item in data.items {
+            let id = ItemId(item.id);
+            if corpus.contains(id) {
+                return Err(invalid_data(format!("duplicate item ID {}", item.id)))
+            }
+
+            corpus.insert_with_id(id, item.text, item.normalization);
+            for alias in item.aliases {
+                corpus.add_alias(id, alias);
+            }
+            for tag in item.tags {
+                corpus.add_tag(id, tag);
+            }
+            corpus.set_pinned(id, item.pinned);
+            Arc::make_mut(&mut corpus.usage).restore(id, item.selections);
+        }
+        corpus.next_id = corpus.next_id.max(data.next_id);
+        Ok(corpus)
+    }
+
+    /// Write the corpus as JSON. See [`Corpus::export`].
+    ///
+    /// This is only available with the `json` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if writing fails.
+    #[cfg(feature = "json")]
+    pub fn to_json(&self, writer: impl Write) -> io::Result<()> {
+        serde_json::to_writer(writer, &self.export()).map_err(io::Error::from)
+    }
+
+    /// Read a corpus from JSON. See [`Corpus::import`].
+    ///
+    /// This is only available with the `json` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if reading fails, or the JSON isn't a valid corpus.
+    #[cfg(feature = "json")]
+    pub fn from_json(reader: impl Read) -> io::Result<Self> {
+        let data = serde_json::from_reader(reader).map_err(io::Error::from)?;
+        Self::import(data)
+    }
+
+    /// Write the corpus as MessagePack. See [`Corpus::export`].
+    ///
+    /// This is only available with the `msgpack` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if writing fails.
+    #[cfg(feature = "msgpack")]
+    pub fn to_msgpack(&self, mut writer: impl Write) -> io::Result<()> {
+        rmp_serde::encode::write_named(&mut writer, &self.export()).map_err(io::Error::other)
+    }
+
+    /// Read a corpus from MessagePack. See [`Corpus::import`].
+    ///
+    /// This is only available with the `msgpack` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if reading fails, or the data isn't a valid corpus.
+    #[cfg(feature = "msgpack")]
+    pub fn from_msgpack(reader: impl Read) -> io::Result<Self> {
+        let data = rmp_serde::from_read(reader).map_err(invalid_data)?;
+        Self::import(data)
+    }
+}
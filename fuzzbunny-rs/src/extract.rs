@@ -0,0 +1,52 @@
+//! Extraction of the best matching choice for a query.
+
+use crate::{score_prepared, FuzzyFilterResult, StringScore};
+
+/// Find the single best match for a query among a set of choices, e.g. to map free-text input
+/// to a known canonical value.
+///
+/// Choices are scored the same as by [`fuzzy_filter`](crate::fuzzy_filter), and only the best
+/// is kept, so this is cheaper than filtering and taking the first result. Only the best match
+/// has its highlights computed.
+///
+/// # Returns
+///
+/// The best match, ranked the same as the first result of `fuzzy_filter`, or [`None`] if no
+/// choice matches with a score of at least `cutoff`. An empty query matches the first
+/// non-empty choice with a score of 0.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::extract_one;
+///
+/// let countries = ["United Kingdom", "United States of America", "New Zealand"];
+///
+/// let best = extract_one("usa", countries, 0).unwrap();
+/// assert_eq!(best.item, "United States of America");
+///
+/// // "new" matches, but not well enough
+/// assert!(extract_one("new", countries, 5000).is_none());
+/// ```
+pub fn extract_one<'c>(query: &str, choices: impl IntoIterator<Item = &'c str>, cutoff: u32) -> Option<FuzzyFilterResult<'c>> {
+    let search = query.trim().to_lowercase();
+
+    let mut best: Option<(&'c str, StringScore)> = None;
+    for choice in choices {
+        let Some(string_score) = score_prepared(choice, None, None, &search) else { continue };
+        if string_score.score < cutoff {
+            continue
+        }
+        // Equal scores rank the smaller choice first, and otherwise the earliest choice. As with
+        // `fuzzy_filter`, the matches of an empty search aren't ranked
+        let is_better = best.as_ref().is_none_or(|(best_choice, best_score)| {
+            string_score.score > best_score.score
+                || (string_score.score == best_score.score && !search.is_empty() && choice < *best_choice)
+        });
+        if is_better {
+            best = Some((choice, string_score));
+        }
+    }
+
+    best.map(|(choice, string_score)| FuzzyFilterResult::matched(choice, string_score.score, string_score.ranges))
+}
@@ -0,0 +1,129 @@
+//! Searches across several independent corpora, with per-corpus weights.
+
+use std::cmp::Ordering;
+
+use crate::{instrument, map_items, sort_results, Corpus, CorpusMatch};
+
+/// A filter result from a [`FederatedSearch`], attributed to the corpus it came from.
+#[derive(Debug, PartialEq, Eq)]
+pub struct FederatedMatch<'c> {
+    /// The name of the corpus holding the matched item.
+    pub source: &'c str,
+    /// The match within that corpus, with its score already weighted.
+    pub matched: CorpusMatch<'c>,
+}
+
+impl<'c> PartialOrd for FederatedMatch<'c> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+impl<'c> Ord for FederatedMatch<'c> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_attributed((&self.matched, self.source), (&other.matched, other.source))
+    }
+}
+
+/// Compare two matches attributed to named sources, ranking identical matches from different
+/// sources by source name.
+pub(crate) fn cmp_attributed(a: (&CorpusMatch<'_>, &str), b: (&CorpusMatch<'_>, &str)) -> Ordering {
+    a.0.cmp(b.0).then_with(|| b.1.cmp(a.1))
+}
+
+/// A single weighted corpus of a [`FederatedSearch`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Source<'c> {
+    pub(crate) name: &'c str,
+    pub(crate) corpus: &'c Corpus,
+    pub(crate) weight: f32,
+}
+
+/// A search across several corpora at once, such as commands, files and players.
+///
+/// Each corpus is given a name, used to attribute its results, and a weight, that its match
+/// scores are multiplied by so more important corpora rank higher. The corpora are searched in
+/// parallel if the `rayon` feature is enabled, and the results merged into a single list.
+///
+/// Unlike a [`ShardedCorpus`](crate::ShardedCorpus), the corpora are only borrowed, so they can
+/// be owned and mutated independently between searches.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{Corpus, FederatedSearch};
+///
+/// let commands = Corpus::from_iter(["Open file", "Close window"]);
+/// let files = Corpus::from_iter(["open.txt", "notes.txt"]);
+///
+/// let search = FederatedSearch::new()
+///     .with_corpus("commands", &commands, 2.0)
+///     .with_corpus("files", &files, 1.0);
+///
+/// let results = search.search("open");
+/// assert_eq!(results[0].source, "commands");
+/// assert_eq!(results[1].source, "files");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FederatedSearch<'c> {
+    sources: Vec<Source<'c>>,
+}
+
+impl<'c> FederatedSearch<'c> {
+    /// Create a search without any corpora.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a corpus to the search.
+    ///
+    /// The scores of its matches are multiplied by `weight` (and rounded), so a weight of `1.0`
+    /// leaves them unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `weight` is negative or not finite.
+    pub fn with_corpus(mut self, name: &'c str, corpus: &'c Corpus, weight: f32) -> Self {
+        assert!(weight.is_finite() && weight >= 0.0, "corpus weight must be finite and non-negative");
+        self.sources.push(Source { name, corpus, weight });
+        self
+    }
+
+    /// Search every corpus and return the merged, weighted and sorted matches.
+    ///
+    /// Each corpus is searched as with [`Corpus::search`]. For an empty search, the results of
+    /// each corpus are returned in the order the corpora were added, with pinned items first.
+    pub fn search(&self, search: &str) -> Vec<FederatedMatch<'c>> {
+        search_sources("federated_search", &self.sources, search)
+    }
+}
+
+/// Search every source and return the merged, weighted and sorted matches, recorded under
+/// `span_name`. See [`FederatedSearch::search`].
+pub(crate) fn search_sources<'c>(span_name: &'static str, sources: &[Source<'c>], search: &str) -> Vec<FederatedMatch<'c>> {
+    let parallel = sources.len() > 1;
+    let items = sources.iter().map(|source| source.corpus.len()).sum();
+    let span = instrument::FilterSpan::enter(span_name, items, search.len(), parallel);
+
+    let source_results = map_items(sources, parallel, |source| {
+        source.corpus
+            .search(search)
+            .into_iter()
+            .map(|mut matched| {
+                if source.weight != 1.0 {
+                    matched.result.score = (matched.result.score as f32 * source.weight).round() as u32;
+                }
+                FederatedMatch { source: source.name, matched }
+            })
+            .collect::<Vec<_>>()
+    });
+    let mut results: Vec<_> = source_results.into_iter().flatten().collect();
+
+    if !search.is_empty() {
+        let stage = instrument::Stage::start("merge");
+        sort_results(&mut results, parallel);
+        stage.finish(results.len(), results.len());
+    } else {
+        results.sort_by_key(|res| !res.matched.pinned);
+    }
+
+    span.finish(results.len());
+    results
+}
@@ -0,0 +1,187 @@
+//! Targets made of several independently scored and weighted fields.
+
+use core::cmp::Ordering;
+
+use crate::pipeline::Derived;
+use crate::normalize::map_ranges;
+use crate::prelude::*;
+use crate::scoring::round_score;
+use crate::{
+    collect_matches_by, highlights_from_ranges, instrument, is_parallel_workload, score_prepared, sort_results,
+    Highlights,
+};
+
+/// A single field of a [`MultiFieldTarget`].
+#[derive(Debug, Clone)]
+struct Field {
+    text: String,
+    weight: f64,
+    /// The lower case form and skip indices of `text`.
+    derived: Derived,
+}
+
+/// A target made of several fields (e.g. the name, description and tags of an item), each
+/// scored independently and weighted.
+///
+/// Unlike a [`CompositeTarget`](crate::CompositeTarget), a search has to match within a single
+/// field, and matches in fields with higher weights rank higher, e.g. so a match in the name of
+/// an item ranks above the same match in its description. The score of a target is the highest
+/// weighted score of its fields, where a field's score is multiplied by its weight.
+///
+/// The lower case form and skip indices of each field are precomputed on construction.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{fuzzy_filter_fields, MultiFieldTarget};
+///
+/// let targets = vec![
+///     MultiFieldTarget::weighted([("Hammer", 2.0), ("Drives nails", 1.0)]),
+///     MultiFieldTarget::weighted([("Nail Gun", 2.0), ("Drives nails with compressed air", 1.0)]),
+/// ];
+///
+/// let results = fuzzy_filter_fields(&targets, "nail");
+/// assert_eq!(results[0].target.field(0), Some("Nail Gun"));
+/// assert_eq!(results[0].fields[0].highlights, vec!["", "Nail", " Gun"]);
+/// assert_eq!(results[1].fields[0].field, 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MultiFieldTarget {
+    fields: Vec<Field>,
+}
+
+impl PartialEq for MultiFieldTarget {
+    fn eq(&self, other: &Self) -> bool {
+        self.fields.len() == other.fields.len()
+            && self.fields.iter().zip(&other.fields).all(|(a, b)| a.text == b.text && a.weight == b.weight)
+    }
+}
+
+impl MultiFieldTarget {
+    /// Build a target from its fields, in order, each with a weight of 1.
+    pub fn new<S: AsRef<str>>(fields: impl IntoIterator<Item = S>) -> Self {
+        MultiFieldTarget::weighted(fields.into_iter().map(|field| (field, 1.0)))
+    }
+
+    /// Build a target from its fields and their weights, in order.
+    ///
+    /// Weights are usually positive, and a weight of 0 gives the field's matches a score of 0.
+    pub fn weighted<S: AsRef<str>>(fields: impl IntoIterator<Item = (S, f64)>) -> Self {
+        let fields = fields
+            .into_iter()
+            .map(|(text, weight)| {
+                let text = text.as_ref();
+                Field { text: text.to_string(), weight, derived: Derived::new(text) }
+            })
+            .collect();
+        MultiFieldTarget { fields }
+    }
+
+    /// The number of fields.
+    pub fn field_count(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Get the text of a field.
+    pub fn field(&self, field: usize) -> Option<&str> {
+        self.fields.get(field).map(|field| field.text.as_str())
+    }
+
+    /// Get the weight of a field.
+    pub fn weight(&self, field: usize) -> Option<f64> {
+        self.fields.get(field).map(|field| field.weight)
+    }
+
+    /// The total length of the fields, in bytes.
+    fn len(&self) -> usize {
+        self.fields.iter().map(|field| field.text.len()).sum()
+    }
+
+    /// Score each field of this target against a lower case search string.
+    fn filter(&self, search: &str) -> Option<MultiFieldFilterResult<'_>> {
+        let mut score = None;
+        let mut fields = vec![];
+        for (index, field) in self.fields.iter().enumerate() {
+            let derived = &field.derived;
+            let Some(string_score) = score_prepared(&field.text, Some(&derived.lower), Some(&derived.skips), search) else {
+                continue
+            };
+            let weighted = round_score(f64::from(string_score.score) * field.weight);
+            score = score.max(Some(weighted));
+            fields.push(FieldMatch {
+                field: index,
+                score: weighted,
+                highlights: highlights_from_ranges(&field.text, map_ranges(string_score.ranges, &derived.offsets)),
+            });
+        }
+
+        Some(MultiFieldFilterResult { target: self, score: score?, fields })
+    }
+}
+
+/// A match within a single field of a [`MultiFieldTarget`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldMatch<'t> {
+    /// The index of the field, in the order the fields were given.
+    pub field: usize,
+    /// The weighted score of the match.
+    pub score: u32,
+    /// The text of the field split into alternating unmatched and matched sections, the same
+    /// as [`FuzzyFilterResult::highlights`](crate::FuzzyFilterResult::highlights).
+    pub highlights: Highlights<'t>,
+}
+
+/// Filter result for a [`MultiFieldTarget`], with the highlights of each matched field.
+#[derive(Debug, Clone)]
+pub struct MultiFieldFilterResult<'t> {
+    /// The matched target.
+    pub target: &'t MultiFieldTarget,
+    /// The highest weighted score of the matched fields.
+    pub score: u32,
+    /// The matches of each field that matched, in field order.
+    pub fields: Vec<FieldMatch<'t>>,
+}
+
+impl PartialEq for MultiFieldFilterResult<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.score == other.score && self.target == other.target
+    }
+}
+impl Eq for MultiFieldFilterResult<'_> {}
+
+impl PartialOrd for MultiFieldFilterResult<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+impl Ord for MultiFieldFilterResult<'_> {
+    /// The same order as [`FuzzyFilterResult`](crate::FuzzyFilterResult): by score, then the
+    /// target with the smaller fields ranks higher.
+    fn cmp(&self, other: &Self) -> Ordering {
+        let texts = |result: &Self| result.target.fields.iter().map(|field| field.text.as_str()).collect::<Vec<_>>();
+        self.score.cmp(&other.score).then_with(|| texts(other).cmp(&texts(self)))
+    }
+}
+
+/// Search a slice of [`MultiFieldTarget`]s and return a filtered and sorted vector of
+/// [`MultiFieldFilterResult`].
+///
+/// Each field is matched the same as [`fuzzy_filter`](crate::fuzzy_filter) matches an item,
+/// and a target matches if any of its fields do. As with `fuzzy_filter`, the results of an
+/// empty search aren't sorted.
+pub fn fuzzy_filter_fields<'t>(targets: &'t [MultiFieldTarget], search: &str) -> Vec<MultiFieldFilterResult<'t>> {
+    let search_lower_cased = search.trim().to_lowercase();
+    let parallel = is_parallel_workload(targets.len(), |i| targets[i].len(), search_lower_cased.len());
+    let span = instrument::FilterSpan::enter("fuzzy_filter_fields", targets.len(), search_lower_cased.len(), parallel);
+
+    let stage = instrument::Stage::start("score");
+    let mut results = collect_matches_by(targets, parallel, |target| target.filter(&search_lower_cased));
+    stage.finish(targets.len(), results.len());
+
+    if !search.is_empty() {
+        let stage = instrument::Stage::start("sort");
+        sort_results(&mut results, parallel);
+        stage.finish(results.len(), results.len());
+    }
+
+    span.finish(results.len());
+    results
+}
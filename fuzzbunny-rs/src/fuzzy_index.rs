@@ -0,0 +1,516 @@
+//! An owned, incrementally updated index of targets.
+//!
+//! # Format
+//!
+//! A saved index (see [`FuzzyIndex::save`]) starts with the 4 byte magic `FZBF`, a single
+//! format version byte (currently `1`), the `u32` scoring version it was built with and a flags
+//! byte, where bit 0 is set if the index keeps an n-gram index. The body is the next ID to
+//! assign and the number of items, followed by each item in order:
+//!
+//! |Field|Encoding|
+//! |---|---|
+//! |ID|Delta from the previous ID (or 0)|
+//! |Text|Length, UTF-8 text|
+//! |Lower case text|Length, UTF-8 text|
+//! |Skip indices|Count, then the delta from the previous index (or 0) of each|
+//! |Offsets|Count, then the delta from the previous offset (or 0) of each (none unless lower casing changed the text length)|
+//!
+//! The index ends with a `u64` FNV-1a checksum of every preceding byte. Fixed size integers are
+//! encoded as little-endian, and every other integer as an unsigned LEB128 varint. The n-gram
+//! index itself isn't saved, as it's rebuilt from the lower case texts and skip indices.
+
+use std::cmp::Ordering;
+use std::io::{self, ErrorKind, Read, Write};
+
+use crate::pipeline::Derived;
+use crate::index::{fnv1a, invalid_index};
+use crate::ngram::NgramIndex;
+use crate::normalize::map_ranges;
+use crate::{
+    collect_matches_by, instrument, is_parallel_workload, score_prepared, sort_results, FuzzyFilterResult, ItemId,
+    SCORING_VERSION,
+};
+
+const SAVED_INDEX_MAGIC: &[u8; 4] = b"FZBF";
+const SAVED_INDEX_VERSION: u8 = 1;
+/// The flag set if a saved index keeps an n-gram index.
+const NGRAM_INDEX_FLAG: u8 = 1;
+
+/// An item of a [`FuzzyIndex`], with its precomputed search data.
+#[derive(Debug, Clone)]
+struct IndexEntry {
+    id: ItemId,
+    text: String,
+    /// The lower case form and skip indices of `text`.
+    derived: Derived,
+}
+
+impl IndexEntry {
+    fn new(id: ItemId, text: String) -> Self {
+        let derived = Derived::new(&text);
+        IndexEntry { id, text, derived }
+    }
+
+    /// Score this entry against a lower case search string.
+    fn filter(&self, search: &str) -> Option<IndexMatch<'_>> {
+        let derived = &self.derived;
+        let string_score = score_prepared(&self.text, Some(&derived.lower), Some(&derived.skips), search)?;
+        Some(IndexMatch {
+            id: self.id,
+            result: FuzzyFilterResult::matched(&self.text, string_score.score, map_ranges(string_score.ranges, &derived.offsets)),
+        })
+    }
+}
+
+/// An owned index of strings, along with their precomputed lower case forms and skip indices,
+/// that can be updated incrementally.
+///
+/// Unlike a vector of [`Target`](crate::Target)s, the index owns its strings, so long-lived
+/// applications don't have to keep the source strings alive, and inserting, removing or
+/// updating an item only recomputes the search data of that item. Each item is identified by
+/// the [`ItemId`] assigned when it's inserted, which stays the same until it's removed.
+///
+/// This is a lean alternative to a [`Corpus`](crate::Corpus), without support for pinning,
+/// tags, aliases or usage boosts, or sharing the search data of identical items.
+///
+/// For large indexes, an inverted n-gram index can be kept alongside the items with
+/// [`FuzzyIndex::with_ngram_index`], so searches only score the items that could match them.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::FuzzyIndex;
+///
+/// let mut index = FuzzyIndex::new();
+/// let paris = index.insert("Paris");
+/// let perth = index.insert("Perth");
+/// assert_eq!(index.search("pa")[0].id, paris);
+///
+/// index.update(perth, "Pasadena");
+/// index.remove(paris);
+/// let results = index.search("pa");
+/// assert_eq!(results.len(), 1);
+/// assert_eq!(results[0].result.highlights, Some(vec!["", "Pa", "sadena"]));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FuzzyIndex {
+    /// The items, in ascending order of ID, which is also insertion order.
+    entries: Vec<IndexEntry>,
+    next_id: u64,
+    /// The n-grams of every item, if enabled.
+    ngrams: Option<NgramIndex>,
+}
+
+/// A filter result for an item in a [`FuzzyIndex`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct IndexMatch<'i> {
+    /// The ID of the matched item.
+    pub id: ItemId,
+    /// The match result, including the item text, score and highlights.
+    pub result: FuzzyFilterResult<'i>,
+}
+
+impl PartialOrd for IndexMatch<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+impl Ord for IndexMatch<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Identical items are ranked in insertion order
+        self.result.cmp(&other.result).then_with(|| other.id.cmp(&self.id))
+    }
+}
+
+impl FuzzyIndex {
+    /// Create an empty index.
+    pub fn new() -> Self {
+        FuzzyIndex::default()
+    }
+
+    /// Keep an inverted n-gram index of the items, which is used to skip scoring the items that
+    /// can't match a search.
+    ///
+    /// Every character of a search has to appear in a match, and each pair of consecutive
+    /// characters has to either appear next to each other or the second has to start a word, so
+    /// only the items sharing those n-grams with a search are scored. This is much faster for
+    /// searches of more than a couple of characters over large indexes (e.g. millions of items),
+    /// at the cost of the memory used by the n-gram index and slower updates. The results are
+    /// always the same as without the n-gram index.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fuzzbunny_rs::FuzzyIndex;
+    ///
+    /// let mut index = FuzzyIndex::new().with_ngram_index();
+    /// index.extend(["United States of America", "United Kingdom", "Australia"]);
+    ///
+    /// let results = index.search("usam");
+    /// assert_eq!(results[0].result.item, "United States of America");
+    /// ```
+    pub fn with_ngram_index(mut self) -> Self {
+        if self.ngrams.is_none() {
+            let mut ngrams = NgramIndex::default();
+            for entry in &self.entries {
+                ngrams.insert(entry.id, &entry.derived.lower, &entry.derived.skips);
+            }
+            self.ngrams = Some(ngrams);
+        }
+        self
+    }
+
+    /// Whether the index keeps an n-gram index. See [`FuzzyIndex::with_ngram_index`].
+    pub fn has_ngram_index(&self) -> bool {
+        self.ngrams.is_some()
+    }
+
+    /// The number of items in the index.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the index has no items.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The position of an item in `entries`.
+    fn position(&self, id: ItemId) -> Option<usize> {
+        self.entries.binary_search_by_key(&id, |entry| entry.id).ok()
+    }
+
+    /// Insert a new item into the index.
+    ///
+    /// # Returns
+    ///
+    /// The [`ItemId`] assigned to the new item.
+    pub fn insert(&mut self, text: impl Into<String>) -> ItemId {
+        let id = ItemId(self.next_id);
+        self.next_id += 1;
+        let entry = IndexEntry::new(id, text.into());
+        if let Some(ngrams) = &mut self.ngrams {
+            ngrams.insert(id, &entry.derived.lower, &entry.derived.skips);
+        }
+        self.entries.push(entry);
+        id
+    }
+
+    /// Remove an item from the index.
+    ///
+    /// This is O(n) in the size of the index, as the remaining items are kept in order.
+    ///
+    /// # Returns
+    ///
+    /// The text of the removed item, or [`None`] if there is no item with the given ID.
+    pub fn remove(&mut self, id: ItemId) -> Option<String> {
+        let position = self.position(id)?;
+        let entry = self.entries.remove(position);
+        if let Some(ngrams) = &mut self.ngrams {
+            ngrams.remove(id, &entry.derived.lower, &entry.derived.skips);
+        }
+        Some(entry.text)
+    }
+
+    /// Replace the text of an item, recomputing the search data for that item only.
+    ///
+    /// # Returns
+    ///
+    /// The previous text of the item, or [`None`] if there is no item with the given ID.
+    pub fn update(&mut self, id: ItemId, text: impl Into<String>) -> Option<String> {
+        let position = self.position(id)?;
+        let entry = std::mem::replace(&mut self.entries[position], IndexEntry::new(id, text.into()));
+        if let Some(ngrams) = &mut self.ngrams {
+            let derived = &self.entries[position].derived;
+            ngrams.remove(id, &entry.derived.lower, &entry.derived.skips);
+            ngrams.insert(id, &derived.lower, &derived.skips);
+        }
+        Some(entry.text)
+    }
+
+    /// Get the text of an item.
+    pub fn get(&self, id: ItemId) -> Option<&str> {
+        self.position(id).map(|position| self.entries[position].text.as_str())
+    }
+
+    /// Whether the index has an item with the given ID.
+    pub fn contains(&self, id: ItemId) -> bool {
+        self.position(id).is_some()
+    }
+
+    /// Iterate over the items of the index in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (ItemId, &str)> + '_ {
+        self.entries.iter().map(|entry| (entry.id, entry.text.as_str()))
+    }
+
+    /// Search the index and return the sorted matches.
+    ///
+    /// This behaves the same as [`fuzzy_filter`](crate::fuzzy_filter) over the items of the
+    /// index, with every result also carrying the [`ItemId`] of the matched item.
+    pub fn search(&self, search: &str) -> Vec<IndexMatch<'_>> {
+        self.search_boosted(search, |_, _| 0)
+    }
+
+    /// Search the index, boosting the score of each match, and return the sorted matches.
+    ///
+    /// `boost` is called with the ID and text of every matched item, and returns the score added
+    /// to its match (or subtracted, if negative), without affecting whether an item matches at
+    /// all. See [`fuzzy_filter_boosted`](crate::fuzzy_filter_boosted).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fuzzbunny_rs::FuzzyIndex;
+    ///
+    /// let mut index = FuzzyIndex::new();
+    /// index.insert("Git: Commit");
+    /// let clone = index.insert("Git: Clone");
+    ///
+    /// let results = index.search_boosted("git c", |id, _| if id == clone { 500 } else { 0 });
+    /// assert_eq!(results[0].id, clone);
+    /// ```
+    pub fn search_boosted<'i>(&'i self, search: &str, boost: impl Fn(ItemId, &str) -> i32 + Sync) -> Vec<IndexMatch<'i>> {
+        let search_lower_cased = search.trim().to_lowercase();
+        let entries = &self.entries;
+
+        // The positions of the entries to score, if the n-gram index rules any out
+        let candidates: Option<Vec<usize>> = self.ngrams.as_ref().and_then(|ngrams| {
+            let stage = instrument::Stage::start("prefilter");
+            let ids = ngrams.candidates(&search_lower_cased)?;
+            let positions: Vec<usize> = ids.into_iter().filter_map(|id| self.position(id)).collect();
+            stage.finish(entries.len(), positions.len());
+            Some(positions)
+        });
+        let count = candidates.as_ref().map_or(entries.len(), Vec::len);
+        let entry = |i: usize| &entries[candidates.as_ref().map_or(i, |positions| positions[i])];
+
+        let parallel = is_parallel_workload(count, |i| entry(i).text.len(), search_lower_cased.len());
+        let span = instrument::FilterSpan::enter("fuzzy_index_search", entries.len(), search_lower_cased.len(), parallel);
+
+        let stage = instrument::Stage::start("score");
+        let filter = |entry: &'i IndexEntry| {
+            let mut found = entry.filter(&search_lower_cased)?;
+            found.result.score = found.result.score.saturating_add_signed(boost(entry.id, &entry.text));
+            Some(found)
+        };
+        let mut matches = match &candidates {
+            Some(positions) => collect_matches_by(positions, parallel, |&i| filter(&entries[i])),
+            None => collect_matches_by(entries, parallel, filter),
+        };
+        stage.finish(count, matches.len());
+
+        if !search.is_empty() {
+            let stage = instrument::Stage::start("sort");
+            sort_results(&mut matches, parallel);
+            stage.finish(matches.len(), matches.len());
+        }
+
+        span.finish(matches.len());
+        matches
+    }
+}
+
+/// Append an unsigned LEB128 varint.
+fn write_varint(buffer: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buffer.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    buffer.push(value as u8);
+}
+
+/// Append a string, prefixed with its length.
+fn write_text(buffer: &mut Vec<u8>, text: &str) {
+    write_varint(buffer, text.len() as u64);
+    buffer.extend_from_slice(text.as_bytes());
+}
+
+/// Append ascending values, prefixed with their count, as the deltas between them.
+fn write_deltas(buffer: &mut Vec<u8>, values: &[usize]) {
+    write_varint(buffer, values.len() as u64);
+    let mut previous = 0;
+    for &value in values {
+        write_varint(buffer, (value - previous) as u64);
+        previous = value;
+    }
+}
+
+fn read_varint(reader: &mut &[u8]) -> io::Result<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = reader.split_first().ok_or_else(|| invalid_index("truncated index"))?;
+        *reader = rest;
+        let bits = u64::from(byte & 0x7f);
+        if bits << shift >> shift != bits {
+            break
+        }
+        value |= bits << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value)
+        }
+    }
+    Err(invalid_index("invalid varint in index"))
+}
+
+/// Read a length or count, which must fit in the remaining bytes of the index as each element
+/// takes at least a byte.
+fn read_len(reader: &mut &[u8]) -> io::Result<usize> {
+    let len = read_varint(reader)?;
+    if len > reader.len() as u64 {
+        return Err(invalid_index("truncated index"))
+    }
+    Ok(len as usize)
+}
+
+fn read_text(reader: &mut &[u8]) -> io::Result<String> {
+    let len = read_len(reader)?;
+    let (text, rest) = reader.split_at(len);
+    *reader = rest;
+    String::from_utf8(text.to_vec()).map_err(|err| io::Error::new(ErrorKind::InvalidData, err))
+}
+
+fn read_deltas(reader: &mut &[u8]) -> io::Result<Vec<usize>> {
+    let count = read_len(reader)?;
+    let mut values = Vec::with_capacity(count);
+    let mut previous = 0usize;
+    for _ in 0..count {
+        let delta = usize::try_from(read_varint(reader)?).map_err(|_| invalid_index("invalid index entry"))?;
+        previous = previous.checked_add(delta).ok_or_else(|| invalid_index("invalid index entry"))?;
+        values.push(previous);
+    }
+    Ok(values)
+}
+
+impl FuzzyIndex {
+    /// Save the index, including the precomputed search data of every item, so it can be
+    /// loaded with [`FuzzyIndex::load`] without recomputing it.
+    ///
+    /// The index is written in a compact binary format (see the [module docs](self)), in a
+    /// single write of a buffer of the whole index, so `writer` needn't be buffered.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if writing fails.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fuzzbunny_rs::FuzzyIndex;
+    ///
+    /// let index: FuzzyIndex = ["Paris", "Perth", "Pasadena"].into_iter().collect();
+    /// let mut bytes = vec![];
+    /// index.save(&mut bytes)?;
+    ///
+    /// let loaded = FuzzyIndex::load(bytes.as_slice())?;
+    /// assert_eq!(loaded.search("pa")[0].result.item, "Paris");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn save(&self, mut writer: impl Write) -> io::Result<()> {
+        let mut buffer = vec![];
+        buffer.extend_from_slice(SAVED_INDEX_MAGIC);
+        buffer.push(SAVED_INDEX_VERSION);
+        buffer.extend_from_slice(&SCORING_VERSION.to_le_bytes());
+        buffer.push(if self.ngrams.is_some() { NGRAM_INDEX_FLAG } else { 0 });
+        write_varint(&mut buffer, self.next_id);
+        write_varint(&mut buffer, self.entries.len() as u64);
+
+        let mut previous_id = 0;
+        for entry in &self.entries {
+            write_varint(&mut buffer, entry.id.0 - previous_id);
+            previous_id = entry.id.0;
+            write_text(&mut buffer, &entry.text);
+            write_text(&mut buffer, &entry.derived.lower);
+            write_deltas(&mut buffer, &entry.derived.skips);
+            write_deltas(&mut buffer, &entry.derived.offsets);
+        }
+
+        buffer.extend_from_slice(&fnv1a(&buffer).to_le_bytes());
+        writer.write_all(&buffer)
+    }
+
+    /// Load an index saved by [`FuzzyIndex::save`], without recomputing the search data of its
+    /// items. If the saved index kept an n-gram index, it's rebuilt.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if reading fails. Returns an error of kind
+    /// [`ErrorKind::InvalidData`] if the index is corrupt (its checksum doesn't match), or was
+    /// saved with a different format or scoring version, in which case its search data could
+    /// produce wrong results.
+    pub fn load(mut reader: impl Read) -> io::Result<Self> {
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes)?;
+
+        if bytes.len() < 8 {
+            return Err(invalid_index("truncated index"))
+        }
+        let (body, checksum) = bytes.split_at(bytes.len() - 8);
+        if fnv1a(body).to_le_bytes() != checksum {
+            return Err(invalid_index("index checksum mismatch"))
+        }
+        if body.len() < 10 || &body[..4] != SAVED_INDEX_MAGIC || body[4] != SAVED_INDEX_VERSION {
+            return Err(invalid_index("not a supported index"))
+        }
+        if body[5..9] != SCORING_VERSION.to_le_bytes() {
+            return Err(invalid_index("index built with a different scoring version"))
+        }
+        let flags = body[9];
+        if flags & !NGRAM_INDEX_FLAG != 0 {
+            return Err(invalid_index("not a supported index"))
+        }
+
+        let mut reader = &body[10..];
+        let mut index = FuzzyIndex::new();
+        index.next_id = read_varint(&mut reader)?;
+        let count = read_len(&mut reader)?;
+        index.entries.reserve(count);
+
+        for i in 0..count {
+            let previous_id = index.entries.last().map_or(0, |entry: &IndexEntry| entry.id.0);
+            let delta = read_varint(&mut reader)?;
+            // IDs are strictly ascending, and below the next ID to assign
+            let id = previous_id.checked_add(delta).filter(|&id| (i == 0 || delta > 0) && id < index.next_id);
+            let id = ItemId(id.ok_or_else(|| invalid_index("invalid item ID in index"))?);
+
+            let text = read_text(&mut reader)?;
+            let lower = read_text(&mut reader)?;
+            let skips = read_deltas(&mut reader)?;
+            let offsets = read_deltas(&mut reader)?;
+
+            // Malformed skips or offsets would otherwise cause out of bounds slicing when scoring,
+            // or slicing part-way through a character
+            let offsets_valid = match offsets.last() {
+                Some(&last) => offsets.len() == lower.len() + 1 && last == text.len(),
+                None => lower.len() == text.len(),
+            };
+            let boundaries_valid = skips.iter().all(|&skip| lower.is_char_boundary(skip))
+                && offsets.iter().all(|&offset| text.is_char_boundary(offset));
+            if skips.last() != Some(&lower.len()) || !offsets_valid || !boundaries_valid {
+                return Err(invalid_index("invalid index entry"))
+            }
+            index.entries.push(IndexEntry { id, text, derived: Derived { lower, skips, offsets } });
+        }
+
+        if !reader.is_empty() {
+            return Err(invalid_index("trailing data in index"))
+        }
+        if flags & NGRAM_INDEX_FLAG != 0 {
+            index = index.with_ngram_index();
+        }
+        Ok(index)
+    }
+}
+
+impl<S: Into<String>> FromIterator<S> for FuzzyIndex {
+    fn from_iter<I: IntoIterator<Item = S>>(items: I) -> Self {
+        let mut index = FuzzyIndex::new();
+        index.extend(items);
+        index
+    }
+}
+
+impl<S: Into<String>> Extend<S> for FuzzyIndex {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, items: I) {
+        for text in items {
+            self.insert(text);
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//! Grouping of identical targets, so duplicates are scored and returned once.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use crate::{collect_matches_by, filter_target, instrument, is_parallel_workload, sort_results, FuzzyFilterResult, Target};
+
+/// Filter result for a group of identical targets, returned by [`fuzzy_filter_grouped`].
+#[derive(Debug, Clone)]
+pub struct GroupedFilterResult<'a> {
+    /// The match result of the targets, with the [`index`](FuzzyFilterResult::index) of the
+    /// first of them.
+    pub result: FuzzyFilterResult<'a>,
+    /// The indices of every target in the group, in ascending order.
+    pub indices: Vec<usize>,
+}
+
+impl PartialEq for GroupedFilterResult<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+impl Eq for GroupedFilterResult<'_> {}
+
+impl PartialOrd for GroupedFilterResult<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+impl Ord for GroupedFilterResult<'_> {
+    /// The same order as [`FuzzyFilterResult`], with groups of identical items ranked in the
+    /// order of their first targets.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.result.cmp(&other.result).then_with(|| other.indices.first().cmp(&self.indices.first()))
+    }
+}
+
+/// Search a slice of [`Target`]s, grouping the targets with identical strings, and return a
+/// filtered and sorted vector of [`GroupedFilterResult`], with one result per group.
+///
+/// Each group is only scored once, with the skip indices of its first target, so lists with
+/// many duplicate strings (e.g. the same product name in different categories) don't repeat
+/// them in the results or pay for scoring them again. Strings are only grouped if they're
+/// byte for byte identical.
+///
+/// This returns the same results as [`fuzzy_filter`](crate::fuzzy_filter) with duplicates
+/// removed. As with `fuzzy_filter`, the results of an empty search aren't sorted, and are left
+/// in the order of the first targets of the groups.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{fuzzy_filter_grouped, precompute_skips_for_items};
+///
+/// let targets = precompute_skips_for_items(vec!["Apple", "Banana", "Apple", "Pineapple", "Apple"]);
+///
+/// let results = fuzzy_filter_grouped(&targets, "apple");
+/// assert_eq!(results.len(), 2);
+/// assert_eq!(results[0].result.item, "Apple");
+/// assert_eq!(results[0].indices, vec![0, 2, 4]);
+/// assert_eq!(results[1].indices, vec![3]);
+/// ```
+pub fn fuzzy_filter_grouped<'a>(items: &[Target<'a>], search: &str) -> Vec<GroupedFilterResult<'a>> {
+    let search_lower_cased = search.trim().to_lowercase();
+
+    // The indices of the targets of each group, in the order of their first targets
+    let mut positions: HashMap<&str, usize> = HashMap::new();
+    let mut groups: Vec<Vec<usize>> = vec![];
+    for (index, target) in items.iter().enumerate() {
+        match positions.get(target.0) {
+            Some(&position) => groups[position].push(index),
+            None => {
+                positions.insert(target.0, groups.len());
+                groups.push(vec![index]);
+            },
+        }
+    }
+
+    let parallel = is_parallel_workload(groups.len(), |i| items[groups[i][0]].0.len(), search_lower_cased.len());
+    let span = instrument::FilterSpan::enter("fuzzy_filter_grouped", items.len(), search_lower_cased.len(), parallel);
+
+    let stage = instrument::Stage::start("score");
+    let mut results = collect_matches_by(&groups, parallel, |indices| {
+        let result = filter_target(&items[indices[0]], &search_lower_cased)?;
+        Some(GroupedFilterResult { result: result.with_index(indices[0]), indices: indices.clone() })
+    });
+    stage.finish(groups.len(), results.len());
+
+    if !search.is_empty() {
+        let stage = instrument::Stage::start("sort");
+        sort_results(&mut results, parallel);
+        stage.finish(results.len(), results.len());
+    }
+
+    span.finish(results.len());
+    results
+}
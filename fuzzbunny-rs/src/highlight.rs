@@ -0,0 +1,206 @@
+//! Rendering of highlights as HTML, ANSI escape codes or markdown.
+//!
+//! [`Highlights`] alternate between unmatched and matched sections of a target, so rendering
+//! them wraps every other section in markup. The [`to_html`], [`to_ansi`] and [`to_markdown`]
+//! functions cover the common formats, and [`render`] accepts any [`Formatter`] for others.
+//!
+//! Each function accepts anything that can be split into highlights (see [`Highlighted`]),
+//! including [`Highlights`], a [`FuzzyFilterResult`], or a target with its matched ranges.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use fuzzbunny_rs::{fuzzy_match, highlight};
+//!
+//! let result = fuzzy_match("Fish & Chips", Some("chi")).unwrap();
+//!
+//! assert_eq!(highlight::to_html(&result, "mark", None), "Fish &amp; <mark>Chi</mark>ps");
+//! assert_eq!(highlight::to_markdown(&result), "Fish & **Chi**ps");
+//! assert_eq!(highlight::to_ansi(&result, "1;31"), "Fish & \x1b[1;31mChi\x1b[0mps");
+//! ```
+
+use crate::prelude::*;
+use crate::{highlights_from_ranges, FuzzyFilterResult, FuzzyFilterResultOwned, Highlights, Range};
+
+/// A source of [`Highlights`] to render.
+pub trait Highlighted {
+    /// The highlights, alternating between unmatched and matched sections.
+    fn highlights(&self) -> Highlights<'_>;
+}
+
+impl Highlighted for [&str] {
+    fn highlights(&self) -> Highlights<'_> {
+        self.to_vec()
+    }
+}
+
+impl Highlighted for Vec<&str> {
+    fn highlights(&self) -> Highlights<'_> {
+        self.clone()
+    }
+}
+
+/// A target with the ranges matched in it.
+impl Highlighted for (&str, &[Range]) {
+    fn highlights(&self) -> Highlights<'_> {
+        highlights_from_ranges(self.0, self.1.to_vec())
+    }
+}
+
+/// The highlights of a result, or its whole item unmatched if it has none.
+impl Highlighted for FuzzyFilterResult<'_> {
+    fn highlights(&self) -> Highlights<'_> {
+        match &self.highlights {
+            Some(highlights) => highlights.clone(),
+            None => vec![self.item],
+        }
+    }
+}
+
+/// The highlights of an owned result, or its whole item unmatched if it has no ranges.
+impl Highlighted for FuzzyFilterResultOwned {
+    fn highlights(&self) -> Highlights<'_> {
+        self.highlights().unwrap_or_else(|| vec![&self.item])
+    }
+}
+
+impl<T: Highlighted + ?Sized> Highlighted for &T {
+    fn highlights(&self) -> Highlights<'_> {
+        (**self).highlights()
+    }
+}
+
+/// A format that highlights are rendered in, for [`render`].
+pub trait Formatter {
+    /// Append a matched section to the output.
+    fn matched(&self, section: &str, out: &mut String);
+
+    /// Append an unmatched section to the output. By default, the section is appended as is.
+    fn unmatched(&self, section: &str, out: &mut String) {
+        out.push_str(section);
+    }
+}
+
+/// Render highlights with a [`Formatter`].
+pub fn render(highlights: impl Highlighted, formatter: &impl Formatter) -> String {
+    let highlights = highlights.highlights();
+    let mut out = String::with_capacity(highlights.iter().map(|section| section.len()).sum());
+
+    // Highlights alternate between unmatched and matched sections
+    for (i, section) in highlights.iter().enumerate() {
+        if i % 2 == 1 {
+            formatter.matched(section, &mut out);
+        } else {
+            formatter.unmatched(section, &mut out);
+        }
+    }
+    out
+}
+
+/// Append a text to an HTML string, escaping the characters with special meanings in HTML
+/// content and attribute values.
+fn push_html_escaped(text: &str, out: &mut String) {
+    for char in text.chars() {
+        match char {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(char),
+        }
+    }
+}
+
+/// Renders matched sections as HTML elements, escaping every section.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Html<'f> {
+    /// The name of the element that matched sections are wrapped in, e.g. `mark`.
+    pub tag: &'f str,
+    /// The class of the element, if any.
+    pub class: Option<&'f str>,
+}
+
+impl Formatter for Html<'_> {
+    fn matched(&self, section: &str, out: &mut String) {
+        out.push('<');
+        out.push_str(self.tag);
+        if let Some(class) = self.class {
+            out.push_str(" class=\"");
+            push_html_escaped(class, out);
+            out.push('"');
+        }
+        out.push('>');
+        push_html_escaped(section, out);
+        out.push_str("</");
+        out.push_str(self.tag);
+        out.push('>');
+    }
+
+    fn unmatched(&self, section: &str, out: &mut String) {
+        push_html_escaped(section, out);
+    }
+}
+
+/// Renders matched sections with ANSI escape codes, for terminals.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Ansi<'f> {
+    /// The SGR parameters of the matched sections, e.g. `1;31` for bold red.
+    pub style: &'f str,
+}
+
+impl Formatter for Ansi<'_> {
+    fn matched(&self, section: &str, out: &mut String) {
+        out.push_str("\x1b[");
+        out.push_str(self.style);
+        out.push('m');
+        out.push_str(section);
+        out.push_str("\x1b[0m");
+    }
+}
+
+/// Renders matched sections in bold markdown, escaping the characters with special meanings in
+/// markdown.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Markdown;
+
+impl Markdown {
+    /// Append a text, escaping the characters that could be taken as markdown.
+    fn push_escaped(text: &str, out: &mut String) {
+        for char in text.chars() {
+            if matches!(char, '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|' | '~') {
+                out.push('\\');
+            }
+            out.push(char);
+        }
+    }
+}
+
+impl Formatter for Markdown {
+    fn matched(&self, section: &str, out: &mut String) {
+        out.push_str("**");
+        Markdown::push_escaped(section, out);
+        out.push_str("**");
+    }
+
+    fn unmatched(&self, section: &str, out: &mut String) {
+        Markdown::push_escaped(section, out);
+    }
+}
+
+/// Render highlights as HTML, wrapping the matched sections in `tag` elements with an optional
+/// `class`. Every section is HTML escaped.
+pub fn to_html(highlights: impl Highlighted, tag: &str, class: Option<&str>) -> String {
+    render(highlights, &Html { tag, class })
+}
+
+/// Render highlights for a terminal, styling the matched sections with the ANSI SGR parameters
+/// in `style`, e.g. `1;31` for bold red.
+pub fn to_ansi(highlights: impl Highlighted, style: &str) -> String {
+    render(highlights, &Ansi { style })
+}
+
+/// Render highlights as markdown, with the matched sections in bold. Every section is escaped.
+pub fn to_markdown(highlights: impl Highlighted) -> String {
+    render(highlights, &Markdown)
+}
@@ -0,0 +1,270 @@
+//! Persisted corpus indexes, holding the precomputed search data of every item.
+//!
+//! # Format
+//!
+//! An index starts with the 4 byte magic `FZBI`, a single format version byte (currently `2`)
+//! and the `u32` scoring version it was built with. The body is the `u64` next ID to assign and
+//! the `u64` number of entries, followed by one entry per distinct text:
+//!
+//! |Field|Encoding|
+//! |---|---|
+//! |IDs|`u32` count, then a `u64` per ID|
+//! |Text|`u32` length, UTF-8 text|
+//! |Normalization profile|A single byte, `0` for Unicode, `1` for German or `2` for Turkish|
+//! |Lower case text|`u32` length, UTF-8 text|
+//! |Skip indices|`u32` count, then a `u64` per index|
+//! |Offsets|`u32` count, then a `u64` per offset (none unless normalization changed the text length)|
+//!
+//! The index ends with a `u64` FNV-1a checksum of every preceding byte. All integers are
+//! encoded as little-endian.
+
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use crate::pipeline::Derived;
+use crate::{Corpus, ItemId, Normalization, SCORING_VERSION};
+
+const INDEX_MAGIC: &[u8; 4] = b"FZBI";
+const INDEX_VERSION: u8 = 2;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// The 64-bit FNV-1a hash of some bytes.
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(FNV_OFFSET_BASIS, |hash, &byte| (hash ^ byte as u64).wrapping_mul(FNV_PRIME))
+}
+
+/// Create an [`io::Error`] for an invalid index.
+pub(crate) fn invalid_index(message: &str) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, message)
+}
+
+fn write_len(buffer: &mut Vec<u8>, len: usize) -> io::Result<()> {
+    let len = u32::try_from(len).map_err(|_| io::Error::new(ErrorKind::InvalidInput, "index entry too long"))?;
+    buffer.extend_from_slice(&len.to_le_bytes());
+    Ok(())
+}
+
+fn write_text(buffer: &mut Vec<u8>, text: &str) -> io::Result<()> {
+    write_len(buffer, text.len())?;
+    buffer.extend_from_slice(text.as_bytes());
+    Ok(())
+}
+
+fn write_usizes(buffer: &mut Vec<u8>, values: &[usize]) -> io::Result<()> {
+    write_len(buffer, values.len())?;
+    for &value in values {
+        buffer.extend_from_slice(&(value as u64).to_le_bytes());
+    }
+    Ok(())
+}
+
+fn read_u8(reader: &mut &[u8]) -> io::Result<u8> {
+    let mut bytes = [0; 1];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes[0])
+}
+
+fn read_u32(reader: &mut &[u8]) -> io::Result<u32> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64(reader: &mut &[u8]) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn read_usizes(reader: &mut &[u8]) -> io::Result<Vec<usize>> {
+    let count = read_u32(reader)?;
+    let mut values = Vec::with_capacity(count.min(1024) as usize);
+    for _ in 0..count {
+        values.push(read_u64(reader)? as usize);
+    }
+    Ok(values)
+}
+
+fn read_text(reader: &mut &[u8]) -> io::Result<String> {
+    let len = read_u32(reader)? as usize;
+    if len > reader.len() {
+        return Err(invalid_index("truncated index entry"))
+    }
+    let (text, rest) = reader.split_at(len);
+    *reader = rest;
+    String::from_utf8(text.to_vec()).map_err(|err| io::Error::new(ErrorKind::InvalidData, err))
+}
+
+impl Corpus {
+    /// Write an index of the corpus, including the precomputed search data of every item.
+    ///
+    /// Only the items and their IDs are included, and not any tags, pins or selection counts.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if writing fails.
+    pub fn write_index(&self, mut writer: impl Write) -> io::Result<()> {
+        let mut buffer = vec![];
+        buffer.extend_from_slice(INDEX_MAGIC);
+        buffer.push(INDEX_VERSION);
+        buffer.extend_from_slice(&SCORING_VERSION.to_le_bytes());
+        buffer.extend_from_slice(&self.next_id.to_le_bytes());
+
+        let entries = self.storage.items.iter().filter(|item| !item.is_tombstone());
+        buffer.extend_from_slice(&(self.unique_len() as u64).to_le_bytes());
+        for item in entries {
+            write_len(&mut buffer, item.ids.len())?;
+            for id in &item.ids {
+                buffer.extend_from_slice(&id.0.to_le_bytes());
+            }
+            write_text(&mut buffer, &item.text)?;
+            buffer.push(item.normalization.index() as u8);
+
+            let derived = item.derived.get().unwrap_or_else(|| Arc::new(item.derive()));
+            write_text(&mut buffer, &derived.lower)?;
+            write_usizes(&mut buffer, &derived.skips)?;
+            write_usizes(&mut buffer, &derived.offsets)?;
+        }
+
+        buffer.extend_from_slice(&fnv1a(&buffer).to_le_bytes());
+        writer.write_all(&buffer)
+    }
+
+    /// Read a corpus from an index written by [`Corpus::write_index`], without recomputing any
+    /// search data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if reading fails. Returns an error of kind
+    /// [`ErrorKind::InvalidData`] if the index is corrupt (its checksum doesn't match), or was
+    /// written with a different format or scoring version, in which case its search data could
+    /// produce wrong results.
+    pub fn read_index(mut reader: impl Read) -> io::Result<Self> {
+        let mut bytes = vec![];
+        reader.read_to_end(&mut bytes)?;
+
+        if bytes.len() < 8 {
+            return Err(invalid_index("truncated index"))
+        }
+        let (body, checksum) = bytes.split_at(bytes.len() - 8);
+        if fnv1a(body).to_le_bytes() != checksum {
+            return Err(invalid_index("index checksum mismatch"))
+        }
+        if body.len() < 9 || &body[..4] != INDEX_MAGIC || body[4] != INDEX_VERSION {
+            return Err(invalid_index("not a supported index"))
+        }
+
+        let mut reader = &body[5..];
+        if read_u32(&mut reader)? != SCORING_VERSION {
+            return Err(invalid_index("index built with a different scoring version"))
+        }
+        Self::read_index_body(&mut reader).map_err(|err| match err.kind() {
+            ErrorKind::UnexpectedEof => invalid_index("truncated index"),
+            _ => err,
+        })
+    }
+
+    fn read_index_body(reader: &mut &[u8]) -> io::Result<Self> {
+        let mut corpus = Corpus::new();
+        corpus.next_id = read_u64(reader)?;
+        let storage = Arc::make_mut(&mut corpus.storage);
+
+        let entries = read_u64(reader)?;
+        for _ in 0..entries {
+            let id_count = read_u32(reader)?;
+            let mut ids = Vec::with_capacity(id_count.min(1024) as usize);
+            for _ in 0..id_count {
+                let id = ItemId(read_u64(reader)?);
+                if id.0 >= corpus.next_id || storage.positions.contains_key(&id) || ids.contains(&id) {
+                    return Err(invalid_index("invalid item ID in index"))
+                }
+                ids.push(id);
+            }
+
+            let text: Arc<str> = read_text(reader)?.into();
+            let normalization = Normalization::from_index(read_u8(reader)?)
+                .ok_or_else(|| invalid_index("unknown normalization profile in index"))?;
+            let lower = read_text(reader)?;
+            let skips = read_usizes(reader)?;
+            let offsets = read_usizes(reader)?;
+
+            // Malformed skips or offsets would otherwise cause out of bounds slicing when scoring
+            let sorted = |values: &[usize]| values.windows(2).all(|pair| pair[0] <= pair[1]);
+            let offsets_valid = match offsets.last() {
+                Some(&last) => offsets.len() == lower.len() + 1 && last == text.len() && sorted(&offsets),
+                None => lower.len() == text.len(),
+            };
+            let valid = skips.last() == Some(&lower.len())
+                && offsets_valid
+                && (normalization != Normalization::Unicode || !storage.texts.contains_key(&text));
+            if ids.is_empty() || !sorted(&skips) || !valid {
+                return Err(invalid_index("invalid index entry"))
+            }
+            storage.push_entry(ids, text, Derived { lower, skips, offsets }, normalization);
+        }
+
+        if !reader.is_empty() {
+            return Err(invalid_index("trailing data in index"))
+        }
+        Ok(corpus)
+    }
+
+    /// Write an index of the corpus to a file. See [`Corpus::write_index`].
+    ///
+    /// The index is written to a temporary file next to `path`, and then renamed over it, so a
+    /// crash while saving leaves any previous index intact.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if the file can't be written.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let mut tmp_path = path.to_path_buf().into_os_string();
+        tmp_path.push(".tmp");
+        let tmp_path = PathBuf::from(tmp_path);
+
+        let mut writer = BufWriter::new(File::create(&tmp_path)?);
+        self.write_index(&mut writer)?;
+        writer.into_inner().map_err(|err| err.into_error())?.sync_all()?;
+        fs::rename(&tmp_path, path)
+    }
+
+    /// Load a corpus from the index at `index_path`, falling back to rebuilding it from the
+    /// source file at `source_path` (see [`Corpus::from_lines_file`]).
+    ///
+    /// The corpus is rebuilt if the index is missing, corrupt, or was written by a version of
+    /// this crate with a different index format or scoring algorithm, and the rebuilt index is
+    /// then saved to `index_path`. Failing to save the index isn't an error, as the index is only
+    /// a cache of the source.
+    ///
+    /// The index isn't compared against the source file, so it must be saved again (or deleted)
+    /// whenever the source changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if the index is unusable and the source file can't be read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use fuzzbunny_rs::Corpus;
+    ///
+    /// let corpus = Corpus::load("items.idx", "items.txt")?;
+    /// let results = corpus.search("app");
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn load(index_path: impl AsRef<Path>, source_path: impl AsRef<Path>) -> io::Result<Self> {
+        let index_path = index_path.as_ref();
+        if let Ok(corpus) = File::open(index_path).and_then(|file| Corpus::read_index(BufReader::new(file))) {
+            return Ok(corpus)
+        }
+
+        let corpus = Corpus::from_lines_file(source_path)?;
+        let _ = corpus.save(index_path);
+        Ok(corpus)
+    }
+}
@@ -0,0 +1,131 @@
+//! Optional `tracing` instrumentation and telemetry of the filtering stages.
+//!
+//! Each filter call is split into a `"score"` stage and, for non-empty searches, a `"sort"`
+//! stage. Targets are dropped as soon as they fail to match, so filtering is folded into the
+//! `"score"` stage rather than being a stage of its own: its input is the number of targets,
+//! and its output is the number of matches.
+//!
+//! Without the `tracing` feature, and without a telemetry hook set, every call is a no-op, as is
+//! every call without the `std` feature.
+
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+#[cfg(feature = "std")]
+use crate::prelude::*;
+#[cfg(feature = "std")]
+use crate::telemetry::{self, QueryMetrics, StageMetrics};
+
+/// The details of a query that's reported to the telemetry hook, if one was set when it started.
+#[cfg(feature = "std")]
+struct Telemetry {
+    name: &'static str,
+    items: usize,
+    search_len: usize,
+    parallel: bool,
+    start: Instant,
+    depth: usize,
+}
+
+/// A span covering a full filter call, entered for as long as this is held.
+pub(crate) struct FilterSpan {
+    #[cfg(feature = "std")]
+    telemetry: Option<Telemetry>,
+    #[cfg(feature = "tracing")]
+    start: Instant,
+    #[cfg(feature = "tracing")]
+    _span: tracing::span::EnteredSpan,
+}
+
+impl FilterSpan {
+    /// Enter a new filter span for a search over `items` targets.
+    #[inline]
+    #[cfg_attr(not(feature = "std"), allow(unused_variables))]
+    pub(crate) fn enter(name: &'static str, items: usize, search_len: usize, parallel: bool) -> Self {
+        #[cfg(feature = "std")]
+        let telemetry = telemetry::is_enabled().then(|| Telemetry {
+            name,
+            items,
+            search_len,
+            parallel,
+            start: Instant::now(),
+            depth: telemetry::start_query(),
+        });
+        FilterSpan {
+            #[cfg(feature = "std")]
+            telemetry,
+            #[cfg(feature = "tracing")]
+            start: Instant::now(),
+            #[cfg(feature = "tracing")]
+            _span: tracing::debug_span!("fuzzy_filter", name, items, search_len, parallel).entered(),
+        }
+    }
+
+    /// Record the end of the filter call, with the number of results returned.
+    #[inline]
+    #[cfg_attr(not(feature = "std"), allow(unused_variables))]
+    pub(crate) fn finish(self, results: usize) {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(results, elapsed_us = self.start.elapsed().as_micros() as u64, "filter finished");
+
+        #[cfg(feature = "std")]
+        if let Some(query) = self.telemetry {
+            let metrics = QueryMetrics {
+                operation: query.name,
+                query_len: query.search_len,
+                corpus_size: query.items,
+                match_count: results,
+                parallel: query.parallel,
+                stages: vec![],
+                elapsed: query.start.elapsed(),
+            };
+            telemetry::finish_query(query.depth, metrics);
+        }
+    }
+}
+
+/// A single timed stage (e.g. scoring or sorting) within a filter call.
+pub(crate) struct Stage {
+    #[cfg(feature = "std")]
+    name: &'static str,
+    /// When the stage started, if it's timed for `tracing` or telemetry.
+    #[cfg(feature = "std")]
+    start: Option<Instant>,
+}
+
+impl Stage {
+    /// Start timing the named stage.
+    #[inline]
+    #[cfg_attr(not(feature = "std"), allow(unused_variables))]
+    pub(crate) fn start(name: &'static str) -> Self {
+        #[cfg(feature = "std")]
+        let stage = {
+            let timed = cfg!(feature = "tracing") || telemetry::is_enabled();
+            Stage { name, start: timed.then(Instant::now) }
+        };
+        #[cfg(not(feature = "std"))]
+        let stage = Stage {};
+        stage
+    }
+
+    /// Record the end of the stage, with the number of items it processed and produced.
+    #[inline]
+    #[cfg_attr(not(feature = "std"), allow(unused_variables))]
+    pub(crate) fn finish(self, input: usize, output: usize) {
+        #[cfg(feature = "std")]
+        self.record(input, output);
+    }
+
+    #[cfg(feature = "std")]
+    fn record(self, input: usize, output: usize) {
+        let Some(start) = self.start else { return };
+        let elapsed = start.elapsed();
+
+        #[cfg(feature = "tracing")]
+        tracing::trace!(stage = self.name, input, output, elapsed_us = elapsed.as_micros() as u64, "stage finished");
+
+        if telemetry::is_enabled() {
+            telemetry::record_stage(StageMetrics { name: self.name, input, output, elapsed });
+        }
+    }
+}
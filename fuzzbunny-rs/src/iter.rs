@@ -0,0 +1,81 @@
+//! Lazy filtering of targets produced by an iterator.
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::prelude::*;
+use crate::{filter_target, FuzzyFilterResult};
+
+/// An iterator over the matches of a search among the items of another iterator, created by
+/// [`fuzzy_filter_iter`].
+#[derive(Debug, Clone)]
+pub struct FuzzyFilterIter<I> {
+    items: I,
+    /// The trimmed, lower case search.
+    search: String,
+}
+
+impl<'a, I: Iterator<Item = &'a str>> Iterator for FuzzyFilterIter<I> {
+    type Item = FuzzyFilterResult<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let search = &self.search;
+        self.items.find_map(|item| filter_target(&(item, None), search))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.items.size_hint().1)
+    }
+}
+
+/// Lazily search the items of an iterator, yielding a [`FuzzyFilterResult`] for each match.
+///
+/// Unlike [`fuzzy_filter`](crate::fuzzy_filter), the items don't have to be collected up front,
+/// and each item is only scored when the next match is requested, so huge inputs (e.g. the lines
+/// of a log file) can be searched in constant memory, and callers can stop early (e.g. with
+/// [`Iterator::take`]). The matches are yielded in the order of `items`, as ranking them would
+/// require scoring every item first. To keep only the best matches of a huge input, see
+/// [`fuzzy_filter_reader`](crate::fuzzy_filter_reader).
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::fuzzy_filter_iter;
+///
+/// let log = "GET /index.html\nPOST /api/login\nGET /api/users\n";
+///
+/// let mut results = fuzzy_filter_iter(log.lines(), "get api");
+/// assert_eq!(results.next().unwrap().item, "GET /api/users");
+/// assert!(results.next().is_none());
+/// ```
+pub fn fuzzy_filter_iter<'a, I: IntoIterator<Item = &'a str>>(items: I, search: &str) -> FuzzyFilterIter<I::IntoIter> {
+    FuzzyFilterIter { items: items.into_iter(), search: search.trim().to_lowercase() }
+}
+
+/// Lazily search the items of a rayon [`ParallelIterator`], scoring the items in parallel.
+///
+/// This is the parallel form of [`fuzzy_filter_iter`]. As with any parallel iterator, the
+/// matches are processed in no particular order, though collecting them keeps the order of
+/// `items`.
+///
+/// This is only available with the `rayon` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::fuzzy_filter_par_iter;
+/// use rayon::prelude::*;
+///
+/// let items = vec!["apple", "banana", "application"];
+///
+/// let results: Vec<_> = fuzzy_filter_par_iter(items.par_iter().copied(), "app").collect();
+/// assert_eq!(results.len(), 2);
+/// ```
+#[cfg(feature = "rayon")]
+pub fn fuzzy_filter_par_iter<'a>(
+    items: impl ParallelIterator<Item = &'a str>,
+    search: &str,
+) -> impl ParallelIterator<Item = FuzzyFilterResult<'a>> {
+    let search = search.trim().to_lowercase();
+    items.filter_map(move |item| filter_target(&(item, None), &search))
+}
@@ -0,0 +1,314 @@
+//! Append-only journals of corpus mutations, for fast restarts.
+//!
+//! # Format
+//!
+//! A journal starts with the 4 byte magic `FZBJ` and a single format version byte (currently
+//! `1`), followed by any number of entries. Each entry is a single tag byte followed by its
+//! fields, with all integers encoded as little-endian:
+//!
+//! |Tag|Entry|Fields|
+//! |---|---|---|
+//! |`1`|Insert|`u64` ID, `u32` text length, UTF-8 text|
+//! |`2`|Remove|`u64` ID|
+//! |`3`|Update|`u64` ID, `u32` text length, UTF-8 text|
+//! |`4`|Reserve|`u64` next ID to assign|
+//!
+//! A truncated final entry (e.g. from a crash mid-write) is ignored on replay.
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufReader, BufWriter, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
+
+use crate::{Corpus, ItemId, Normalization};
+
+const JOURNAL_MAGIC: &[u8; 4] = b"FZBJ";
+const JOURNAL_VERSION: u8 = 1;
+
+const TAG_INSERT: u8 = 1;
+const TAG_REMOVE: u8 = 2;
+const TAG_UPDATE: u8 = 3;
+const TAG_RESERVE: u8 = 4;
+
+/// A single mutation recorded in a journal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JournalEntry {
+    /// An item was inserted with the given ID.
+    Insert { id: ItemId, text: String },
+    /// An item was removed.
+    Remove { id: ItemId },
+    /// The text of an item was replaced.
+    Update { id: ItemId, text: String },
+    /// IDs below `next_id` have been assigned, and must not be reused.
+    Reserve { next_id: u64 },
+}
+
+impl JournalEntry {
+    /// Apply this entry to a corpus.
+    pub fn apply(&self, corpus: &mut Corpus) {
+        match self {
+            JournalEntry::Insert { id, text } => corpus.insert_with_id(*id, text.clone(), Normalization::Unicode),
+            JournalEntry::Update { id, text } => {
+                // Updates keep the normalization profile of the item, as with `Corpus::update`
+                let normalization = corpus.normalization(*id).unwrap_or_default();
+                corpus.insert_with_id(*id, text.clone(), normalization)
+            },
+            JournalEntry::Remove { id } => {
+                corpus.remove(*id);
+            },
+            JournalEntry::Reserve { next_id } => corpus.next_id = corpus.next_id.max(*next_id),
+        }
+    }
+}
+
+/// Writes [`JournalEntry`] records to an underlying writer.
+#[derive(Debug)]
+pub struct JournalWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> JournalWriter<W> {
+    /// Start a new journal, writing the header to `writer`.
+    pub fn new(mut writer: W) -> io::Result<Self> {
+        writer.write_all(JOURNAL_MAGIC)?;
+        writer.write_all(&[JOURNAL_VERSION])?;
+        Ok(JournalWriter { writer })
+    }
+
+    /// Continue an existing journal, where `writer` is positioned after its last entry.
+    pub fn append(writer: W) -> Self {
+        JournalWriter { writer }
+    }
+
+    /// Append an entry to the journal.
+    pub fn record(&mut self, entry: &JournalEntry) -> io::Result<()> {
+        match entry {
+            JournalEntry::Insert { id, text } => self.write_text_entry(TAG_INSERT, *id, text),
+            JournalEntry::Update { id, text } => self.write_text_entry(TAG_UPDATE, *id, text),
+            JournalEntry::Remove { id } => {
+                self.writer.write_all(&[TAG_REMOVE])?;
+                self.writer.write_all(&id.0.to_le_bytes())
+            },
+            JournalEntry::Reserve { next_id } => {
+                self.writer.write_all(&[TAG_RESERVE])?;
+                self.writer.write_all(&next_id.to_le_bytes())
+            },
+        }
+    }
+
+    /// Flush any buffered entries to the underlying writer.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Unwrap the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn write_text_entry(&mut self, tag: u8, id: ItemId, text: &str) -> io::Result<()> {
+        let len = u32::try_from(text.len()).map_err(|_| io::Error::new(ErrorKind::InvalidInput, "item text too long"))?;
+        self.writer.write_all(&[tag])?;
+        self.writer.write_all(&id.0.to_le_bytes())?;
+        self.writer.write_all(&len.to_le_bytes())?;
+        self.writer.write_all(text.as_bytes())
+    }
+}
+
+/// Read every entry from a journal.
+///
+/// # Errors
+///
+/// Returns an [`io::Error`] if reading fails, or the journal header or an entry is invalid.
+/// A truncated header or final entry is not an error, and is skipped.
+pub fn read_journal(reader: impl Read) -> io::Result<Vec<JournalEntry>> {
+    read_journal_entries(reader).map(|(entries, _)| entries)
+}
+
+/// Read every entry from a journal, along with whether the final entry was truncated.
+fn read_journal_entries(reader: impl Read) -> io::Result<(Vec<JournalEntry>, bool)> {
+    let mut reader = BufReader::new(reader);
+
+    let mut header = vec![];
+    (&mut reader).take(5).read_to_end(&mut header)?;
+    let expected = [&JOURNAL_MAGIC[..], &[JOURNAL_VERSION]].concat();
+    if header.len() < expected.len() && expected.starts_with(&header) {
+        // A crash before the header was fully written leaves a journal without any entries
+        return Ok((vec![], true))
+    }
+    if header != expected {
+        return Err(io::Error::new(ErrorKind::InvalidData, "not a supported journal"))
+    }
+
+    let mut entries = vec![];
+    loop {
+        match read_entry(&mut reader) {
+            Ok(Some(entry)) => entries.push(entry),
+            Ok(None) => return Ok((entries, false)),
+            Err(err) if err.kind() == ErrorKind::UnexpectedEof => return Ok((entries, true)),
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Read a single entry, or [`None`] at the end of the journal.
+fn read_entry(reader: &mut impl Read) -> io::Result<Option<JournalEntry>> {
+    let mut tag = [0; 1];
+    if reader.read(&mut tag)? == 0 {
+        return Ok(None)
+    }
+
+    let entry = match tag[0] {
+        TAG_INSERT => JournalEntry::Insert { id: ItemId(read_u64(reader)?), text: read_text(reader)? },
+        TAG_REMOVE => JournalEntry::Remove { id: ItemId(read_u64(reader)?) },
+        TAG_UPDATE => JournalEntry::Update { id: ItemId(read_u64(reader)?), text: read_text(reader)? },
+        TAG_RESERVE => JournalEntry::Reserve { next_id: read_u64(reader)? },
+        _ => return Err(io::Error::new(ErrorKind::InvalidData, "unknown journal entry")),
+    };
+    Ok(Some(entry))
+}
+
+fn read_u64(reader: &mut impl Read) -> io::Result<u64> {
+    let mut bytes = [0; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+fn read_text(reader: &mut impl Read) -> io::Result<String> {
+    let mut len = [0; 4];
+    reader.read_exact(&mut len)?;
+    let len = u32::from_le_bytes(len) as u64;
+    // Read no more than is available, rather than trusting the length to allocate
+    let mut bytes = vec![];
+    reader.take(len).read_to_end(&mut bytes)?;
+    if (bytes.len() as u64) < len {
+        return Err(ErrorKind::UnexpectedEof.into())
+    }
+    String::from_utf8(bytes).map_err(|err| io::Error::new(ErrorKind::InvalidData, err))
+}
+
+/// A [`Corpus`] whose mutations are recorded to an append-only journal file.
+///
+/// On startup, [`JournaledCorpus::open`] replays the journal to rebuild the corpus, so
+/// applications with slowly growing corpora never need to re-serialize the whole corpus. As
+/// removals and updates leave stale entries behind, the journal can be rewritten to the minimal
+/// set of entries with [`JournaledCorpus::compact`].
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use fuzzbunny_rs::JournaledCorpus;
+///
+/// let mut corpus = JournaledCorpus::open("items.journal")?;
+/// corpus.insert("apple")?;
+///
+/// let results = corpus.corpus().search("app");
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct JournaledCorpus {
+    corpus: Corpus,
+    path: PathBuf,
+    journal: JournalWriter<BufWriter<File>>,
+}
+
+impl JournaledCorpus {
+    /// Open a journaled corpus, replaying the journal at `path` if it exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if the journal can't be read or created, or is invalid.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut corpus = Corpus::new();
+
+        let (journal, truncated) = match File::open(&path) {
+            Ok(file) => {
+                let (entries, truncated) = read_journal_entries(file)?;
+                for entry in entries {
+                    entry.apply(&mut corpus);
+                }
+                let file = OpenOptions::new().append(true).open(&path)?;
+                (JournalWriter::append(BufWriter::new(file)), truncated)
+            },
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                (JournalWriter::new(BufWriter::new(File::create(&path)?))?, false)
+            },
+            Err(err) => return Err(err),
+        };
+
+        let mut journaled = JournaledCorpus { corpus, path, journal };
+        if truncated {
+            // Appending after a truncated entry would make the following entries unreadable
+            journaled.compact()?;
+        }
+        Ok(journaled)
+    }
+
+    /// The current corpus.
+    pub fn corpus(&self) -> &Corpus {
+        &self.corpus
+    }
+
+    /// Insert a new item, and record it in the journal. See [`Corpus::insert`].
+    pub fn insert(&mut self, text: impl Into<String>) -> io::Result<ItemId> {
+        let text = text.into();
+        let id = ItemId(self.corpus.next_id);
+        self.journal.record(&JournalEntry::Insert { id, text: text.clone() })?;
+        Ok(self.corpus.insert(text))
+    }
+
+    /// Remove an item, and record it in the journal. See [`Corpus::remove`].
+    pub fn remove(&mut self, id: ItemId) -> io::Result<Option<String>> {
+        if !self.corpus.contains(id) {
+            return Ok(None)
+        }
+        self.journal.record(&JournalEntry::Remove { id })?;
+        Ok(self.corpus.remove(id))
+    }
+
+    /// Update an item, and record it in the journal. See [`Corpus::update`].
+    pub fn update(&mut self, id: ItemId, text: impl Into<String>) -> io::Result<Option<String>> {
+        if !self.corpus.contains(id) {
+            return Ok(None)
+        }
+        let text = text.into();
+        self.journal.record(&JournalEntry::Update { id, text: text.clone() })?;
+        Ok(self.corpus.update(id, text))
+    }
+
+    /// Flush any buffered journal entries to disk.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.journal.flush()
+    }
+
+    /// Rewrite the journal to contain only the current items.
+    ///
+    /// The compacted journal is written to a temporary file next to the journal, and then
+    /// renamed over it, so a crash during compaction leaves the original journal intact.
+    pub fn compact(&mut self) -> io::Result<()> {
+        self.journal.flush()?;
+
+        let mut tmp_path = self.path.clone().into_os_string();
+        tmp_path.push(".compact");
+        let tmp_path = PathBuf::from(tmp_path);
+
+        let mut journal = JournalWriter::new(BufWriter::new(File::create(&tmp_path)?))?;
+        journal.record(&JournalEntry::Reserve { next_id: self.corpus.next_id })?;
+        for (id, text) in self.corpus.iter() {
+            journal.record(&JournalEntry::Insert { id, text: text.to_string() })?;
+        }
+        journal.flush()?;
+        journal.into_inner().into_inner().map_err(|err| err.into_error())?.sync_all()?;
+
+        fs::rename(&tmp_path, &self.path)?;
+        let file = OpenOptions::new().append(true).open(&self.path)?;
+        self.journal = JournalWriter::append(BufWriter::new(file));
+        Ok(())
+    }
+}
+
+impl Drop for JournaledCorpus {
+    fn drop(&mut self) {
+        let _ = self.journal.flush();
+    }
+}
@@ -0,0 +1,112 @@
+//! Searching arbitrary items by a text key.
+
+use core::cmp::Ordering;
+
+use crate::prelude::*;
+use crate::{
+    collect_matches_by, highlights_from_ranges, instrument, is_parallel_workload, score_prepared, sort_results,
+    Highlights,
+};
+
+/// Filter result for an item matched by [`fuzzy_filter_map`], referencing the original item.
+#[derive(Debug)]
+pub struct FuzzyFilterResultRef<'a, T> {
+    /// The original item that was matched.
+    pub item: &'a T,
+    /// The index of the item in the searched slice.
+    pub index: usize,
+    /// The key of the item that was matched against.
+    pub key: &'a str,
+    /// The score of the match.
+    pub score: u32,
+    /// The key split into alternating unmatched and matched sections, the same as
+    /// [`FuzzyFilterResult::highlights`](crate::FuzzyFilterResult::highlights).
+    pub highlights: Highlights<'a>,
+}
+
+impl<T> Clone for FuzzyFilterResultRef<'_, T> {
+    fn clone(&self) -> Self {
+        FuzzyFilterResultRef { highlights: self.highlights.clone(), ..*self }
+    }
+}
+
+impl<T> PartialEq for FuzzyFilterResultRef<'_, T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.score == other.score && self.index == other.index && self.key == other.key
+    }
+}
+impl<T> Eq for FuzzyFilterResultRef<'_, T> {}
+
+impl<T> PartialOrd for FuzzyFilterResultRef<'_, T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+impl<T> Ord for FuzzyFilterResultRef<'_, T> {
+    /// The same order as [`FuzzyFilterResult`](crate::FuzzyFilterResult): by score, then the
+    /// smaller key ranks higher, with items of identical keys ranked in slice order.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score
+            .cmp(&other.score)
+            .then_with(|| other.key.cmp(self.key))
+            .then_with(|| other.index.cmp(&self.index))
+    }
+}
+
+/// Search a slice of arbitrary items by a text key and return a filtered and sorted vector of
+/// [`FuzzyFilterResultRef`], each referencing the original item.
+///
+/// This matches the key of each item (e.g. the name of a struct) the same as
+/// [`fuzzy_filter`](crate::fuzzy_filter) matches a target, so applications don't have to keep
+/// a parallel vector of targets and map results back to their items. As with `fuzzy_filter`,
+/// the results of an empty search aren't sorted.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::fuzzy_filter_map;
+///
+/// struct Hero { name: String, power: &'static str }
+///
+/// let heroes = vec![
+///     Hero { name: "Claire Bennet".to_string(), power: "Regeneration" },
+///     Hero { name: "Hiro Nakamura".to_string(), power: "Time travel" },
+/// ];
+///
+/// let results = fuzzy_filter_map(&heroes, "hn", |hero| &hero.name);
+/// assert_eq!(results[0].item.power, "Time travel");
+/// assert_eq!(results[0].index, 1);
+/// assert_eq!(results[0].highlights, vec!["", "H", "iro ", "N", "akamura"]);
+/// ```
+pub fn fuzzy_filter_map<'a, T: Sync>(
+    items: &'a [T],
+    search: &str,
+    key_fn: impl Fn(&'a T) -> &'a str + Sync + Send,
+) -> Vec<FuzzyFilterResultRef<'a, T>> {
+    let search_lower_cased = search.trim().to_lowercase();
+    let parallel = is_parallel_workload(items.len(), |i| key_fn(&items[i]).len(), search_lower_cased.len());
+    let span = instrument::FilterSpan::enter("fuzzy_filter_map", items.len(), search_lower_cased.len(), parallel);
+
+    let stage = instrument::Stage::start("score");
+    let indices: Vec<usize> = (0..items.len()).collect();
+    let mut results = collect_matches_by(&indices, parallel, |&index| {
+        let item = &items[index];
+        let key = key_fn(item);
+        let string_score = score_prepared(key, None, None, &search_lower_cased)?;
+        Some(FuzzyFilterResultRef {
+            item,
+            index,
+            key,
+            score: string_score.score,
+            highlights: highlights_from_ranges(key, string_score.ranges),
+        })
+    });
+    stage.finish(items.len(), results.len());
+
+    if !search.is_empty() {
+        let stage = instrument::Stage::start("sort");
+        sort_results(&mut results, parallel);
+        stage.finish(results.len(), results.len());
+    }
+
+    span.finish(results.len());
+    results
+}
@@ -0,0 +1,120 @@
+//! Corpora that are materialized from an iterator on demand.
+
+use std::collections::HashSet;
+
+use crate::{Corpus, CorpusMatch, ItemId};
+
+/// The default number of items pulled from the source at a time.
+const DEFAULT_BATCH_SIZE: usize = 1024;
+
+/// A [`Corpus`] that pulls its items from an iterator as they're needed, rather than upfront.
+///
+/// Items are pulled in batches, and cached in the materialized corpus so the source is only
+/// ever iterated once. Searches only pull as many items as it takes to find the requested
+/// number of matches, so an expensive (or endless) source doesn't have to be drained before the
+/// first results can be shown.
+///
+/// Items are assigned [`ItemId`]s in the order they're pulled, starting from 0.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::LazyCorpus;
+///
+/// // An endless source of items
+/// let mut corpus = LazyCorpus::new((0..).map(|i| format!("item {i}"))).with_batch_size(100);
+///
+/// let results = corpus.search("item 42", 5);
+/// assert!(results.len() >= 5);
+/// assert_eq!(corpus.materialized().len(), 500);
+/// ```
+#[derive(Debug)]
+pub struct LazyCorpus<I> {
+    corpus: Corpus,
+    /// The remaining items, or [`None`] once the source is exhausted.
+    source: Option<I>,
+    batch_size: usize,
+}
+
+impl<S: Into<String>, I: Iterator<Item = S>> LazyCorpus<I> {
+    /// Create a corpus over a source of items, without pulling any of them yet.
+    pub fn new(source: impl IntoIterator<IntoIter = I>) -> Self {
+        LazyCorpus { corpus: Corpus::new(), source: Some(source.into_iter()), batch_size: DEFAULT_BATCH_SIZE }
+    }
+
+    /// Set the number of items pulled from the source at a time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch_size` is 0.
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        assert!(batch_size > 0, "batch size must be non-zero");
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// The items pulled from the source so far.
+    pub fn materialized(&self) -> &Corpus {
+        &self.corpus
+    }
+
+    /// Unwrap the materialized corpus, dropping the rest of the source.
+    pub fn into_materialized(self) -> Corpus {
+        self.corpus
+    }
+
+    /// Whether every item has been pulled from the source.
+    pub fn is_exhausted(&self) -> bool {
+        self.source.is_none()
+    }
+
+    /// Pull up to `count` more items from the source.
+    ///
+    /// # Returns
+    ///
+    /// The IDs of the pulled items, which are fewer than `count` if the source was exhausted.
+    pub fn pull(&mut self, count: usize) -> Vec<ItemId> {
+        let Some(source) = &mut self.source else { return vec![] };
+        let ids = self.corpus.extend(source.by_ref().take(count));
+        if ids.len() < count {
+            self.source = None;
+        }
+        ids
+    }
+
+    /// Pull every remaining item from the source.
+    ///
+    /// This never returns for an endless source.
+    pub fn pull_all(&mut self) -> &Corpus {
+        while !self.is_exhausted() {
+            self.pull(self.batch_size);
+        }
+        &self.corpus
+    }
+
+    /// Get the text of an item, pulling items from the source until it is reached.
+    pub fn get(&mut self, id: ItemId) -> Option<&str> {
+        while self.corpus.next_id <= id.0 && !self.is_exhausted() {
+            let count = (id.0 - self.corpus.next_id + 1).min(self.batch_size as u64) as usize;
+            self.pull(count);
+        }
+        self.corpus.get(id)
+    }
+
+    /// Search the corpus, pulling items from the source until there are at least `min_results`
+    /// matches or the source is exhausted.
+    ///
+    /// The returned matches are sorted as with [`Corpus::search`], across every item pulled so
+    /// far. As the source may hold better matches that haven't been pulled yet, searching again
+    /// with a larger `min_results` can reorder the results.
+    pub fn search(&mut self, search: &str, min_results: usize) -> Vec<CorpusMatch<'_>> {
+        let mut matches = self.corpus.search(search).len();
+        while matches < min_results && !self.is_exhausted() {
+            let pulled: HashSet<ItemId> = self.pull(self.batch_size).into_iter().collect();
+            if !pulled.is_empty() {
+                matches += self.corpus.search_filtered(search, Some(&pulled), None).len();
+            }
+        }
+        self.corpus.search(search)
+    }
+}
@@ -6,9 +6,11 @@
 //! ## Features
 //!
 //! - **Fuzzy matching**: Perform efficient fuzzy string matching based on string prefixes
-//! - **Parallel processing**: Leverages `rayon` for parallelized filtering and sorting
+//! - **Parallel processing**: Leverages `rayon` (behind the default `rayon` feature) for parallelized filtering and sorting
 //! - **Highlighting**: Automatically generates highlighted substrings for matched ranges
 //! - **Performance optimizations**: Uses precomputed skip indices for efficient prefix matching
+//! - **`no_std` support**: Scoring, highlighting and sequential filtering only need `alloc`
+//!   (see below)
 //!
 //! ## Usage
 //!
@@ -30,13 +32,232 @@
 //! - Matches at the beginning of strings
 //! - Contiguous matches (longer matches score higher)
 //! - Matches closer to the start of the string
+//!
+//! ## `no_std`
+//!
+//! The default `std` feature can be disabled to use the crate with only `core` and `alloc`, e.g.
+//! in embedded or sandboxed plugin runtimes. This keeps the scoring, highlighting and matching
+//! options, along with the filtering functions over slices of targets, which then always run
+//! sequentially. Everything that needs threads, I/O, clocks or hash maps (e.g. [`Corpus`] and
+//! [`FuzzyIndex`], telemetry and the parallel `rayon` feature) requires `std`, as does every other
+//! optional feature.
+
+#![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+use core::cmp::Ordering;
+
+#[cfg(feature = "rayon")]
 use rayon::prelude::*;
 
+#[cfg(feature = "std")]
+mod alias;
+#[cfg(feature = "std")]
+mod autocomplete;
+mod batch;
+mod boost;
+#[cfg(feature = "std")]
+mod budget;
+mod bytes;
+#[cfg(feature = "cabi")]
+pub mod cabi;
+#[cfg(feature = "std")]
+mod cancel;
+#[cfg(feature = "std")]
+mod cluster;
+mod composite;
+#[cfg(feature = "std")]
+mod corpus;
+#[cfg(feature = "std")]
+mod dictionary;
+#[cfg(feature = "serde")]
+mod export;
+mod explain;
+mod extract;
+#[cfg(feature = "std")]
+mod federated;
+mod fields;
+#[cfg(feature = "std")]
+mod fuzzy_index;
+#[cfg(feature = "std")]
+mod group;
+pub mod highlight;
+#[cfg(feature = "std")]
+mod index;
+mod instrument;
+mod iter;
+#[cfg(feature = "std")]
+mod journal;
+mod keyed;
+#[cfg(feature = "std")]
+mod lazy;
+#[cfg(feature = "std")]
+mod matcher;
+mod matrix;
+#[cfg(feature = "std")]
+mod ngram;
+mod normalize;
+mod numeric;
+mod options;
+mod owned;
+mod page;
+#[cfg(feature = "std")]
+mod parallelism;
+#[cfg(feature = "serde")]
+mod payload;
+#[cfg(feature = "std")]
+mod patch;
+#[cfg(feature = "std")]
+mod path;
+mod pipeline;
+mod prelude;
+mod prepared;
+mod preset;
+mod probe;
+mod query;
+mod ranges;
+#[cfg(feature = "std")]
+mod reader;
+#[cfg(feature = "std")]
+mod resumable;
+#[cfg(feature = "serde")]
+mod saved;
+mod scoring;
+pub mod scoring_v1;
+#[cfg(feature = "std")]
+mod session;
+#[cfg(feature = "std")]
+mod shard;
+#[cfg(feature = "std")]
+mod similarity;
+mod skips;
+#[cfg(feature = "std")]
+mod snapshot;
+mod sort;
+#[cfg(feature = "std")]
+mod stream;
+mod suggest;
+#[cfg(feature = "std")]
+mod telemetry;
+mod top;
+mod trace;
+mod truncate;
+mod typo;
+#[cfg(feature = "std")]
+mod usage;
+#[cfg(feature = "wasm")]
+mod wasm;
+#[cfg(feature = "notify")]
+mod watch;
+
+#[cfg(feature = "std")]
+pub use autocomplete::AutocompleteIndex;
+pub use batch::fuzzy_filter_batch;
+pub use boost::fuzzy_filter_boosted;
+pub use bytes::{fuzzy_filter_bytes, ByteFilterResult};
+#[cfg(feature = "std")]
+pub use cancel::SearchHandle;
+#[cfg(feature = "std")]
+pub use cluster::cluster_near_duplicates;
+pub use composite::{fuzzy_filter_composite, CompositeFilterResult, CompositeTarget, FieldRange};
+#[cfg(feature = "std")]
+pub use corpus::{Corpus, CorpusMatch, CorpusStats, ItemId};
+#[cfg(feature = "std")]
+pub use dictionary::Dictionaries;
+#[cfg(feature = "serde")]
+pub use export::{CorpusExport, ExportedItem};
+pub use explain::explain_pair;
+pub use extract::extract_one;
+#[cfg(feature = "std")]
+pub use federated::{FederatedMatch, FederatedSearch};
+pub use fields::{fuzzy_filter_fields, FieldMatch, MultiFieldFilterResult, MultiFieldTarget};
+#[cfg(feature = "std")]
+pub use fuzzy_index::{FuzzyIndex, IndexMatch};
+#[cfg(feature = "std")]
+pub use group::{fuzzy_filter_grouped, GroupedFilterResult};
+pub use iter::{fuzzy_filter_iter, FuzzyFilterIter};
+#[cfg(feature = "rayon")]
+pub use iter::fuzzy_filter_par_iter;
+#[cfg(feature = "std")]
+pub use journal::{read_journal, JournalEntry, JournalWriter, JournaledCorpus};
+pub use keyed::{fuzzy_filter_map, FuzzyFilterResultRef};
+#[cfg(feature = "std")]
+pub use lazy::LazyCorpus;
+#[cfg(feature = "std")]
+pub use matcher::{FuzzyMatcher, Matcher};
+pub use matrix::{score_matrix, ScoreMatrix};
+pub use normalize::Normalization;
+pub use options::{
+    fuzzy_filter_with_options, fuzzy_score_item_with_options, Anchor, MatchMode, MatchOptions, ScoringVersion,
+};
+pub use owned::{fuzzy_filter_owned, FuzzyFilterResultOwned};
+pub use page::{fuzzy_filter_page, FilterOptions, FilterPage};
+#[cfg(feature = "std")]
+pub use parallelism::{fuzzy_filter_with_parallelism, Parallelism};
+#[cfg(feature = "std")]
+pub use patch::patch_filter_results;
+#[cfg(feature = "std")]
+pub use path::{fuzzy_filter_os, OsFilterResult};
+pub use pipeline::{NormalizationPipeline, NormalizedText};
+pub use prepared::{
+    fuzzy_filter_prepared, fuzzy_filter_prepared_normalized, prepare_targets, prepare_targets_normalized, PreparedTarget,
+};
+pub use preset::Preset;
+pub use probe::match_at;
+pub use query::{fuzzy_filter_query, Query, QueryToken};
+pub use ranges::{clip_ranges, merge_ranges, segments, Segment};
+#[cfg(feature = "serde")]
+pub use payload::{PayloadCorpus, PayloadMatch};
+#[cfg(feature = "std")]
+pub use reader::{fuzzy_filter_reader, ReaderMatch};
+#[cfg(feature = "std")]
+pub use resumable::ResumableSearch;
+#[cfg(feature = "serde")]
+pub use saved::{SavedTarget, SavedTargets};
+pub use scoring::ScoringConfig;
+#[cfg(feature = "std")]
+pub use session::SearchSession;
+#[cfg(feature = "std")]
+pub use shard::{ShardedCorpus, ShardedMatch};
+#[cfg(feature = "std")]
+pub use similarity::similarity;
+pub use skips::SkipRules;
+#[cfg(feature = "std")]
+pub use snapshot::SharedCorpus;
+pub use sort::{fuzzy_filter_sorted, ResultComparator, SortPolicy};
+#[cfg(feature = "std")]
+pub use stream::fuzzy_filter_to_channel;
+pub use suggest::suggest;
+#[cfg(feature = "std")]
+pub use telemetry::{clear_telemetry_hook, set_telemetry_hook, QueryMetrics, StageMetrics, TelemetryHook};
+pub use top::fuzzy_filter_top_k;
+pub use trace::{trace_match, Alignment, MatchPath, MatchTrace};
+pub use truncate::{char_width, display_width, highlights_with_context, truncate_highlights};
+#[cfg(feature = "std")]
+pub use usage::{RecentSelection, UsageBoost};
+#[cfg(feature = "wasm")]
+pub use wasm::{fuzzy_filter_js, fuzzy_match_js};
+#[cfg(feature = "notify")]
+pub use watch::{CorpusChange, WatchedCorpus};
+#[cfg(feature = "async")]
+pub use stream::{fuzzy_filter_stream, FuzzyFilterStream};
+
+use pipeline::is_lower_case_aligned;
+use prelude::*;
+use normalize::{map_ranges, map_skips};
+
 const SCORE_START_STR: u32 = 1000;
 const SCORE_PREFIX: u32 = 200;
 const SCORE_CONTIGUOUS: u32 = 300;
 
+/// The version of the scoring algorithm and the search data it derives from targets.
+///
+/// This must be incremented whenever a change would make previously persisted search data (e.g.
+/// skip indices) produce different results, so that stale indexes are rebuilt.
+#[cfg(feature = "std")]
+pub(crate) const SCORING_VERSION: u32 = 2;
+
 /// Highlighted substrings of a full string.
 ///
 /// Every second string in the [`Vec`] represents a substring that matches
@@ -64,6 +285,9 @@ pub type Target<'a> = (&'a str, Option<Vec<usize>>);
 ///
 /// The `score` represents the match score for the string, while `ranges` holds
 /// [`Range`] items for each substring that matches between a search and target string.
+///
+/// With the `serde` feature, this serializes as a struct with `score` and `ranges` fields.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StringScore {
     /// The match score for a search string against a target string.
     pub score: u32,
@@ -71,8 +295,40 @@ pub struct StringScore {
     pub ranges: Vec<Range>,
 }
 
+impl StringScore {
+    /// The relevance of the match to `target` (the string it was scored against), from 0.0 for
+    /// no match to 1.0 for an exact match of the whole target.
+    ///
+    /// Unlike [`StringScore::score`], which grows with the length of the match regardless of
+    /// the length of the target, this is comparable between targets of different lengths, so
+    /// it suits relevance cutoffs. It's the mean of three parts, each from 0.0 to 1.0:
+    ///
+    /// - Coverage: the fraction of the target (in bytes) that is matched.
+    /// - Contiguity: the fraction of the match that is in its longest range.
+    /// - Position: how close the match starts to the start of the target.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fuzzbunny_rs::fuzzy_score_item;
+    ///
+    /// let score = fuzzy_score_item(&("apple", None), "apple").unwrap();
+    /// assert_eq!(score.normalized_score("apple"), 1.0);
+    ///
+    /// let short = fuzzy_score_item(&("apple", None), "app").unwrap().normalized_score("apple");
+    /// let long = fuzzy_score_item(&("application", None), "app").unwrap().normalized_score("application");
+    /// assert!(short > long);
+    /// ```
+    pub fn normalized_score(&self, target: &str) -> f32 {
+        scoring::normalized_score(&self.ranges, target.len())
+    }
+}
+
 /// A matched substring range in a larger string.
-#[derive(Debug)]
+///
+/// With the `serde` feature, this serializes as a `[start, len]` pair of byte offsets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Range(
     /// The start index of the match range.
     pub usize,
@@ -81,12 +337,47 @@ pub struct Range(
 );
 
 impl Range {
-    /// Calculate the byte index of the final character of the range.
+    /// The byte index of the start of the range.
+    #[inline]
+    pub const fn start(&self) -> usize {
+        self.0
+    }
+
+    /// The length of the range in bytes.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.1
+    }
+
+    /// Whether the range is empty.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.1 == 0
+    }
+
+    /// The byte index just past the end of the range.
     #[inline]
-    const fn end_index(&self) -> usize {
+    pub const fn end(&self) -> usize {
         self.0 + self.1
     }
 
+    /// The part of this range inside a window, e.g. the visible part of a line of text, or
+    /// [`None`] if they don't overlap. The offsets are still relative to the whole text.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fuzzbunny_rs::Range;
+    ///
+    /// assert_eq!(Range(2, 5).clip(4..10), Some(Range(4, 3)));
+    /// assert_eq!(Range(2, 5).clip(7..10), None);
+    /// ```
+    pub fn clip(&self, window: core::ops::Range<usize>) -> Option<Range> {
+        let start = self.0.max(window.start);
+        let end = self.end().min(window.end);
+        (start < end).then(|| Range(start, end - start))
+    }
+
     /// Merge another [`Range`] into this one by concatenation.
     ///
     /// # Panics
@@ -94,7 +385,7 @@ impl Range {
     /// This function panics if this range doesn't directly precede the one to be merged.
     #[inline]
     fn merge(&mut self, other: Range) {
-        assert_eq!(self.end_index(), other.0);
+        assert_eq!(self.end(), other.0);
         self.1 += other.1;
     }
 
@@ -104,35 +395,78 @@ impl Range {
     /// for matches closer to the beginning of the string.
     #[inline]
     const fn get_score(&self, is_prefix: bool) -> u32 {
-        let mut score: u32 = 0;
-
-        // increase score exponentially per letter matched so that contiguous matches are ranked higher
-        // i.e '[abc]' ranks higher than '[ab]ott [c]hemicals'
-        score += SCORE_CONTIGUOUS * ((self.1 * self.1) as u32); // u16 * u16 can at most be u32
+        ScoringConfig::DEFAULT.range_score(self, is_prefix)
+    }
+}
 
-        score += if self.0 == 0 {
-            // matching at the start of string gets a ranking bonus
-            SCORE_START_STR
-        } else if is_prefix {
-            // closer to the start, the higher it ranks
-            SCORE_PREFIX - self.0 as u32 // We assume the input string won't be more than u32::MAX in length
-        } else {
-            0
-        };
+impl From<Range> for core::ops::Range<usize> {
+    fn from(range: Range) -> Self {
+        range.0..range.end()
+    }
+}
 
-        score
+/// A range of bytes, where a range that ends before it starts is empty.
+impl From<core::ops::Range<usize>> for Range {
+    fn from(range: core::ops::Range<usize>) -> Self {
+        Range(range.start, range.end.saturating_sub(range.start))
     }
 }
 
 /// Filter result for a target string including match score and highlights.
-#[derive(Debug)]
+///
+/// With the `serde` feature, this serializes as a struct with `item`, `score`, `highlights`,
+/// `ranges` and (if known) `index` fields, e.g.
+/// `{"item": "apple", "score": 3700, "highlights": ["", "app", "le"], "ranges": [[0, 3]], "index": 0}`.
+/// As the strings are borrowed, only formats that can borrow them (e.g. JSON strings without
+/// escapes) can deserialize it.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FuzzyFilterResult<'a> {
     /// The target string that the search string was matched against.
     pub item: &'a str,
     /// The match score for a search string against a target string.
     pub score: u32,
     /// The highlight substrings of the target string. See [`Highlights`]. [`None`] if there is no match.
+    #[cfg_attr(feature = "serde", serde(borrow))]
     pub highlights: Option<Highlights<'a>>,
+    /// The matched ranges of the target string, as byte offsets, for rendering the matches
+    /// without splitting the target into highlights. These are the same sections as the
+    /// matched highlights. [`None`] if there is no match.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub ranges: Option<Vec<Range>>,
+    /// The position of the target in the items that were searched, for keeping the original
+    /// order of equally scored results (see [`SortPolicy::OriginalOrder`]). [`None`] for
+    /// searches that don't track the positions of their targets.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub index: Option<usize>,
+}
+
+impl<'a> FuzzyFilterResult<'a> {
+    /// Create the result of a match, with both the highlights and ranges of the match.
+    #[inline]
+    pub(crate) fn matched(item: &'a str, score: u32, ranges: Vec<Range>) -> Self {
+        FuzzyFilterResult {
+            item,
+            score,
+            highlights: Some(highlights_from_ranges(item, ranges.clone())),
+            ranges: Some(ranges),
+            index: None,
+        }
+    }
+
+    /// Set the position of the target in the items that were searched.
+    #[inline]
+    pub(crate) fn with_index(mut self, index: usize) -> Self {
+        self.index = Some(index);
+        self
+    }
+
+    /// The relevance of the match, from 0.0 to 1.0. See [`StringScore::normalized_score`].
+    ///
+    /// This is 0.0 if the result has no ranges.
+    pub fn normalized_score(&self) -> f32 {
+        scoring::normalized_score(self.ranges.as_deref().unwrap_or_default(), self.item.len())
+    }
 }
 
 impl<'a> PartialEq for FuzzyFilterResult<'a> {
@@ -142,28 +476,48 @@ impl<'a> PartialEq for FuzzyFilterResult<'a> {
 }
 impl<'a> Eq for FuzzyFilterResult<'a> {}
 impl<'a> PartialOrd for FuzzyFilterResult<'a> {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> { Some(self.cmp(other)) }
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
 }
 impl<'a> Ord for FuzzyFilterResult<'a> {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+    fn cmp(&self, other: &Self) -> Ordering {
         self.score.cmp(&other.score).then_with(|| other.item.cmp(self.item))
     }
 }
 
+/// Number of items below which skips are precomputed sequentially.
+const PARALLEL_PRECOMPUTE_THRESHOLD: usize = 4096;
+
 /// Convert an interator of string items to a [`Target`] vector.
 ///
 /// This is a convenience function to quickly convert a set of plain strings
 /// into [`Target`] items for use with [`fuzzy_filter`]. If you don't want the skips
 /// computed, you can manually wrap string items as `(item, None)` instead.
 ///
+/// For large sets of items, the skip indices are computed in parallel if the `rayon`
+/// feature is enabled. Targets are still lower cased for every search; to precompute their
+/// lower case forms as well, see [`prepare_targets`].
+///
 /// # Returns
 ///
-/// The string items wrapped as [`Target`] items including the precomputed skip indices.
+/// The string items wrapped as [`Target`] items including the precomputed skip indices,
+/// in the same order as `items`, ready to be passed to [`fuzzy_filter`].
 pub fn precompute_skips_for_items<'a>(items: impl IntoIterator<Item = &'a str>) -> Vec<Target<'a>> {
-    items
-        .into_iter()
-        .map(|string| (string, Some(get_target_skips(string))))
-        .collect()
+    let items: Vec<&'a str> = items.into_iter().collect();
+    let parallel = items.len() >= PARALLEL_PRECOMPUTE_THRESHOLD;
+
+    map_items(&items, parallel, |&string| (string, Some(get_target_skips(string))))
+}
+
+/// Add a matched range, extending the previous range if they're contiguous.
+#[inline]
+fn push_range(ranges: &mut Vec<Range>, range: Range) {
+    if range.is_empty() {
+        return
+    }
+    match ranges.last_mut() {
+        Some(prev_range) if prev_range.end() == range.0 => prev_range.merge(range),
+        _ => ranges.push(range),
+    }
 }
 
 /// Perform a prefix match for a search string on the target string.
@@ -178,74 +532,66 @@ pub fn precompute_skips_for_items<'a>(items: impl IntoIterator<Item = &'a str>)
 /// [`None`] if the search string doesn't match word prefixes starting at the given index.
 /// Otherwise, returns the match [`Range`]s as a [`Vec`].
 #[inline]
-fn fuzzy_prefix_match(skip_idx: usize, search: &str, target: &str, target_skips: &[usize]) -> Option<Vec<Range>> {
+fn fuzzy_prefix_match(skip_idx: usize, search: &str, target: &str, target_skips: &[usize], recorder: &mut impl trace::Recorder) -> Option<Vec<Range>> {
     let mut ranges: Vec<Range> = Vec::with_capacity(target_skips.len());
-    let mut search_chars = search.bytes();
+    let mut search_chars = search.chars();
     let mut search_char = search_chars.next();
+    // Where the last partially matched word stopped matching, for the recorder
+    let mut broke_at = None;
 
     for i in skip_idx..target_skips.len() - 1 {
         let start_idx = target_skips[i];
-        let mut target_cnt = target_skips[i + 1] - start_idx;
+        // The start of the current match relative to the word, and its length in bytes, so ranges
+        // always end on a character boundary
+        let mut match_start = 0;
         let mut match_len = 0;
+        let mut is_word_matched = false;
 
-        // Set up character iterators
-        let mut target_chars = target.bytes();
-
-        // Initialise the characters to the start of the ranges
-        let mut target_char = target_chars.nth(start_idx);
-
-        while target_char.is_some() && search_char.is_some() && target_cnt > 0 {
-            // Safe to unwrap
-            let t_char = target_char.unwrap();
-            let s_char = search_char.unwrap();
+        // Set up a character iterator over the word, with byte offsets relative to its start
+        let word = target.get(start_idx..target_skips[i + 1]).unwrap_or("");
+        let mut target_chars = word.char_indices();
+        let mut target_char = target_chars.next();
 
+        while let (Some((offset, t_char)), Some(s_char)) = (target_char, search_char) {
             if t_char == s_char {
                 target_char = target_chars.next();
                 search_char = search_chars.next();
-                match_len += 1;
-                target_cnt -= 1; // Decrement when target_char increments
+                match_len += t_char.len_utf8();
+                is_word_matched = true;
                 continue;
             }
 
-            // spaces shouldn't break matching
-            if t_char == b' ' {
+            // spaces shouldn't break matching, but aren't part of the match either
+            if t_char == ' ' {
+                push_range(&mut ranges, Range(start_idx + match_start, match_len));
+                match_start = offset + 1;
+                match_len = 0;
                 target_char = target_chars.next();
-                target_cnt -= 1; // Decrement when target_char increments
                 continue;
             }
-            if s_char == b' ' {
+            if s_char == ' ' {
                 search_char = search_chars.next();
                 continue;
             }
 
+            if is_word_matched {
+                broke_at = Some(start_idx + offset);
+            }
             break;
         }
 
-        // Make contiguous ranges if possible
-        // TODO This looks terrible
-        if match_len > 0 {
-            let this_range = Range(start_idx, match_len);
-            // If the most recent range butts up against this one, simply extend the previous
-            if ranges.is_empty() {
-                ranges.push(this_range);
-            } else {
-                let prev_range = ranges.last_mut().unwrap();
-                if prev_range.end_index() == start_idx {
-                    // Update previous range
-                    prev_range.merge(this_range)
-                } else {
-                    // Add restore the previous range and add the new this_range
-                    ranges.push(this_range);
-                };
-            }
-        }
+        push_range(&mut ranges, Range(start_idx + match_start, match_len));
 
         if search_char.is_none() {
             // Search is fully matched, return ranges
+            recorder.alignment(skip_idx, target_skips[skip_idx], search.len(), broke_at);
             return Some(ranges)
         }
     }
 
+    // The search characters after the one that failed to match are still left in the iterator
+    let matched = search.len() - search_chars.as_str().len() - search_char.map_or(0, char::len_utf8);
+    recorder.alignment(skip_idx, target_skips[skip_idx], matched, broke_at);
     None
 }
 
@@ -254,31 +600,13 @@ fn fuzzy_prefix_match(skip_idx: usize, search: &str, target: &str, target_skips:
 /// Skip indices mark word and punctuation boundaries, including camel/PascalCase
 /// case changes. These are used to quickly find prefix matches in the target string
 /// without traversing the entire string each time.
+///
+/// The indices are byte offsets, so they always fall on character boundaries of the target.
+/// The last index is the length of the target. To use different word boundaries, see
+/// [`SkipRules`].
 #[inline]
 pub fn get_target_skips(target: &str) -> Vec<usize> {
-    let mut target_skips = vec![];
-    let mut was_alpha_num = false;
-    let mut was_upper_case = false;
-
-    for (i, char) in target.chars().enumerate() {
-        let is_alpha_num = char.is_alphanumeric();
-        let is_upper_case = char.is_uppercase();
-
-        if (is_alpha_num && !was_alpha_num) || (is_upper_case && !was_upper_case) || char.is_ascii_punctuation() {
-            target_skips.push(i);
-        }
-
-        was_alpha_num = is_alpha_num;
-        was_upper_case = is_upper_case;
-    }
-
-    // We push the length as the last skip so when matching
-    // every range aligns between skip[i] and skip[i + 1]
-    // and we don't have to do extraneous overflow checks
-    target_skips.push(target.len());
-
-    // NOTE: these can possibly be cached on the items for a faster search next time
-    target_skips
+    SkipRules::DEFAULT.skips(target)
 }
 
 /// Calculate the highlighted substrings of a target string for the given match ranges.
@@ -301,7 +629,7 @@ pub fn highlights_from_ranges<'a>(target: &'a str, ranges: Vec<Range>) -> Highli
 
     for range in ranges {
         let start_index = range.0;
-        let end_index = range.end_index();
+        let end_index = range.end();
         highlights.push(&target[last_index..start_index]);
         highlights.push(&target[start_index..end_index]);
         last_index = end_index;
@@ -322,7 +650,29 @@ pub fn highlights_from_ranges<'a>(target: &'a str, ranges: Vec<Range>) -> Highli
 ///
 /// Note that `search` string MUST be lower case.
 pub fn fuzzy_score_item(target: &Target<'_>, search: &str) -> Option<StringScore> {
-    if target.0.is_empty() {
+    score_prepared(target.0, None, target.1.as_deref(), search)
+}
+
+/// Compute a raw score and highlight ranges for a target with optionally precomputed data.
+///
+/// The lower case form of the target and its skip indices are computed on the fly if not given.
+///
+/// Note that `search` string MUST be lower case.
+#[inline]
+pub(crate) fn score_prepared(target: &str, target_lower: Option<&str>, target_skips: Option<&[usize]>, search: &str) -> Option<StringScore> {
+    score_recorded(target, target_lower, target_skips, search, &ScoringConfig::DEFAULT, &mut ())
+}
+
+/// The same as [`score_prepared`], reporting each decision of the matcher to a recorder.
+pub(crate) fn score_recorded(
+    target: &str,
+    target_lower: Option<&str>,
+    target_skips: Option<&[usize]>,
+    search: &str,
+    config: &ScoringConfig,
+    recorder: &mut impl trace::Recorder,
+) -> Option<StringScore> {
+    if target.is_empty() {
         return None
     }
 
@@ -338,21 +688,51 @@ pub fn fuzzy_score_item(target: &Target<'_>, search: &str) -> Option<StringScore
     // NOTE: ending quote is optional so user can get incremental matching as they type.
     let is_quoted_search_str = search.bytes().next().is_some_and(|char| char == b'"');
     if is_quoted_search_str {
-        let end_index = if search.ends_with('"') { search.len() - 1 } else { search.len() };
-        search_str = &search[1..end_index];
+        let quoted = &search[1..];
+        search_str = quoted.strip_suffix('"').unwrap_or(quoted);
+        // a lone quote (or an empty pair of quotes) is an empty search
+        if search_str.is_empty() {
+            return Some(StringScore { score: 0, ranges: vec![] })
+        }
     }
 
 
     // try substring search first
-    let l_case_target_str = target.0.to_lowercase();
+    let l_case_owned;
+    let l_case_target_str = match target_lower {
+        Some(lower) => lower,
+        None => {
+            l_case_owned = target.to_lowercase();
+            // Lower casing can change the length of non-ASCII characters (e.g. 'İ'), in which case
+            // the target is matched on a lower case form with offsets back to the original text
+            if !is_lower_case_aligned(target, &l_case_owned) {
+                let derived = NormalizationPipeline::default().derive(target, false);
+                // Given skips (e.g. from custom skip rules) are kept, moved to the lower case form
+                let mapped_skips;
+                let skips = match target_skips {
+                    Some(skips) => {
+                        mapped_skips = map_skips(skips, &derived.offsets);
+                        &mapped_skips
+                    },
+                    None => &derived.skips,
+                };
+                let string_score = score_recorded(&derived.lower, Some(&derived.lower), Some(skips), search, config, recorder)?;
+                return Some(StringScore { score: string_score.score, ranges: map_ranges(string_score.ranges, &derived.offsets) })
+            }
+            &l_case_owned
+        },
+    };
     let match_idx = l_case_target_str.find(search_str);
     let search_len = search_str.len();
+    recorder.substring(match_idx);
 
     if let Some(idx) = match_idx {
         let match_range = Range(idx, search_len);
-        let is_word_prefix = idx > 0 && !char::from(target.0.as_bytes()[idx - 1]).is_alphanumeric();
+        // Normalization can change the length of the text, in which case only the normalized form
+        // lines up with the match index
+        let is_word_prefix = l_case_target_str[..idx].chars().next_back().is_some_and(|char| !char.is_alphanumeric());
         return Some(StringScore {
-            score: match_range.get_score(is_word_prefix),
+            score: config.score(&[Range(idx, search_len)], |_| is_word_prefix, l_case_target_str),
             ranges: vec![match_range]
         })
     }
@@ -366,21 +746,21 @@ pub fn fuzzy_score_item(target: &Target<'_>, search: &str) -> Option<StringScore
     // fall back to fuzzy matching which matches word prefixes or punctuations
     // because we've precomputed targetSkips, its O(m+n) for avg case
     // the skip array helps us make faster alignments, rather than letter by letter
-    let target_skips = if target.1.is_none() {
-        &get_target_skips(target.0)
-    } else {
-        target.1.as_ref().unwrap()
+    let target_skips = match target_skips {
+        Some(skips) => skips,
+        None => &get_target_skips(target),
     };
 
-    let first_search_char = search_str.bytes().next().unwrap();
+    // Comparing the first bytes is enough to find possible alignments, as the prefix match compares
+    // whole characters
+    let first_search_byte = search_str.as_bytes()[0];
     for skip_idx in 0..(target_skips.len() - 1) {
         let tgt_idx = target_skips[skip_idx];
-        let targ_char = l_case_target_str.as_bytes()[tgt_idx];
-        if targ_char == first_search_char {
+        if l_case_target_str.as_bytes().get(tgt_idx) == Some(&first_search_byte) {
             // possible alignment, perform prefix match
-            let ranges = fuzzy_prefix_match(skip_idx, search, &l_case_target_str, target_skips);
+            let ranges = fuzzy_prefix_match(skip_idx, search, l_case_target_str, target_skips, recorder);
             if let Some(ranges) = ranges {
-                let score = ranges.iter().map(|rng| rng.get_score(true)).sum();
+                let score = config.score(&ranges, |_| true, l_case_target_str);
                 return Some(StringScore { score, ranges })
             }
         }
@@ -400,48 +780,273 @@ pub fn fuzzy_match<'t>(target: &'t str, search: Option<&str>) -> Option<FuzzyFil
 
     let string_match = fuzzy_score_item(&(target, None), search);
 
-    string_match.map(|mat| {
-        FuzzyFilterResult {
-            item: target,
-            score: mat.score,
-            highlights: Some(highlights_from_ranges(target, mat.ranges)),
-        }
+    string_match.map(|mat| FuzzyFilterResult::matched(target, mat.score, mat.ranges))
+}
+
+/// Score a single [`Target`] and build its [`FuzzyFilterResult`].
+///
+/// Note that `search` string MUST be lower case.
+///
+/// # Returns
+///
+/// [`None`] if the search string doesn't match the target.
+#[inline]
+pub(crate) fn filter_target<'a>(target: &Target<'a>, search: &str) -> Option<FuzzyFilterResult<'a>> {
+    fuzzy_score_item(target, search).map(|match_item| FuzzyFilterResult::matched(target.0, match_item.score, match_item.ranges))
+}
+
+/// Estimated scoring work (in bytes compared) below which the rayon overhead outweighs its benefit.
+const PARALLEL_WORK_THRESHOLD: usize = 256 * 1024;
+/// Number of targets sampled when estimating the average target length.
+const LENGTH_SAMPLE_SIZE: usize = 64;
+
+/// Decide whether scoring `items` against `search` is worth parallelising.
+///
+/// Always `false` if the `rayon` feature is disabled.
+#[inline]
+fn should_parallelise(items: &[Target<'_>], search: &str) -> bool {
+    is_parallel_workload(items.len(), |i| items[i].0.len(), search.len())
+}
+
+/// Decide whether scoring `count` targets against a search of `search_len` bytes is worth
+/// parallelising, where `target_len` gives the byte length of the target at an index.
+///
+/// The work is estimated from the corpus size, the average target length (sampled evenly
+/// across the corpus) and the search length, as short queries over small corpora pay more in
+/// scheduling overhead than they gain from the extra threads.
+///
+/// Always `false` if the `rayon` feature is disabled.
+#[inline]
+pub(crate) fn is_parallel_workload(count: usize, target_len: impl Fn(usize) -> usize, search_len: usize) -> bool {
+    if !cfg!(feature = "rayon") || count < LENGTH_SAMPLE_SIZE {
+        return false
+    }
+
+    let stride = count / LENGTH_SAMPLE_SIZE;
+    let sampled_len: usize = (0..count).step_by(stride).take(LENGTH_SAMPLE_SIZE).map(target_len).sum();
+    let avg_target_len = sampled_len / LENGTH_SAMPLE_SIZE;
+
+    count * (avg_target_len + search_len) >= PARALLEL_WORK_THRESHOLD
+}
+
+/// Score every target against `search`, and collect the [`FuzzyFilterResult`] of each match.
+///
+/// The matches are collected in the order of `items`, and each carries the index of its target
+/// plus `first_index`, which is the index of the first target when `items` is a batch of a
+/// larger slice. If `parallel` is set and the `rayon` feature is enabled, the targets are scored
+/// in parallel.
+///
+/// Note that `search` string MUST be lower case.
+pub(crate) fn collect_matches<'a>(items: &[Target<'a>], first_index: usize, search: &str, parallel: bool) -> Vec<FuzzyFilterResult<'a>> {
+    collect_indexed_matches_by(items, parallel, |index, target| {
+        filter_target(target, search).map(|result| result.with_index(first_index + index))
     })
 }
 
+/// Apply `score` to every item and its index, and collect every [`Some`] result in the order of
+/// `items`.
+///
+/// If `parallel` is set and the `rayon` feature is enabled, the items are scored in parallel.
+#[cfg_attr(not(feature = "rayon"), allow(unused_variables))]
+pub(crate) fn collect_indexed_matches_by<'i, T: Sync, R: Send>(
+    items: &'i [T],
+    parallel: bool,
+    score: impl Fn(usize, &'i T) -> Option<R> + Sync + Send,
+) -> Vec<R> {
+    #[cfg(feature = "rayon")]
+    if parallel {
+        return items
+            .par_iter()
+            .enumerate()
+            .filter_map(|(index, item)| score(index, item))
+            .collect()
+    }
+
+    items
+        .iter()
+        .enumerate()
+        .filter_map(|(index, item)| score(index, item))
+        .collect()
+}
+
+/// Apply `score` to every item, and collect every [`Some`] result in the order of `items`.
+///
+/// If `parallel` is set and the `rayon` feature is enabled, the items are scored in parallel.
+#[cfg_attr(not(feature = "rayon"), allow(unused_variables))]
+pub(crate) fn collect_matches_by<'i, T: Sync, R: Send>(items: &'i [T], parallel: bool, score: impl Fn(&'i T) -> Option<R> + Sync + Send) -> Vec<R> {
+    #[cfg(feature = "rayon")]
+    if parallel {
+        return items
+            .par_iter()
+            .filter_map(score)
+            .collect()
+    }
+
+    items
+        .iter()
+        .filter_map(score)
+        .collect()
+}
+
+/// Apply `map` to every item, and collect the results in the order of `items`.
+///
+/// If `parallel` is set and the `rayon` feature is enabled, the items are mapped in parallel.
+#[cfg_attr(not(feature = "rayon"), allow(unused_variables))]
+pub(crate) fn map_items<'i, T: Sync, R: Send>(items: &'i [T], parallel: bool, map: impl Fn(&'i T) -> R + Sync + Send) -> Vec<R> {
+    #[cfg(feature = "rayon")]
+    if parallel {
+        return items
+            .par_iter()
+            .map(map)
+            .collect()
+    }
+
+    items
+        .iter()
+        .map(map)
+        .collect()
+}
+
+/// Sort results from highest to lowest, in parallel if `parallel` is set and the
+/// `rayon` feature is enabled.
+#[cfg_attr(not(feature = "rayon"), allow(unused_variables))]
+pub(crate) fn sort_results<T: Ord + Send>(results: &mut [T], parallel: bool) {
+    sort_results_by(results, parallel, |a, b| b.cmp(a));
+}
+
+/// Sort results with a comparator, where the results that compare less come first, in parallel
+/// if `parallel` is set and the `rayon` feature is enabled.
+#[cfg_attr(not(feature = "rayon"), allow(unused_variables))]
+pub(crate) fn sort_results_by<T: Send>(results: &mut [T], parallel: bool, compare: impl Fn(&T, &T) -> Ordering + Sync) {
+    #[cfg(feature = "rayon")]
+    if parallel {
+        results.par_sort_by(compare);
+        return
+    }
+
+    results.sort_by(compare);
+}
+
 /// Search a vector of [`Target`]s and return a filtered and sorted vector
 /// of [`FuzzyFilterResult`].
 ///
 /// Each provided target is scored against the `search` string. Only non-zero scores are returned.
 ///
-/// This version makes use of rayon to parallelise the scoring (an embarrassingly parallel problem)
-/// and sorting the scored results.
+/// For large enough workloads, this version makes use of rayon to parallelise the scoring
+/// (an embarrassingly parallel problem) and sorting the scored results. Small workloads, or
+/// any workload if the `rayon` feature is disabled, are processed sequentially. To use a
+/// different thread pool or threshold, see [`fuzzy_filter_with_parallelism`].
+///
+/// Results with equal scores are ordered by the bytes of their item strings. To order them
+/// differently (e.g. in the order of `items`), see [`fuzzy_filter_sorted`].
 pub fn fuzzy_filter<'a>(items: &Vec<Target<'a>>, search: &str) -> Vec<FuzzyFilterResult<'a>> {
     let search_lower_cased = search.trim().to_lowercase();
+    let parallel = should_parallelise(items, &search_lower_cased);
+    let span = instrument::FilterSpan::enter("fuzzy_filter", items.len(), search_lower_cased.len(), parallel);
 
-    // In parallel, process the results
-    let mut results: Vec<FuzzyFilterResult<'a>> = items
-        .into_par_iter()
-        .map(|target| {
-            let match_item = fuzzy_score_item(target, &search_lower_cased);
-            match_item.map_or(
-                FuzzyFilterResult { item: target.0, score: 0, highlights: None },
-                |match_item| {
-                    FuzzyFilterResult {
-                        item: target.0,
-                        score: match_item.score,
-                        highlights: Some(highlights_from_ranges(target.0, match_item.ranges)),
-                    }
-                }
-            )
-        })
-        .filter(|res| res.highlights.is_some())
-        .collect();
+    let stage = instrument::Stage::start("score");
+    let mut results = collect_matches(items, 0, &search_lower_cased, parallel);
+    stage.finish(items.len(), results.len());
+
+    if !search.is_empty() {
+        let stage = instrument::Stage::start("sort");
+        sort_results(&mut results, parallel);
+        stage.finish(results.len(), results.len());
+    }
+
+    span.finish(results.len());
+    results
+}
+
+/// Search a slice of [`Target`]s and return a filtered and sorted vector of
+/// [`FuzzyFilterResult`], dropping weak matches.
+///
+/// This is the same as [`fuzzy_filter`], but only keeps the matches with a
+/// [normalized score](FuzzyFilterResult::normalized_score) of at least `min_score`, between
+/// 0.0 and 1.0. As an empty search has nothing to be weakly matched, it keeps every match.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{fuzzy_filter_with_min_score, precompute_skips_for_items};
+///
+/// let targets = precompute_skips_for_items(vec!["Apple", "A short description of an apple"]);
+///
+/// let results = fuzzy_filter_with_min_score(&targets, "apple", 0.8);
+/// let items: Vec<&str> = results.iter().map(|res| res.item).collect();
+/// assert_eq!(items, vec!["Apple"]);
+/// ```
+pub fn fuzzy_filter_with_min_score<'a>(items: &[Target<'a>], search: &str, min_score: f32) -> Vec<FuzzyFilterResult<'a>> {
+    let search_lower_cased = search.trim().to_lowercase();
+    let parallel = should_parallelise(items, &search_lower_cased);
+    let span = instrument::FilterSpan::enter("fuzzy_filter_with_min_score", items.len(), search_lower_cased.len(), parallel);
+
+    let stage = instrument::Stage::start("score");
+    let mut results = collect_indexed_matches_by(items, parallel, |index, target| {
+        let result = filter_target(target, &search_lower_cased)?.with_index(index);
+        (search_lower_cased.is_empty() || result.normalized_score() >= min_score).then_some(result)
+    });
+    stage.finish(items.len(), results.len());
+
+    if !search.is_empty() {
+        let stage = instrument::Stage::start("sort");
+        sort_results(&mut results, parallel);
+        stage.finish(results.len(), results.len());
+    }
+
+    span.finish(results.len());
+    results
+}
+
+/// Search a vector of [`Target`]s and return a filtered vector of [`FuzzyFilterResult`],
+/// sorted by a custom comparator.
+///
+/// This is the same as [`fuzzy_filter`], but the results are sorted with `compare`, where the
+/// results that compare [`Ordering::Less`] come first, so applications can inject their own
+/// ordering (e.g. directories before files) without sorting the results again. Results that
+/// compare [`Ordering::Equal`] are left in the same order as `fuzzy_filter`, so a comparator
+/// only needs to order the results it cares about. To keep the best matches first and only
+/// break ties between equal scores, compare the scores first.
+///
+/// As with `fuzzy_filter`, the results of an empty search aren't sorted.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{fuzzy_filter_by, precompute_skips_for_items};
+///
+/// let targets = precompute_skips_for_items(vec!["src/main.rs", "src/", "docs/", "README.md"]);
+/// let is_dir = |item: &str| item.ends_with('/');
+///
+/// // Directories before files, then by score
+/// let results = fuzzy_filter_by(&targets, "s", |a, b| is_dir(b.item).cmp(&is_dir(a.item)));
+/// let items: Vec<&str> = results.iter().map(|res| res.item).collect();
+/// assert_eq!(items, vec!["src/", "docs/", "src/main.rs"]);
+///
+/// // Break ties between equal scores only
+/// let results = fuzzy_filter_by(&targets, "s", |a, b| b.score.cmp(&a.score).then(is_dir(b.item).cmp(&is_dir(a.item))));
+/// let items: Vec<&str> = results.iter().map(|res| res.item).collect();
+/// assert_eq!(items, vec!["src/", "src/main.rs", "docs/"]);
+/// ```
+pub fn fuzzy_filter_by<'a>(
+    items: &[Target<'a>],
+    search: &str,
+    compare: impl Fn(&FuzzyFilterResult<'a>, &FuzzyFilterResult<'a>) -> Ordering + Sync,
+) -> Vec<FuzzyFilterResult<'a>> {
+    let search_lower_cased = search.trim().to_lowercase();
+    let parallel = should_parallelise(items, &search_lower_cased);
+    let span = instrument::FilterSpan::enter("fuzzy_filter_by", items.len(), search_lower_cased.len(), parallel);
+
+    let stage = instrument::Stage::start("score");
+    let mut results = collect_matches(items, 0, &search_lower_cased, parallel);
+    stage.finish(items.len(), results.len());
 
     if !search.is_empty() {
-        // Then sort in parallel.
-        results.par_sort_by(|a, b| b.cmp(a));
+        let stage = instrument::Stage::start("sort");
+        sort_results_by(&mut results, parallel, |a, b| compare(a, b).then_with(|| b.cmp(a)));
+        stage.finish(results.len(), results.len());
     }
 
+    span.finish(results.len());
     results
 }
@@ -0,0 +1,117 @@
+//! An object-safe interface over matching engines.
+
+use crate::options::score_with_options;
+use crate::{
+    collect_matches_by, fuzzy_score_item, instrument, is_parallel_workload, sort_results, Corpus, CorpusMatch,
+    FuzzyFilterResult, MatchOptions, StringScore, Target,
+};
+
+/// A matching engine, which scores and highlights targets for a search.
+///
+/// The trait is object safe, so applications can choose an engine at runtime (e.g. to A/B test
+/// an alternate algorithm) and hold it as a `Box<dyn Matcher>`. Only [`Matcher::score`] has to
+/// be implemented; searching a slice of targets or a [`Corpus`] is built on top of it, but
+/// engines can override those with faster versions.
+///
+/// The built-in engine is [`FuzzyMatcher`], and [`MatchOptions`] also implement the trait.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{precompute_skips_for_items, FuzzyMatcher, MatchMode, MatchOptions, Matcher};
+///
+/// let engines: Vec<Box<dyn Matcher>> = vec![
+///     Box::new(FuzzyMatcher),
+///     Box::new(MatchOptions::default().with_mode(MatchMode::Initials)),
+/// ];
+/// let targets = precompute_skips_for_items(vec!["Git: Commit", "gcc"]);
+///
+/// let counts: Vec<(&str, usize)> = engines.iter().map(|engine| (engine.name(), engine.filter(&targets, "gc").len())).collect();
+/// assert_eq!(counts, vec![("fuzzy", 2), ("initials", 1)]);
+/// ```
+pub trait Matcher: Send + Sync {
+    /// A short name for the engine, e.g. to tag metrics and logs.
+    fn name(&self) -> &str;
+
+    /// Compute the score and highlight ranges of a target for a search, or [`None`] if it
+    /// doesn't match. See [`fuzzy_score_item`].
+    ///
+    /// Note that `search` string MUST be lower case.
+    fn score(&self, target: &Target<'_>, search: &str) -> Option<StringScore>;
+
+    /// Search a slice of [`Target`]s and return a filtered and sorted vector of
+    /// [`FuzzyFilterResult`], the same as [`fuzzy_filter`](crate::fuzzy_filter) does for the
+    /// built-in engine.
+    ///
+    /// The search is trimmed and lower cased, and the results of an empty search aren't sorted.
+    fn filter<'a>(&self, items: &[Target<'a>], search: &str) -> Vec<FuzzyFilterResult<'a>> {
+        let search_lower_cased = search.trim().to_lowercase();
+        let parallel = is_parallel_workload(items.len(), |i| items[i].0.len(), search_lower_cased.len());
+        let span = instrument::FilterSpan::enter("matcher_filter", items.len(), search_lower_cased.len(), parallel);
+
+        let mut results = collect_matches_by(items, parallel, |target| {
+            let string_score = self.score(target, &search_lower_cased)?;
+            Some(FuzzyFilterResult::matched(target.0, string_score.score, string_score.ranges))
+        });
+        if !search.is_empty() {
+            sort_results(&mut results, parallel);
+        }
+
+        span.finish(results.len());
+        results
+    }
+
+    /// Search the items of a [`Corpus`] and return the sorted matches, the same as
+    /// [`Corpus::search`] does for the built-in engine.
+    ///
+    /// Unless overridden, pinned items still rank first, but aliases and usage boosts aren't
+    /// applied, as they're specific to the built-in engine.
+    fn search_corpus<'c>(&self, corpus: &'c Corpus, search: &str) -> Vec<CorpusMatch<'c>> {
+        let search_lower_cased = search.trim().to_lowercase();
+        let items: Vec<_> = corpus.iter().collect();
+        let parallel = is_parallel_workload(items.len(), |i| items[i].1.len(), search_lower_cased.len());
+        let span = instrument::FilterSpan::enter("matcher_search_corpus", items.len(), search_lower_cased.len(), parallel);
+
+        let mut matches = collect_matches_by(&items, parallel, |&(id, text)| {
+            let string_score = self.score(&(text, None), &search_lower_cased)?;
+            let result = FuzzyFilterResult::matched(text, string_score.score, string_score.ranges);
+            Some(CorpusMatch { id, result, pinned: corpus.is_pinned(id), alias: None })
+        });
+        if !search.is_empty() {
+            sort_results(&mut matches, parallel);
+        }
+
+        span.finish(matches.len());
+        matches
+    }
+}
+
+/// The built-in matching engine, the same as [`fuzzy_filter`](crate::fuzzy_filter) and
+/// [`Corpus::search`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct FuzzyMatcher;
+
+impl Matcher for FuzzyMatcher {
+    fn name(&self) -> &str {
+        "fuzzy"
+    }
+
+    fn score(&self, target: &Target<'_>, search: &str) -> Option<StringScore> {
+        fuzzy_score_item(target, search)
+    }
+
+    fn search_corpus<'c>(&self, corpus: &'c Corpus, search: &str) -> Vec<CorpusMatch<'c>> {
+        corpus.search(search)
+    }
+}
+
+impl Matcher for MatchOptions {
+    /// The name of the match mode.
+    fn name(&self) -> &str {
+        self.mode.name()
+    }
+
+    fn score(&self, target: &Target<'_>, search: &str) -> Option<StringScore> {
+        score_with_options(target.0, None, target.1.as_deref(), search, self)
+    }
+}
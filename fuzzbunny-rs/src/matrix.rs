@@ -0,0 +1,121 @@
+//! Scoring of every query against every target.
+
+use crate::prelude::*;
+use crate::{collect_matches_by, fuzzy_score_item, instrument, is_parallel_workload, map_items, Target};
+
+/// The scores of a set of queries against a set of targets, from [`score_matrix`].
+///
+/// Only matches scoring at least the cutoff are stored, so a matrix of mostly non-matching
+/// pairs stays small.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScoreMatrix {
+    /// The matches of each query, as `(target index, score)` in order of target index.
+    rows: Vec<Vec<(usize, u32)>>,
+    target_count: usize,
+}
+
+impl ScoreMatrix {
+    /// The number of queries, i.e. rows.
+    pub fn query_count(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// The number of targets, i.e. columns.
+    pub fn target_count(&self) -> usize {
+        self.target_count
+    }
+
+    /// The number of stored matches.
+    pub fn match_count(&self) -> usize {
+        self.rows.iter().map(Vec::len).sum()
+    }
+
+    /// Get the score of a query against a target.
+    ///
+    /// # Returns
+    ///
+    /// [`None`] if the target doesn't match the query, the match scored below the cutoff, or
+    /// either index is out of bounds.
+    pub fn get(&self, query: usize, target: usize) -> Option<u32> {
+        let row = self.rows.get(query)?;
+        let index = row.binary_search_by_key(&target, |&(target, _)| target).ok()?;
+        Some(row[index].1)
+    }
+
+    /// The matches of a query, as `(target index, score)` in order of target index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `query` is out of bounds.
+    pub fn row(&self, query: usize) -> &[(usize, u32)] {
+        &self.rows[query]
+    }
+
+    /// Iterate over every stored match, as `(query index, target index, score)` in row order.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, u32)> + '_ {
+        self.rows
+            .iter()
+            .enumerate()
+            .flat_map(|(query, row)| row.iter().map(move |&(target, score)| (query, target, score)))
+    }
+
+    /// Expand the matrix into a dense row for each query, holding the score against every
+    /// target, or [`None`] where there's no stored match.
+    pub fn to_dense(&self) -> Vec<Vec<Option<u32>>> {
+        self.rows
+            .iter()
+            .map(|row| {
+                let mut dense = vec![None; self.target_count];
+                for &(target, score) in row {
+                    dense[target] = Some(score);
+                }
+                dense
+            })
+            .collect()
+    }
+}
+
+/// Score every query against every target, e.g. for offline evaluation or entity matching.
+///
+/// This is the same as scoring each pair with [`fuzzy_score_item`], but each query is only
+/// prepared once, and the pairs are scored in parallel if the `rayon` feature is enabled and
+/// the workload is large enough. Highlights aren't computed.
+///
+/// Matches scoring below `cutoff` aren't stored, so a higher cutoff makes the matrix sparser.
+/// A `cutoff` of 0 keeps every match.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{precompute_skips_for_items, score_matrix};
+///
+/// let targets = precompute_skips_for_items(vec!["apple", "banana", "pineapple"]);
+/// let matrix = score_matrix(&["app", "nana"], &targets, 0);
+///
+/// assert_eq!(matrix.get(0, 0), Some(3700));
+/// assert_eq!(matrix.get(0, 1), None);
+/// assert_eq!(matrix.row(1), &[(1, 4800)]);
+///
+/// // Only keep the strongest matches
+/// let matrix = score_matrix(&["app", "nana"], &targets, 4000);
+/// assert_eq!(matrix.match_count(), 1);
+/// ```
+pub fn score_matrix<S: AsRef<str>>(queries: &[S], targets: &[Target<'_>], cutoff: u32) -> ScoreMatrix {
+    let searches: Vec<String> = queries.iter().map(|query| query.as_ref().trim().to_lowercase()).collect();
+    let avg_search_len = searches.iter().map(String::len).sum::<usize>() / searches.len().max(1);
+    let pairs = searches.len() * targets.len();
+    let parallel = is_parallel_workload(pairs, |i| targets[i % targets.len()].0.len(), avg_search_len);
+    let span = instrument::FilterSpan::enter("score_matrix", pairs, avg_search_len, parallel);
+
+    let target_indices: Vec<usize> = (0..targets.len()).collect();
+    let rows = map_items(&searches, parallel, |search| {
+        collect_matches_by(&target_indices, parallel, |&target| {
+            let score = fuzzy_score_item(&targets[target], search)?.score;
+            (score >= cutoff).then_some((target, score))
+        })
+    });
+
+    let matrix = ScoreMatrix { rows, target_count: targets.len() };
+    span.finish(matrix.match_count());
+    matrix
+}
@@ -0,0 +1,175 @@
+//! Inverted n-gram indexes, for skipping items that can't match a search.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::ItemId;
+
+/// The distinct n-grams of a lower case text that a search has to share to match it.
+///
+/// Spaces are ignored, as matching skips over them in both the target and search.
+struct Grams {
+    chars: Vec<char>,
+    /// Pairs of characters that follow each other, ignoring spaces.
+    bigrams: Vec<(char, char)>,
+    /// The first character of every word.
+    word_starts: Vec<char>,
+}
+
+impl Grams {
+    fn new(lower: &str, skips: &[usize]) -> Self {
+        let mut chars: Vec<char> = lower.chars().filter(|&char| char != ' ').collect();
+        let mut bigrams: Vec<(char, char)> = chars.windows(2).map(|pair| (pair[0], pair[1])).collect();
+        let mut word_starts: Vec<char> = skips.iter().filter_map(|&skip| lower.get(skip..)?.chars().next()).collect();
+
+        chars.sort_unstable();
+        chars.dedup();
+        bigrams.sort_unstable();
+        bigrams.dedup();
+        word_starts.sort_unstable();
+        word_starts.dedup();
+        Grams { chars, bigrams, word_starts }
+    }
+}
+
+/// Add an ID to a sorted posting list.
+fn insert_posting(postings: &mut Vec<ItemId>, id: ItemId) {
+    match postings.last() {
+        // IDs are assigned in ascending order, so new items go at the end
+        Some(&last) if last >= id => {
+            if let Err(position) = postings.binary_search(&id) {
+                postings.insert(position, id);
+            }
+        },
+        _ => postings.push(id),
+    }
+}
+
+/// Remove an ID from the sorted posting list of a gram, dropping the list once it's empty.
+fn remove_posting<K: Hash + Eq>(index: &mut HashMap<K, Vec<ItemId>>, gram: K, id: ItemId) {
+    if let Some(postings) = index.get_mut(&gram) {
+        if let Ok(position) = postings.binary_search(&id) {
+            postings.remove(position);
+        }
+        if postings.is_empty() {
+            index.remove(&gram);
+        }
+    }
+}
+
+/// The posting list of a gram, which is empty if no item contains it.
+fn postings(postings: Option<&Vec<ItemId>>) -> &[ItemId] {
+    postings.map_or(&[], Vec::as_slice)
+}
+
+/// The IDs in either of two sorted lists.
+fn union(a: &[ItemId], b: &[ItemId]) -> Vec<ItemId> {
+    let mut either = Vec::with_capacity(a.len() + b.len());
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            Ordering::Less => {
+                either.push(a[i]);
+                i += 1;
+            },
+            Ordering::Greater => {
+                either.push(b[j]);
+                j += 1;
+            },
+            Ordering::Equal => {
+                either.push(a[i]);
+                (i, j) = (i + 1, j + 1);
+            },
+        }
+    }
+    either.extend_from_slice(&a[i..]);
+    either.extend_from_slice(&b[j..]);
+    either
+}
+
+/// An inverted index from the n-grams of every item to the IDs of the items containing them.
+///
+/// Trigrams of a search only have to appear in targets that match it as a substring, so they
+/// can't be used to reject fuzzy matches, which match the prefixes of words (e.g. "usam" matches
+/// "United States of America"). Instead, every character of a search has to appear in a match,
+/// and each pair of consecutive characters has to either appear next to each other (ignoring
+/// spaces) or the second has to start a word, which rules out most items for longer searches
+/// without ever rejecting a match.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct NgramIndex {
+    chars: HashMap<char, Vec<ItemId>>,
+    bigrams: HashMap<(char, char), Vec<ItemId>>,
+    word_starts: HashMap<char, Vec<ItemId>>,
+}
+
+impl NgramIndex {
+    /// Index an item by the lower case form and skip indices it is matched with.
+    pub(crate) fn insert(&mut self, id: ItemId, lower: &str, skips: &[usize]) {
+        let grams = Grams::new(lower, skips);
+        for char in grams.chars {
+            insert_posting(self.chars.entry(char).or_default(), id);
+        }
+        for bigram in grams.bigrams {
+            insert_posting(self.bigrams.entry(bigram).or_default(), id);
+        }
+        for char in grams.word_starts {
+            insert_posting(self.word_starts.entry(char).or_default(), id);
+        }
+    }
+
+    /// Remove an item, given the same data it was inserted with.
+    pub(crate) fn remove(&mut self, id: ItemId, lower: &str, skips: &[usize]) {
+        let grams = Grams::new(lower, skips);
+        for char in grams.chars {
+            remove_posting(&mut self.chars, char, id);
+        }
+        for bigram in grams.bigrams {
+            remove_posting(&mut self.bigrams, bigram, id);
+        }
+        for char in grams.word_starts {
+            remove_posting(&mut self.word_starts, char, id);
+        }
+    }
+
+    /// The IDs of the items that could match a lower case search, in ascending order.
+    ///
+    /// # Returns
+    ///
+    /// [`None`] if every item could match, e.g. for an empty search.
+    pub(crate) fn candidates(&self, search: &str) -> Option<Vec<ItemId>> {
+        // Quoted searches only match substrings, so consecutive characters can't skip to a word
+        let (search, is_fuzzy) = match search.strip_prefix('"') {
+            Some(quoted) => (quoted.strip_suffix('"').unwrap_or(quoted), false),
+            None => (search, true),
+        };
+        let chars: Vec<char> = search.chars().filter(|&char| char != ' ').collect();
+        if chars.is_empty() {
+            return None
+        }
+
+        // Each requirement is met by the items in either of two posting lists. The first character
+        // has to appear in a match, and each pair of consecutive characters has to either appear
+        // next to each other, or the second has to start a word, which also means both appear.
+        let mut requirements: Vec<(&[ItemId], &[ItemId])> = Vec::with_capacity(chars.len());
+        requirements.push((postings(self.chars.get(&chars[0])), &[]));
+        for pair in chars.windows(2) {
+            let word_starts = if is_fuzzy { postings(self.word_starts.get(&pair[1])) } else { &[] };
+            requirements.push((postings(self.bigrams.get(&(pair[0], pair[1]))), word_starts));
+        }
+
+        // Starting from the rarest requirement keeps the candidates few, so the rest can be
+        // checked by binary search
+        requirements.sort_unstable_by_key(|(a, b)| a.len() + b.len());
+        let mut requirements = requirements.into_iter();
+        let (a, b) = requirements.next().unwrap_or_default();
+        let mut candidates = union(a, b);
+        for (a, b) in requirements {
+            if candidates.is_empty() {
+                break
+            }
+            candidates.retain(|id| a.binary_search(id).is_ok() || b.binary_search(id).is_ok());
+        }
+        Some(candidates)
+    }
+}
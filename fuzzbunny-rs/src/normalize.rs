@@ -0,0 +1,174 @@
+//! Language-specific normalization profiles for corpus items.
+
+#[cfg(feature = "std")]
+use crate::pipeline::Derived;
+use crate::Range;
+use crate::prelude::*;
+
+/// The rules used to normalize an item's text (and the search string it's matched against)
+/// before matching.
+///
+/// Each item in a [`Corpus`](crate::Corpus) can have its own profile, so corpora mixing
+/// several languages match every item with the right rules. See
+/// [`Corpus::insert_normalized`](crate::Corpus::insert_normalized).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "lowercase"))]
+pub enum Normalization {
+    /// Unicode lower casing, the same as [`fuzzy_filter`](crate::fuzzy_filter).
+    #[default]
+    Unicode,
+    /// Unicode lower casing, with the German umlauts and sharp s folded to their two letter
+    /// spellings, so "strasse" matches "Straße" and "muller" matches "Müller" (as "mueller").
+    German,
+    /// Turkish lower casing, where dotted and dotless i are distinct letters: "I" lower cases to
+    /// "ı", and "İ" to "i".
+    Turkish,
+}
+
+impl Normalization {
+    /// The number of profiles, for indexing per-profile data.
+    #[cfg(feature = "std")]
+    pub(crate) const COUNT: usize = 3;
+
+    /// The index of this profile in per-profile data.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub(crate) fn index(self) -> usize {
+        self as usize
+    }
+
+    /// The profile with an index, the inverse of [`Normalization::index`].
+    #[cfg(feature = "std")]
+    pub(crate) fn from_index(index: u8) -> Option<Self> {
+        match index {
+            0 => Some(Normalization::Unicode),
+            1 => Some(Normalization::German),
+            2 => Some(Normalization::Turkish),
+            _ => None,
+        }
+    }
+
+    /// Append the normalized form of a single character to `out`.
+    pub(crate) fn push_char(self, char: char, out: &mut String) {
+        match (self, char) {
+            (Normalization::German, 'ä' | 'Ä') => out.push_str("ae"),
+            (Normalization::German, 'ö' | 'Ö') => out.push_str("oe"),
+            (Normalization::German, 'ü' | 'Ü') => out.push_str("ue"),
+            (Normalization::German, 'ß' | 'ẞ') => out.push_str("ss"),
+            (Normalization::Turkish, 'I') => out.push('ı'),
+            (Normalization::Turkish, 'İ') => out.push('i'),
+            _ => out.extend(char.to_lowercase()),
+        }
+    }
+
+    /// Normalize a string with this profile.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fuzzbunny_rs::Normalization;
+    ///
+    /// assert_eq!(Normalization::German.normalize("Straße"), "strasse");
+    /// assert_eq!(Normalization::Turkish.normalize("İstanbul Irmak"), "istanbul ırmak");
+    /// ```
+    pub fn normalize(self, text: &str) -> String {
+        if self == Normalization::Unicode {
+            return text.to_lowercase()
+        }
+
+        let mut out = String::with_capacity(text.len());
+        for char in text.chars() {
+            self.push_char(char, &mut out);
+        }
+        out
+    }
+
+    /// Compute the derived search data of a text with this profile.
+    ///
+    /// Unlike [`Derived::new`], the skip indices are byte offsets into the normalized text, and
+    /// if normalization changed the length of any character, a map from normalized byte offsets
+    /// back to the original text is included.
+    #[cfg(feature = "std")]
+    pub(crate) fn derive(self, text: &str) -> Derived {
+        if self == Normalization::Unicode {
+            return Derived::new(text)
+        }
+
+        let mut lower = String::with_capacity(text.len());
+        let mut skips = vec![];
+        let mut offsets = Vec::with_capacity(text.len() + 1);
+        let mut was_alpha_num = false;
+        let mut was_upper_case = false;
+        let mut aligned = true;
+
+        // The same word boundaries as `get_target_skips`
+        for (i, char) in text.char_indices() {
+            let is_alpha_num = char.is_alphanumeric();
+            let is_upper_case = char.is_uppercase();
+            if (is_alpha_num && !was_alpha_num) || (is_upper_case && !was_upper_case) || char.is_ascii_punctuation() {
+                skips.push(lower.len());
+            }
+            was_alpha_num = is_alpha_num;
+            was_upper_case = is_upper_case;
+
+            let start = lower.len();
+            self.push_char(char, &mut lower);
+            offsets.resize(offsets.len() + lower.len() - start, i);
+            // Byte offsets only line up if each character maps to a single one of the same length
+            let normalized = &lower[start..];
+            aligned &= normalized.len() == char.len_utf8() && normalized.chars().nth(1).is_none();
+        }
+        skips.push(lower.len());
+        offsets.push(text.len());
+
+        if aligned {
+            offsets = vec![];
+        }
+
+        Derived { lower, skips, offsets }
+    }
+}
+
+/// Map match ranges in a normalized text back to the original text, using the offsets computed
+/// by [`Normalization::derive`].
+///
+/// A range covering part of a character that was expanded by normalization is widened to cover
+/// the whole original character, merging it with any adjacent range.
+pub(crate) fn map_ranges(ranges: Vec<Range>, offsets: &[usize]) -> Vec<Range> {
+    if offsets.is_empty() {
+        return ranges
+    }
+
+    let mut mapped: Vec<Range> = Vec::with_capacity(ranges.len());
+    for Range(start, len) in ranges {
+        let end = start + len;
+        let start_offset = offsets[start];
+        let end_offset = if end + 1 < offsets.len() && offsets[end] == offsets[end - 1] {
+            // The range ends part way through an expanded character
+            next_offset(offsets, end)
+        } else {
+            offsets[end]
+        };
+
+        match mapped.last_mut() {
+            Some(last) if last.end() >= start_offset => last.1 = end_offset - last.0,
+            _ => mapped.push(Range(start_offset, end_offset - start_offset)),
+        }
+    }
+    mapped
+}
+
+/// Map skip indices of the original text to the normalized text, using the offsets computed by
+/// [`Normalization::derive`], so each skip starts the first normalized byte of its character.
+pub(crate) fn map_skips(skips: &[usize], offsets: &[usize]) -> Vec<usize> {
+    if offsets.is_empty() {
+        return skips.to_vec()
+    }
+    skips.iter().map(|&skip| offsets.partition_point(|&offset| offset < skip)).collect()
+}
+
+/// The original offset of the first normalized byte after the character containing `index`.
+fn next_offset(offsets: &[usize], index: usize) -> usize {
+    let original = offsets[index];
+    offsets[index..].iter().copied().find(|&offset| offset != original).unwrap_or(original)
+}
@@ -0,0 +1,91 @@
+//! Canonical forms of numbers, so numbers with the same value match each other.
+
+use crate::pipeline::Piece;
+use crate::prelude::*;
+
+/// Find the end of the number starting at `start` (a run of ASCII digits, optionally separated
+/// by single dots), along with the byte ranges of its dot separated components.
+fn number_components(bytes: &[u8], start: usize) -> (usize, Vec<(usize, usize)>) {
+    let mut components = vec![];
+    let mut end = start;
+    loop {
+        let component_start = end;
+        while end < bytes.len() && bytes[end].is_ascii_digit() {
+            end += 1;
+        }
+        components.push((component_start, end));
+        // Only a dot followed by another digit continues the number
+        if end + 1 < bytes.len() && bytes[end] == b'.' && bytes[end + 1].is_ascii_digit() {
+            end += 1;
+        } else {
+            return (end, components)
+        }
+    }
+}
+
+/// The byte ranges of a number that are kept in its canonical form.
+///
+/// A number with a single dot is a decimal, which loses the leading zeros of its integer part
+/// and the trailing zeros of its fraction (and the dot, if the fraction is all zeros), so "01.50"
+/// is "1.5" and "1.00" is "1". Any other number (an integer, or a version like "1.02.3") loses
+/// the leading zeros of each component, so "007" is "7" and "1.02.3" is "1.2.3".
+fn canonical_parts(bytes: &[u8], components: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    let strip_leading = |(start, end): (usize, usize)| {
+        let zeros = bytes[start..end - 1].iter().take_while(|&&byte| byte == b'0').count();
+        (start + zeros, end)
+    };
+
+    match *components {
+        [integer, (fraction_start, fraction_end)] => {
+            let integer = strip_leading(integer);
+            let zeros = bytes[fraction_start..fraction_end].iter().rev().take_while(|&&byte| byte == b'0').count();
+            if zeros == fraction_end - fraction_start {
+                vec![integer]
+            } else {
+                // Keep the dot along with the fraction
+                vec![integer, (fraction_start - 1, fraction_end - zeros)]
+            }
+        },
+        _ => components
+            .iter()
+            .enumerate()
+            .flat_map(|(i, &component)| {
+                // Keep the dot before every component but the first
+                let dot = (i > 0).then(|| (component.0 - 1, component.0));
+                dot.into_iter().chain([strip_leading(component)])
+            })
+            .collect(),
+    }
+}
+
+/// Put every number of a text in its canonical form, removing the characters it doesn't keep.
+pub(crate) fn canonicalize_pieces(pieces: &mut Vec<Piece>) {
+    // Digits and dots are ASCII, so any other character can stand in for the rest
+    let bytes: Vec<u8> = pieces.iter().map(|piece| if piece.char.is_ascii() { piece.char as u8 } else { 0 }).collect();
+    let mut kept = Vec::with_capacity(pieces.len());
+    // A removed character that started a word passes that on to the next kept character
+    let mut starts_word = false;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if !bytes[i].is_ascii_digit() {
+            kept.push(Piece { starts_word: starts_word | pieces[i].starts_word, ..pieces[i] });
+            starts_word = false;
+            i += 1;
+            continue
+        }
+
+        let (end, components) = number_components(&bytes, i);
+        let parts = canonical_parts(&bytes, &components);
+        for (j, piece) in pieces.iter().enumerate().take(end).skip(i) {
+            if parts.iter().any(|&(start, end)| (start..end).contains(&j)) {
+                kept.push(Piece { starts_word: starts_word | piece.starts_word, ..*piece });
+                starts_word = false;
+            } else {
+                starts_word |= piece.starts_word;
+            }
+        }
+        i = end;
+    }
+    *pieces = kept;
+}
@@ -0,0 +1,623 @@
+//! Options controlling how searches match targets.
+
+use alloc::borrow::Cow;
+use core::ops;
+
+use crate::pipeline::Derived;
+use crate::normalize::{map_ranges, map_skips};
+use crate::prelude::*;
+use crate::typo::score_typos;
+use crate::{
+    collect_indexed_matches_by, fuzzy_prefix_match, get_target_skips, instrument, is_lower_case_aligned, is_parallel_workload, score_recorded,
+    sort_results, FuzzyFilterResult, NormalizationPipeline, Range, ScoringConfig, SkipRules, StringScore, Target,
+};
+
+/// How a search matches a target.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum MatchMode {
+    /// The default matching of [`fuzzy_filter`](crate::fuzzy_filter): a substring match, falling
+    /// back to matching the prefixes of words in order. A quoted search only matches substrings.
+    #[default]
+    Fuzzy,
+    /// Every character of the search must match the first character of a distinct word, in
+    /// order (e.g. "gc" matches "Git: Commit" but not "gcc"), which suits keyboard-driven
+    /// command palettes where looser matches are noise.
+    ///
+    /// Words start at the same boundaries as skip indices (see
+    /// [`get_target_skips`](crate::get_target_skips)), including camelCase changes, other than
+    /// punctuation. Whitespace in the search is ignored, and quotes have no special meaning.
+    Initials,
+    /// The search must be a substring of the target, with no fallback to matching the prefixes
+    /// of words. This is how a quoted search matches in [`MatchMode::Fuzzy`], so "ton" matches
+    /// "Washington" but not "the Toronto news".
+    ///
+    /// Quotes around the search are ignored.
+    Substring,
+    /// The search must be a prefix of the target or of one of its words, as in classic
+    /// autocomplete, so "york" matches "New York" but not "Yorkshire Pudding Cookbook" or
+    /// "Porkyorkie".
+    ///
+    /// Words start at the same boundaries as skip indices (see
+    /// [`get_target_skips`](crate::get_target_skips)), and the search may continue past the end
+    /// of a word, so "new y" matches "New York". Quotes around the search are ignored.
+    WordPrefix,
+    /// The search must be the whole target, ignoring case and surrounding whitespace.
+    ///
+    /// Quotes around the search are ignored.
+    Exact,
+}
+
+impl MatchMode {
+    /// The name of the mode, e.g. `"word-prefix"` for [`MatchMode::WordPrefix`].
+    pub fn name(self) -> &'static str {
+        match self {
+            MatchMode::Fuzzy => "fuzzy",
+            MatchMode::Initials => "initials",
+            MatchMode::Substring => "substring",
+            MatchMode::WordPrefix => "word-prefix",
+            MatchMode::Exact => "exact",
+        }
+    }
+}
+
+/// Where a match may start in a target, for [`MatchOptions::anchor`].
+///
+/// The first matched range of a match must start at the anchor, and the rest may be anywhere
+/// after it. With [`MatchOptions::search_range`], anchors are relative to the range.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Anchor {
+    /// Matches may start anywhere.
+    #[default]
+    Anywhere,
+    /// Matches must start at the start of the target, as in a command palette.
+    StringStart,
+    /// Matches must start at the start of a word, at the same boundaries as skip indices (see
+    /// [`get_target_skips`](crate::get_target_skips)).
+    WordStart,
+}
+
+/// A version of the scoring algorithm.
+///
+/// Each version is frozen once released: the same targets and searches always get the same
+/// scores and highlights, so snapshot-tested rankings don't silently change when the crate is
+/// upgraded. Improvements to scoring are released as new versions, which have to be opted into
+/// by setting [`MatchOptions::scoring`] (or using their module, such as
+/// [`scoring_v1`](crate::scoring_v1)).
+///
+/// The default is [`ScoringVersion::LATEST`], which may change between releases, so pin a
+/// version to keep rankings stable.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum ScoringVersion {
+    /// The original scoring algorithm. See [`scoring_v1`](crate::scoring_v1).
+    #[default]
+    V1,
+}
+
+impl ScoringVersion {
+    /// The latest version.
+    pub const LATEST: ScoringVersion = ScoringVersion::V1;
+
+    /// The version number, e.g. `1` for [`ScoringVersion::V1`].
+    pub fn number(self) -> u32 {
+        match self {
+            ScoringVersion::V1 => 1,
+        }
+    }
+}
+
+/// Options for [`fuzzy_filter_with_options`] and [`fuzzy_score_item_with_options`].
+///
+/// The default options match the same as [`fuzzy_filter`](crate::fuzzy_filter).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MatchOptions {
+    /// How the search matches each target.
+    pub mode: MatchMode,
+    /// Whether numbers match by value, so "2" matches "02" and "1.0" matches "1.00" (e.g. for
+    /// versioned item names and IDs).
+    ///
+    /// Leading zeros are ignored, as are the trailing zeros of a decimal's fraction. A number
+    /// with more than one dot (e.g. "1.02.3") is taken to be a version, where leading zeros of
+    /// each component are ignored instead. Highlights still reference the original text.
+    ///
+    /// Precomputed skip indices can't be used with this option, so targets are prepared again
+    /// for every search.
+    pub numeric_equivalence: bool,
+    /// The transforms applied to each target and search before matching, or [`None`] to only
+    /// lower case them. See [`NormalizationPipeline`].
+    ///
+    /// As with [`MatchOptions::numeric_equivalence`], targets are prepared again for every
+    /// search when this is set.
+    pub pipeline: Option<NormalizationPipeline>,
+    /// The version of the scoring algorithm. See [`ScoringVersion`].
+    pub scoring: ScoringVersion,
+    /// The weights used to score matches. See [`ScoringConfig`].
+    pub scoring_config: ScoringConfig,
+    /// The most typos (inserted, deleted, substituted or transposed characters) tolerated when a
+    /// search doesn't otherwise match a target, e.g. so "aplication" matches "Application".
+    ///
+    /// A target with typos matches the substring closest to the search, and each typo reduces
+    /// its score by [`ScoringConfig::typo_penalty`]. Fewer than half the characters of a search
+    /// can be typos, so short searches don't match almost anything. Typos are only tolerated by
+    /// [`MatchMode::Fuzzy`], for unquoted searches without an [`anchor`](MatchOptions::anchor).
+    /// The default of 0 tolerates no typos.
+    pub max_typos: usize,
+    /// The word boundaries of targets without precomputed skip indices, or [`None`] for the
+    /// default boundaries. See [`SkipRules`].
+    ///
+    /// Targets with precomputed skip indices keep them, so they should be prepared with
+    /// [`SkipRules::precompute_skips_for_items`]. As the pipeline and numeric equivalence
+    /// compute their own skip indices, these rules aren't used with either.
+    pub skip_rules: Option<SkipRules>,
+    /// Where matches may start. See [`Anchor`].
+    ///
+    /// A search that matches a target elsewhere can still match it at the anchor, e.g. with
+    /// [`Anchor::WordStart`], "ap" matches the "Ap" of "Snapshot Apply" rather than the "ap" of
+    /// "Snapshot".
+    pub anchor: Anchor,
+    /// The byte range of each target that matches may be placed in, or [`None`] for the whole
+    /// target, e.g. so searches only match the file name of a path.
+    ///
+    /// The range is clipped to the length of each target, and a target is scored as if it only
+    /// held the text in the range, so [`Anchor::StringStart`] anchors matches to the start of
+    /// the range. Ranges are still byte offsets into the whole target. A target with a range
+    /// that doesn't start and end on character boundaries doesn't match.
+    pub search_range: Option<ops::Range<usize>>,
+    /// Whether a search that falls back to matching the prefixes of words scores every way of
+    /// aligning them with the target, and takes the best, rather than the first that matches.
+    ///
+    /// The first alignment starts at the earliest word it can, which isn't always where a
+    /// person would place the match, e.g. "fbaz" first aligns with the initials of "Fun Big
+    /// Apple Zoo" in "Fun Big Apple Zoo, Foo Baz" rather than with "Foo Baz". This only applies
+    /// to [`MatchMode::Fuzzy`], and costs a scan of every word of targets that don't match a
+    /// search as a substring, so it's off by default, which also keeps the scores of
+    /// [`ScoringVersion::V1`] unchanged.
+    pub best_alignment: bool,
+}
+
+impl MatchOptions {
+    /// Set how the search matches each target.
+    pub fn with_mode(mut self, mode: MatchMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Set whether numbers match by value. See [`MatchOptions::numeric_equivalence`].
+    pub fn with_numeric_equivalence(mut self, numeric_equivalence: bool) -> Self {
+        self.numeric_equivalence = numeric_equivalence;
+        self
+    }
+
+    /// Set the version of the scoring algorithm. See [`ScoringVersion`].
+    pub fn with_scoring(mut self, scoring: ScoringVersion) -> Self {
+        self.scoring = scoring;
+        self
+    }
+
+    /// Set the weights used to score matches. See [`ScoringConfig`].
+    pub fn with_scoring_config(mut self, scoring_config: ScoringConfig) -> Self {
+        self.scoring_config = scoring_config;
+        self
+    }
+
+    /// Set the most typos tolerated. See [`MatchOptions::max_typos`].
+    pub fn with_max_typos(mut self, max_typos: usize) -> Self {
+        self.max_typos = max_typos;
+        self
+    }
+
+    /// Set the word boundaries of targets without precomputed skip indices. See
+    /// [`MatchOptions::skip_rules`].
+    pub fn with_skip_rules(mut self, skip_rules: SkipRules) -> Self {
+        self.skip_rules = Some(skip_rules);
+        self
+    }
+
+    /// Set where matches may start. See [`Anchor`].
+    pub fn with_anchor(mut self, anchor: Anchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+
+    /// Set the byte range of each target that matches may be placed in. See
+    /// [`MatchOptions::search_range`].
+    pub fn with_search_range(mut self, search_range: ops::Range<usize>) -> Self {
+        self.search_range = Some(search_range);
+        self
+    }
+
+    /// Set whether to take the best alignment of the prefixes of words. See
+    /// [`MatchOptions::best_alignment`].
+    pub fn with_best_alignment(mut self, best_alignment: bool) -> Self {
+        self.best_alignment = best_alignment;
+        self
+    }
+
+    /// Set the transforms applied to each target and search before matching. See
+    /// [`MatchOptions::pipeline`].
+    pub fn with_pipeline(mut self, pipeline: NormalizationPipeline) -> Self {
+        self.pipeline = Some(pipeline);
+        self
+    }
+}
+
+/// Match every character of a search against the first character of a distinct word of the
+/// target, in order.
+///
+/// The earliest word that matches each character is taken, which also gives the best score.
+fn score_initials(target_lower: &str, target_skips: &[usize], search: &str, config: &ScoringConfig) -> Option<StringScore> {
+    let mut ranges = vec![];
+    let mut words = target_skips[..target_skips.len() - 1].iter().filter_map(|&start| {
+        // Only words start with alphanumeric characters, rather than punctuation
+        let char = target_lower.get(start..)?.chars().next()?;
+        char.is_alphanumeric().then_some((start, char))
+    });
+
+    for search_char in search.chars().filter(|char| !char.is_whitespace()) {
+        let (start, char) = words.find(|&(_, char)| char == search_char)?;
+        ranges.push(Range(start, char.len_utf8()));
+    }
+
+    let score = config.score(&ranges, |_| true, target_lower);
+    Some(StringScore { score, ranges })
+}
+
+/// Match a search as a substring of the target, scored the same as a quoted search.
+fn score_substring(target_lower: &str, search: &str, config: &ScoringConfig) -> Option<StringScore> {
+    let start = target_lower.find(search)?;
+    Some(score_substring_at(target_lower, start, search, config))
+}
+
+/// Score a search matched as a substring of the target at `start`.
+fn score_substring_at(target_lower: &str, start: usize, search: &str, config: &ScoringConfig) -> StringScore {
+    let ranges = vec![Range(start, search.len())];
+    let is_word_prefix = target_lower[..start].chars().next_back().is_some_and(|char| !char.is_alphanumeric());
+    let score = config.score(&ranges, |_| is_word_prefix, target_lower);
+    StringScore { score, ranges }
+}
+
+/// Match a search as a prefix of the earliest word of the target that it's a prefix of.
+fn score_word_prefix(target_lower: &str, target_skips: &[usize], search: &str, config: &ScoringConfig) -> Option<StringScore> {
+    let start = target_skips[..target_skips.len() - 1]
+        .iter()
+        .copied()
+        .find(|&start| target_lower.get(start..).is_some_and(|word| word.starts_with(search)))?;
+    let ranges = vec![Range(start, search.len())];
+    let score = config.score(&ranges, |_| true, target_lower);
+    Some(StringScore { score, ranges })
+}
+
+/// Match a search as the whole target, other than its surrounding whitespace.
+fn score_exact(target_lower: &str, search: &str, config: &ScoringConfig) -> Option<StringScore> {
+    let trimmed = target_lower.trim();
+    if trimmed != search {
+        return None
+    }
+    let ranges = vec![Range(target_lower.len() - target_lower.trim_start().len(), search.len())];
+    let score = config.score(&ranges, |_| true, target_lower);
+    Some(StringScore { score, ranges })
+}
+
+/// Match a search the same as [`score_recorded`], but only where `anchor` allows the first
+/// range to start. If `best_alignment` is set, every alignment of the prefixes of words is
+/// scored and the best is taken, rather than the first.
+///
+/// The search must be unquoted. If `substring_only` is set, there is no fallback to matching the
+/// prefixes of words, as for a quoted search.
+fn score_anchored(
+    target_lower: &str,
+    target_skips: &[usize],
+    search: &str,
+    substring_only: bool,
+    anchor: Anchor,
+    best_alignment: bool,
+    config: &ScoringConfig,
+) -> Option<StringScore> {
+    if search.is_empty() {
+        return Some(StringScore { score: 0, ranges: vec![] })
+    }
+    let words = &target_skips[..target_skips.len() - 1];
+    // The skip indices of the words the match may start at
+    let starts = match anchor {
+        Anchor::Anywhere => 0..words.len(),
+        Anchor::StringStart => 0..usize::from(words.first() == Some(&0)),
+        Anchor::WordStart => 0..words.len(),
+    };
+
+    let substring_start = match anchor {
+        Anchor::Anywhere => target_lower.find(search),
+        Anchor::StringStart => target_lower.starts_with(search).then_some(0),
+        Anchor::WordStart => words.iter().copied().find(|&start| target_lower.get(start..).is_some_and(|word| word.starts_with(search))),
+    };
+    if let Some(start) = substring_start {
+        return Some(score_substring_at(target_lower, start, search, config))
+    }
+
+    if substring_only || search.len() == 1 {
+        return None
+    }
+    let first_search_byte = search.as_bytes()[0];
+    let mut alignments = starts
+        .filter(|&skip_idx| target_lower.as_bytes().get(target_skips[skip_idx]) == Some(&first_search_byte))
+        .filter_map(|skip_idx| fuzzy_prefix_match(skip_idx, search, target_lower, target_skips, &mut ()))
+        .map(|ranges| StringScore { score: config.score(&ranges, |_| true, target_lower), ranges });
+    if !best_alignment {
+        return alignments.next()
+    }
+    // The earliest of the best scoring alignments
+    alignments.fold(None, |best: Option<StringScore>, alignment| match best {
+        Some(best) if best.score >= alignment.score => Some(best),
+        _ => Some(alignment),
+    })
+}
+
+/// Whether a match starts at the start of the target, if the anchor requires it.
+///
+/// The other match modes always start matches at the start of a word, and at the earliest word
+/// they can, so only [`Anchor::StringStart`] can rule out their matches.
+fn is_at_string_start(string_score: &StringScore, anchor: Anchor) -> bool {
+    anchor != Anchor::StringStart || string_score.ranges.first().is_none_or(|range| range.0 == 0)
+}
+
+/// The text of a search, without the quotes that make a fuzzy search only match substrings.
+fn unquote(search: &str) -> &str {
+    match search.strip_prefix('"') {
+        Some(quoted) => quoted.strip_suffix('"').unwrap_or(quoted),
+        None => search,
+    }
+}
+
+/// Score a target against a search without its quotes, on the lower case form of the target
+/// (see [`score_lower_case`]). An empty search matches every non-empty target with a score of 0,
+/// as with the fuzzy mode.
+fn score_unquoted(
+    target: &str,
+    target_lower: Option<&str>,
+    target_skips: Option<&[usize]>,
+    search: &str,
+    config: &ScoringConfig,
+    score: impl FnOnce(&str, &[usize], &str) -> Option<StringScore>,
+) -> Option<StringScore> {
+    let search = unquote(search);
+    if search.is_empty() {
+        return score_recorded(target, target_lower, target_skips, "", config, &mut ())
+    }
+    score_lower_case(target, target_lower, target_skips, |lower, skips| score(lower, skips, search))
+}
+
+/// Score a non-empty target on its lower case form and skip indices, computing any that aren't
+/// given.
+///
+/// Lower casing can change the length of non-ASCII characters, in which case the target is
+/// scored on a normalized form and the ranges are mapped back to the original text.
+fn score_lower_case(
+    target: &str,
+    target_lower: Option<&str>,
+    target_skips: Option<&[usize]>,
+    score: impl FnOnce(&str, &[usize]) -> Option<StringScore>,
+) -> Option<StringScore> {
+    if target.is_empty() {
+        return None
+    }
+    let skips = || match target_skips {
+        Some(skips) => Cow::Borrowed(skips),
+        None => Cow::Owned(get_target_skips(target)),
+    };
+    if let Some(lower) = target_lower {
+        return score(lower, &skips())
+    }
+
+    let lower = target.to_lowercase();
+    if is_lower_case_aligned(target, &lower) {
+        return score(&lower, &skips())
+    }
+    let derived = Derived::new(target);
+    // Given skips (e.g. from custom skip rules) are kept, moved to the lower case form
+    let skips = match target_skips {
+        Some(skips) => Cow::Owned(map_skips(skips, &derived.offsets)),
+        None => Cow::Borrowed(derived.skips.as_slice()),
+    };
+    let string_score = score(&derived.lower, &skips)?;
+    Some(StringScore { score: string_score.score, ranges: map_ranges(string_score.ranges, &derived.offsets) })
+}
+
+/// Score a target with precomputed data, matching as set by `options`.
+///
+/// Note that `search` string MUST be lower case.
+pub(crate) fn score_with_options(
+    target: &str,
+    target_lower: Option<&str>,
+    target_skips: Option<&[usize]>,
+    search: &str,
+    options: &MatchOptions,
+) -> Option<StringScore> {
+    match &options.search_range {
+        Some(range) => score_range(target, target_lower, target_skips, search, options, range),
+        None => score_whole(target, target_lower, target_skips, search, options),
+    }
+}
+
+/// Score the part of a target in `range` as if it were the whole target, with the matched
+/// ranges offset back into the target.
+fn score_range(
+    target: &str,
+    target_lower: Option<&str>,
+    target_skips: Option<&[usize]>,
+    search: &str,
+    options: &MatchOptions,
+    range: &ops::Range<usize>,
+) -> Option<StringScore> {
+    let end = range.end.min(target.len());
+    let start = range.start.min(end);
+    let part = target.get(start..end)?;
+
+    // Precomputed data only lines up with the target if lower casing didn't change its length
+    let aligned = target_lower.is_none_or(|lower| lower.len() == target.len());
+    let part_lower = target_lower.filter(|_| aligned).and_then(|lower| lower.get(start..end));
+    let part_skips = target_skips.filter(|_| aligned).map(|skips| {
+        let mut part_skips: Vec<usize> = skips.iter().filter(|&&skip| skip >= start && skip < end).map(|skip| skip - start).collect();
+        // The part starts a word, as it would if it were the whole target
+        if part_skips.first() != Some(&0) && part.chars().next().is_some_and(|char| !char.is_whitespace()) {
+            part_skips.insert(0, 0);
+        }
+        part_skips.push(part.len());
+        part_skips
+    });
+
+    let string_score = score_whole(part, part_lower, part_skips.as_deref(), search, options)?;
+    let ranges = string_score.ranges.into_iter().map(|range| Range(range.0 + start, range.1)).collect();
+    Some(StringScore { score: string_score.score, ranges })
+}
+
+/// Score a whole target with precomputed data, matching as set by `options` other than its
+/// search range.
+fn score_whole(
+    target: &str,
+    target_lower: Option<&str>,
+    target_skips: Option<&[usize]>,
+    search: &str,
+    options: &MatchOptions,
+) -> Option<StringScore> {
+    // The core matcher implements the only version so far, so there's nothing to choose between
+    let ScoringVersion::V1 = options.scoring;
+
+    if options.numeric_equivalence || options.pipeline.is_some() {
+        let default_pipeline;
+        let pipeline = match &options.pipeline {
+            Some(pipeline) => pipeline,
+            None => {
+                default_pipeline = NormalizationPipeline::default();
+                &default_pipeline
+            },
+        };
+        let derived = pipeline.derive(target, options.numeric_equivalence);
+        let search = pipeline.derive(search, options.numeric_equivalence).lower;
+        let lower = derived.lower.as_str();
+        let string_score = score_with_mode(lower, Some(lower), Some(&derived.skips), &search, options)?;
+        return Some(StringScore {
+            score: string_score.score,
+            ranges: map_ranges(string_score.ranges, &derived.offsets),
+        })
+    }
+    score_with_mode(target, target_lower, target_skips, search, options)
+}
+
+/// Score a target with precomputed data, matching as set by the mode and scoring config of
+/// `options`.
+fn score_with_mode(
+    target: &str,
+    target_lower: Option<&str>,
+    target_skips: Option<&[usize]>,
+    search: &str,
+    options: &MatchOptions,
+) -> Option<StringScore> {
+    let config = &options.scoring_config;
+    let rule_skips;
+    let target_skips = match (target_skips, &options.skip_rules) {
+        (None, Some(rules)) => {
+            rule_skips = rules.skips(target);
+            Some(rule_skips.as_slice())
+        },
+        (target_skips, _) => target_skips,
+    };
+    match options.mode {
+        MatchMode::Fuzzy => {
+            let string_score = match options.anchor {
+                Anchor::Anywhere if !options.best_alignment => {
+                    score_recorded(target, target_lower, target_skips, search, config, &mut ())
+                },
+                anchor => {
+                    let quoted = search.starts_with('"');
+                    score_lower_case(target, target_lower, target_skips, |lower, skips| {
+                        score_anchored(lower, skips, unquote(search), quoted, anchor, options.best_alignment, config)
+                    })
+                },
+            };
+            let anchored = options.anchor != Anchor::Anywhere;
+            if string_score.is_some() || options.max_typos == 0 || search.starts_with('"') || anchored {
+                return string_score
+            }
+            match target_lower {
+                Some(lower) => score_typos(lower, search, options.max_typos, config),
+                None => {
+                    let derived = Derived::new(target);
+                    let string_score = score_typos(&derived.lower, search, options.max_typos, config)?;
+                    Some(StringScore { score: string_score.score, ranges: map_ranges(string_score.ranges, &derived.offsets) })
+                },
+            }
+        },
+        MatchMode::Initials => score_lower_case(target, target_lower, target_skips, |lower, skips| {
+            score_initials(lower, skips, search, config).filter(|string_score| is_at_string_start(string_score, options.anchor))
+        }),
+        MatchMode::Substring => score_unquoted(target, target_lower, target_skips, search, config, |lower, skips, search| {
+            match options.anchor {
+                Anchor::Anywhere => score_substring(lower, search, config),
+                anchor => score_anchored(lower, skips, search, true, anchor, false, config),
+            }
+        }),
+        MatchMode::WordPrefix => score_unquoted(target, target_lower, target_skips, search, config, |lower, skips, search| {
+            score_word_prefix(lower, skips, search, config).filter(|string_score| is_at_string_start(string_score, options.anchor))
+        }),
+        MatchMode::Exact => score_unquoted(target, target_lower, target_skips, search, config, |lower, _, search| {
+            score_exact(lower, search, config).filter(|string_score| is_at_string_start(string_score, options.anchor))
+        }),
+    }
+}
+
+/// Compute a raw score and highlight ranges for a target and search string, matching as set by
+/// `options`. See [`fuzzy_score_item`](crate::fuzzy_score_item).
+///
+/// Note that `search` string MUST be lower case.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{fuzzy_score_item_with_options, MatchMode, MatchOptions};
+///
+/// let options = MatchOptions::default().with_mode(MatchMode::Initials);
+///
+/// assert!(fuzzy_score_item_with_options(&("Git: Commit", None), "gc", &options).is_some());
+/// assert!(fuzzy_score_item_with_options(&("gcc", None), "gc", &options).is_none());
+/// ```
+pub fn fuzzy_score_item_with_options(target: &Target<'_>, search: &str, options: &MatchOptions) -> Option<StringScore> {
+    score_with_options(target.0, None, target.1.as_deref(), search, options)
+}
+
+/// Search a slice of [`Target`]s, matching as set by `options`, and return a filtered and
+/// sorted vector of [`FuzzyFilterResult`].
+///
+/// This behaves the same as [`fuzzy_filter`](crate::fuzzy_filter) with the default options.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{fuzzy_filter_with_options, precompute_skips_for_items, MatchMode, MatchOptions};
+///
+/// let targets = precompute_skips_for_items(vec!["Git: Commit", "Git: Checkout", "gcc", "Go to Config"]);
+/// let options = MatchOptions::default().with_mode(MatchMode::Initials);
+///
+/// let results = fuzzy_filter_with_options(&targets, "gc", &options);
+/// let items: Vec<&str> = results.iter().map(|res| res.item).collect();
+/// assert_eq!(items, vec!["Git: Checkout", "Git: Commit", "Go to Config"]);
+/// ```
+pub fn fuzzy_filter_with_options<'a>(items: &[Target<'a>], search: &str, options: &MatchOptions) -> Vec<FuzzyFilterResult<'a>> {
+    let search_lower_cased = search.trim().to_lowercase();
+    let parallel = is_parallel_workload(items.len(), |i| items[i].0.len(), search_lower_cased.len());
+    let span = instrument::FilterSpan::enter("fuzzy_filter_with_options", items.len(), search_lower_cased.len(), parallel);
+
+    let stage = instrument::Stage::start("score");
+    let mut results = collect_indexed_matches_by(items, parallel, |index, target| {
+        let string_score = score_with_options(target.0, None, target.1.as_deref(), &search_lower_cased, options)?;
+        Some(FuzzyFilterResult::matched(target.0, string_score.score, string_score.ranges).with_index(index))
+    });
+    stage.finish(items.len(), results.len());
+
+    if !search.is_empty() {
+        let stage = instrument::Stage::start("sort");
+        sort_results(&mut results, parallel);
+        stage.finish(results.len(), results.len());
+    }
+
+    span.finish(results.len());
+    results
+}
@@ -0,0 +1,158 @@
+//! Results that own their item strings.
+
+use core::cmp::Ordering;
+
+use crate::prelude::*;
+use crate::{
+    collect_indexed_matches_by, fuzzy_score_item, highlights_from_ranges, instrument, should_parallelise, sort_results,
+    FuzzyFilterResult, Highlights, Range, Target,
+};
+
+/// Filter result that owns its item string, so it doesn't borrow from the searched targets.
+///
+/// A [`FuzzyFilterResult`] borrows its item and highlights from the targets, so it can't
+/// outlive them, e.g. once the items are refreshed. This owns a copy of the item and its
+/// matched ranges instead, so it can be stored or sent to other threads freely, and computes
+/// the highlights from them when needed.
+///
+/// With the `serde` feature, this serializes as a struct with `item`, `score`, `ranges` and (if
+/// known) `index` fields, and as it owns its item, it can be deserialized from any format.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FuzzyFilterResultOwned {
+    /// The target string that the search string was matched against.
+    pub item: String,
+    /// The match score for a search string against a target string.
+    pub score: u32,
+    /// The matched ranges of the item, as byte offsets. See [`FuzzyFilterResult::ranges`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub ranges: Option<Vec<Range>>,
+    /// The position of the target in the items that were searched. See
+    /// [`FuzzyFilterResult::index`].
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub index: Option<usize>,
+}
+
+impl FuzzyFilterResultOwned {
+    /// The highlights of the item, computed from its ranges. See [`FuzzyFilterResult::highlights`].
+    pub fn highlights(&self) -> Option<Highlights<'_>> {
+        let ranges = self.ranges.clone()?;
+        Some(highlights_from_ranges(&self.item, ranges))
+    }
+
+    /// Borrow this as a [`FuzzyFilterResult`], e.g. to pass it to functions that take one.
+    pub fn as_result(&self) -> FuzzyFilterResult<'_> {
+        FuzzyFilterResult {
+            item: &self.item,
+            score: self.score,
+            highlights: self.highlights(),
+            ranges: self.ranges.clone(),
+            index: self.index,
+        }
+    }
+
+    /// The relevance of the match, from 0.0 to 1.0. See [`FuzzyFilterResult::normalized_score`].
+    pub fn normalized_score(&self) -> f32 {
+        crate::scoring::normalized_score(self.ranges.as_deref().unwrap_or_default(), self.item.len())
+    }
+}
+
+/// The matched ranges of highlights, which alternate between unmatched and matched sections.
+fn ranges_from_highlights(highlights: &[&str]) -> Vec<Range> {
+    let mut ranges = vec![];
+    let mut start = 0;
+    for (i, section) in highlights.iter().enumerate() {
+        if i % 2 == 1 {
+            ranges.push(Range(start, section.len()));
+        }
+        start += section.len();
+    }
+    ranges
+}
+
+impl<'a> FuzzyFilterResult<'a> {
+    /// Copy this into a [`FuzzyFilterResultOwned`], which doesn't borrow from the targets.
+    ///
+    /// Only the item string is copied, along with the ranges, as the highlights can be computed
+    /// from them.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn to_owned(&self) -> FuzzyFilterResultOwned {
+        let ranges = match (&self.ranges, &self.highlights) {
+            (Some(ranges), _) => Some(ranges.clone()),
+            (None, Some(highlights)) => Some(ranges_from_highlights(highlights)),
+            (None, None) => None,
+        };
+        FuzzyFilterResultOwned { item: self.item.to_string(), score: self.score, ranges, index: self.index }
+    }
+}
+
+impl From<FuzzyFilterResult<'_>> for FuzzyFilterResultOwned {
+    fn from(result: FuzzyFilterResult<'_>) -> Self {
+        result.to_owned()
+    }
+}
+
+impl PartialEq for FuzzyFilterResultOwned {
+    fn eq(&self, other: &Self) -> bool {
+        self.score == other.score && self.item == other.item
+    }
+}
+impl Eq for FuzzyFilterResultOwned {}
+impl PartialOrd for FuzzyFilterResultOwned {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+impl Ord for FuzzyFilterResultOwned {
+    /// The same order as [`FuzzyFilterResult`].
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score.cmp(&other.score).then_with(|| other.item.cmp(&self.item))
+    }
+}
+
+/// Search a slice of [`Target`]s and return a filtered and sorted vector of
+/// [`FuzzyFilterResultOwned`], which don't borrow from `items`.
+///
+/// This returns the same results as [`fuzzy_filter`](crate::fuzzy_filter), converted with
+/// [`FuzzyFilterResult::to_owned`]. As the owned results compute their highlights when
+/// needed, none are computed here.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{fuzzy_filter_owned, precompute_skips_for_items};
+///
+/// let results = {
+///     let items = vec!["apple".to_string(), "banana".to_string()];
+///     let targets = precompute_skips_for_items(items.iter().map(|s| s.as_str()));
+///     fuzzy_filter_owned(&targets, "ban")
+/// };
+///
+/// // The results outlive the items they were matched against
+/// assert_eq!(results[0].item, "banana");
+/// assert_eq!(results[0].highlights(), Some(vec!["", "ban", "ana"]));
+/// ```
+pub fn fuzzy_filter_owned(items: &[Target<'_>], search: &str) -> Vec<FuzzyFilterResultOwned> {
+    let search_lower_cased = search.trim().to_lowercase();
+    let parallel = should_parallelise(items, &search_lower_cased);
+    let span = instrument::FilterSpan::enter("fuzzy_filter_owned", items.len(), search_lower_cased.len(), parallel);
+
+    let stage = instrument::Stage::start("score");
+    let mut results = collect_indexed_matches_by(items, parallel, |index, target| {
+        let string_score = fuzzy_score_item(target, &search_lower_cased)?;
+        Some(FuzzyFilterResultOwned {
+            item: target.0.to_string(),
+            score: string_score.score,
+            ranges: Some(string_score.ranges),
+            index: Some(index),
+        })
+    });
+    stage.finish(items.len(), results.len());
+
+    if !search.is_empty() {
+        let stage = instrument::Stage::start("sort");
+        sort_results(&mut results, parallel);
+        stage.finish(results.len(), results.len());
+    }
+
+    span.finish(results.len());
+    results
+}
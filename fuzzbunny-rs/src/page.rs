@@ -0,0 +1,121 @@
+//! Pagination of filter results.
+
+use core::cmp::Ordering;
+
+use crate::prelude::*;
+use crate::{
+    collect_indexed_matches_by, fuzzy_score_item, instrument, should_parallelise, FuzzyFilterResult, Range, Target,
+};
+
+/// Options for [`fuzzy_filter_page`], selecting a window of the sorted results.
+///
+/// The default options select every result.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct FilterOptions {
+    /// The most results to return, or [`None`] for every result after the offset.
+    pub limit: Option<usize>,
+    /// The number of best results to skip, e.g. the results of the previous pages.
+    pub offset: usize,
+}
+
+impl FilterOptions {
+    /// Set the most results to return.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Set the number of best results to skip.
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Select the `page`th page (counting from 0) of `page_size` results.
+    pub fn page(page: usize, page_size: usize) -> Self {
+        FilterOptions { limit: Some(page_size), offset: page.saturating_mul(page_size) }
+    }
+}
+
+/// A window of the sorted results of a search, returned by [`fuzzy_filter_page`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterPage<'a> {
+    /// The results in the window, from best to worst.
+    pub results: Vec<FuzzyFilterResult<'a>>,
+    /// The number of targets that matched the search, including those outside the window.
+    pub total: usize,
+}
+
+/// A match whose highlights haven't been computed, as it may be outside the window.
+struct Candidate<'a> {
+    index: usize,
+    item: &'a str,
+    score: u32,
+    ranges: Vec<Range>,
+}
+
+/// The order of [`FuzzyFilterResult`]s from [`fuzzy_filter`](crate::fuzzy_filter), where the
+/// better candidate compares [`Ordering::Less`], falling back to the order of the targets.
+fn compare_candidates(a: &Candidate<'_>, b: &Candidate<'_>) -> Ordering {
+    b.score.cmp(&a.score).then_with(|| a.item.cmp(b.item)).then_with(|| a.index.cmp(&b.index))
+}
+
+/// Search a slice of [`Target`]s and return a window of the sorted results, along with the
+/// total number of matches.
+///
+/// This returns the same results as taking the window from those of
+/// [`fuzzy_filter`](crate::fuzzy_filter), but only the results up to the end of the window are
+/// sorted (after selecting them in linear time), and highlights are only computed for the
+/// results in the window, so serving a page of a large result set doesn't pay for sorting and
+/// highlighting every match.
+///
+/// As with `fuzzy_filter`, the results of an empty search aren't sorted, so the window is taken
+/// from the non-empty targets in order.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{fuzzy_filter_page, precompute_skips_for_items, FilterOptions};
+///
+/// let targets = precompute_skips_for_items(vec!["pineapple", "apple", "banana", "application", "appendix"]);
+///
+/// let page = fuzzy_filter_page(&targets, "app", &FilterOptions::page(1, 2));
+/// let items: Vec<&str> = page.results.iter().map(|res| res.item).collect();
+/// assert_eq!(items, vec!["application", "pineapple"]);
+/// assert_eq!(page.total, 4);
+/// ```
+pub fn fuzzy_filter_page<'a>(items: &[Target<'a>], search: &str, options: &FilterOptions) -> FilterPage<'a> {
+    let search_lower_cased = search.trim().to_lowercase();
+    let parallel = should_parallelise(items, &search_lower_cased);
+    let span = instrument::FilterSpan::enter("fuzzy_filter_page", items.len(), search_lower_cased.len(), parallel);
+
+    let stage = instrument::Stage::start("score");
+    let mut candidates = collect_indexed_matches_by(items, parallel, |index, target| {
+        let string_score = fuzzy_score_item(target, &search_lower_cased)?;
+        Some(Candidate { index, item: target.0, score: string_score.score, ranges: string_score.ranges })
+    });
+    stage.finish(items.len(), candidates.len());
+
+    let total = candidates.len();
+    let start = options.offset.min(total);
+    let end = options.limit.map_or(total, |limit| start.saturating_add(limit).min(total));
+
+    if !search.is_empty() && start < end {
+        let stage = instrument::Stage::start("sort");
+        // Only the candidates up to the end of the window have to be in order
+        if end < total {
+            candidates.select_nth_unstable_by(end, compare_candidates);
+            candidates.truncate(end);
+        }
+        candidates.sort_unstable_by(compare_candidates);
+        stage.finish(total, end);
+    }
+
+    let results: Vec<FuzzyFilterResult<'a>> = candidates
+        .drain(start..end)
+        .map(|candidate| FuzzyFilterResult::matched(candidate.item, candidate.score, candidate.ranges).with_index(candidate.index))
+        .collect();
+
+    span.finish(results.len());
+    FilterPage { results, total }
+}
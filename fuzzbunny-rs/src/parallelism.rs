@@ -0,0 +1,145 @@
+//! Control over how searches are parallelised.
+
+#[cfg(feature = "rayon")]
+use std::sync::Arc;
+
+#[cfg(feature = "rayon")]
+use rayon::{ThreadPool, ThreadPoolBuildError, ThreadPoolBuilder};
+
+use crate::{collect_matches, instrument, is_parallel_workload, sort_results, FuzzyFilterResult, Target};
+
+/// How a search is spread across threads, for [`fuzzy_filter_with_parallelism`].
+///
+/// By default, searches are parallelised on the global rayon thread pool once the estimated
+/// work (from the number of targets, their average length and the search length) is large
+/// enough to outweigh the scheduling overhead, the same as [`fuzzy_filter`](crate::fuzzy_filter).
+/// Applications with their own thread pool can run searches on it instead, so the two pools
+/// don't compete for the same cores, and can set the number of targets below which searches
+/// always run on the calling thread.
+///
+/// Without the `rayon` feature, every search runs on the calling thread, which suits
+/// single-threaded targets such as WASM.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{fuzzy_filter_with_parallelism, precompute_skips_for_items, Parallelism};
+///
+/// let targets = precompute_skips_for_items(vec!["apple", "banana", "application"]);
+///
+/// let parallelism = Parallelism::new().with_min_items(10_000);
+/// let results = fuzzy_filter_with_parallelism(&targets, "app", &parallelism);
+/// assert_eq!(results.len(), 2);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Parallelism {
+    /// The pool to run searches on, or [`None`] for the global pool.
+    #[cfg(feature = "rayon")]
+    pool: Option<Arc<ThreadPool>>,
+    /// The number of targets below which searches are sequential, or [`None`] to estimate
+    /// whether the work is worth parallelising.
+    min_items: Option<usize>,
+}
+
+impl Parallelism {
+    /// Parallelise searches on the global rayon thread pool, when the work is large enough.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Never parallelise searches.
+    pub fn sequential() -> Self {
+        Self::new().with_min_items(usize::MAX)
+    }
+
+    /// Run searches on the given thread pool instead of the global pool.
+    ///
+    /// This is only available with the `rayon` feature.
+    #[cfg(feature = "rayon")]
+    pub fn with_thread_pool(mut self, pool: Arc<ThreadPool>) -> Self {
+        self.pool = Some(pool);
+        self
+    }
+
+    /// Run searches on a new thread pool with `num_threads` threads, or as many threads as
+    /// rayon chooses by default if `num_threads` is 0.
+    ///
+    /// This is only available with the `rayon` feature.
+    ///
+    /// # Returns
+    ///
+    /// An error if the thread pool couldn't be created.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fuzzbunny_rs::Parallelism;
+    ///
+    /// let parallelism = Parallelism::with_num_threads(2).unwrap().with_min_items(10_000);
+    /// ```
+    #[cfg(feature = "rayon")]
+    pub fn with_num_threads(num_threads: usize) -> Result<Self, ThreadPoolBuildError> {
+        let pool = ThreadPoolBuilder::new().num_threads(num_threads).build()?;
+        Ok(Self::new().with_thread_pool(Arc::new(pool)))
+    }
+
+    /// Run searches over fewer than `min_items` targets on the calling thread, and parallelise
+    /// every other search, rather than estimating whether the work is worth parallelising.
+    pub fn with_min_items(mut self, min_items: usize) -> Self {
+        self.min_items = Some(min_items);
+        self
+    }
+
+    /// Whether searching `items` with a search of `search_len` bytes should be parallelised.
+    fn is_parallel(&self, items: &[Target<'_>], search_len: usize) -> bool {
+        match self.min_items {
+            Some(min_items) => cfg!(feature = "rayon") && items.len() >= min_items,
+            None => is_parallel_workload(items.len(), |i| items[i].0.len(), search_len),
+        }
+    }
+
+    /// Run `op` on the thread pool, if one was given.
+    fn install<R: Send>(&self, op: impl FnOnce() -> R + Send) -> R {
+        #[cfg(feature = "rayon")]
+        if let Some(pool) = &self.pool {
+            return pool.install(op)
+        }
+
+        op()
+    }
+}
+
+/// Search a slice of [`Target`]s in the same way as [`fuzzy_filter`](crate::fuzzy_filter),
+/// spreading the work across threads as set by `parallelism`.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{fuzzy_filter, fuzzy_filter_with_parallelism, precompute_skips_for_items, Parallelism};
+///
+/// let targets = precompute_skips_for_items(vec!["apple", "banana", "application"]);
+///
+/// let results = fuzzy_filter_with_parallelism(&targets, "app", &Parallelism::sequential());
+/// assert_eq!(results, fuzzy_filter(&targets, "app"));
+/// ```
+pub fn fuzzy_filter_with_parallelism<'a>(items: &[Target<'a>], search: &str, parallelism: &Parallelism) -> Vec<FuzzyFilterResult<'a>> {
+    let search_lower_cased = search.trim().to_lowercase();
+    let parallel = parallelism.is_parallel(items, search_lower_cased.len());
+    let span = instrument::FilterSpan::enter("fuzzy_filter_with_parallelism", items.len(), search_lower_cased.len(), parallel);
+
+    let results = parallelism.install(|| {
+        let stage = instrument::Stage::start("score");
+        let mut results = collect_matches(items, 0, &search_lower_cased, parallel);
+        stage.finish(items.len(), results.len());
+
+        if !search.is_empty() {
+            let stage = instrument::Stage::start("sort");
+            sort_results(&mut results, parallel);
+            stage.finish(results.len(), results.len());
+        }
+        results
+    });
+
+    span.finish(results.len());
+    results
+}
@@ -0,0 +1,85 @@
+//! Patching of existing filter results after small corpus mutations.
+
+use crate::{collect_matches, sort_results, FuzzyFilterResult, Target};
+
+/// Update a previous [`fuzzy_filter`](crate::fuzzy_filter) result set after a corpus mutation,
+/// without re-scoring the unchanged targets.
+///
+/// `stale` holds the positions in the previous corpus of the items that left it (removed items,
+/// and the previous text of updated items), while `fresh` holds the targets that entered it
+/// (added items, and the new text of updated items), which are appended to the patched corpus
+/// from `first_index`, the number of items left in it. The [`index`](FuzzyFilterResult::index)
+/// of every result is recomputed against the patched corpus, so the results can still be
+/// sorted by [`SortPolicy::OriginalOrder`](crate::SortPolicy::OriginalOrder).
+///
+/// `search` MUST be the same search string that produced `results`, and `results` MUST carry
+/// the positions of their targets, otherwise the patched results will be inconsistent.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{fuzzy_filter, patch_filter_results, precompute_skips_for_items};
+///
+/// let targets = precompute_skips_for_items(vec!["apple", "banana", "application"]);
+/// let mut results = fuzzy_filter(&targets, "app");
+///
+/// // "apple" was renamed to "apple pie", and "appendix" was added
+/// let fresh = precompute_skips_for_items(vec!["apple pie", "appendix"]);
+/// patch_filter_results(&mut results, "app", &[0], &fresh, 2);
+///
+/// let items: Vec<(&str, Option<usize>)> = results.iter().map(|res| (res.item, res.index)).collect();
+/// assert_eq!(items, vec![("appendix", Some(3)), ("apple pie", Some(2)), ("application", Some(1))]);
+/// ```
+pub fn patch_filter_results<'a>(
+    results: &mut Vec<FuzzyFilterResult<'a>>,
+    search: &str,
+    stale: &[usize],
+    fresh: &[Target<'a>],
+    first_index: usize,
+) {
+    if !stale.is_empty() {
+        let mut stale = stale.to_vec();
+        stale.sort_unstable();
+
+        results.retain_mut(|res| {
+            let Some(index) = res.index else { return true };
+            match stale.binary_search(&index) {
+                Ok(_) => false,
+                Err(removed_before) => {
+                    res.index = Some(index - removed_before);
+                    true
+                },
+            }
+        });
+    }
+
+    let search_lower_cased = search.trim().to_lowercase();
+    let mut new_results = collect_matches(fresh, first_index, &search_lower_cased, false);
+    if new_results.is_empty() {
+        return
+    }
+
+    // Unsorted results (from an empty search) are kept in corpus order, so just append
+    if search.is_empty() {
+        results.append(&mut new_results);
+        return
+    }
+
+    sort_results(&mut new_results, false);
+
+    // Merge the two sorted result sets
+    let old_results = std::mem::take(results);
+    results.reserve(old_results.len() + new_results.len());
+    let mut old_iter = old_results.into_iter().peekable();
+    let mut new_iter = new_results.into_iter().peekable();
+
+    loop {
+        let next = match (old_iter.peek(), new_iter.peek()) {
+            (Some(old), Some(new)) => if new > old { new_iter.next() } else { old_iter.next() },
+            (Some(_), None) => old_iter.next(),
+            (None, Some(_)) => new_iter.next(),
+            (None, None) => break,
+        };
+        results.extend(next);
+    }
+}
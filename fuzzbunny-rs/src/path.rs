@@ -0,0 +1,103 @@
+//! Searching of OS strings and paths, which may not be valid UTF-8.
+
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::ffi::OsStr;
+
+use crate::normalize::map_ranges;
+use crate::{
+    collect_matches_by, highlights_from_ranges, instrument, is_parallel_workload, score_prepared, sort_results,
+    Highlights, NormalizationPipeline, Range,
+};
+
+/// An OS string matched by [`fuzzy_filter_os`].
+#[derive(Debug)]
+pub struct OsFilterResult<'a> {
+    /// The original OS string that was matched, e.g. to open the file it names.
+    pub item: &'a OsStr,
+    /// The lossy text of the item that was matched against, where any invalid sequences are
+    /// replaced with `U+FFFD REPLACEMENT CHARACTER`. This borrows the item if it's valid UTF-8.
+    pub text: Cow<'a, str>,
+    /// The score of the match.
+    pub score: u32,
+    /// The matched ranges of `text`.
+    ranges: Vec<Range>,
+}
+
+impl OsFilterResult<'_> {
+    /// The lossy text of the item split into alternating unmatched and matched sections, the
+    /// same as [`FuzzyFilterResult::highlights`](crate::FuzzyFilterResult::highlights).
+    pub fn highlights(&self) -> Highlights<'_> {
+        highlights_from_ranges(&self.text, self.ranges.iter().map(|range| Range(range.0, range.1)).collect())
+    }
+}
+
+impl PartialEq for OsFilterResult<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.score == other.score && self.item == other.item
+    }
+}
+impl Eq for OsFilterResult<'_> {}
+
+impl PartialOrd for OsFilterResult<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+impl Ord for OsFilterResult<'_> {
+    /// The same order as [`FuzzyFilterResult`](crate::FuzzyFilterResult): by score, then the
+    /// smaller text ranks higher, with the original items breaking ties between lossy texts.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score
+            .cmp(&other.score)
+            .then_with(|| other.text.cmp(&self.text))
+            .then_with(|| other.item.cmp(self.item))
+    }
+}
+
+/// Search a slice of OS strings (e.g. [`Path`](std::path::Path)s or
+/// [`OsString`](std::ffi::OsString)s) and return a filtered and sorted vector of
+/// [`OsFilterResult`].
+///
+/// File names aren't guaranteed to be valid UTF-8 (e.g. on Linux), so each item is matched on
+/// a lossy view of its text, but results keep the original item, so file pickers don't have to
+/// drop or mangle such entries. Otherwise, this matches the same as
+/// [`fuzzy_filter`](crate::fuzzy_filter).
+///
+/// # Examples
+///
+/// ```rust
+/// use std::path::Path;
+/// use fuzzbunny_rs::fuzzy_filter_os;
+///
+/// let paths = [Path::new("src/main.rs"), Path::new("src/lib.rs"), Path::new("README.md")];
+///
+/// let results = fuzzy_filter_os(&paths, "lib");
+/// assert_eq!(results.len(), 1);
+/// assert_eq!(results[0].item, Path::new("src/lib.rs"));
+/// assert_eq!(results[0].highlights(), vec!["src/", "lib", ".rs"]);
+/// ```
+pub fn fuzzy_filter_os<'a, S: AsRef<OsStr> + Sync>(items: &'a [S], search: &str) -> Vec<OsFilterResult<'a>> {
+    let search_lower_cased = search.trim().to_lowercase();
+    let parallel = is_parallel_workload(items.len(), |i| items[i].as_ref().len(), search_lower_cased.len());
+    let span = instrument::FilterSpan::enter("fuzzy_filter_os", items.len(), search_lower_cased.len(), parallel);
+
+    let stage = instrument::Stage::start("score");
+    let mut results = collect_matches_by(items, parallel, |item| {
+        let item = item.as_ref();
+        let text = item.to_string_lossy();
+        // Replacement characters aren't ASCII, so the text is prepared with byte offsets
+        let derived = NormalizationPipeline::default().derive(&text, false);
+        let string_score = score_prepared(&derived.lower, Some(&derived.lower), Some(&derived.skips), &search_lower_cased)?;
+        let ranges = map_ranges(string_score.ranges, &derived.offsets);
+        Some(OsFilterResult { item, text, score: string_score.score, ranges })
+    });
+    stage.finish(items.len(), results.len());
+
+    if !search.is_empty() {
+        let stage = instrument::Stage::start("sort");
+        sort_results(&mut results, parallel);
+        stage.finish(results.len(), results.len());
+    }
+
+    span.finish(results.len());
+    results
+}
@@ -0,0 +1,279 @@
+//! Corpora whose items carry an arbitrary, serializable payload.
+
+use std::collections::HashMap;
+#[cfg(any(feature = "json", feature = "msgpack"))]
+use std::io::{self, Read, Write};
+use std::ops::Deref;
+
+use serde::de::{DeserializeOwned, Error as _};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{Corpus, CorpusExport, CorpusMatch, ItemId, UsageBoost};
+
+/// A filter result for an item in a [`PayloadCorpus`], along with its payload.
+#[derive(Debug, PartialEq, Eq)]
+pub struct PayloadMatch<'c, T> {
+    /// The match, including the ID, text, score and highlights of the item.
+    pub matched: CorpusMatch<'c>,
+    /// The payload of the matched item.
+    pub payload: &'c T,
+}
+
+/// A [`Corpus`] where every item carries a user payload, e.g. the record that the item's text
+/// was taken from.
+///
+/// The payload of each match is returned alongside it, and the payloads are serialized along
+/// with the rest of the corpus, so small applications can use the corpus as the single source of
+/// truth for their data. The corpus itself can be read through [`Deref`], while mutations go
+/// through the methods here so every item keeps its payload.
+///
+/// This is only available with the `serde` feature.
+///
+/// # Serialization
+///
+/// A payload corpus serializes as a document with the [`CorpusExport`] of its items and the
+/// payload of each item by ID. In JSON, it looks like:
+///
+/// ```json
+/// {
+///   "corpus": { "format": 1, "next_id": 1, "usage_boost": { ... }, "items": [{ "id": 0, "text": "Dragon" }] },
+///   "payloads": [{ "id": 0, "payload": { "level": 40 } }]
+/// }
+/// ```
+///
+/// Deserialization fails unless every item has exactly one payload.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::PayloadCorpus;
+///
+/// let mut corpus = PayloadCorpus::new();
+/// corpus.insert("Slay the dragon", 40);
+/// corpus.insert("Find the sword", 5);
+///
+/// let results = corpus.search("drag");
+/// assert_eq!(*results[0].payload, 40);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PayloadCorpus<T> {
+    corpus: Corpus,
+    payloads: HashMap<ItemId, T>,
+}
+
+impl<T> Default for PayloadCorpus<T> {
+    fn default() -> Self {
+        PayloadCorpus { corpus: Corpus::new(), payloads: HashMap::new() }
+    }
+}
+
+impl<T> Deref for PayloadCorpus<T> {
+    type Target = Corpus;
+
+    fn deref(&self) -> &Corpus {
+        &self.corpus
+    }
+}
+
+impl<T> PayloadCorpus<T> {
+    /// Create a new, empty corpus.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Insert a new item with a payload. See [`Corpus::insert`].
+    pub fn insert(&mut self, text: impl Into<String>, payload: T) -> ItemId {
+        let id = self.corpus.insert(text);
+        self.payloads.insert(id, payload);
+        id
+    }
+
+    /// Remove an item. See [`Corpus::remove`].
+    ///
+    /// # Returns
+    ///
+    /// The text and payload of the removed item, or [`None`] if there is no item with the
+    /// given ID.
+    pub fn remove(&mut self, id: ItemId) -> Option<(String, T)> {
+        let text = self.corpus.remove(id)?;
+        Some((text, self.payloads.remove(&id)?))
+    }
+
+    /// Replace the text of an item, keeping its payload. See [`Corpus::update`].
+    pub fn update(&mut self, id: ItemId, text: impl Into<String>) -> Option<String> {
+        self.corpus.update(id, text)
+    }
+
+    /// The payload of an item.
+    pub fn payload(&self, id: ItemId) -> Option<&T> {
+        self.payloads.get(&id)
+    }
+
+    /// The payload of an item, to mutate it.
+    pub fn payload_mut(&mut self, id: ItemId) -> Option<&mut T> {
+        self.payloads.get_mut(&id)
+    }
+
+    /// Replace the payload of an item.
+    ///
+    /// # Returns
+    ///
+    /// The previous payload, or [`None`] if there is no item with the given ID.
+    pub fn set_payload(&mut self, id: ItemId, payload: T) -> Option<T> {
+        self.payloads.get_mut(&id).map(|previous| std::mem::replace(previous, payload))
+    }
+
+    /// Tag an item with a category. See [`Corpus::add_tag`].
+    pub fn add_tag(&mut self, id: ItemId, tag: impl Into<String>) -> bool {
+        self.corpus.add_tag(id, tag)
+    }
+
+    /// Remove a tag from an item. See [`Corpus::remove_tag`].
+    pub fn remove_tag(&mut self, id: ItemId, tag: &str) -> bool {
+        self.corpus.remove_tag(id, tag)
+    }
+
+    /// Pin or unpin an item. See [`Corpus::set_pinned`].
+    pub fn set_pinned(&mut self, id: ItemId, pinned: bool) -> bool {
+        self.corpus.set_pinned(id, pinned)
+    }
+
+    /// Record that an item was selected by the user. See [`Corpus::record_selection`].
+    pub fn record_selection(&mut self, id: ItemId) -> bool {
+        self.corpus.record_selection(id)
+    }
+
+    /// Configure how recorded selections boost item scores. See [`Corpus::set_usage_boost`].
+    pub fn set_usage_boost(&mut self, config: UsageBoost) {
+        self.corpus.set_usage_boost(config)
+    }
+
+    /// Limit the memory used by derived search data. See [`Corpus::set_memory_budget`].
+    pub fn set_memory_budget(&mut self, budget: Option<usize>) {
+        self.corpus.set_memory_budget(budget)
+    }
+
+    /// Reclaim the storage left behind by removed items. See [`Corpus::compact`].
+    pub fn compact(&mut self) {
+        self.corpus.compact()
+    }
+
+    /// Search the corpus and return the sorted matches with their payloads. See
+    /// [`Corpus::search`].
+    pub fn search(&self, search: &str) -> Vec<PayloadMatch<'_, T>> {
+        self.with_payloads(self.corpus.search(search))
+    }
+
+    /// Search only the items with at least one of the given tags. See [`Corpus::search_tagged`].
+    pub fn search_tagged(&self, search: &str, tags: &[&str]) -> Vec<PayloadMatch<'_, T>> {
+        self.with_payloads(self.corpus.search_tagged(search, tags))
+    }
+
+    /// Unwrap the corpus and the payloads of its items.
+    pub fn into_parts(self) -> (Corpus, HashMap<ItemId, T>) {
+        (self.corpus, self.payloads)
+    }
+
+    fn with_payloads<'c>(&'c self, results: Vec<CorpusMatch<'c>>) -> Vec<PayloadMatch<'c, T>> {
+        results
+            .into_iter()
+            .map(|matched| PayloadMatch { payload: &self.payloads[&matched.id], matched })
+            .collect()
+    }
+}
+
+impl<T: Serialize + DeserializeOwned> PayloadCorpus<T> {
+    /// Write the corpus and its payloads as JSON.
+    ///
+    /// This is only available with the `json` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if writing or serializing a payload fails.
+    #[cfg(feature = "json")]
+    pub fn to_json(&self, writer: impl Write) -> io::Result<()> {
+        serde_json::to_writer(writer, self).map_err(io::Error::from)
+    }
+
+    /// Read a corpus and its payloads from JSON.
+    ///
+    /// This is only available with the `json` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if reading fails, or the JSON isn't a valid payload corpus.
+    #[cfg(feature = "json")]
+    pub fn from_json(reader: impl Read) -> io::Result<Self> {
+        serde_json::from_reader(reader).map_err(io::Error::from)
+    }
+
+    /// Write the corpus and its payloads as MessagePack.
+    ///
+    /// This is only available with the `msgpack` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if writing or serializing a payload fails.
+    #[cfg(feature = "msgpack")]
+    pub fn to_msgpack(&self, mut writer: impl Write) -> io::Result<()> {
+        rmp_serde::encode::write_named(&mut writer, self).map_err(io::Error::other)
+    }
+
+    /// Read a corpus and its payloads from MessagePack.
+    ///
+    /// This is only available with the `msgpack` feature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if reading fails, or the data isn't a valid payload corpus.
+    #[cfg(feature = "msgpack")]
+    pub fn from_msgpack(reader: impl Read) -> io::Result<Self> {
+        rmp_serde::from_read(reader).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+}
+
+/// The payload of a single item, in the serialized form of a [`PayloadCorpus`].
+#[derive(Serialize, Deserialize)]
+struct PayloadEntry<T> {
+    id: u64,
+    payload: T,
+}
+
+/// The serialized form of a [`PayloadCorpus`].
+#[derive(Serialize, Deserialize)]
+struct PayloadDocument<T> {
+    corpus: CorpusExport,
+    payloads: Vec<PayloadEntry<T>>,
+}
+
+impl<T: Serialize> Serialize for PayloadCorpus<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let corpus = self.corpus.export();
+        let payloads = corpus
+            .items
+            .iter()
+            .map(|item| PayloadEntry { id: item.id, payload: &self.payloads[&ItemId(item.id)] })
+            .collect();
+        PayloadDocument { corpus, payloads }.serialize(serializer)
+    }
+}
+
+impl<'de, T: DeserializeOwned> Deserialize<'de> for PayloadCorpus<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let document = PayloadDocument::<T>::deserialize(deserializer)?;
+        let corpus = Corpus::import(document.corpus).map_err(D::Error::custom)?;
+
+        let mut payloads = HashMap::with_capacity(document.payloads.len());
+        for entry in document.payloads {
+            let id = ItemId(entry.id);
+            if !corpus.contains(id) || payloads.insert(id, entry.payload).is_some() {
+                return Err(D::Error::custom(format!("unexpected payload for item ID {}", entry.id)))
+            }
+        }
+        if payloads.len() != corpus.len() {
+            return Err(D::Error::custom("missing item payloads"))
+        }
+
+        Ok(PayloadCorpus { corpus, payloads })
+    }
+}
@@ -0,0 +1,514 @@
+//! Composable chains of text transforms, applied before matching.
+
+use core::fmt;
+use core::ops::Range as ByteRange;
+use alloc::sync::Arc;
+
+use crate::normalize::map_ranges;
+use crate::prelude::*;
+use crate::{get_target_skips, numeric, Normalization, Range};
+
+/// A character of normalized text, with where it came from in the original text.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Piece {
+    pub(crate) char: char,
+    /// The byte offset in the original text of the character this came from.
+    pub(crate) offset: usize,
+    /// Whether this starts a word, for the skip indices.
+    pub(crate) starts_word: bool,
+}
+
+/// A closure appending the replacement for a character to a string.
+type MapFn = dyn Fn(char, &mut String) + Send + Sync;
+
+/// A single transform of a [`NormalizationPipeline`].
+#[derive(Clone)]
+pub(crate) enum Step {
+    Lowercase,
+    Profile(Normalization),
+    Decompose,
+    CaseFold,
+    FoldDiacritics,
+    Numeric,
+    Strip(Arc<dyn Fn(char) -> bool + Send + Sync>),
+    Map(Arc<MapFn>),
+}
+
+impl Step {
+    /// Apply this step to every character of a text, in place.
+    pub(crate) fn apply(&self, pieces: &mut Vec<Piece>) {
+        let mut out = String::new();
+        let mut map_each = |pieces: &mut Vec<Piece>, map: &dyn Fn(char, &mut String)| {
+            let mut mapped = Vec::with_capacity(pieces.len());
+            // A removed character that started a word passes that on to the next character
+            let mut starts_word = false;
+            for piece in pieces.drain(..) {
+                out.clear();
+                map(piece.char, &mut out);
+                starts_word |= piece.starts_word;
+                for char in out.chars() {
+                    mapped.push(Piece { char, offset: piece.offset, starts_word });
+                    starts_word = false;
+                }
+            }
+            *pieces = mapped;
+        };
+
+        match self {
+            Step::Lowercase => map_each(pieces, &|char, out| out.extend(char.to_lowercase())),
+            Step::Profile(normalization) => map_each(pieces, &|char, out| normalization.push_char(char, out)),
+            Step::Decompose => map_each(pieces, &decompose),
+            Step::CaseFold => map_each(pieces, &case_fold),
+            Step::FoldDiacritics => map_each(pieces, &|char, out| match fold_diacritic(char) {
+                Some(folded) => out.push_str(folded),
+                None => out.push(char),
+            }),
+            Step::Numeric => numeric::canonicalize_pieces(pieces),
+            Step::Strip(strip) => map_each(pieces, &|char, out| {
+                if !strip(char) {
+                    out.push(char)
+                }
+            }),
+            Step::Map(map) => map_each(pieces, &|char, out| map(char, out)),
+        }
+    }
+}
+
+impl PartialEq for Step {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Step::Lowercase, Step::Lowercase)
+            | (Step::Decompose, Step::Decompose)
+            | (Step::CaseFold, Step::CaseFold)
+            | (Step::FoldDiacritics, Step::FoldDiacritics)
+            | (Step::Numeric, Step::Numeric) => true,
+            (Step::Profile(a), Step::Profile(b)) => a == b,
+            // Closures are only equal to themselves
+            (Step::Strip(a), Step::Strip(b)) => Arc::ptr_eq(a, b),
+            (Step::Map(a), Step::Map(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Step {}
+
+impl fmt::Debug for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Step::Lowercase => f.write_str("Lowercase"),
+            Step::Profile(normalization) => f.debug_tuple("Profile").field(normalization).finish(),
+            Step::Decompose => f.write_str("Decompose"),
+            Step::CaseFold => f.write_str("CaseFold"),
+            Step::FoldDiacritics => f.write_str("FoldDiacritics"),
+            Step::Numeric => f.write_str("Numeric"),
+            Step::Strip(_) => f.write_str("Strip(..)"),
+            Step::Map(_) => f.write_str("Map(..)"),
+        }
+    }
+}
+
+/// The base letters of the accented Latin letters, with the ligatures spelled out.
+fn fold_diacritic(char: char) -> Option<&'static str> {
+    let folded = match char {
+        'À'..='Å' | 'Ā' | 'Ă' | 'Ą' => "A",
+        'à'..='å' | 'ā' | 'ă' | 'ą' => "a",
+        'Æ' => "AE",
+        'æ' => "ae",
+        'Ç' | 'Ć' | 'Ĉ' | 'Ċ' | 'Č' => "C",
+        'ç' | 'ć' | 'ĉ' | 'ċ' | 'č' => "c",
+        'Ð' | 'Ď' | 'Đ' => "D",
+        'ð' | 'ď' | 'đ' => "d",
+        'È'..='Ë' | 'Ē' | 'Ĕ' | 'Ė' | 'Ę' | 'Ě' => "E",
+        'è'..='ë' | 'ē' | 'ĕ' | 'ė' | 'ę' | 'ě' => "e",
+        'Ĝ' | 'Ğ' | 'Ġ' | 'Ģ' => "G",
+        'ĝ' | 'ğ' | 'ġ' | 'ģ' => "g",
+        'Ĥ' | 'Ħ' => "H",
+        'ĥ' | 'ħ' => "h",
+        'Ì'..='Ï' | 'Ĩ' | 'Ī' | 'Ĭ' | 'Į' | 'İ' => "I",
+        'ì'..='ï' | 'ĩ' | 'ī' | 'ĭ' | 'į' | 'ı' => "i",
+        'Ĳ' => "IJ",
+        'ĳ' => "ij",
+        'Ĵ' => "J",
+        'ĵ' => "j",
+        'Ķ' => "K",
+        'ķ' | 'ĸ' => "k",
+        'Ĺ' | 'Ļ' | 'Ľ' | 'Ŀ' | 'Ł' => "L",
+        'ĺ' | 'ļ' | 'ľ' | 'ŀ' | 'ł' => "l",
+        'Ñ' | 'Ń' | 'Ņ' | 'Ň' | 'Ŋ' => "N",
+        'ñ' | 'ń' | 'ņ' | 'ň' | 'ŉ' | 'ŋ' => "n",
+        'Ò'..='Ö' | 'Ø' | 'Ō' | 'Ŏ' | 'Ő' => "O",
+        'ò'..='ö' | 'ø' | 'ō' | 'ŏ' | 'ő' => "o",
+        'Œ' => "OE",
+        'œ' => "oe",
+        'Ŕ' | 'Ŗ' | 'Ř' => "R",
+        'ŕ' | 'ŗ' | 'ř' => "r",
+        'Ś' | 'Ŝ' | 'Ş' | 'Š' => "S",
+        'ś' | 'ŝ' | 'ş' | 'š' | 'ſ' => "s",
+        'ß' => "ss",
+        'Ţ' | 'Ť' | 'Ŧ' => "T",
+        'ţ' | 'ť' | 'ŧ' => "t",
+        'Ù'..='Ü' | 'Ũ' | 'Ū' | 'Ŭ' | 'Ů' | 'Ű' | 'Ų' => "U",
+        'ù'..='ü' | 'ũ' | 'ū' | 'ŭ' | 'ů' | 'ű' | 'ų' => "u",
+        'Ŵ' => "W",
+        'ŵ' => "w",
+        'Ý' | 'Ŷ' | 'Ÿ' => "Y",
+        'ý' | 'ÿ' | 'ŷ' => "y",
+        'Ź' | 'Ż' | 'Ž' => "Z",
+        'ź' | 'ż' | 'ž' => "z",
+        // Combining diacritical marks, left over from decomposed text
+        '\u{300}'..='\u{36f}' => "",
+        _ => return None,
+    };
+    Some(folded)
+}
+
+/// The combining mark of a precomposed Latin-1 or Latin Extended-A letter, whose base letter
+/// is given by [`fold_diacritic`].
+fn combining_mark(char: char) -> Option<char> {
+    let mark = match char {
+        'À' | 'à' | 'È' | 'è' | 'Ì' | 'ì' | 'Ò' | 'ò' | 'Ù' | 'ù' => '\u{300}',
+        'Á' | 'á' | 'É' | 'é' | 'Í' | 'í' | 'Ó' | 'ó' | 'Ú' | 'ú' | 'Ý' | 'ý' | 'Ć' | 'ć' | 'Ĺ' | 'ĺ' | 'Ń' | 'ń'
+        | 'Ŕ' | 'ŕ' | 'Ś' | 'ś' | 'Ź' | 'ź' => '\u{301}',
+        'Â' | 'â' | 'Ê' | 'ê' | 'Î' | 'î' | 'Ô' | 'ô' | 'Û' | 'û' | 'Ĉ' | 'ĉ' | 'Ĝ' | 'ĝ' | 'Ĥ' | 'ĥ' | 'Ĵ' | 'ĵ'
+        | 'Ŝ' | 'ŝ' | 'Ŵ' | 'ŵ' | 'Ŷ' | 'ŷ' => '\u{302}',
+        'Ã' | 'ã' | 'Ñ' | 'ñ' | 'Õ' | 'õ' | 'Ĩ' | 'ĩ' | 'Ũ' | 'ũ' => '\u{303}',
+        'Ā' | 'ā' | 'Ē' | 'ē' | 'Ī' | 'ī' | 'Ō' | 'ō' | 'Ū' | 'ū' => '\u{304}',
+        'Ă' | 'ă' | 'Ĕ' | 'ĕ' | 'Ğ' | 'ğ' | 'Ĭ' | 'ĭ' | 'Ŏ' | 'ŏ' | 'Ŭ' | 'ŭ' => '\u{306}',
+        'Ċ' | 'ċ' | 'Ė' | 'ė' | 'Ġ' | 'ġ' | 'İ' | 'Ż' | 'ż' => '\u{307}',
+        'Ä' | 'ä' | 'Ë' | 'ë' | 'Ï' | 'ï' | 'Ö' | 'ö' | 'Ü' | 'ü' | 'ÿ' | 'Ÿ' => '\u{308}',
+        'Å' | 'å' | 'Ů' | 'ů' => '\u{30a}',
+        'Ő' | 'ő' | 'Ű' | 'ű' => '\u{30b}',
+        'Č' | 'č' | 'Ď' | 'ď' | 'Ě' | 'ě' | 'Ľ' | 'ľ' | 'Ň' | 'ň' | 'Ř' | 'ř' | 'Š' | 'š' | 'Ť' | 'ť' | 'Ž' | 'ž' => '\u{30c}',
+        'Ç' | 'ç' | 'Ģ' | 'ģ' | 'Ķ' | 'ķ' | 'Ļ' | 'ļ' | 'Ņ' | 'ņ' | 'Ŗ' | 'ŗ' | 'Ş' | 'ş' | 'Ţ' | 'ţ' => '\u{327}',
+        'Ą' | 'ą' | 'Ę' | 'ę' | 'Į' | 'į' | 'Ų' | 'ų' => '\u{328}',
+        _ => return None,
+    };
+    Some(mark)
+}
+
+/// Append the compatibility decomposition of a character to `out`, as in Unicode NFKD.
+///
+/// This covers the precomposed Latin-1 and Latin Extended-A letters, which decompose to their
+/// base letter and a combining mark, along with the compatibility characters most often found
+/// in text: full-width ASCII, Latin ligatures, super- and subscript digits, circled numbers,
+/// Roman numerals, vulgar fractions and special spaces. Other characters are left unchanged.
+fn decompose(char: char, out: &mut String) {
+    if let Some(mark) = combining_mark(char) {
+        out.push_str(fold_diacritic(char).unwrap_or_default());
+        out.push(mark);
+        return
+    }
+
+    let code = char as u32;
+    let decomposed = match char {
+        // Full-width forms of the printable ASCII characters
+        '\u{ff01}'..='\u{ff5e}' => return out.extend(char::from_u32(code - 0xfee0)),
+        '\u{a0}' | '\u{2000}'..='\u{200a}' | '\u{202f}' | '\u{205f}' | '\u{3000}' => " ",
+        'Ĳ' => "IJ",
+        'ĳ' => "ij",
+        'Ŀ' => "L·",
+        'ŀ' => "l·",
+        'ŉ' => "ʼn",
+        'ﬀ' => "ff",
+        'ﬁ' => "fi",
+        'ﬂ' => "fl",
+        'ﬃ' => "ffi",
+        'ﬄ' => "ffl",
+        'ﬅ' | 'ﬆ' => "st",
+        '⁰' | '₀' => "0",
+        '¹' | '₁' => "1",
+        '²' | '₂' => "2",
+        '³' | '₃' => "3",
+        '⁴'..='⁹' => return out.extend(char::from_u32(code - 0x2074 + '4' as u32)),
+        '₄'..='₉' => return out.extend(char::from_u32(code - 0x2084 + '4' as u32)),
+        '①'..='⑨' => return out.extend(char::from_u32(code - 0x2460 + '1' as u32)),
+        '⑩'..='⑳' => return out.push_str(&(code - 0x2460 + 1).to_string()),
+        'Ⅰ'..='Ⅻ' => ["I", "II", "III", "IV", "V", "VI", "VII", "VIII", "IX", "X", "XI", "XII"][(code - 0x2160) as usize],
+        'ⅰ'..='ⅻ' => ["i", "ii", "iii", "iv", "v", "vi", "vii", "viii", "ix", "x", "xi", "xii"][(code - 0x2170) as usize],
+        '¼' => "1⁄4",
+        '½' => "1⁄2",
+        '¾' => "3⁄4",
+        '…' => "...",
+        '™' => "TM",
+        _ => return out.push(char),
+    };
+    out.push_str(decomposed);
+}
+
+/// Append the full case folding of a character to `out`.
+///
+/// This is the same as lower casing, other than the letters whose case folding differs from
+/// their lower case form, such as "ß" which folds to "ss" and the final sigma "ς" which folds
+/// to "σ".
+fn case_fold(char: char, out: &mut String) {
+    match char {
+        'ß' | 'ẞ' => out.push_str("ss"),
+        'ς' => out.push('σ'),
+        'ſ' => out.push('s'),
+        'ŉ' => out.push_str("ʼn"),
+        'ﬅ' | 'ﬆ' => out.push_str("st"),
+        _ => out.extend(char.to_lowercase()),
+    }
+}
+
+/// A composable chain of text transforms, applied identically to targets and searches before
+/// they're matched.
+///
+/// Each transform maps every character to any number of characters (including none), and the
+/// pipeline keeps track of where each character came from, so highlights always reference the
+/// original text. Word boundaries (see [`get_target_skips`]) are taken from the original text,
+/// so camelCase words still start where they did before lower casing.
+///
+/// The default pipeline only lower cases, which matches the same as
+/// [`fuzzy_filter`](crate::fuzzy_filter). A pipeline is used for matching by setting
+/// [`MatchOptions::pipeline`](crate::MatchOptions::pipeline). Searches are lower cased before
+/// the pipeline is applied, so pipelines should generally lower case targets as well.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::NormalizationPipeline;
+///
+/// let pipeline = NormalizationPipeline::new()
+///     .lowercase()
+///     .fold_diacritics()
+///     .strip(|char| char == '\'');
+///
+/// assert_eq!(pipeline.normalize("Crème Brûlée"), "creme brulee");
+/// assert_eq!(pipeline.normalize("Rock 'n' Roll"), "rock n roll");
+///
+/// // Ranges in the normalized text map back to the original text
+/// let normalized = pipeline.normalize_with_offsets("Ærøskøbing");
+/// assert_eq!(normalized.text(), "aeroskobing");
+/// assert_eq!(normalized.original_range(0..1), 0..2);
+/// assert_eq!(normalized.original_range(3..7), 3..9);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NormalizationPipeline {
+    steps: Vec<Step>,
+}
+
+impl Default for NormalizationPipeline {
+    fn default() -> Self {
+        NormalizationPipeline::new().lowercase()
+    }
+}
+
+impl NormalizationPipeline {
+    /// Create a pipeline with no transforms, which leaves text unchanged.
+    pub fn new() -> Self {
+        NormalizationPipeline { steps: vec![] }
+    }
+
+    /// Create a pipeline that matches regardless of case, accents and compatibility forms, so
+    /// "cafe" matches "Café", "strasse" matches "Straße" and "office" matches "Oﬃce".
+    ///
+    /// This decomposes, case folds and then folds diacritics. See
+    /// [`NormalizationPipeline::decompose`], [`NormalizationPipeline::case_fold`] and
+    /// [`NormalizationPipeline::fold_diacritics`].
+    pub fn folded() -> Self {
+        NormalizationPipeline::new().decompose().case_fold().fold_diacritics()
+    }
+
+    /// Add a transform to the end of the pipeline.
+    fn then(mut self, step: Step) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    /// Lower case the text, the same as [`str::to_lowercase`].
+    pub fn lowercase(self) -> Self {
+        self.then(Step::Lowercase)
+    }
+
+    /// Normalize the text with a language profile. See [`Normalization`].
+    pub fn profile(self, normalization: Normalization) -> Self {
+        self.then(Step::Profile(normalization))
+    }
+
+    /// Decompose characters the same as Unicode compatibility decomposition (NFKD), so
+    /// precomposed letters (e.g. "é") match their decomposed forms (e.g. "e\u{301}"), and
+    /// compatibility characters match their plain forms (e.g. "ﬁ" matches "fi", and "Ｒｕｓｔ"
+    /// matches "Rust").
+    ///
+    /// Only the precomposed Latin-1 and Latin Extended-A letters and the most common
+    /// compatibility characters (full-width ASCII, ligatures, super- and subscript digits,
+    /// circled numbers, Roman numerals, fractions and special spaces) are decomposed.
+    pub fn decompose(self) -> Self {
+        self.then(Step::Decompose)
+    }
+
+    /// Case fold the text, which is the same as lower casing other than for the few letters
+    /// whose case folding differs, such as "ß" which folds to "ss" and "ς" which folds to "σ".
+    ///
+    /// Language-specific rules such as Turkish dotted and dotless i are handled by profiles
+    /// instead. See [`NormalizationPipeline::profile`].
+    pub fn case_fold(self) -> Self {
+        self.then(Step::CaseFold)
+    }
+
+    /// Fold accented Latin letters to their base letters (e.g. "é" to "e"), spell out ligatures
+    /// (e.g. "æ" to "ae"), and remove combining diacritical marks, so searches match regardless
+    /// of accents.
+    pub fn fold_diacritics(self) -> Self {
+        self.then(Step::FoldDiacritics)
+    }
+
+    /// Put numbers in a canonical form, so numbers with the same value match each other. See
+    /// [`MatchOptions::numeric_equivalence`](crate::MatchOptions::numeric_equivalence).
+    pub fn numeric(self) -> Self {
+        self.then(Step::Numeric)
+    }
+
+    /// Remove every character for which `strip` returns true.
+    pub fn strip(self, strip: impl Fn(char) -> bool + Send + Sync + 'static) -> Self {
+        self.then(Step::Strip(Arc::new(strip)))
+    }
+
+    /// Transform every character with a closure, which appends the replacement for a character
+    /// (of any length, including none) to a string.
+    ///
+    /// Pipelines holding closures are only equal if they hold the same closures, such as clones
+    /// of the same pipeline.
+    pub fn map(self, map: impl Fn(char, &mut String) + Send + Sync + 'static) -> Self {
+        self.then(Step::Map(Arc::new(map)))
+    }
+
+    /// Apply the pipeline, and any extra steps, to a text.
+    fn pieces<'s>(&'s self, text: &str, extra: impl IntoIterator<Item = &'s Step>) -> Vec<Piece> {
+        let mut pieces = Vec::with_capacity(text.len());
+        let mut was_alpha_num = false;
+        let mut was_upper_case = false;
+
+        // The same word boundaries as `get_target_skips`
+        for (offset, char) in text.char_indices() {
+            let is_alpha_num = char.is_alphanumeric();
+            let is_upper_case = char.is_uppercase();
+            let starts_word =
+                (is_alpha_num && !was_alpha_num) || (is_upper_case && !was_upper_case) || char.is_ascii_punctuation();
+            was_alpha_num = is_alpha_num;
+            was_upper_case = is_upper_case;
+            pieces.push(Piece { char, offset, starts_word });
+        }
+
+        for step in self.steps.iter().chain(extra) {
+            step.apply(&mut pieces);
+        }
+        pieces
+    }
+
+    /// Normalize a text with the pipeline.
+    pub fn normalize(&self, text: &str) -> String {
+        self.pieces(text, None).into_iter().map(|piece| piece.char).collect()
+    }
+
+    /// Normalize a text with the pipeline, keeping a map of offsets back to the original text.
+    pub fn normalize_with_offsets(&self, text: &str) -> NormalizedText {
+        let derived = self.derive(text, false);
+        NormalizedText { text: derived.lower, offsets: derived.offsets }
+    }
+
+    /// Compute the derived search data of a text with the pipeline, followed by the numeric
+    /// step if `numeric` is set.
+    ///
+    /// As for [`Normalization::derive`], the skip indices are byte offsets into the normalized
+    /// text, and the offsets are empty if every byte lines up with the original text.
+    pub(crate) fn derive(&self, text: &str, numeric: bool) -> Derived {
+        let pieces = self.pieces(text, numeric.then_some(&Step::Numeric));
+        let mut lower = String::with_capacity(pieces.len());
+        let mut skips = vec![];
+        let mut offsets = Vec::with_capacity(pieces.len() + 1);
+
+        for piece in pieces {
+            if piece.starts_word {
+                skips.push(lower.len());
+            }
+            let start = lower.len();
+            lower.push(piece.char);
+            offsets.resize(offsets.len() + lower.len() - start, piece.offset);
+        }
+        skips.push(lower.len());
+        offsets.push(text.len());
+
+        // Every byte is where it was, so no offsets are needed
+        if lower.len() == text.len() && offsets.iter().enumerate().all(|(i, &offset)| offset == i) {
+            offsets = vec![];
+        }
+        Derived { lower, skips, offsets }
+    }
+}
+
+/// A text normalized with a [`NormalizationPipeline`], along with where each part of it came
+/// from in the original text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NormalizedText {
+    text: String,
+    /// The offsets back to the original text, as in [`Derived`].
+    offsets: Vec<usize>,
+}
+
+impl NormalizedText {
+    /// The normalized text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Map a byte range of the normalized text back to the original text.
+    ///
+    /// A range covering part of a character that was expanded by normalization (e.g. the "a" of
+    /// "ae" from "æ") is widened to cover the whole original character.
+    /// Any characters removed by normalization directly after the range are also covered.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds of the normalized text.
+    pub fn original_range(&self, range: ByteRange<usize>) -> ByteRange<usize> {
+        assert!(range.start <= range.end && range.end <= self.text.len(), "range out of bounds");
+        if range.is_empty() {
+            let start = if self.offsets.is_empty() { range.start } else { self.offsets[range.start] };
+            return start..start
+        }
+        match map_ranges(vec![Range(range.start, range.end - range.start)], &self.offsets)[..] {
+            [Range(start, len)] => start..start + len,
+            _ => unreachable!("a single range maps to a single range"),
+        }
+    }
+}
+
+/// Search data derived from the text of an item, that can be recomputed at any time.
+#[derive(Debug, Clone)]
+pub(crate) struct Derived {
+    /// The lower case form of the text.
+    pub(crate) lower: String,
+    /// The skip indices of the text. See [`get_target_skips`].
+    pub(crate) skips: Vec<usize>,
+    /// The byte offset in the original text of each byte of `lower`, followed by the length of
+    /// the text, or empty if they're the same. See
+    /// [`Normalization::derive`](crate::Normalization::derive).
+    pub(crate) offsets: Vec<usize>,
+}
+
+impl Derived {
+    /// Compute the derived data for a string.
+    ///
+    /// Lower casing can change the length of non-ASCII characters, in which case the skips are
+    /// byte offsets into the lower case form and offsets back to the text are included.
+    pub(crate) fn new(text: &str) -> Self {
+        let lower = text.to_lowercase();
+        if !is_lower_case_aligned(text, &lower) {
+            return NormalizationPipeline::default().derive(text, false)
+        }
+        Derived { lower, skips: get_target_skips(text), offsets: vec![] }
+    }
+}
+
+/// Whether every character of a text has the same length as its lower case form, so byte
+/// offsets into one line up with the other.
+pub(crate) fn is_lower_case_aligned(text: &str, lower: &str) -> bool {
+    text.is_ascii()
+        || (text.len() == lower.len()
+            && text.chars().all(|char| {
+                char.is_ascii() || char.to_lowercase().map(char::len_utf8).sum::<usize>() == char.len_utf8()
+            }))
+}
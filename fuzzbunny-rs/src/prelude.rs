@@ -0,0 +1,6 @@
+//! The `alloc` types that the `std` prelude would otherwise provide, for `no_std` builds.
+
+pub(crate) use alloc::format;
+pub(crate) use alloc::string::{String, ToString};
+pub(crate) use alloc::vec;
+pub(crate) use alloc::vec::Vec;
@@ -0,0 +1,169 @@
+//! Targets with their lower case forms cached alongside their skip indices.
+
+use crate::pipeline::Derived;
+use crate::normalize::map_ranges;
+use crate::prelude::*;
+use crate::{
+    collect_matches_by, instrument, is_parallel_workload, map_items, score_prepared, sort_results, FuzzyFilterResult,
+    NormalizationPipeline, StringScore, PARALLEL_PRECOMPUTE_THRESHOLD,
+};
+
+/// A target string with its lower case form and skip indices precomputed, so neither has to
+/// be computed again for every search.
+///
+/// Matching a [`Target`](crate::Target) lower cases it on every search, even when its skip
+/// indices are precomputed. For large lists searched repeatedly (e.g. on every keystroke),
+/// preparing the targets once with [`prepare_targets`] avoids that allocation per item, at the
+/// cost of keeping a lower case copy of each item in memory.
+#[derive(Debug, Clone)]
+pub struct PreparedTarget<'a> {
+    text: &'a str,
+    /// The lower case form and skip indices of `text`.
+    derived: Derived,
+}
+
+impl<'a> PreparedTarget<'a> {
+    /// Prepare a single target.
+    pub fn new(text: &'a str) -> Self {
+        PreparedTarget { text, derived: Derived::new(text) }
+    }
+
+    /// Prepare a single target, normalizing it with a pipeline rather than only lower casing it.
+    ///
+    /// The target only matches as intended when searched with the same pipeline. See
+    /// [`fuzzy_filter_prepared_normalized`].
+    pub fn normalized(text: &'a str, pipeline: &NormalizationPipeline) -> Self {
+        PreparedTarget { text, derived: pipeline.derive(text, false) }
+    }
+
+    /// The original text of the target.
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// The lower case (or normalized) form of the target that is matched against.
+    pub fn lower(&self) -> &str {
+        &self.derived.lower
+    }
+
+    /// Compute the score and highlight ranges of this target for a search, the same as
+    /// [`fuzzy_score_item`](crate::fuzzy_score_item). The ranges are byte offsets into the
+    /// original text.
+    ///
+    /// Note that `search` string MUST be lower case.
+    pub fn score(&self, search: &str) -> Option<StringScore> {
+        let derived = &self.derived;
+        let string_score = score_prepared(self.text, Some(&derived.lower), Some(&derived.skips), search)?;
+        Some(StringScore { score: string_score.score, ranges: map_ranges(string_score.ranges, &derived.offsets) })
+    }
+}
+
+/// Prepare a set of plain strings as [`PreparedTarget`]s, computing the lower case form and
+/// skip indices of each once.
+///
+/// For large sets of items, the targets are prepared in parallel if the `rayon` feature is
+/// enabled.
+///
+/// # Returns
+///
+/// The prepared targets, in the same order as `items`, ready to be passed to
+/// [`fuzzy_filter_prepared`].
+pub fn prepare_targets<'a>(items: impl IntoIterator<Item = &'a str>) -> Vec<PreparedTarget<'a>> {
+    let items: Vec<&'a str> = items.into_iter().collect();
+    let parallel = items.len() >= PARALLEL_PRECOMPUTE_THRESHOLD;
+
+    map_items(&items, parallel, |&text| PreparedTarget::new(text))
+}
+
+/// Prepare a set of plain strings as [`PreparedTarget`]s, normalizing each once with a
+/// pipeline, e.g. [`NormalizationPipeline::folded`] to match regardless of accents.
+///
+/// The targets must be searched with [`fuzzy_filter_prepared_normalized`] and the same
+/// pipeline, so searches are normalized the same way.
+pub fn prepare_targets_normalized<'a>(
+    items: impl IntoIterator<Item = &'a str>,
+    pipeline: &NormalizationPipeline,
+) -> Vec<PreparedTarget<'a>> {
+    let items: Vec<&'a str> = items.into_iter().collect();
+    let parallel = items.len() >= PARALLEL_PRECOMPUTE_THRESHOLD;
+
+    map_items(&items, parallel, |&text| PreparedTarget::normalized(text, pipeline))
+}
+
+/// Search a slice of [`PreparedTarget`]s and return a filtered and sorted vector of
+/// [`FuzzyFilterResult`].
+///
+/// This returns the same results as [`fuzzy_filter`](crate::fuzzy_filter), without lower
+/// casing every target for every search.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{fuzzy_filter_prepared, prepare_targets};
+///
+/// let targets = prepare_targets(vec!["Claire Bennet", "Elle Bishop"]);
+///
+/// let results = fuzzy_filter_prepared(&targets, "EB");
+/// assert_eq!(results[0].item, "Elle Bishop");
+/// assert_eq!(results[0].highlights, Some(vec!["", "E", "lle ", "B", "ishop"]));
+/// ```
+pub fn fuzzy_filter_prepared<'a>(items: &[PreparedTarget<'a>], search: &str) -> Vec<FuzzyFilterResult<'a>> {
+    filter_prepared("fuzzy_filter_prepared", items, search, search.trim().to_lowercase())
+}
+
+/// Search a slice of [`PreparedTarget`]s that were normalized with a pipeline, and return a
+/// filtered and sorted vector of [`FuzzyFilterResult`].
+///
+/// The search is lower cased and normalized with the same pipeline as the targets (see
+/// [`prepare_targets_normalized`]), so it matches regardless of whatever the pipeline folds
+/// away. Highlights reference the original text of each target.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{fuzzy_filter_prepared_normalized, prepare_targets_normalized, NormalizationPipeline};
+///
+/// let pipeline = NormalizationPipeline::folded();
+/// let targets = prepare_targets_normalized(vec!["Café Müller", "Straße", "Oﬃce"], &pipeline);
+///
+/// let results = fuzzy_filter_prepared_normalized(&targets, "cafe mu", &pipeline);
+/// assert_eq!(results[0].highlights, Some(vec!["", "Café Mü", "ller"]));
+/// assert_eq!(fuzzy_filter_prepared_normalized(&targets, "STRASSE", &pipeline)[0].item, "Straße");
+/// assert_eq!(fuzzy_filter_prepared_normalized(&targets, "office", &pipeline)[0].item, "Oﬃce");
+/// ```
+pub fn fuzzy_filter_prepared_normalized<'a>(
+    items: &[PreparedTarget<'a>],
+    search: &str,
+    pipeline: &NormalizationPipeline,
+) -> Vec<FuzzyFilterResult<'a>> {
+    let search_normalized = pipeline.normalize(&search.trim().to_lowercase());
+    filter_prepared("fuzzy_filter_prepared_normalized", items, search, search_normalized)
+}
+
+/// Score every prepared target against a search, which has already been lower cased and
+/// normalized as `search_normalized`.
+fn filter_prepared<'a>(
+    name: &'static str,
+    items: &[PreparedTarget<'a>],
+    search: &str,
+    search_normalized: String,
+) -> Vec<FuzzyFilterResult<'a>> {
+    let parallel = is_parallel_workload(items.len(), |i| items[i].text.len(), search_normalized.len());
+    let span = instrument::FilterSpan::enter(name, items.len(), search_normalized.len(), parallel);
+
+    let stage = instrument::Stage::start("score");
+    let mut results = collect_matches_by(items, parallel, |target| {
+        let string_score = target.score(&search_normalized)?;
+        Some(FuzzyFilterResult::matched(target.text, string_score.score, string_score.ranges))
+    });
+    stage.finish(items.len(), results.len());
+
+    if !search.is_empty() {
+        let stage = instrument::Stage::start("sort");
+        sort_results(&mut results, parallel);
+        stage.finish(results.len(), results.len());
+    }
+
+    span.finish(results.len());
+    results
+}
@@ -0,0 +1,66 @@
+//! Named option presets for common kinds of targets.
+
+use crate::{MatchMode, MatchOptions, ScoringConfig};
+
+/// A named bundle of [`MatchOptions`] with sensible defaults for a common kind of target, so
+/// new users get reasonable behavior without tuning each option.
+///
+/// Presets are a starting point: the options they produce can be adjusted further.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{fuzzy_filter_with_options, precompute_skips_for_items, MatchOptions, Preset};
+///
+/// let targets = precompute_skips_for_items(vec!["src/main.rs", "src/lib.rs", "README.md"]);
+/// let options = MatchOptions::from(Preset::FilePaths);
+///
+/// let results = fuzzy_filter_with_options(&targets, "lib", &options);
+/// assert_eq!(results[0].item, "src/lib.rs");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Preset {
+    /// File and directory paths, e.g. for a file picker.
+    FilePaths,
+    /// Command names in a keyboard-driven command palette, e.g. "View: Toggle Word Wrap".
+    ///
+    /// Commands are matched by their initials (see [`MatchMode::Initials`]), so loose matches
+    /// in the middle of words don't clutter the palette.
+    CommandPalette,
+    /// People's names, e.g. for a contact search.
+    PersonNames,
+    /// Identifiers in source code, e.g. for a symbol search.
+    CodeIdentifiers,
+}
+
+impl Preset {
+    /// Every preset.
+    pub const ALL: [Preset; 4] = [Preset::FilePaths, Preset::CommandPalette, Preset::PersonNames, Preset::CodeIdentifiers];
+
+    /// The options for this preset, including how matches are scored (see [`ScoringConfig`]).
+    pub fn options(self) -> MatchOptions {
+        let mode = match self {
+            // Substrings and word prefixes match path segments, first and last names, and the
+            // words of camelCase and snake_case identifiers
+            Preset::FilePaths | Preset::PersonNames | Preset::CodeIdentifiers => MatchMode::Fuzzy,
+            Preset::CommandPalette => MatchMode::Initials,
+        };
+        let scoring_config = match self {
+            // Shorter paths are usually closer to what was meant, e.g. "lib" for "src/lib.rs"
+            // rather than "src/lib/util/mod.rs"
+            Preset::FilePaths => ScoringConfig::default().with_length_normalization(true),
+            // Last names are searched as often as first names
+            Preset::PersonNames => ScoringConfig::default().with_prefix_bonus(ScoringConfig::DEFAULT.start_bonus),
+            // Matches spanning fewer words of an identifier are more likely to be the one meant
+            Preset::CodeIdentifiers => ScoringConfig::default().with_range_decay_percent(20),
+            Preset::CommandPalette => ScoringConfig::default(),
+        };
+        MatchOptions { mode, scoring_config, ..MatchOptions::default() }
+    }
+}
+
+impl From<Preset> for MatchOptions {
+    fn from(preset: Preset) -> Self {
+        preset.options()
+    }
+}
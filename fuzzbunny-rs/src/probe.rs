@@ -0,0 +1,73 @@
+//! Matching at a specific position of a target.
+
+use crate::normalize::map_ranges;
+use crate::prelude::*;
+use crate::{fuzzy_prefix_match, NormalizationPipeline, Range, StringScore};
+
+/// Test whether a search matches a target starting at a specific byte offset, e.g. to jump
+/// between the places a search matches in an editor.
+///
+/// The search matches at an offset if the target contains it as a substring starting there,
+/// or (unless the search is quoted) if it fuzzily matches the prefixes of words starting with
+/// the word at that offset, the same as [`fuzzy_match`](crate::fuzzy_match). Like `fuzzy_match`,
+/// the search is trimmed and lower cased.
+///
+/// # Returns
+///
+/// The score and ranges of the match starting at `byte_offset`, or [`None`] if the search
+/// doesn't match there, or `byte_offset` isn't on a character boundary of the target. An empty
+/// search matches anywhere in a non-empty target, with no ranges.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{highlights_from_ranges, match_at};
+///
+/// let target = "let total = sub_total + tax_total";
+///
+/// assert!(match_at(target, "total", 0).is_none());
+/// assert!(match_at(target, "total", 4).is_some());
+/// // Word prefixes match too
+/// assert!(match_at(target, "s t", 12).is_some());
+///
+/// // Jump to the next match after the first
+/// let next = (5..=target.len()).find_map(|offset| match_at(target, "total", offset)).unwrap();
+/// assert_eq!(highlights_from_ranges(target, next.ranges), vec!["let total = sub_", "total", " + tax_total"]);
+/// ```
+pub fn match_at(target: &str, search: &str, byte_offset: usize) -> Option<StringScore> {
+    if target.is_empty() || !target.is_char_boundary(byte_offset) {
+        return None
+    }
+    let search = search.trim().to_lowercase();
+    if search.is_empty() {
+        return Some(StringScore { score: 0, ranges: vec![] })
+    }
+
+    let derived = NormalizationPipeline::default().derive(target, false);
+    let lower = derived.lower.as_str();
+    let start = if derived.offsets.is_empty() {
+        byte_offset
+    } else {
+        derived.offsets.iter().position(|&offset| offset == byte_offset)?
+    };
+
+    // The same quoting rules as `fuzzy_match`
+    let is_quoted = search.starts_with('"');
+    let search_str = if is_quoted { search[1..].strip_suffix('"').unwrap_or(&search[1..]) } else { search.as_str() };
+
+    let (score, ranges) = if !search_str.is_empty() && lower[start..].starts_with(search_str) {
+        let range = Range(start, search_str.len());
+        let is_word_prefix = lower[..start].chars().next_back().is_some_and(|char| !char.is_alphanumeric());
+        (range.get_score(is_word_prefix), vec![range])
+    } else {
+        // The first word has to match, or the match would start at a later word
+        if is_quoted || search_str.len() == 1 || lower.as_bytes().get(start) != search_str.as_bytes().first() {
+            return None
+        }
+        let skip_idx = derived.skips[..derived.skips.len() - 1].iter().position(|&skip| skip == start)?;
+        let ranges = fuzzy_prefix_match(skip_idx, search_str, lower, &derived.skips, &mut ())?;
+        (ranges.iter().map(|range| range.get_score(true)).sum(), ranges)
+    };
+
+    Some(StringScore { score, ranges: map_ranges(ranges, &derived.offsets) })
+}
@@ -0,0 +1,204 @@
+//! Queries of several independently matched tokens.
+
+use crate::pipeline::is_lower_case_aligned;
+use crate::normalize::map_ranges;
+use crate::prelude::*;
+use crate::{
+    collect_matches_by, instrument, is_parallel_workload, merge_ranges, score_prepared, sort_results, FuzzyFilterResult,
+    NormalizationPipeline, StringScore, Target,
+};
+
+/// A single token of a [`Query`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum QueryToken {
+    /// A bare word, matched the same as a [`fuzzy_filter`](crate::fuzzy_filter) search.
+    Fuzzy(String),
+    /// A quoted phrase, which only matches as an exact substring.
+    Phrase(String),
+    /// A word or quoted phrase prefixed with `-`, which excludes every target that contains it.
+    Exclude(String),
+}
+
+/// A search split into tokens that must all match a target.
+///
+/// Bare words are matched fuzzily, while quoted phrases (which may contain spaces) only match
+/// as exact substrings, so `"las vegas" hotel cheap` matches targets containing "las vegas"
+/// that also fuzzily match "hotel" and "cheap", in any order. As with a quoted search, the
+/// closing quote of the last phrase is optional, so results update as the user types.
+///
+/// Words and phrases prefixed with `-` (e.g. `petrelli -nathan`) exclude every target that
+/// contains them as a substring, so excluded text has to be spelled out rather than fuzzily
+/// matched. A `-` on its own is a bare word.
+///
+/// A target's score is the sum of the scores of its tokens, and its highlights cover the
+/// ranges matched by every token. Exclusions don't contribute to the score.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{Query, QueryToken};
+///
+/// let query = Query::parse("\"Las Vegas\" hotel");
+/// assert_eq!(query.tokens(), [QueryToken::Phrase("las vegas".to_string()), QueryToken::Fuzzy("hotel".to_string())]);
+///
+/// assert!(query.score(&("Hotel Las Vegas", None)).is_some());
+/// assert!(query.score(&("Las Cruces Vegas Hotel", None)).is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query {
+    tokens: Vec<QueryToken>,
+    /// The search string that each token other than an exclusion is scored with.
+    searches: Vec<String>,
+}
+
+impl Query {
+    /// Parse a search into its tokens, lower casing them.
+    ///
+    /// Tokens are separated by whitespace, and phrases are enclosed in double quotes. Empty
+    /// phrases are ignored.
+    pub fn parse(search: &str) -> Self {
+        let search = search.to_lowercase();
+        let mut tokens = vec![];
+        let mut rest = search.as_str();
+
+        loop {
+            rest = rest.trim_start();
+            let excluded = rest.strip_prefix('-').filter(|after| after.starts_with(|char: char| !char.is_whitespace()));
+            let is_exclusion = excluded.is_some();
+            rest = excluded.unwrap_or(rest);
+
+            if let Some(quoted) = rest.strip_prefix('"') {
+                let (phrase, after) = quoted.split_once('"').unwrap_or((quoted, ""));
+                let phrase = phrase.trim();
+                if !phrase.is_empty() {
+                    let phrase = phrase.to_string();
+                    tokens.push(if is_exclusion { QueryToken::Exclude(phrase) } else { QueryToken::Phrase(phrase) });
+                }
+                rest = after;
+                continue
+            }
+
+            // A bare word ends at whitespace, or where a phrase starts
+            let end = rest.find(|char: char| char.is_whitespace() || char == '"').unwrap_or(rest.len());
+            if end == 0 {
+                break
+            }
+            let word = rest[..end].to_string();
+            tokens.push(if is_exclusion { QueryToken::Exclude(word) } else { QueryToken::Fuzzy(word) });
+            rest = &rest[end..];
+        }
+
+        let searches = tokens
+            .iter()
+            .filter_map(|token| match token {
+                QueryToken::Fuzzy(word) => Some(word.clone()),
+                // An unclosed quote only matches substrings
+                QueryToken::Phrase(phrase) => Some(format!("\"{phrase}")),
+                QueryToken::Exclude(_) => None,
+            })
+            .collect();
+        Query { tokens, searches }
+    }
+
+    /// The tokens of the query, in the order they appear in the search.
+    pub fn tokens(&self) -> &[QueryToken] {
+        &self.tokens
+    }
+
+    /// Whether the query has no tokens, in which case it matches every non-empty target.
+    ///
+    /// A query of only exclusions isn't empty, but still matches every non-empty target that it
+    /// doesn't exclude, with a score of 0.
+    pub fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    /// Compute the score and highlight ranges of a target for this query.
+    ///
+    /// # Returns
+    ///
+    /// [`None`] unless every token matches the target, or if the target contains any excluded
+    /// word or phrase.
+    pub fn score(&self, target: &Target<'_>) -> Option<StringScore> {
+        let (text, skips) = (target.0, target.1.as_deref());
+        let lower = text.to_lowercase();
+        if is_lower_case_aligned(text, &lower) {
+            return self.score_lower(text, &lower, skips)
+        }
+
+        // Lower casing changed the length of the text, so match the lower case form with offsets
+        // back to the original text
+        let derived = NormalizationPipeline::default().derive(text, false);
+        let string_score = self.score_lower(&derived.lower, &derived.lower, Some(&derived.skips))?;
+        Some(StringScore { score: string_score.score, ranges: map_ranges(string_score.ranges, &derived.offsets) })
+    }
+
+    /// Score every token against a target with its lower case form, which must have the same
+    /// length as the target.
+    fn score_lower(&self, text: &str, lower: &str, skips: Option<&[usize]>) -> Option<StringScore> {
+        if text.is_empty() {
+            return None
+        }
+        // Exclusions are checked first, as they're much cheaper than scoring the other tokens
+        let is_excluded = |token: &QueryToken| matches!(token, QueryToken::Exclude(excluded) if lower.contains(excluded.as_str()));
+        if self.tokens.iter().any(is_excluded) {
+            return None
+        }
+
+        let mut score = 0u32;
+        let mut ranges = vec![];
+        for search in &self.searches {
+            let string_score = score_prepared(text, Some(lower), skips, search)?;
+            score = score.saturating_add(string_score.score);
+            ranges.extend(string_score.ranges);
+        }
+
+        // Tokens can match overlapping parts of the target, so merge their ranges in order
+        Some(StringScore { score, ranges: merge_ranges(ranges) })
+    }
+}
+
+/// Search a slice of [`Target`]s with a multi-token [`Query`], and return a filtered and sorted
+/// vector of [`FuzzyFilterResult`].
+///
+/// Unlike [`fuzzy_filter`](crate::fuzzy_filter), which matches the whole search at once, each
+/// token of the search has to match independently. A search of a single bare word matches the
+/// same as `fuzzy_filter`. As with `fuzzy_filter`, the results of an empty search aren't sorted.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{fuzzy_filter_query, precompute_skips_for_items};
+///
+/// let targets = precompute_skips_for_items(vec![
+///     "Cheap hotel, Las Vegas",
+///     "Las Vegas Strip hotel",
+///     "Cheap hotel, Los Angeles",
+/// ]);
+///
+/// let results = fuzzy_filter_query(&targets, "\"las vegas\" hotel cheap");
+/// assert_eq!(results.len(), 1);
+/// assert_eq!(results[0].highlights, Some(vec!["", "Cheap", " ", "hotel", ", ", "Las Vegas"]));
+/// ```
+pub fn fuzzy_filter_query<'a>(items: &[Target<'a>], search: &str) -> Vec<FuzzyFilterResult<'a>> {
+    let query = Query::parse(search);
+    let search_len = search.trim().len();
+    let parallel = is_parallel_workload(items.len(), |i| items[i].0.len(), search_len);
+    let span = instrument::FilterSpan::enter("fuzzy_filter_query", items.len(), search_len, parallel);
+
+    let stage = instrument::Stage::start("score");
+    let mut results = collect_matches_by(items, parallel, |target| {
+        let string_score = query.score(target)?;
+        Some(FuzzyFilterResult::matched(target.0, string_score.score, string_score.ranges))
+    });
+    stage.finish(items.len(), results.len());
+
+    if !search.is_empty() {
+        let stage = instrument::Stage::start("sort");
+        sort_results(&mut results, parallel);
+        stage.finish(results.len(), results.len());
+    }
+
+    span.finish(results.len());
+    results
+}
@@ -0,0 +1,92 @@
+//! Utilities for rendering matched [`Range`]s with a custom text layout.
+
+use core::ops;
+
+use crate::Range;
+use crate::prelude::*;
+
+/// Merge ranges that overlap or touch into single ranges, and sort them by their start, e.g.
+/// to combine the ranges matched by each token of a multi-token search. Empty ranges are
+/// dropped.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{merge_ranges, Range};
+///
+/// let ranges = vec![Range(6, 3), Range(0, 2), Range(7, 4), Range(2, 1), Range(14, 0)];
+/// assert_eq!(merge_ranges(ranges), vec![Range(0, 3), Range(6, 5)]);
+/// ```
+pub fn merge_ranges(ranges: impl IntoIterator<Item = Range>) -> Vec<Range> {
+    let mut ranges: Vec<Range> = ranges.into_iter().filter(|range| !range.is_empty()).collect();
+    ranges.sort_by_key(|range| range.0);
+
+    let mut merged: Vec<Range> = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if last.end() >= range.0 => last.1 = last.end().max(range.end()) - last.0,
+            _ => merged.push(range),
+        }
+    }
+    merged
+}
+
+/// The parts of ranges inside a window, e.g. the visible part of a line of text, dropping the
+/// ranges outside it. See [`Range::clip`].
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{clip_ranges, Range};
+///
+/// let ranges = [Range(0, 2), Range(4, 4), Range(10, 1)];
+/// assert_eq!(clip_ranges(&ranges, 1..6), vec![Range(1, 1), Range(4, 2)]);
+/// ```
+pub fn clip_ranges(ranges: &[Range], window: ops::Range<usize>) -> Vec<Range> {
+    ranges.iter().filter_map(|range| range.clip(window.clone())).collect()
+}
+
+/// A section of a target, either matched or between matches, yielded by [`segments`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Segment {
+    /// The byte range of the section in the target.
+    pub range: ops::Range<usize>,
+    /// Whether the section was matched by the search.
+    pub matched: bool,
+}
+
+/// Iterate over the sections of a target of `len` bytes, alternating between the unmatched
+/// sections and the matched `ranges`, as byte ranges rather than the strings of
+/// [`Highlights`](crate::Highlights).
+///
+/// Unlike highlights, empty sections are skipped, so the first segment is only unmatched if
+/// the first range doesn't start at 0. The ranges must be sorted and not overlap, as the ranges
+/// of a match are (or see [`merge_ranges`]).
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{fuzzy_match, segments, Segment};
+///
+/// let result = fuzzy_match("United States", Some("us")).unwrap();
+/// let sections: Vec<_> = segments(result.item.len(), result.ranges.as_deref().unwrap())
+///     .map(|segment| (&result.item[segment.range.clone()], segment.matched))
+///     .collect();
+/// assert_eq!(sections, vec![("U", true), ("nited ", false), ("S", true), ("tates", false)]);
+/// ```
+pub fn segments(len: usize, ranges: &[Range]) -> impl Iterator<Item = Segment> + '_ {
+    let mut last_end = 0;
+    let mut ranges = ranges.iter().filter_map(move |range| range.clip(0..len)).peekable();
+
+    core::iter::from_fn(move || {
+        let next_start = ranges.peek().map_or(len, |range| range.0);
+        if last_end < next_start {
+            let range = last_end..next_start;
+            last_end = next_start;
+            return Some(Segment { range, matched: false })
+        }
+        let range = ranges.next()?;
+        last_end = range.end();
+        Some(Segment { range: range.into(), matched: true })
+    })
+}
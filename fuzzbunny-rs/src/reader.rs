@@ -0,0 +1,136 @@
+//! Top-k searches over lines streamed from a reader.
+
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::io::{self, BufRead};
+
+use crate::{highlights_from_ranges, score_prepared, Highlights, Range};
+
+/// A line matched by [`fuzzy_filter_reader`].
+///
+/// Unlike a [`FuzzyFilterResult`](crate::FuzzyFilterResult), the line is owned, as the reader
+/// only holds a single line at a time.
+#[derive(Debug)]
+pub struct ReaderMatch {
+    /// The index of the line in the reader, starting from 0.
+    pub line_number: usize,
+    /// The text of the line, without its line ending.
+    pub line: String,
+    /// The score of the match.
+    pub score: u32,
+    /// The matched ranges of the line.
+    ranges: Vec<Range>,
+}
+
+impl ReaderMatch {
+    /// The line split into alternating unmatched and matched sections, the same as
+    /// [`FuzzyFilterResult::highlights`](crate::FuzzyFilterResult::highlights).
+    pub fn highlights(&self) -> Highlights<'_> {
+        highlights_from_ranges(&self.line, self.ranges.clone())
+    }
+}
+
+/// Compare how two scored lines rank, where the greater line ranks higher.
+///
+/// This is the same order as [`fuzzy_filter`](crate::fuzzy_filter): by score, then by the text
+/// of the line, and then by the order the lines were read in.
+fn rank(a: (u32, &str, usize), b: (u32, &str, usize)) -> Ordering {
+    a.0.cmp(&b.0).then_with(|| b.1.cmp(a.1)).then_with(|| b.2.cmp(&a.2))
+}
+
+impl PartialEq for ReaderMatch {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl Eq for ReaderMatch {}
+
+impl PartialOrd for ReaderMatch {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+impl Ord for ReaderMatch {
+    fn cmp(&self, other: &Self) -> Ordering {
+        rank((self.score, &self.line, self.line_number), (other.score, &other.line, other.line_number))
+    }
+}
+
+/// Search the lines of a reader, keeping only the `k` best matches.
+///
+/// Lines are scored as they are read, and only the best `k` matches seen so far are held in
+/// memory, so arbitrarily large inputs (e.g. multi-gigabyte log files) can be searched with
+/// memory bounded by `k` and the longest line. A line only has to be copied if it ranks among
+/// the best so far.
+///
+/// Lines are split on `\n`, with any trailing `\r` removed.
+///
+/// # Returns
+///
+/// The best `k` matches sorted from best to worst, in the same order as
+/// [`fuzzy_filter`](crate::fuzzy_filter) would rank them. As with `fuzzy_filter`, an empty search
+/// matches every non-empty line without sorting them, so only the first `k` are read.
+///
+/// # Errors
+///
+/// Returns any error from reading `reader`, including [`io::ErrorKind::InvalidData`] if a line
+/// isn't valid UTF-8.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::fuzzy_filter_reader;
+///
+/// let reader = "pineapple\napple\nbanana\napplication\n".as_bytes();
+///
+/// let results = fuzzy_filter_reader(reader, "app", 2).unwrap();
+/// let lines: Vec<&str> = results.iter().map(|res| res.line.as_str()).collect();
+/// assert_eq!(lines, vec!["apple", "application"]);
+/// assert_eq!(results[0].highlights(), vec!["", "app", "le"]);
+/// ```
+pub fn fuzzy_filter_reader(mut reader: impl BufRead, search: &str, k: usize) -> io::Result<Vec<ReaderMatch>> {
+    if k == 0 {
+        return Ok(vec![])
+    }
+    let search_lower_cased = search.trim().to_lowercase();
+    // A min-heap of the best matches, so the worst of them can be replaced
+    let mut best: BinaryHeap<Reverse<ReaderMatch>> = BinaryHeap::with_capacity(k.saturating_add(1).min(1024));
+    let mut buffer = String::new();
+    let mut line_number = 0;
+
+    // Every line scores 0 for an empty search, so no later line can rank above the first `k`
+    while best.len() < k || !search_lower_cased.is_empty() {
+        buffer.clear();
+        if reader.read_line(&mut buffer)? == 0 {
+            break
+        }
+        let line = buffer.strip_suffix('\n').unwrap_or(&buffer);
+        let line = line.strip_suffix('\r').unwrap_or(line);
+
+        if let Some(string_score) = score_prepared(line, None, None, &search_lower_cased) {
+            let is_better = match best.peek() {
+                Some(Reverse(worst)) if best.len() == k => {
+                    rank((string_score.score, line, line_number), (worst.score, &worst.line, worst.line_number)).is_gt()
+                },
+                _ => true,
+            };
+            if is_better {
+                if best.len() == k {
+                    best.pop();
+                }
+                best.push(Reverse(ReaderMatch {
+                    line_number,
+                    line: line.to_string(),
+                    score: string_score.score,
+                    ranges: string_score.ranges,
+                }));
+            }
+        }
+        line_number += 1;
+    }
+
+    // Sorting the min-heap in ascending order of `Reverse` puts the best match first
+    let mut results: Vec<ReaderMatch> = best.into_sorted_vec().into_iter().map(|Reverse(result)| result).collect();
+    if search_lower_cased.is_empty() {
+        results.sort_unstable_by_key(|result| result.line_number);
+    }
+    Ok(results)
+}
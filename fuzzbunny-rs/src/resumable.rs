@@ -0,0 +1,93 @@
+//! Resumable searches for single-threaded environments.
+
+use std::task::Poll;
+
+use crate::{collect_matches, sort_results, FuzzyFilterResult, Target};
+
+/// A search that is performed incrementally, a bounded batch of targets at a time.
+///
+/// This is intended for single-threaded environments (e.g. WASM in a browser without web
+/// workers, where the `rayon` feature is disabled), where a long running [`fuzzy_filter`](crate::fuzzy_filter)
+/// would block the event loop. Each call to [`ResumableSearch::poll`] scores at most
+/// `batch_size` targets, so the caller can interleave matching with rendering.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::task::Poll;
+/// use fuzzbunny_rs::{precompute_skips_for_items, ResumableSearch};
+///
+/// let targets = precompute_skips_for_items(vec!["apple", "banana", "application"]);
+/// let mut search = ResumableSearch::new(&targets, "app", 2);
+///
+/// assert!(search.poll().is_pending());
+/// let Poll::Ready(results) = search.poll() else { panic!() };
+///
+/// assert_eq!(results[0].item, "apple");
+/// assert_eq!(results[1].item, "application");
+/// ```
+pub struct ResumableSearch<'s, 'a> {
+    items: &'s [Target<'a>],
+    search: String,
+    sort: bool,
+    batch_size: usize,
+    next_index: usize,
+    results: Vec<FuzzyFilterResult<'a>>,
+}
+
+impl<'s, 'a> ResumableSearch<'s, 'a> {
+    /// Create a new search over `items`, scoring at most `batch_size` targets per poll.
+    ///
+    /// # Panics
+    ///
+    /// This function panics if `batch_size` is 0.
+    pub fn new(items: &'s [Target<'a>], search: &str, batch_size: usize) -> Self {
+        assert!(batch_size > 0, "batch_size must be non-zero");
+
+        ResumableSearch {
+            items,
+            search: search.trim().to_lowercase(),
+            sort: !search.is_empty(),
+            batch_size,
+            next_index: 0,
+            results: vec![],
+        }
+    }
+
+    /// Score the next batch of targets.
+    ///
+    /// # Returns
+    ///
+    /// [`Poll::Pending`] while there are still targets left to score. Once every target has
+    /// been scored, the sorted results are returned as [`Poll::Ready`]. Polling again after
+    /// that returns an empty [`Vec`].
+    pub fn poll(&mut self) -> Poll<Vec<FuzzyFilterResult<'a>>> {
+        let end_index = self.items.len().min(self.next_index + self.batch_size);
+        let batch = &self.items[self.next_index..end_index];
+        self.results.extend(collect_matches(batch, self.next_index, &self.search, false));
+        self.next_index = end_index;
+
+        if self.next_index < self.items.len() {
+            return Poll::Pending
+        }
+
+        let mut results = std::mem::take(&mut self.results);
+        if self.sort {
+            sort_results(&mut results, false);
+        }
+        Poll::Ready(results)
+    }
+
+    /// The matches found so far, in the order of the targets and NOT sorted by score.
+    pub fn partial_results(&self) -> &[FuzzyFilterResult<'a>] {
+        &self.results
+    }
+
+    /// The fraction of targets that have been scored so far, between 0.0 and 1.0.
+    pub fn progress(&self) -> f32 {
+        if self.items.is_empty() {
+            return 1.0
+        }
+        self.next_index as f32 / self.items.len() as f32
+    }
+}
@@ -0,0 +1,104 @@
+//! An owned, serializable form of targets and their precomputed skip indices.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{get_target_skips, Target, SCORING_VERSION};
+
+/// The version of the [`SavedTargets`] format.
+const SAVED_FORMAT: u32 = 1;
+
+/// A single target of a [`SavedTargets`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedTarget {
+    /// The text of the target.
+    pub text: String,
+    /// The precomputed skip indices of the target, if any. See [`get_target_skips`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skips: Option<Vec<usize>>,
+}
+
+/// An owned form of a set of [`Target`]s, including their skip indices, that can be persisted
+/// and loaded again without recomputing the skip indices.
+///
+/// This is only available with the `serde` feature.
+///
+/// # Format
+///
+/// The document can be serialized with any serde format. In JSON, it looks like:
+///
+/// ```json
+/// {
+///   "format": 1,
+///   "scoring_version": 2,
+///   "targets": [
+///     { "text": "Maya Herrera", "skips": [0, 5, 12] },
+///     { "text": "Sylar" }
+///   ]
+/// }
+/// ```
+///
+/// `format` is currently `1`, and `scoring_version` is the version of the scoring algorithm
+/// the skip indices were computed for. `skips` may be omitted for targets without precomputed
+/// skip indices. Skip indices are byte offsets into `text`, ending with its length.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{fuzzy_filter, precompute_skips_for_items, SavedTargets};
+///
+/// let saved = SavedTargets::new(&precompute_skips_for_items(vec!["Maya Herrera", "Sylar"]));
+///
+/// // ... persist and load `saved` ...
+///
+/// let targets = saved.targets();
+/// assert_eq!(fuzzy_filter(&targets, "mh")[0].item, "Maya Herrera");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedTargets {
+    /// The version of the format, currently `1`.
+    pub format: u32,
+    /// The version of the scoring algorithm that the skip indices were computed for.
+    pub scoring_version: u32,
+    /// The targets, in order.
+    pub targets: Vec<SavedTarget>,
+}
+
+impl SavedTargets {
+    /// Create the owned form of a set of targets.
+    pub fn new(targets: &[Target<'_>]) -> Self {
+        SavedTargets {
+            format: SAVED_FORMAT,
+            scoring_version: SCORING_VERSION,
+            targets: targets
+                .iter()
+                .map(|(text, skips)| SavedTarget { text: text.to_string(), skips: skips.clone() })
+                .collect(),
+        }
+    }
+
+    /// Borrow the saved targets as [`Target`]s, ready to be passed to
+    /// [`fuzzy_filter`](crate::fuzzy_filter).
+    ///
+    /// Skip indices that were computed for a different format or scoring version, or that are
+    /// malformed (e.g. out of order, or not ending at the length of the text), could produce
+    /// wrong results, so they're recomputed instead.
+    pub fn targets(&self) -> Vec<Target<'_>> {
+        let current = self.format == SAVED_FORMAT && self.scoring_version == SCORING_VERSION;
+
+        self.targets
+            .iter()
+            .map(|target| {
+                let text = target.text.as_str();
+                let skips = target.skips.as_ref().map(|skips| {
+                    let sorted = skips.windows(2).all(|pair| pair[0] <= pair[1]);
+                    if current && sorted && skips.last() == Some(&text.len()) {
+                        skips.clone()
+                    } else {
+                        get_target_skips(text)
+                    }
+                });
+                (text, skips)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,202 @@
+//! Configurable weights for scoring matches.
+
+use crate::{Range, SCORE_CONTIGUOUS, SCORE_PREFIX, SCORE_START_STR};
+
+/// The weights used to score matches, set with [`MatchOptions::scoring_config`](crate::MatchOptions::scoring_config).
+///
+/// Each matched range of a target scores `contiguous_weight` times the square of its length,
+/// plus a bonus if it starts the target, or a smaller bonus (reduced by how far into the target
+/// it is) if it starts a word. The scores of the ranges are then summed, plus a bonus if the
+/// ranges match the initials of the words of the target.
+///
+/// The default config scores the same as [`fuzzy_filter`](crate::fuzzy_filter).
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{fuzzy_filter_with_options, precompute_skips_for_items, MatchOptions, ScoringConfig};
+///
+/// let targets = precompute_skips_for_items(vec!["Carpet Cleaning Services", "Red Car"]);
+/// let items = |options: &MatchOptions| -> Vec<&str> {
+///     fuzzy_filter_with_options(&targets, "car", options).iter().map(|res| res.item).collect()
+/// };
+/// assert_eq!(items(&MatchOptions::default()), vec!["Carpet Cleaning Services", "Red Car"]);
+///
+/// // Word prefixes score as highly as the start of the target, and shorter targets rank higher
+/// let config = ScoringConfig::default().with_prefix_bonus(1000).with_length_normalization(true);
+/// assert_eq!(items(&MatchOptions::default().with_scoring_config(config)), vec!["Red Car", "Carpet Cleaning Services"]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ScoringConfig {
+    /// The bonus of a range starting the target.
+    pub start_bonus: u32,
+    /// The bonus of a range starting a word, less the byte offset of the range.
+    pub prefix_bonus: u32,
+    /// The weight of the square of the length of each range, so longer contiguous matches rank
+    /// higher.
+    pub contiguous_weight: u32,
+    /// The percentage (up to 100) by which the score of each range is reduced relative to the
+    /// range before it, so matches split across fewer words rank higher.
+    pub range_decay_percent: u32,
+    /// Whether scores are scaled by how much of the target is matched, from half the score when
+    /// almost none of it is to the full score when all of it is, so shorter targets rank higher.
+    pub length_normalization: bool,
+    /// The penalty for each typo of a match, with [`MatchOptions::max_typos`](crate::MatchOptions::max_typos).
+    pub typo_penalty: u32,
+    /// The bonus of an acronym match, where the search matches the initials of several words,
+    /// e.g. "usam" matching "United States of America" or "fb" matching "FuzzBunny".
+    ///
+    /// A match is an acronym if it has more than one range, and every range starts a word (or
+    /// camelCase hump) and is at most 2 bytes long (e.g. "Am" of "America"). The default of 0
+    /// gives no bonus.
+    pub acronym_bonus: u32,
+}
+
+impl ScoringConfig {
+    /// The default config.
+    pub const DEFAULT: ScoringConfig = ScoringConfig {
+        start_bonus: SCORE_START_STR,
+        prefix_bonus: SCORE_PREFIX,
+        contiguous_weight: SCORE_CONTIGUOUS,
+        range_decay_percent: 0,
+        length_normalization: false,
+        typo_penalty: 1000,
+        acronym_bonus: 0,
+    };
+
+    /// Set the bonus of a range starting the target.
+    pub fn with_start_bonus(mut self, start_bonus: u32) -> Self {
+        self.start_bonus = start_bonus;
+        self
+    }
+
+    /// Set the bonus of a range starting a word.
+    pub fn with_prefix_bonus(mut self, prefix_bonus: u32) -> Self {
+        self.prefix_bonus = prefix_bonus;
+        self
+    }
+
+    /// Set the weight of contiguous matches.
+    pub fn with_contiguous_weight(mut self, contiguous_weight: u32) -> Self {
+        self.contiguous_weight = contiguous_weight;
+        self
+    }
+
+    /// Set the percentage by which the score of each range is reduced. See
+    /// [`ScoringConfig::range_decay_percent`].
+    pub fn with_range_decay_percent(mut self, range_decay_percent: u32) -> Self {
+        self.range_decay_percent = range_decay_percent;
+        self
+    }
+
+    /// Set whether scores are scaled by how much of the target is matched. See
+    /// [`ScoringConfig::length_normalization`].
+    pub fn with_length_normalization(mut self, length_normalization: bool) -> Self {
+        self.length_normalization = length_normalization;
+        self
+    }
+
+    /// Set the penalty for each typo of a match.
+    pub fn with_typo_penalty(mut self, typo_penalty: u32) -> Self {
+        self.typo_penalty = typo_penalty;
+        self
+    }
+
+    /// Set the bonus of an acronym match. See [`ScoringConfig::acronym_bonus`].
+    pub fn with_acronym_bonus(mut self, acronym_bonus: u32) -> Self {
+        self.acronym_bonus = acronym_bonus;
+        self
+    }
+
+    /// Calculate the score of a single range, which starts a word if `is_prefix` is set.
+    #[inline]
+    pub(crate) const fn range_score(&self, range: &Range, is_prefix: bool) -> u32 {
+        // increase score exponentially per letter matched so that contiguous matches are ranked higher
+        // i.e '[abc]' ranks higher than '[ab]ott [c]hemicals'
+        let contiguity = self.contiguous_weight.saturating_mul((range.1 * range.1) as u32);
+        let bonus = if range.0 == 0 {
+            // matching at the start of string gets a ranking bonus
+            self.start_bonus
+        } else if is_prefix {
+            // closer to the start, the higher it ranks
+            self.prefix_bonus.saturating_sub(range.0 as u32)
+        } else {
+            0
+        };
+        contiguity.saturating_add(bonus)
+    }
+
+    /// Calculate the total score of the ranges matched in a target, where `is_prefix` tells
+    /// whether a range starts a word.
+    pub(crate) fn score(&self, ranges: &[Range], is_prefix: impl Fn(&Range) -> bool, target: &str) -> u32 {
+        let score = if self.range_decay_percent == 0 {
+            ranges.iter().map(|range| self.range_score(range, is_prefix(range))).sum()
+        } else {
+            let retained = f64::from(100 - self.range_decay_percent.min(100)) / 100.0;
+            let mut factor = 1.0;
+            let mut score = 0.0;
+            for range in ranges {
+                score += f64::from(self.range_score(range, is_prefix(range))) * factor;
+                factor *= retained;
+            }
+            round_score(score)
+        };
+        let score = if self.acronym_bonus > 0 && is_acronym(ranges, &is_prefix, target) {
+            score.saturating_add(self.acronym_bonus)
+        } else {
+            score
+        };
+
+        let target_len = target.len();
+        if !self.length_normalization || target_len == 0 {
+            return score
+        }
+        let matched: usize = ranges.iter().map(|range| range.1).sum();
+        let scaled = u64::from(score) * (target_len + matched.min(target_len)) as u64 / (2 * target_len) as u64;
+        scaled as u32
+    }
+}
+
+/// Round a score to the nearest integer (halves away from zero) and convert it to a `u32`, as
+/// `score.round() as u32` would, which needs `std`.
+#[inline]
+pub(crate) fn round_score(score: f64) -> u32 {
+    let truncated = score as u32;
+    if score - f64::from(truncated) >= 0.5 {
+        truncated.saturating_add(1)
+    } else {
+        truncated
+    }
+}
+
+/// Whether every one of several ranges starts a word and is at most 2 characters long, so the
+/// ranges match the initials of the words.
+fn is_acronym(ranges: &[Range], is_prefix: impl Fn(&Range) -> bool, target: &str) -> bool {
+    ranges.len() > 1
+        && ranges.iter().all(|range| {
+            let is_short = target.get(range.0..range.end()).is_some_and(|matched| matched.chars().nth(2).is_none());
+            is_short && (range.0 == 0 || is_prefix(range))
+        })
+}
+
+/// Compute the relevance of the ranges matched in a target of `target_len` bytes, from 0.0 to
+/// 1.0, as the mean of how much of the target is matched, how much of the match is in its
+/// longest range, and how close the match starts to the start of the target.
+pub(crate) fn normalized_score(ranges: &[Range], target_len: usize) -> f32 {
+    let (Some(first), Some(longest)) = (ranges.first(), ranges.iter().map(|range| range.1).max()) else { return 0.0 };
+    let matched: usize = ranges.iter().map(|range| range.1).sum();
+    if target_len == 0 || matched == 0 {
+        return 0.0
+    }
+
+    let coverage = matched.min(target_len) as f32 / target_len as f32;
+    let contiguity = longest as f32 / matched as f32;
+    let position = 1.0 - first.0.min(target_len) as f32 / target_len as f32;
+    (coverage + contiguity + position) / 3.0
+}
+
+impl Default for ScoringConfig {
+    fn default() -> Self {
+        ScoringConfig::DEFAULT
+    }
+}
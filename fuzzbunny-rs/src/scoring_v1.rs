@@ -0,0 +1,41 @@
+//! Version 1 of the scoring algorithm, frozen so rankings stay stable across releases.
+//!
+//! This is the original fuzzbunny algorithm: a substring match scores by its length and how
+//! close it is to the start of the target (or of a word), and otherwise the search is matched
+//! against the prefixes of words in order. Scores and highlights from these functions won't
+//! change in future releases, unlike the unversioned functions such as
+//! [`fuzzy_filter`](crate::fuzzy_filter), which use [`ScoringVersion::LATEST`].
+//!
+//! # Examples
+//!
+//! ```rust
+//! use fuzzbunny_rs::{precompute_skips_for_items, scoring_v1};
+//!
+//! let targets = precompute_skips_for_items(vec!["apple", "pineapple"]);
+//! let results = scoring_v1::fuzzy_filter(&targets, "app");
+//!
+//! assert_eq!(results[0].score, 3700);
+//! assert_eq!(results[1].score, 2700);
+//! ```
+
+use crate::prelude::*;
+use crate::{fuzzy_filter_with_options, score_prepared, FuzzyFilterResult, MatchOptions, ScoringVersion, StringScore, Target};
+
+/// The options selecting this version.
+fn options() -> MatchOptions {
+    MatchOptions::default().with_scoring(ScoringVersion::V1)
+}
+
+/// Compute a raw score and highlight ranges for a target and search string. See
+/// [`fuzzy_score_item`](crate::fuzzy_score_item).
+///
+/// Note that `search` string MUST be lower case.
+pub fn score(target: &Target<'_>, search: &str) -> Option<StringScore> {
+    score_prepared(target.0, None, target.1.as_deref(), search)
+}
+
+/// Search a slice of [`Target`]s and return a filtered and sorted vector of
+/// [`FuzzyFilterResult`]. See [`fuzzy_filter`](crate::fuzzy_filter).
+pub fn fuzzy_filter<'a>(items: &[Target<'a>], search: &str) -> Vec<FuzzyFilterResult<'a>> {
+    fuzzy_filter_with_options(items, search, &options())
+}
@@ -0,0 +1,91 @@
+//! Search sessions that narrow the previous results as the user types.
+
+use crate::{collect_matches_by, filter_target, instrument, is_parallel_workload, sort_results, FuzzyFilterResult, Target};
+
+/// A sequence of searches over the same targets, such as the searches made as a user types
+/// into a search box.
+///
+/// The session remembers the previous search and which targets it matched. When the next
+/// search extends the previous one (e.g. `"ap"` then `"app"`), only the targets that matched
+/// before are scored again, as a target that doesn't match a search can't match a longer form
+/// of it. Any other search (e.g. after a backspace) falls back to scoring every target.
+///
+/// The results are always the same as those of [`fuzzy_filter`](crate::fuzzy_filter).
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{precompute_skips_for_items, SearchSession};
+///
+/// let targets = precompute_skips_for_items(vec!["apple", "banana", "application", "grape"]);
+/// let mut session = SearchSession::new(&targets);
+///
+/// assert_eq!(session.search("ap").len(), 3);
+/// // Only "apple", "application" and "grape" are scored again
+/// let results = session.search("app");
+/// assert_eq!(results[0].item, "apple");
+/// assert_eq!(results[1].item, "application");
+/// ```
+#[derive(Debug, Clone)]
+pub struct SearchSession<'s, 'a> {
+    items: &'s [Target<'a>],
+    /// The trimmed, lower case form of the previous search, if there was one.
+    query: Option<String>,
+    /// The indices of the targets matched by the previous search, in ascending order.
+    matches: Vec<usize>,
+}
+
+impl<'s, 'a> SearchSession<'s, 'a> {
+    /// Create a new session over `items`, with no previous search.
+    pub fn new(items: &'s [Target<'a>]) -> Self {
+        SearchSession { items, query: None, matches: vec![] }
+    }
+
+    /// The trimmed, lower case form of the previous search, or [`None`] if there hasn't been one
+    /// since the session was created or reset.
+    pub fn query(&self) -> Option<&str> {
+        self.query.as_deref()
+    }
+
+    /// Forget the previous search, so the next search scores every target.
+    pub fn reset(&mut self) {
+        self.query = None;
+        self.matches.clear();
+    }
+
+    /// Search the targets and return a filtered and sorted vector of [`FuzzyFilterResult`].
+    ///
+    /// If this search extends the previous one, only the targets matched by the previous search
+    /// are scored.
+    pub fn search(&mut self, search: &str) -> Vec<FuzzyFilterResult<'a>> {
+        let search_lower_cased = search.trim().to_lowercase();
+        let items = self.items;
+
+        let candidates = match &self.query {
+            Some(query) if search_lower_cased.starts_with(query.as_str()) => std::mem::take(&mut self.matches),
+            _ => (0..items.len()).collect(),
+        };
+
+        let parallel = is_parallel_workload(candidates.len(), |i| items[candidates[i]].0.len(), search_lower_cased.len());
+        let span = instrument::FilterSpan::enter("search_session", candidates.len(), search_lower_cased.len(), parallel);
+
+        let stage = instrument::Stage::start("score");
+        let matches = collect_matches_by(&candidates, parallel, |&index| {
+            filter_target(&items[index], &search_lower_cased).map(|result| (index, result))
+        });
+        stage.finish(candidates.len(), matches.len());
+
+        let (indices, mut results): (Vec<usize>, Vec<_>) = matches.into_iter().unzip();
+        self.matches = indices;
+        self.query = Some(search_lower_cased);
+
+        if !search.is_empty() {
+            let stage = instrument::Stage::start("sort");
+            sort_results(&mut results, parallel);
+            stage.finish(results.len(), results.len());
+        }
+
+        span.finish(results.len());
+        results
+    }
+}
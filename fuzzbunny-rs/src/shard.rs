@@ -0,0 +1,143 @@
+//! Corpora split into independently managed shards.
+
+use std::cmp::Ordering;
+
+use crate::federated::{cmp_attributed, search_sources, Source};
+use crate::{Corpus, CorpusMatch};
+
+/// A filter result for an item in a [`ShardedCorpus`].
+#[derive(Debug, PartialEq, Eq)]
+pub struct ShardedMatch<'c> {
+    /// The name of the shard holding the matched item.
+    pub shard: &'c str,
+    /// The match within that shard. Its ID is only unique within the shard.
+    pub matched: CorpusMatch<'c>,
+}
+
+impl<'c> PartialOrd for ShardedMatch<'c> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+impl<'c> Ord for ShardedMatch<'c> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_attributed((&self.matched, self.shard), (&other.matched, other.shard))
+    }
+}
+
+/// A single named shard of a [`ShardedCorpus`].
+#[derive(Debug, Clone)]
+struct Shard {
+    name: String,
+    corpus: Corpus,
+}
+
+/// A collection of named [`Corpus`] shards that are searched together.
+///
+/// Each shard is an independent corpus (e.g. one per data source), so a single shard can be
+/// mutated or entirely reloaded without touching the others. Searches query every shard in
+/// parallel if the `rayon` feature is enabled, and merge the results by score.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{Corpus, ShardedCorpus};
+///
+/// let mut sharded = ShardedCorpus::new();
+/// sharded.set_shard("fruit", Corpus::from_iter(["apple", "banana"]));
+/// sharded.set_shard("software", Corpus::from_iter(["application", "app store"]));
+///
+/// let results = sharded.search("app");
+/// assert_eq!(results.len(), 3);
+/// assert_eq!(results[0].shard, "software");
+/// assert_eq!(results[0].matched.result.item, "app store");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ShardedCorpus {
+    /// The shards, in the order they were first added.
+    shards: Vec<Shard>,
+}
+
+impl ShardedCorpus {
+    /// Create a new corpus without any shards.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a shard, or replace the corpus of an existing shard with the same name.
+    ///
+    /// Replaced shards keep their position in the shard order.
+    ///
+    /// # Returns
+    ///
+    /// The previous corpus of the shard, if it already existed.
+    pub fn set_shard(&mut self, name: impl Into<String>, corpus: Corpus) -> Option<Corpus> {
+        let name = name.into();
+        match self.shards.iter_mut().find(|shard| shard.name == name) {
+            Some(shard) => Some(std::mem::replace(&mut shard.corpus, corpus)),
+            None => {
+                self.shards.push(Shard { name, corpus });
+                None
+            },
+        }
+    }
+
+    /// Remove a shard.
+    ///
+    /// # Returns
+    ///
+    /// The corpus of the removed shard, or [`None`] if there is no shard with the given name.
+    pub fn remove_shard(&mut self, name: &str) -> Option<Corpus> {
+        let position = self.shards.iter().position(|shard| shard.name == name)?;
+        Some(self.shards.remove(position).corpus)
+    }
+
+    /// Get the corpus of a shard.
+    pub fn shard(&self, name: &str) -> Option<&Corpus> {
+        self.shards.iter().find(|shard| shard.name == name).map(|shard| &shard.corpus)
+    }
+
+    /// Get the corpus of a shard, to mutate it.
+    pub fn shard_mut(&mut self, name: &str) -> Option<&mut Corpus> {
+        self.shards.iter_mut().find(|shard| shard.name == name).map(|shard| &mut shard.corpus)
+    }
+
+    /// The names of the shards, in the order they were added.
+    pub fn shard_names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.shards.iter().map(|shard| shard.name.as_str())
+    }
+
+    /// The total number of items across every shard.
+    pub fn len(&self) -> usize {
+        self.shards.iter().map(|shard| shard.corpus.len()).sum()
+    }
+
+    /// Whether no shard contains any items.
+    pub fn is_empty(&self) -> bool {
+        self.shards.iter().all(|shard| shard.corpus.is_empty())
+    }
+
+    /// Search every shard and return the merged, sorted matches.
+    ///
+    /// Each shard is searched as with [`Corpus::search`]. For an empty search, the results of
+    /// each shard are returned in shard order, with pinned items first.
+    pub fn search(&self, search: &str) -> Vec<ShardedMatch<'_>> {
+        // A federated search where every shard has the same weight
+        let sources: Vec<_> = self.shards
+            .iter()
+            .map(|shard| Source { name: &shard.name, corpus: &shard.corpus, weight: 1.0 })
+            .collect();
+        search_sources("sharded_search", &sources, search)
+            .into_iter()
+            .map(|res| ShardedMatch { shard: res.source, matched: res.matched })
+            .collect()
+    }
+}
+
+impl<S: Into<String>> FromIterator<(S, Corpus)> for ShardedCorpus {
+    fn from_iter<I: IntoIterator<Item = (S, Corpus)>>(iter: I) -> Self {
+        let mut sharded = ShardedCorpus::new();
+        for (name, corpus) in iter {
+            sharded.set_shard(name, corpus);
+        }
+        sharded
+    }
+}
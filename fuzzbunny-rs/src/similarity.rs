@@ -0,0 +1,71 @@
+//! Pairwise similarity of strings.
+
+use crate::pipeline::Derived;
+use crate::score_prepared;
+
+/// A string prepared for comparison with other strings.
+pub(crate) struct Prepared<'a> {
+    text: &'a str,
+    derived: Derived,
+    /// The score of the text matched against itself, which is the best score any search can get.
+    self_score: u32,
+}
+
+impl<'a> Prepared<'a> {
+    pub(crate) fn new(text: &'a str) -> Self {
+        let text = text.trim();
+        let derived = Derived::new(text);
+        let self_score = score_prepared(text, Some(&derived.lower), Some(&derived.skips), &derived.lower).map_or(0, |score| score.score);
+        Prepared { text, derived, self_score }
+    }
+
+    /// How well this string matches as a search against `target`, from 0 (no match) to 1 (the
+    /// same, ignoring case).
+    fn match_fraction(&self, target: &Prepared<'_>) -> f64 {
+        if target.self_score == 0 {
+            return if self.derived.lower == target.derived.lower { 1.0 } else { 0.0 }
+        }
+        let derived = &target.derived;
+        let score = score_prepared(target.text, Some(&derived.lower), Some(&derived.skips), &self.derived.lower);
+        score.map_or(0.0, |score| (f64::from(score.score) / f64::from(target.self_score)).sqrt())
+    }
+
+    /// The similarity of two strings. See [`similarity`].
+    pub(crate) fn similarity(&self, other: &Prepared<'_>) -> f64 {
+        self.match_fraction(other).max(other.match_fraction(self))
+    }
+}
+
+/// Score how similar two strings are, independent of any corpus, e.g. for record linkage.
+///
+/// Each string is scored as a search against the other, and the score is normalized against
+/// the other string's score against itself, so the parts of the other string that aren't
+/// matched lower the similarity. As scores grow with the square of the length of each matched
+/// range, the square root of this fraction is taken, making it roughly the fraction of the other
+/// string that was matched. The better of the two directions is the similarity, so the result
+/// is symmetric.
+///
+/// Strings are trimmed and compared case insensitively.
+///
+/// # Returns
+///
+/// The similarity, from 0 for strings that don't match each other in either direction, to 1
+/// for strings that are the same.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::similarity;
+///
+/// assert_eq!(similarity("Coca-Cola", "coca-cola "), 1.0);
+/// assert_eq!(similarity("Coca-Cola", "Pepsi"), 0.0);
+///
+/// // "Coca Cola" matches the words of "Coca-Cola" separately, which scores lower than a
+/// // contiguous match
+/// let separate = similarity("Coca Cola", "Coca-Cola");
+/// assert!(0.6 < separate && separate < 0.7);
+/// assert_eq!(similarity("Coca Cola", "Coca-Cola"), similarity("Coca-Cola", "Coca Cola"));
+/// ```
+pub fn similarity(a: &str, b: &str) -> f64 {
+    Prepared::new(a).similarity(&Prepared::new(b))
+}
@@ -0,0 +1,118 @@
+//! Configurable word boundaries for computing skip indices.
+
+use crate::prelude::*;
+use crate::{map_items, Target, PARALLEL_PRECOMPUTE_THRESHOLD};
+
+/// Rules for which characters start the words of a target, as marked by its skip indices.
+///
+/// The default rules are the same as [`get_target_skips`](crate::get_target_skips): a word
+/// starts at every alphanumeric character that doesn't follow another, at every upper case
+/// character that doesn't follow another (splitting camelCase and PascalCase), and at every
+/// ASCII punctuation character.
+///
+/// The punctuation characters that act as boundaries can be replaced, e.g. so a path only
+/// splits at `/` and `.`. Characters that are ASCII punctuation but not boundaries are then
+/// treated as part of the word they're in, so `-` doesn't split "my-file". Whitespace always
+/// separates words.
+///
+/// Skip indices are used whenever a search falls back to matching the prefixes of words, so
+/// targets should be prepared with [`SkipRules::precompute_skips_for_items`], and searches that
+/// compute skip indices on the fly should set [`MatchOptions::skip_rules`](crate::MatchOptions::skip_rules).
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{fuzzy_filter, get_target_skips, precompute_skips_for_items, SkipRules};
+///
+/// let rules = SkipRules::new().with_boundaries(['/', '.']);
+/// assert_eq!(get_target_skips("src/my-file.rs"), vec![0, 3, 4, 6, 7, 11, 12, 14]);
+/// assert_eq!(rules.skips("src/my-file.rs"), vec![0, 3, 4, 11, 12, 14]);
+///
+/// // "file" is no longer a word of its own
+/// let items = vec!["src/my-file.rs", "src/main.rs"];
+/// assert_eq!(fuzzy_filter(&precompute_skips_for_items(items.clone()), "sf").len(), 1);
+/// assert!(fuzzy_filter(&rules.precompute_skips_for_items(items), "sf").is_empty());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SkipRules {
+    /// The characters that are boundaries, or [`None`] for every ASCII punctuation character.
+    boundaries: Option<Vec<char>>,
+    /// Whether an upper case character following a character that isn't upper case starts a
+    /// word.
+    camel_case: bool,
+}
+
+impl SkipRules {
+    /// The default rules, the same as [`get_target_skips`](crate::get_target_skips).
+    pub const DEFAULT: SkipRules = SkipRules { boundaries: None, camel_case: true };
+
+    /// Create the default rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the characters that are boundaries, replacing ASCII punctuation. Each boundary
+    /// character starts a word, and so does the character after it.
+    pub fn with_boundaries(mut self, boundaries: impl IntoIterator<Item = char>) -> Self {
+        self.boundaries = Some(boundaries.into_iter().collect());
+        self
+    }
+
+    /// Set whether changes from lower to upper case start a word, as in camelCase.
+    pub fn with_camel_case(mut self, camel_case: bool) -> Self {
+        self.camel_case = camel_case;
+        self
+    }
+
+    /// Whether a character is a boundary.
+    #[inline]
+    fn is_boundary(&self, char: char) -> bool {
+        match &self.boundaries {
+            Some(boundaries) => boundaries.contains(&char),
+            None => char.is_ascii_punctuation(),
+        }
+    }
+
+    /// Compute the skip indices of a target with these rules. See
+    /// [`get_target_skips`](crate::get_target_skips).
+    pub fn skips(&self, target: &str) -> Vec<usize> {
+        let mut target_skips = vec![];
+        let mut was_word = false;
+        let mut was_upper_case = false;
+
+        for (i, char) in target.char_indices() {
+            let is_boundary = self.is_boundary(char);
+            // Punctuation that isn't a boundary joins the characters either side of it
+            let is_word = char.is_alphanumeric() || (char.is_ascii_punctuation() && !is_boundary);
+            let is_upper_case = char.is_uppercase();
+
+            if (is_word && !was_word) || (self.camel_case && is_upper_case && !was_upper_case) || is_boundary {
+                target_skips.push(i);
+            }
+
+            was_word = is_word;
+            was_upper_case = is_upper_case;
+        }
+
+        // We push the length as the last skip so when matching
+        // every range aligns between skip[i] and skip[i + 1]
+        // and we don't have to do extraneous overflow checks
+        target_skips.push(target.len());
+        target_skips
+    }
+
+    /// Convert string items to [`Target`]s with skip indices computed with these rules. See
+    /// [`precompute_skips_for_items`](crate::precompute_skips_for_items).
+    pub fn precompute_skips_for_items<'a>(&self, items: impl IntoIterator<Item = &'a str>) -> Vec<Target<'a>> {
+        let items: Vec<&'a str> = items.into_iter().collect();
+        let parallel = items.len() >= PARALLEL_PRECOMPUTE_THRESHOLD;
+
+        map_items(&items, parallel, |&string| (string, Some(self.skips(string))))
+    }
+}
+
+impl Default for SkipRules {
+    fn default() -> Self {
+        SkipRules::DEFAULT
+    }
+}
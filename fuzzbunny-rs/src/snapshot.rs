@@ -0,0 +1,110 @@
+//! Shared corpora with consistent snapshots for concurrent queries.
+
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::{Corpus, Dictionaries, ItemId};
+
+/// A [`Corpus`] shared between threads, that is queried through cheap snapshots.
+///
+/// Taking a snapshot only clones an [`Arc`], and the snapshot is unaffected by any later
+/// mutations, so a query in flight always sees a consistent view of the corpus. Mutations are
+/// copy-on-write: they're applied in place when no snapshots are held, and otherwise to a copy
+/// that becomes the current corpus. Old versions are reclaimed as soon as their last snapshot
+/// is dropped.
+///
+/// # Panics
+///
+/// A mutation that panics can leave the corpus partly mutated, so every later use of the shared
+/// corpus panics too, rather than querying an inconsistent corpus.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{Corpus, SharedCorpus};
+///
+/// let shared = SharedCorpus::new(Corpus::from_iter(["apple", "banana"]));
+/// let snapshot = shared.snapshot();
+///
+/// shared.insert("application");
+///
+/// assert_eq!(snapshot.search("app").len(), 1);
+/// assert_eq!(shared.snapshot().search("app").len(), 2);
+/// ```
+#[derive(Debug, Default)]
+pub struct SharedCorpus {
+    current: RwLock<Arc<Corpus>>,
+}
+
+impl SharedCorpus {
+    /// Share an existing corpus.
+    pub fn new(corpus: Corpus) -> Self {
+        SharedCorpus { current: RwLock::new(Arc::new(corpus)) }
+    }
+
+    /// Lock the current corpus for reading.
+    fn read(&self) -> RwLockReadGuard<'_, Arc<Corpus>> {
+        // A poisoned lock may hold a half-mutated corpus, as the mutation can panic part-way through
+        self.current.read().expect("a mutation of the shared corpus panicked")
+    }
+
+    /// Lock the current corpus for writing.
+    fn lock(&self) -> RwLockWriteGuard<'_, Arc<Corpus>> {
+        self.current.write().expect("a mutation of the shared corpus panicked")
+    }
+
+    /// Take a snapshot of the current corpus.
+    pub fn snapshot(&self) -> Arc<Corpus> {
+        self.read().clone()
+    }
+
+    /// Apply a mutation to the current corpus.
+    ///
+    /// Snapshots taken before the mutation don't observe it. If any such snapshots are still
+    /// held, the corpus is copied before being mutated.
+    pub fn write<R>(&self, mutate: impl FnOnce(&mut Corpus) -> R) -> R {
+        mutate(Arc::make_mut(&mut self.lock()))
+    }
+
+    /// Insert a new item into the current corpus. See [`Corpus::insert`].
+    pub fn insert(&self, text: impl Into<String>) -> ItemId {
+        self.write(|corpus| corpus.insert(text))
+    }
+
+    /// Remove an item from the current corpus. See [`Corpus::remove`].
+    pub fn remove(&self, id: ItemId) -> Option<String> {
+        self.write(|corpus| corpus.remove(id))
+    }
+
+    /// Update an item in the current corpus. See [`Corpus::update`].
+    pub fn update(&self, id: ItemId, text: impl Into<String>) -> Option<String> {
+        self.write(|corpus| corpus.update(id, text))
+    }
+
+    /// Replace the dictionaries of the current corpus. See [`Corpus::set_dictionaries`].
+    ///
+    /// Snapshots taken before the swap keep using the old dictionaries, so queries in flight
+    /// see consistent results.
+    ///
+    /// # Returns
+    ///
+    /// The new dictionary generation.
+    pub fn set_dictionaries(&self, dictionaries: impl Into<Arc<Dictionaries>>) -> u64 {
+        self.write(|corpus| corpus.set_dictionaries(dictionaries))
+    }
+
+    /// The dictionary generation of the current corpus. See [`Corpus::dictionary_generation`].
+    pub fn dictionary_generation(&self) -> u64 {
+        self.read().dictionary_generation()
+    }
+
+    /// The version of the current corpus. See [`Corpus::version`].
+    pub fn version(&self) -> u64 {
+        self.read().version()
+    }
+}
+
+impl From<Corpus> for SharedCorpus {
+    fn from(corpus: Corpus) -> Self {
+        SharedCorpus::new(corpus)
+    }
+}
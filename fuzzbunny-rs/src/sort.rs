@@ -0,0 +1,103 @@
+//! Policies for ordering equally scored results.
+
+use core::cmp::Ordering;
+use core::fmt;
+use alloc::sync::Arc;
+
+use crate::prelude::*;
+use crate::{collect_matches, instrument, is_parallel_workload, sort_results_by, FuzzyFilterResult, Target};
+
+/// A comparator of two results, for [`SortPolicy::Custom`].
+pub type ResultComparator = Arc<dyn for<'r> Fn(&FuzzyFilterResult<'r>, &FuzzyFilterResult<'r>) -> Ordering + Send + Sync>;
+
+/// How results with equal scores are ordered by [`fuzzy_filter_sorted`].
+///
+/// Results are always sorted from the highest to the lowest score, and the policy only orders
+/// the results that tie. Results that still tie under the policy keep the order of the items
+/// that were searched.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{fuzzy_filter_sorted, precompute_skips_for_items, SortPolicy};
+///
+/// let targets = precompute_skips_for_items(vec!["alfa x", "Zeta x", "beta x"]);
+/// let items = |policy| -> Vec<&str> { fuzzy_filter_sorted(&targets, "x", &policy).iter().map(|res| res.item).collect() };
+///
+/// assert_eq!(items(SortPolicy::Score), vec!["Zeta x", "alfa x", "beta x"]);
+/// assert_eq!(items(SortPolicy::OriginalOrder), vec!["alfa x", "Zeta x", "beta x"]);
+/// assert_eq!(items(SortPolicy::Alphabetical), vec!["alfa x", "beta x", "Zeta x"]);
+/// ```
+#[derive(Clone, Default)]
+pub enum SortPolicy {
+    /// The same order as [`fuzzy_filter`](crate::fuzzy_filter), which breaks ties by the order
+    /// of the bytes of the item strings, so upper case letters come before lower case ones.
+    #[default]
+    Score,
+    /// The order of the items that were searched, as the original fuzzbunny JS library does.
+    OriginalOrder,
+    /// The alphabetical order of the item strings ignoring case, then by their bytes.
+    Alphabetical,
+    /// The shortest item strings first, by their length in bytes.
+    ShortestFirst,
+    /// The order of a comparator, where the results that compare [`Ordering::Less`] come first.
+    Custom(ResultComparator),
+}
+
+impl SortPolicy {
+    /// Create a [`SortPolicy::Custom`] from a comparator.
+    pub fn custom(compare: impl for<'r> Fn(&FuzzyFilterResult<'r>, &FuzzyFilterResult<'r>) -> Ordering + Send + Sync + 'static) -> Self {
+        SortPolicy::Custom(Arc::new(compare))
+    }
+
+    /// Compare two results, where the result that should come first compares
+    /// [`Ordering::Less`].
+    pub fn compare(&self, a: &FuzzyFilterResult<'_>, b: &FuzzyFilterResult<'_>) -> Ordering {
+        let lower = |item| str::chars(item).flat_map(char::to_lowercase);
+        let tie_break = match self {
+            SortPolicy::Score => b.cmp(a),
+            SortPolicy::OriginalOrder => Ordering::Equal,
+            SortPolicy::Alphabetical => lower(a.item).cmp(lower(b.item)).then_with(|| a.item.cmp(b.item)),
+            SortPolicy::ShortestFirst => a.item.len().cmp(&b.item.len()),
+            SortPolicy::Custom(compare) => compare(a, b),
+        };
+        b.score.cmp(&a.score).then(tie_break).then_with(|| a.index.cmp(&b.index))
+    }
+}
+
+impl fmt::Debug for SortPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SortPolicy::Score => f.write_str("Score"),
+            SortPolicy::OriginalOrder => f.write_str("OriginalOrder"),
+            SortPolicy::Alphabetical => f.write_str("Alphabetical"),
+            SortPolicy::ShortestFirst => f.write_str("ShortestFirst"),
+            SortPolicy::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+/// Search a slice of [`Target`]s and return a filtered vector of [`FuzzyFilterResult`], sorted
+/// by score with ties ordered by `policy`.
+///
+/// This is the same as [`fuzzy_filter`](crate::fuzzy_filter) with [`SortPolicy::Score`]. As with
+/// `fuzzy_filter`, the results of an empty search aren't sorted, and are left in the order of
+/// `items`. Every result carries the [`index`](FuzzyFilterResult::index) of its target.
+pub fn fuzzy_filter_sorted<'a>(items: &[Target<'a>], search: &str, policy: &SortPolicy) -> Vec<FuzzyFilterResult<'a>> {
+    let search_lower_cased = search.trim().to_lowercase();
+    let parallel = is_parallel_workload(items.len(), |i| items[i].0.len(), search_lower_cased.len());
+    let span = instrument::FilterSpan::enter("fuzzy_filter_sorted", items.len(), search_lower_cased.len(), parallel);
+
+    let stage = instrument::Stage::start("score");
+    let mut results = collect_matches(items, 0, &search_lower_cased, parallel);
+    stage.finish(items.len(), results.len());
+
+    if !search.is_empty() {
+        let stage = instrument::Stage::start("sort");
+        sort_results_by(&mut results, parallel, |a, b| policy.compare(a, b));
+        stage.finish(results.len(), results.len());
+    }
+
+    span.finish(results.len());
+    results
+}
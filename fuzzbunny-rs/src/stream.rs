@@ -0,0 +1,128 @@
+//! Streaming of filter results to consumers that render incrementally.
+
+use std::sync::mpsc::{SendError, SyncSender};
+#[cfg(feature = "async")]
+use std::{pin::Pin, task::{Context, Poll}};
+
+#[cfg(feature = "async")]
+use futures_core::Stream;
+
+use crate::{collect_matches, FuzzyFilterResult, Target};
+#[cfg(feature = "async")]
+use crate::filter_target;
+
+/// Number of targets scored before the scored matches are handed to the consumer.
+const STREAM_BATCH_SIZE: usize = 1024;
+
+/// Stream the [`FuzzyFilterResult`]s for a search string into a bounded channel.
+///
+/// Targets are scored in small batches (in parallel if the `rayon` feature is enabled), and
+/// every match in a batch is sent before the next batch is scored. As `sender` is bounded, a
+/// consumer that falls behind (e.g. a GUI that is busy rendering) blocks the send and so pauses
+/// scoring, keeping memory usage bounded by the channel capacity plus a single batch.
+///
+/// Results are sent in the order of `items` and are NOT sorted by score.
+///
+/// # Returns
+///
+/// [`Ok`] once every target has been scored, or a [`SendError`] holding the unsent result if
+/// the receiver was dropped. Dropping the receiver is the intended way to abandon a search early.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::sync::mpsc::sync_channel;
+/// use fuzzbunny_rs::{fuzzy_filter_to_channel, precompute_skips_for_items};
+///
+/// let targets = precompute_skips_for_items(vec!["apple", "banana", "application"]);
+/// let (sender, receiver) = sync_channel(1);
+///
+/// std::thread::scope(|scope| {
+///     scope.spawn(move || fuzzy_filter_to_channel(&targets, "app", &sender));
+///
+///     let items: Vec<&str> = receiver.iter().map(|res| res.item).collect();
+///     assert_eq!(items, vec!["apple", "application"]);
+/// });
+/// ```
+pub fn fuzzy_filter_to_channel<'a>(
+    items: &[Target<'a>],
+    search: &str,
+    sender: &SyncSender<FuzzyFilterResult<'a>>,
+) -> Result<(), SendError<FuzzyFilterResult<'a>>> {
+    let search_lower_cased = search.trim().to_lowercase();
+
+    for (i, batch) in items.chunks(STREAM_BATCH_SIZE).enumerate() {
+        let results = collect_matches(batch, i * STREAM_BATCH_SIZE, &search_lower_cased, true);
+
+        // Blocks whenever the channel is full, which stops any further batches being scored
+        for result in results {
+            sender.send(result)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// An asynchronous [`Stream`] of [`FuzzyFilterResult`]s for a search string.
+///
+/// Created by [`fuzzy_filter_stream`]. Targets are only scored as the stream is polled, so
+/// dropping the stream cancels the remainder of the search.
+#[cfg(feature = "async")]
+pub struct FuzzyFilterStream<'s, 'a> {
+    items: std::slice::Iter<'s, Target<'a>>,
+    search: String,
+}
+
+#[cfg(feature = "async")]
+impl<'s, 'a> Stream for FuzzyFilterStream<'s, 'a> {
+    type Item = FuzzyFilterResult<'a>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        for _ in 0..STREAM_BATCH_SIZE {
+            let Some(target) = this.items.next() else {
+                return Poll::Ready(None)
+            };
+            if let Some(result) = filter_target(target, &this.search) {
+                return Poll::Ready(Some(result))
+            }
+        }
+
+        // Yield back to the executor after a batch without matches, so long runs of
+        // non-matching targets don't starve other tasks.
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.items.len()))
+    }
+}
+
+/// Create a [`Stream`] of the [`FuzzyFilterResult`]s for a search string.
+///
+/// Results are yielded in the order of `items` and are NOT sorted by score.
+///
+/// # Examples
+///
+/// ```rust
+/// use futures::{executor::block_on, StreamExt};
+/// use fuzzbunny_rs::{fuzzy_filter_stream, precompute_skips_for_items};
+///
+/// let targets = precompute_skips_for_items(vec!["apple", "banana", "application"]);
+///
+/// block_on(async {
+///     let mut stream = fuzzy_filter_stream(&targets, "app");
+///     while let Some(result) = stream.next().await {
+///         assert!(result.item.starts_with("app"));
+///     }
+/// });
+/// ```
+#[cfg(feature = "async")]
+pub fn fuzzy_filter_stream<'s, 'a>(items: &'s [Target<'a>], search: &str) -> FuzzyFilterStream<'s, 'a> {
+    FuzzyFilterStream {
+        items: items.iter(),
+        search: search.trim().to_lowercase(),
+    }
+}
@@ -0,0 +1,106 @@
+//! "Did you mean" suggestions for mistyped short inputs.
+
+use core::cmp::Reverse;
+
+use crate::prelude::*;
+use crate::{is_lower_case_aligned, score_prepared};
+
+/// The number of typos tolerated in a query of a given number of characters.
+///
+/// Very short queries tolerate no typos, as almost any candidate is a typo or two away.
+fn max_typos(query_len: usize) -> usize {
+    match query_len {
+        0..=2 => 0,
+        3..=5 => 1,
+        _ => 2,
+    }
+}
+
+/// Compute the edit distance from `query` to each prefix of `candidate`, counting insertions,
+/// deletions, substitutions and transpositions of adjacent characters.
+///
+/// # Returns
+///
+/// The distance to the closest prefix of `candidate` (which may be the whole candidate), and the
+/// distance to the whole candidate.
+fn prefix_distances(query: &[char], candidate: &[char]) -> (usize, usize) {
+    let width = candidate.len() + 1;
+    // `distances[i * width + j]` is the distance from the first `i` query characters to the
+    // first `j` candidate characters
+    let mut distances = vec![0; (query.len() + 1) * width];
+    for (j, distance) in distances[..width].iter_mut().enumerate() {
+        *distance = j;
+    }
+
+    for i in 1..=query.len() {
+        distances[i * width] = i;
+        for j in 1..width {
+            let cost = usize::from(query[i - 1] != candidate[j - 1]);
+            let mut distance = (distances[(i - 1) * width + j] + 1)
+                .min(distances[i * width + j - 1] + 1)
+                .min(distances[(i - 1) * width + j - 1] + cost);
+            if i > 1 && j > 1 && query[i - 1] == candidate[j - 2] && query[i - 2] == candidate[j - 1] {
+                distance = distance.min(distances[(i - 2) * width + j - 2] + 1);
+            }
+            distances[i * width + j] = distance;
+        }
+    }
+
+    let last_row = &distances[query.len() * width..];
+    (*last_row.iter().min().unwrap(), last_row[candidate.len()])
+}
+
+/// Suggest corrections for a mistyped query, e.g. for "unknown command, did you mean ...?"
+/// messages.
+///
+/// This is tuned for short, single token inputs such as command names and config keys. A
+/// candidate is suggested if it fuzzy matches the query (see [`fuzzy_match`](crate::fuzzy_match)),
+/// so that incomplete and abbreviated inputs are completed, or if the query is within a few
+/// typos of the start of the candidate, so that misspelt inputs are corrected. Queries of up to
+/// 2 characters tolerate no typos, up to 5 characters tolerate 1, and longer queries tolerate 2.
+///
+/// Matching is case insensitive.
+///
+/// # Returns
+///
+/// Up to `max` suggestions, from best to worst. Candidates are ranked by the fewest typos from
+/// the query to the start of the candidate, then by fuzzy match score, then by the fewest typos
+/// to the whole candidate, and finally alphabetically.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::suggest;
+///
+/// let commands = ["commit", "checkout", "cherry-pick", "status", "stash", "switch"];
+///
+/// assert_eq!(suggest("comit", commands, 3), vec!["commit"]);
+/// assert_eq!(suggest("stat", commands, 3), vec!["status", "stash"]);
+/// assert_eq!(suggest("c p", commands, 3), vec!["cherry-pick"]);
+/// ```
+pub fn suggest<'c>(query: &str, candidates: impl IntoIterator<Item = &'c str>, max: usize) -> Vec<&'c str> {
+    let search = query.trim().to_lowercase();
+    if search.is_empty() {
+        return vec![]
+    }
+    let query_chars: Vec<char> = search.chars().collect();
+    let max_typos = max_typos(query_chars.len());
+
+    let mut suggestions = vec![];
+    for candidate in candidates {
+        let lower = candidate.to_lowercase();
+        let candidate_chars: Vec<char> = lower.chars().collect();
+        let (prefix_distance, distance) = prefix_distances(&query_chars, &candidate_chars);
+        // The lower case form is only used for scoring if its offsets line up with the candidate
+        let aligned_lower = Some(lower.as_str()).filter(|lower| is_lower_case_aligned(candidate, lower));
+        let score = score_prepared(candidate, aligned_lower, None, &search).map(|string_score| string_score.score);
+
+        if score.is_some() || prefix_distance <= max_typos {
+            suggestions.push((prefix_distance, Reverse(score.unwrap_or(0)), distance, candidate));
+        }
+    }
+
+    suggestions.sort_unstable();
+    suggestions.dedup_by_key(|(.., candidate)| *candidate);
+    suggestions.into_iter().take(max).map(|(.., candidate)| candidate).collect()
+}
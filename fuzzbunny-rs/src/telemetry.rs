@@ -0,0 +1,150 @@
+//! Hooks receiving per-query metrics, e.g. to feed dashboards.
+
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+/// The timing of a single stage of a query, such as scoring or sorting.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageMetrics {
+    /// The name of the stage, e.g. `"score"` or `"sort"`. The `"score"` stage also drops the
+    /// targets that don't match, so its output is the number of matches.
+    pub name: &'static str,
+    /// The number of items the stage processed.
+    pub input: usize,
+    /// The number of items the stage produced.
+    pub output: usize,
+    /// How long the stage took.
+    pub elapsed: Duration,
+}
+
+/// The metrics of a single query, passed to a [`TelemetryHook`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryMetrics {
+    /// The operation that ran the query, e.g. `"fuzzy_filter"` or `"corpus_search"`.
+    pub operation: &'static str,
+    /// The length of the trimmed, lower cased query, in bytes.
+    pub query_len: usize,
+    /// The number of items searched.
+    pub corpus_size: usize,
+    /// The number of matches returned.
+    pub match_count: usize,
+    /// Whether the query was processed in parallel.
+    pub parallel: bool,
+    /// The timings of each stage of the query, in the order they finished.
+    pub stages: Vec<StageMetrics>,
+    /// How long the whole query took.
+    pub elapsed: Duration,
+}
+
+/// A callback receiving the [`QueryMetrics`] of every query, set with [`set_telemetry_hook`].
+///
+/// Hooks are called on the thread that ran the query, once it has finished, so they should be
+/// quick (e.g. recording to a metrics registry) to avoid slowing searches down. Operations built
+/// on others (e.g. a [`FederatedSearch`](crate::FederatedSearch) searching each of its corpora)
+/// report metrics for each operation.
+///
+/// Any `Fn(&QueryMetrics)` closure is a hook.
+pub trait TelemetryHook: Send + Sync {
+    /// Receive the metrics of a finished query.
+    fn on_query(&self, metrics: &QueryMetrics);
+}
+
+impl<F: Fn(&QueryMetrics) + Send + Sync> TelemetryHook for F {
+    fn on_query(&self, metrics: &QueryMetrics) {
+        self(metrics)
+    }
+}
+
+/// Whether a hook is set, checked before doing any work for telemetry.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static HOOK: RwLock<Option<Arc<dyn TelemetryHook>>> = RwLock::new(None);
+
+thread_local! {
+    /// The stages finished so far by each query running on this thread, innermost last.
+    static STAGES: RefCell<Vec<Vec<StageMetrics>>> = const { RefCell::new(vec![]) };
+}
+
+/// Set the process-wide hook that receives the metrics of every query, replacing any hook
+/// already set.
+///
+/// Without a hook, no metrics are collected, so there's no overhead to queries.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::sync::Arc;
+/// use fuzzbunny_rs::{clear_telemetry_hook, fuzzy_filter, precompute_skips_for_items, set_telemetry_hook, QueryMetrics};
+///
+/// let queries = Arc::new(AtomicUsize::new(0));
+/// let counter = queries.clone();
+/// set_telemetry_hook(move |metrics: &QueryMetrics| {
+///     if metrics.operation == "fuzzy_filter" {
+///         counter.fetch_add(1, Ordering::Relaxed);
+///     }
+/// });
+///
+/// let targets = precompute_skips_for_items(vec!["Paris", "Perth"]);
+/// fuzzy_filter(&targets, "par");
+/// clear_telemetry_hook();
+///
+/// assert_eq!(queries.load(Ordering::Relaxed), 1);
+/// ```
+pub fn set_telemetry_hook(hook: impl TelemetryHook + 'static) {
+    *HOOK.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(Arc::new(hook));
+    ENABLED.store(true, Ordering::Release);
+}
+
+/// Remove the process-wide telemetry hook, if any. See [`set_telemetry_hook`].
+pub fn clear_telemetry_hook() {
+    ENABLED.store(false, Ordering::Release);
+    *HOOK.write().unwrap_or_else(|poisoned| poisoned.into_inner()) = None;
+}
+
+/// Whether a hook is set.
+#[inline]
+pub(crate) fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Acquire)
+}
+
+/// Start collecting the stages of a query on this thread.
+///
+/// # Returns
+///
+/// The depth to pass to [`finish_query`].
+pub(crate) fn start_query() -> usize {
+    STAGES.with(|stages| {
+        let mut stages = stages.borrow_mut();
+        stages.push(vec![]);
+        stages.len() - 1
+    })
+}
+
+/// Record a finished stage of the innermost query running on this thread.
+pub(crate) fn record_stage(stage: StageMetrics) {
+    STAGES.with(|stages| {
+        if let Some(query) = stages.borrow_mut().last_mut() {
+            query.push(stage);
+        }
+    })
+}
+
+/// Finish a query started at `depth`, passing its metrics to the hook.
+///
+/// The stages of the query are filled in from those recorded since it started.
+pub(crate) fn finish_query(depth: usize, mut metrics: QueryMetrics) {
+    metrics.stages = STAGES.with(|stages| {
+        let mut stages = stages.borrow_mut();
+        // Any queries that didn't finish (e.g. due to a panic) are discarded along with this one
+        let query = stages.get_mut(depth).map(std::mem::take).unwrap_or_default();
+        stages.truncate(depth);
+        query
+    });
+
+    let hook = HOOK.read().unwrap_or_else(|poisoned| poisoned.into_inner()).clone();
+    if let Some(hook) = hook {
+        hook.on_query(&metrics);
+    }
+}
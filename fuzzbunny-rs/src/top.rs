@@ -0,0 +1,142 @@
+//! Searching for only the best few matches.
+
+use core::cmp::{Ordering, Reverse};
+use alloc::collections::BinaryHeap;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::prelude::*;
+use crate::{fuzzy_score_item, instrument, should_parallelise, FuzzyFilterResult, Range, Target};
+
+/// A match that may be among the best `k`, with its highlights computed once it's known to be.
+struct Candidate<'a> {
+    item: &'a str,
+    score: u32,
+    ranges: Vec<Range>,
+}
+
+impl PartialEq for Candidate<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+impl Eq for Candidate<'_> {}
+
+impl PartialOrd for Candidate<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+impl Ord for Candidate<'_> {
+    /// The same order as [`FuzzyFilterResult`].
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score.cmp(&other.score).then_with(|| other.item.cmp(self.item))
+    }
+}
+
+/// Score a target against a lower case search, as a [`Candidate`] if it matches.
+fn score_candidate<'a>(target: &Target<'a>, search: &str) -> Option<Candidate<'a>> {
+    let string_score = fuzzy_score_item(target, search)?;
+    Some(Candidate { item: target.0, score: string_score.score, ranges: string_score.ranges })
+}
+
+/// A min-heap of the best `k` candidates so far, so the worst of them can be replaced.
+type BestK<'a> = BinaryHeap<Reverse<Candidate<'a>>>;
+
+/// Add a candidate to the best `k` if it ranks above the worst of them.
+fn push_bounded<'a>(best: &mut BestK<'a>, k: usize, candidate: Candidate<'a>) {
+    if best.len() == k {
+        match best.peek() {
+            Some(Reverse(worst)) if candidate > *worst => {
+                best.pop();
+            },
+            _ => return,
+        }
+    }
+    best.push(Reverse(candidate));
+}
+
+/// Score every target against `search`, keeping the best `k` matches.
+///
+/// If `parallel` is set and the `rayon` feature is enabled, each worker keeps its own best `k`,
+/// which are then merged.
+#[cfg_attr(not(feature = "rayon"), allow(unused_variables))]
+fn best_k<'a>(items: &[Target<'a>], search: &str, k: usize, parallel: bool) -> BestK<'a> {
+    let candidate = |target: &Target<'a>| score_candidate(target, search);
+
+    #[cfg(feature = "rayon")]
+    if parallel {
+        return items
+            .par_iter()
+            .filter_map(candidate)
+            .fold(BestK::new, |mut best, candidate| {
+                push_bounded(&mut best, k, candidate);
+                best
+            })
+            .reduce(BestK::new, |mut best, other| {
+                for Reverse(candidate) in other {
+                    push_bounded(&mut best, k, candidate);
+                }
+                best
+            })
+    }
+
+    let mut best = BestK::with_capacity(k.saturating_add(1).min(items.len()));
+    for candidate in items.iter().filter_map(candidate) {
+        push_bounded(&mut best, k, candidate);
+    }
+    best
+}
+
+/// Search a slice of [`Target`]s and return the best `k` matches, as a sorted vector of
+/// [`FuzzyFilterResult`].
+///
+/// This returns the same results as truncating those of [`fuzzy_filter`](crate::fuzzy_filter)
+/// to `k`, but only the best `k` matches are kept while scoring (in a bounded heap, per worker
+/// when scoring in parallel), so memory use and sorting cost scale with `k` rather than with
+/// the number of matches, e.g. for autocomplete over a large corpus showing the top 20 results.
+/// Highlights are only computed for the results returned.
+///
+/// As with `fuzzy_filter`, the results of an empty search aren't sorted, so the first `k`
+/// non-empty targets are returned.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{fuzzy_filter_top_k, precompute_skips_for_items};
+///
+/// let targets = precompute_skips_for_items(vec!["pineapple", "apple", "banana", "application"]);
+///
+/// let results = fuzzy_filter_top_k(&targets, "app", 2);
+/// let items: Vec<&str> = results.iter().map(|res| res.item).collect();
+/// assert_eq!(items, vec!["apple", "application"]);
+/// ```
+pub fn fuzzy_filter_top_k<'a>(items: &[Target<'a>], search: &str, k: usize) -> Vec<FuzzyFilterResult<'a>> {
+    let search_lower_cased = search.trim().to_lowercase();
+    let parallel = should_parallelise(items, &search_lower_cased);
+    let span = instrument::FilterSpan::enter("fuzzy_filter_top_k", items.len(), search_lower_cased.len(), parallel);
+
+    let candidates: Vec<Candidate<'a>> = if k == 0 {
+        vec![]
+    } else if search.is_empty() {
+        // Every target matches with a score of 0, and the results aren't sorted
+        items.iter().filter_map(|target| score_candidate(target, "")).take(k).collect()
+    } else {
+        let stage = instrument::Stage::start("score");
+        let best = best_k(items, &search_lower_cased, k, parallel);
+        stage.finish(items.len(), best.len());
+
+        let stage = instrument::Stage::start("sort");
+        // Sorting the min-heap in ascending order of `Reverse` puts the best match first
+        let candidates: Vec<_> = best.into_sorted_vec().into_iter().map(|Reverse(candidate)| candidate).collect();
+        stage.finish(candidates.len(), candidates.len());
+        candidates
+    };
+
+    let results: Vec<_> = candidates
+        .into_iter()
+        .map(|candidate| FuzzyFilterResult::matched(candidate.item, candidate.score, candidate.ranges))
+        .collect();
+
+    span.finish(results.len());
+    results
+}
@@ -0,0 +1,131 @@
+//! Step-by-step traces of the matcher's decisions.
+
+use crate::prelude::*;
+use crate::{score_recorded, ScoringConfig};
+
+/// Receives each decision of the matcher as a target is scored.
+///
+/// Every method does nothing by default, so scoring with the `()` recorder compiles down to the
+/// plain matcher.
+pub(crate) trait Recorder {
+    /// The search was looked up as a substring of the target, found at `index` if it matched.
+    fn substring(&mut self, _index: Option<usize>) {}
+
+    /// A fuzzy alignment starting at the word at `skip` (at byte `start` of the target) was tried.
+    fn alignment(&mut self, _skip: usize, _start: usize, _matched: usize, _broke_at: Option<usize>) {}
+}
+
+impl Recorder for () {}
+
+/// Which path of the matcher matched a target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize), serde(rename_all = "lowercase"))]
+pub enum MatchPath {
+    /// The search is a substring of the target.
+    Substring,
+    /// The search matched the prefixes of words in the target.
+    Fuzzy,
+}
+
+/// A fuzzy alignment tried by the matcher, matching the search against the prefixes of the
+/// words of the target starting from a given word.
+///
+/// Offsets are in bytes of the lower case form of the target.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Alignment {
+    /// The index of the skip index (i.e. word boundary) the alignment started from.
+    pub skip: usize,
+    /// The offset the alignment started from.
+    pub start: usize,
+    /// The number of bytes of the search that were matched, which is the whole search if the
+    /// alignment matched.
+    pub matched: usize,
+    /// The offset where the last partially matched word stopped matching the search, if any
+    /// word did.
+    pub broke_at: Option<usize>,
+}
+
+/// A record of the matcher's decisions while scoring a target, from [`trace_match`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchTrace {
+    /// The traced target.
+    pub target: String,
+    /// The search, trimmed and lower cased as it was matched.
+    pub search: String,
+    /// Whether the search was quoted, which only allows a substring match.
+    pub quoted: bool,
+    /// The offset where the search was found as a substring of the target, if it was.
+    ///
+    /// This is also [`None`] if the matcher stopped before the substring search, which it does
+    /// for an empty target or search.
+    pub substring: Option<usize>,
+    /// The fuzzy alignments that were tried, in order. The matcher only tries the words that
+    /// start with the first byte of the search, and stops at the first alignment that matches.
+    pub alignments: Vec<Alignment>,
+    /// The path that matched the target, or [`None`] if it didn't match.
+    pub path: Option<MatchPath>,
+    /// The score of the match, or [`None`] if the target didn't match.
+    pub score: Option<u32>,
+}
+
+impl MatchTrace {
+    /// The alignment that matched the target, if it matched by the fuzzy path.
+    pub fn matched_alignment(&self) -> Option<&Alignment> {
+        self.alignments.last().filter(|alignment| alignment.matched == self.search.len())
+    }
+}
+
+impl Recorder for MatchTrace {
+    fn substring(&mut self, index: Option<usize>) {
+        self.substring = index;
+    }
+
+    fn alignment(&mut self, skip: usize, start: usize, matched: usize, broke_at: Option<usize>) {
+        self.alignments.push(Alignment { skip, start, matched, broke_at });
+    }
+}
+
+/// Score a target against a search, recording each decision of the matcher.
+///
+/// This is a debugging aid for diagnosing surprising rankings, and for recording regression
+/// fixtures of how targets are matched. It scores the target exactly as
+/// [`fuzzy_match`](crate::fuzzy_match) does, but is slower, so shouldn't be used for filtering.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{trace_match, MatchPath};
+///
+/// let trace = trace_match("United States of America", "usam");
+/// assert_eq!(trace.substring, None);
+/// assert_eq!(trace.path, Some(MatchPath::Fuzzy));
+/// assert_eq!(trace.matched_alignment().unwrap().start, 0);
+///
+/// let trace = trace_match("Las Vegas", "\"vega\"");
+/// assert!(trace.quoted);
+/// assert_eq!(trace.substring, Some(4));
+/// assert_eq!(trace.path, Some(MatchPath::Substring));
+/// ```
+pub fn trace_match(target: &str, search: &str) -> MatchTrace {
+    let search = search.trim().to_lowercase();
+    let mut trace = MatchTrace {
+        target: target.to_string(),
+        quoted: search.starts_with('"'),
+        search,
+        substring: None,
+        alignments: vec![],
+        path: None,
+        score: None,
+    };
+
+    let search = trace.search.clone();
+    if let Some(string_score) = score_recorded(target, None, None, &search, &ScoringConfig::DEFAULT, &mut trace) {
+        trace.score = Some(string_score.score);
+        // An empty search matches as an empty substring, without being looked up
+        let is_substring = trace.substring.is_some() || search.is_empty();
+        trace.path = Some(if is_substring { MatchPath::Substring } else { MatchPath::Fuzzy });
+    }
+    trace
+}
@@ -0,0 +1,294 @@
+//! Truncation of highlighted results to a display width or to the context around matches.
+
+use alloc::borrow::Cow;
+
+use crate::prelude::*;
+use crate::{highlights_from_ranges, merge_ranges, Range};
+
+/// The marker shown where text was cut off.
+const ELLIPSIS: &str = "…";
+
+/// The number of terminal cells a character takes up when displayed.
+///
+/// This approximates the Unicode East Asian Width rules: wide and fullwidth characters (e.g.
+/// CJK ideographs, Hangul, fullwidth forms and most emoji) take up two cells, combining marks,
+/// zero width characters and control characters take up none, and everything else takes up one.
+pub fn char_width(char: char) -> usize {
+    match char {
+        '\u{0}'..='\u{1f}'
+        | '\u{7f}'..='\u{9f}'
+        | '\u{300}'..='\u{36f}'
+        | '\u{483}'..='\u{489}'
+        | '\u{591}'..='\u{5bd}'
+        | '\u{1ab0}'..='\u{1aff}'
+        | '\u{1dc0}'..='\u{1dff}'
+        | '\u{200b}'..='\u{200f}'
+        | '\u{2028}'..='\u{202e}'
+        | '\u{2060}'..='\u{2064}'
+        | '\u{20d0}'..='\u{20ff}'
+        | '\u{fe00}'..='\u{fe0f}'
+        | '\u{fe20}'..='\u{fe2f}'
+        | '\u{feff}'
+        | '\u{e0100}'..='\u{e01ef}' => 0,
+        '\u{1100}'..='\u{115f}'
+        | '\u{231a}'..='\u{231b}'
+        | '\u{2e80}'..='\u{303e}'
+        | '\u{3041}'..='\u{33ff}'
+        | '\u{3400}'..='\u{4dbf}'
+        | '\u{4e00}'..='\u{9fff}'
+        | '\u{a000}'..='\u{a4cf}'
+        | '\u{a960}'..='\u{a97f}'
+        | '\u{ac00}'..='\u{d7a3}'
+        | '\u{f900}'..='\u{faff}'
+        | '\u{fe30}'..='\u{fe4f}'
+        | '\u{ff00}'..='\u{ff60}'
+        | '\u{ffe0}'..='\u{ffe6}'
+        | '\u{1f300}'..='\u{1f64f}'
+        | '\u{1f900}'..='\u{1f9ff}'
+        | '\u{20000}'..='\u{2fffd}'
+        | '\u{30000}'..='\u{3fffd}' => 2,
+        _ => 1,
+    }
+}
+
+/// The number of terminal cells a string takes up when displayed. See [`char_width`].
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::display_width;
+///
+/// assert_eq!(display_width("Tokyo"), 5);
+/// assert_eq!(display_width("東京"), 4);
+/// assert_eq!(display_width("Cre\u{300}me"), 5);
+/// ```
+pub fn display_width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+/// Truncate the [`Highlights`](crate::Highlights) of a result to fit in `width` terminal cells,
+/// keeping the matched sections visible, so rows of a terminal UI don't wrap or cut off a match.
+///
+/// Text is cut from either end as needed, and replaced with an ellipsis ("…", one cell wide).
+/// As much text as fits is kept around the matched sections, with the text before them kept
+/// in preference to the text after. If the matched sections don't fit together, the text from
+/// the start of the first matched section is kept.
+///
+/// # Returns
+///
+/// Highlights in the same form as the input (alternating unmatched and matched sections,
+/// starting with an unmatched one), with the ellipses added to the unmatched sections at either
+/// end. Sections are only copied if an ellipsis is added to them. Highlights that already fit
+/// are returned unchanged, a `width` of 0 returns no sections, and if nothing else fits, only an
+/// ellipsis is returned.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{fuzzy_filter, precompute_skips_for_items, truncate_highlights};
+///
+/// let targets = precompute_skips_for_items(vec!["src/components/navigation/sidebar/SidebarItem.tsx"]);
+/// let results = fuzzy_filter(&targets, "item");
+///
+/// let truncated = truncate_highlights(results[0].highlights.as_ref().unwrap(), 20);
+/// assert_eq!(truncated, vec!["…bar/Sidebar", "Item", ".tsx"]);
+/// ```
+pub fn truncate_highlights<'a>(highlights: &[&'a str], width: usize) -> Vec<Cow<'a, str>> {
+    // Every character, with its width and the index of its section
+    let chars: Vec<(usize, usize)> = highlights
+        .iter()
+        .enumerate()
+        .flat_map(|(section, text)| text.chars().map(move |char| (char_width(char), section)))
+        .collect();
+    let total: usize = chars.iter().map(|&(char_width, _)| char_width).sum();
+    if total <= width {
+        return highlights.iter().map(|&section| Cow::Borrowed(section)).collect()
+    }
+    if width == 0 {
+        return vec![]
+    }
+
+    // The width of the characters in lo..hi, along with the ellipses needed around them
+    let cost = |lo: usize, hi: usize| {
+        chars[lo..hi].iter().map(|&(char_width, _)| char_width).sum::<usize>() + usize::from(lo > 0) + usize::from(hi < chars.len())
+    };
+
+    // The characters from the start of the first matched section to the end of the last
+    let first = chars.iter().position(|&(_, section)| section % 2 == 1).unwrap_or(0);
+    let last = chars.iter().rposition(|&(_, section)| section % 2 == 1).map_or(0, |last| last + 1);
+
+    let (mut lo, mut hi) = (first, last);
+    if cost(lo, hi) > width {
+        hi = lo;
+        while hi < chars.len() && cost(lo, hi + 1) <= width {
+            hi += 1;
+        }
+    } else {
+        // Grow the window one character at a time on each side, while it still fits
+        let (mut grow_left, mut grow_right) = (true, true);
+        while grow_left || grow_right {
+            grow_left = grow_left && lo > 0 && cost(lo - 1, hi) <= width;
+            if grow_left {
+                lo -= 1;
+            }
+            grow_right = grow_right && hi < chars.len() && cost(lo, hi + 1) <= width;
+            if grow_right {
+                hi += 1;
+            }
+        }
+    }
+
+    if lo == hi {
+        // Not even a single character fits alongside the ellipses
+        return vec![Cow::Borrowed(ELLIPSIS)]
+    }
+
+    // Cut each section down to the window, dropping the sections outside of it
+    let mut truncated: Vec<Cow<'a, str>> = vec![];
+    let mut index = 0;
+    for (section, text) in highlights.iter().enumerate() {
+        let count = text.chars().count();
+        let (start, end) = (lo.max(index).min(index + count) - index, hi.max(index).min(index + count) - index);
+        let outside = start == end && (count > 0 || index < lo || index > hi);
+        index += count;
+        if outside {
+            continue
+        }
+        // The window always starts with an unmatched section
+        if truncated.is_empty() && section % 2 == 1 {
+            truncated.push(Cow::Borrowed(""));
+        }
+        let byte_start = text.char_indices().nth(start).map_or(text.len(), |(offset, _)| offset);
+        let byte_end = text.char_indices().nth(end).map_or(text.len(), |(offset, _)| offset);
+        truncated.push(Cow::Borrowed(&text[byte_start..byte_end]));
+    }
+
+    if lo > 0 {
+        truncated[0] = Cow::Owned(format!("{ELLIPSIS}{}", truncated[0]));
+    }
+    if hi < chars.len() {
+        if truncated.len().is_multiple_of(2) {
+            // The last section is matched, so the ellipsis gets an unmatched section of its own
+            truncated.push(Cow::Borrowed(ELLIPSIS));
+        } else {
+            let last = truncated.len() - 1;
+            truncated[last] = Cow::Owned(format!("{}{ELLIPSIS}", truncated[last]));
+        }
+    }
+    truncated
+}
+
+/// Build the [`Highlights`](crate::Highlights) of a long target, e.g. a log line, showing only
+/// the text around its matched `ranges`, so previews of long documents can be rendered from them
+/// directly.
+///
+/// Each matched range is kept along with up to `context_chars` characters on either side of it.
+/// The text between these windows is cut and replaced with an ellipsis ("…"), as is the text
+/// before the first window and after the last. At most `max_len` characters are returned,
+/// including the ellipses, so if the windows don't all fit, the later ones are cut off. Unlike
+/// [`truncate_highlights`], lengths are counted in characters rather than terminal cells.
+///
+/// The `ranges` are byte ranges of `target`, as in a [`StringScore`](crate::StringScore), and
+/// may overlap or be unsorted, e.g. when combined from several searches.
+///
+/// # Returns
+///
+/// Highlights in the usual form (alternating unmatched and matched sections, starting with an
+/// unmatched one), with the ellipses added to the unmatched sections. Targets that already fit
+/// are returned whole, a `max_len` of 0 returns no sections, and if nothing else fits, only an
+/// ellipsis is returned. A target without matches shows its start.
+///
+/// # Examples
+///
+/// ```rust
+/// use fuzzbunny_rs::{fuzzy_match, highlights_with_context};
+///
+/// let line = "2024-05-01 12:00:00 INFO worker-3 connection to upstream db-primary timed out after 30s";
+/// let result = fuzzy_match(line, Some("timed out")).unwrap();
+///
+/// let preview = highlights_with_context(line, result.ranges.as_deref().unwrap(), 40, 8);
+/// assert_eq!(preview, vec!["…primary ", "timed out", " after 3…"]);
+/// ```
+pub fn highlights_with_context<'a>(target: &'a str, ranges: &[Range], max_len: usize, context_chars: usize) -> Vec<Cow<'a, str>> {
+    let ranges: Vec<Range> = merge_ranges(ranges.iter().filter_map(|range| range.clip(0..target.len())));
+
+    // The byte offset of every character, and of the end of the target
+    let offsets: Vec<usize> = target.char_indices().map(|(offset, _)| offset).chain([target.len()]).collect();
+    let len = offsets.len() - 1;
+    if len <= max_len {
+        return highlights_from_ranges(target, ranges).into_iter().map(Cow::Borrowed).collect()
+    }
+    if max_len == 0 {
+        return vec![]
+    }
+
+    // The matched ranges and the windows of context around them, as character ranges
+    let char_index = |offset: usize| offsets.partition_point(|&start| start < offset);
+    let matched: Vec<Range> = ranges.iter().map(|range| Range::from(char_index(range.0)..char_index(range.end()))).collect();
+    let windows = if matched.is_empty() {
+        vec![Range(0, len)]
+    } else {
+        merge_ranges(matched.iter().map(|range| Range::from(range.0.saturating_sub(context_chars)..(range.end() + context_chars).min(len))))
+    };
+
+    // Keep as much of each window as fits, leaving room for the ellipsis after it
+    let mut kept: Vec<Range> = vec![];
+    let (mut remaining, mut last_end) = (max_len, 0);
+    for window in windows {
+        let ellipsis = usize::from(window.0 > last_end);
+        let available = remaining.saturating_sub(ellipsis);
+        let mut take = window.1.min(available);
+        if window.0 + take < len {
+            take = take.min(available.saturating_sub(1));
+        }
+        if take == 0 {
+            break
+        }
+        kept.push(Range(window.0, take));
+        remaining -= ellipsis + take;
+        last_end = window.0 + take;
+        if take < window.1 {
+            break
+        }
+    }
+    if kept.is_empty() {
+        return vec![Cow::Borrowed(ELLIPSIS)]
+    }
+
+    // Split the kept windows into sections, joining the ellipses onto the unmatched ones
+    let mut sections = vec![Cow::Borrowed("")];
+    let mut push = |text: &'a str, is_match: bool| {
+        let last = sections.len() - 1;
+        if sections.len().is_multiple_of(2) != is_match {
+            sections.push(Cow::Borrowed(text));
+        } else if sections[last].is_empty() {
+            sections[last] = Cow::Borrowed(text);
+        } else {
+            sections[last].to_mut().push_str(text);
+        }
+    };
+    let slice = |start: usize, end: usize| &target[offsets[start]..offsets[end]];
+
+    let mut last_end = 0;
+    for window in &kept {
+        if window.0 > last_end {
+            push(ELLIPSIS, false);
+        }
+        let mut cursor = window.0;
+        for range in matched.iter().filter_map(|range| range.clip(window.0..window.end())) {
+            if cursor < range.0 {
+                push(slice(cursor, range.0), false);
+            }
+            push(slice(range.0, range.end()), true);
+            cursor = range.end();
+        }
+        if cursor < window.end() {
+            push(slice(cursor, window.end()), false);
+        }
+        last_end = window.end();
+    }
+    if last_end < len {
+        push(ELLIPSIS, false);
+    }
+    sections
+}
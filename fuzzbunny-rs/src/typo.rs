@@ -0,0 +1,100 @@
+//! Matching that tolerates a bounded number of typos.
+
+use crate::prelude::*;
+use crate::{Range, ScoringConfig, StringScore};
+
+/// The number of typos tolerated in a search of `search_len` characters, given the most
+/// allowed.
+///
+/// Fewer than half the characters of a search may be typos, otherwise short searches would
+/// match almost anything.
+fn typo_limit(search_len: usize, max_typos: usize) -> usize {
+    max_typos.min(search_len.saturating_sub(1) / 2)
+}
+
+/// Match a search against the substrings of a lower case target, tolerating up to `max_typos`
+/// insertions, deletions, substitutions and transpositions of adjacent characters.
+///
+/// The substring with the fewest typos is matched, taking the earliest of equally close
+/// substrings. The ranges cover the characters of the target that match characters of the
+/// search (including transposed characters), and each typo is penalised by
+/// [`ScoringConfig::typo_penalty`].
+///
+/// Note that `search` string MUST be lower case.
+pub(crate) fn score_typos(target_lower: &str, search: &str, max_typos: usize, config: &ScoringConfig) -> Option<StringScore> {
+    let search: Vec<char> = search.chars().collect();
+    let max_typos = typo_limit(search.len(), max_typos);
+    if max_typos == 0 || target_lower.is_empty() {
+        return None
+    }
+    let (offsets, target): (Vec<usize>, Vec<char>) = target_lower.char_indices().unzip();
+
+    let width = target.len() + 1;
+    // `distances[i * width + j]` is the fewest typos from the first `i` search characters to a
+    // substring of the target ending at character `j`, so the first row is all zeros
+    let mut distances = vec![0; (search.len() + 1) * width];
+    for i in 1..=search.len() {
+        distances[i * width] = i;
+        for j in 1..width {
+            let cost = usize::from(search[i - 1] != target[j - 1]);
+            let mut distance = (distances[(i - 1) * width + j] + 1)
+                .min(distances[i * width + j - 1] + 1)
+                .min(distances[(i - 1) * width + j - 1] + cost);
+            if i > 1 && j > 1 && search[i - 1] == target[j - 2] && search[i - 2] == target[j - 1] {
+                distance = distance.min(distances[(i - 2) * width + j - 2] + 1);
+            }
+            distances[i * width + j] = distance;
+        }
+    }
+
+    let last_row = &distances[search.len() * width..];
+    let (end, &typos) = last_row.iter().enumerate().min_by_key(|&(_, &distance)| distance)?;
+    if typos > max_typos {
+        return None
+    }
+
+    // Trace the alignment back from its end, preferring matched characters over substitutions
+    let mut matched = vec![];
+    let (mut i, mut j) = (search.len(), end);
+    let distance = |i: usize, j: usize| distances[i * width + j];
+    while i > 0 {
+        if j > 0 && search[i - 1] == target[j - 1] && distance(i, j) == distance(i - 1, j - 1) {
+            matched.push(j - 1);
+            (i, j) = (i - 1, j - 1);
+        } else if i > 1 && j > 1
+            && search[i - 1] == target[j - 2]
+            && search[i - 2] == target[j - 1]
+            && distance(i, j) == distance(i - 2, j - 2) + 1
+        {
+            matched.extend([j - 1, j - 2]);
+            (i, j) = (i - 2, j - 2);
+        } else if j > 0 && distance(i, j) == distance(i, j - 1) + 1 {
+            // An extra character in the target
+            j -= 1;
+        } else if distance(i, j) == distance(i - 1, j) + 1 {
+            // A missing character in the target
+            i -= 1;
+        } else {
+            // A substituted character
+            (i, j) = (i - 1, j - 1);
+        }
+    }
+    matched.reverse();
+
+    let mut ranges: Vec<Range> = vec![];
+    for char_idx in matched {
+        let start = offsets[char_idx];
+        let len = target[char_idx].len_utf8();
+        match ranges.last_mut() {
+            Some(range) if range.end() == start => range.1 += len,
+            _ => ranges.push(Range(start, len)),
+        }
+    }
+
+    let is_prefix = |range: &Range| {
+        target_lower[..range.0].chars().next_back().is_some_and(|char| !char.is_alphanumeric())
+    };
+    let score = config.score(&ranges, is_prefix, target_lower);
+    let penalty = config.typo_penalty.saturating_mul(typos as u32);
+    Some(StringScore { score: score.saturating_sub(penalty), ranges })
+}
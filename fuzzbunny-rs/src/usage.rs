@@ -0,0 +1,110 @@
+//! Usage-frequency tracking for boosting frequently selected corpus items.
+
+use std::collections::HashMap;
+
+use crate::ItemId;
+
+/// Configuration for how selections of an item boost its score.
+///
+/// Each selection adds `score_per_selection` to the boost of an item, which then decays by
+/// half for every `half_life` selections of any item in the corpus. The boost is capped at
+/// `max_boost`, so a heavily used item can't outrank a much better match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UsageBoost {
+    /// The score added to an item by a single, recent selection.
+    pub score_per_selection: u32,
+    /// The number of selections (of any item) after which a selection's boost has halved.
+    pub half_life: u32,
+    /// The maximum boost that can be applied to an item.
+    pub max_boost: u32,
+}
+
+impl Default for UsageBoost {
+    fn default() -> Self {
+        UsageBoost { score_per_selection: 100, half_life: 50, max_boost: 1000 }
+    }
+}
+
+/// A recent selection of an item, blended into the item's score at query time by
+/// [`Corpus::search_with_recency`](crate::Corpus::search_with_recency).
+///
+/// The weight is the number of selections it counts as, which the caller decays as it sees fit
+/// (e.g. 1.0 for a selection just now, and 0.5 for one an hour ago).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RecentSelection {
+    /// The ID of the selected item.
+    pub id: ItemId,
+    /// The number of selections this counts as. Negative weights are ignored.
+    pub weight: f32,
+}
+
+/// The decayed selection count of a single item.
+#[derive(Debug, Clone, Copy)]
+struct Usage {
+    /// The selection count, as of `updated_at`.
+    count: f32,
+    /// The selection clock when `count` was last updated.
+    updated_at: u64,
+}
+
+/// Tracks the decaying selection counts of items.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct UsageTracker {
+    pub(crate) config: UsageBoost,
+    /// The total number of selections recorded.
+    clock: u64,
+    usage: HashMap<ItemId, Usage>,
+}
+
+impl UsageTracker {
+    /// The factor a count decays by after `elapsed` selections.
+    fn decay(&self, elapsed: u64) -> f32 {
+        if self.config.half_life == 0 {
+            return if elapsed == 0 { 1.0 } else { 0.0 }
+        }
+        0.5_f32.powf(elapsed as f32 / self.config.half_life as f32)
+    }
+
+    /// Record a selection of an item.
+    pub(crate) fn record(&mut self, id: ItemId) {
+        self.clock += 1;
+        let decayed = self.decayed_count(id);
+        self.usage.insert(id, Usage { count: decayed + 1.0, updated_at: self.clock });
+    }
+
+    /// Set the selection count of an item as of the current time, e.g. when importing a corpus.
+    #[cfg(feature = "serde")]
+    pub(crate) fn restore(&mut self, id: ItemId, count: f32) {
+        if count > 0.0 {
+            self.usage.insert(id, Usage { count, updated_at: self.clock });
+        }
+    }
+
+    /// Whether any selections of an item have been recorded.
+    pub(crate) fn tracks(&self, id: ItemId) -> bool {
+        self.usage.contains_key(&id)
+    }
+
+    /// Forget the selections of an item.
+    pub(crate) fn forget(&mut self, id: ItemId) {
+        self.usage.remove(&id);
+    }
+
+    /// The selection count of an item, decayed to the current time.
+    pub(crate) fn decayed_count(&self, id: ItemId) -> f32 {
+        self.usage
+            .get(&id)
+            .map_or(0.0, |usage| usage.count * self.decay(self.clock - usage.updated_at))
+    }
+
+    /// The score boost for an item, with `recent` more selections than have been recorded.
+    #[inline]
+    pub(crate) fn boost(&self, id: ItemId, recent: f32) -> u32 {
+        if self.usage.is_empty() && recent == 0.0 {
+            return 0
+        }
+        let boost = (self.decayed_count(id) + recent) * self.config.score_per_selection as f32;
+        (boost.round() as u32).min(self.config.max_boost)
+    }
+}
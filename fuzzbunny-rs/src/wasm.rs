@@ -0,0 +1,63 @@
+//! WebAssembly bindings with the same API as the original fuzzbunny JS library.
+//!
+//! The bindings are built by the `fuzzbunny-ffi` crate in `ffi/` for `wasm32-unknown-unknown` with
+//! its `wasm` feature, e.g. with
+//! `cargo build --release -p fuzzbunny-ffi --target wasm32-unknown-unknown --features wasm`,
+//! and then processed with `wasm-bindgen`.
+
+use js_sys::{Array, Object, Reflect};
+use wasm_bindgen::prelude::*;
+
+use crate::{fuzzy_filter, fuzzy_match, FuzzyFilterResult, Target};
+
+/// Convert a result into a JS object of the form `{item, score, highlights}`, where
+/// `highlights` is an array of strings alternating between unmatched and matched substrings.
+fn result_to_js(result: &FuzzyFilterResult<'_>) -> JsValue {
+    let highlights: Array = result.highlights.iter().flatten().map(|&highlight| JsValue::from_str(highlight)).collect();
+
+    let object = Object::new();
+    // Setting a property only fails if the object is frozen, which a new object never is
+    let _ = Reflect::set(&object, &"item".into(), &result.item.into());
+    let _ = Reflect::set(&object, &"score".into(), &result.score.into());
+    let _ = Reflect::set(&object, &"highlights".into(), &highlights);
+    object.into()
+}
+
+/// Search an array of strings, and return the matches sorted from best to worst.
+///
+/// This is the WebAssembly form of [`fuzzy_filter`], exported to JS as `fuzzyFilter`. Each
+/// match is returned as an object of the form `{item, score, highlights}`, where `highlights`
+/// alternates between unmatched and matched substrings of `item`, starting with an unmatched
+/// (possibly empty) substring. As with the JS library, an empty search matches every non-empty
+/// item, in their original order.
+///
+/// This is only available with the `wasm` feature. The `rayon` feature should be disabled when
+/// building for `wasm32-unknown-unknown`, as threads aren't available in the browser without web
+/// workers, in which case items are matched on the calling thread.
+///
+/// ```js
+/// import { fuzzyFilter } from "fuzzbunny-rs";
+///
+/// const results = fuzzyFilter(["apple", "banana", "application"], "app");
+/// // [{item: "apple", score: ..., highlights: ["", "app", "le"]}, ...]
+/// ```
+#[wasm_bindgen(js_name = fuzzyFilter)]
+pub fn fuzzy_filter_js(items: Vec<String>, search: &str) -> Array {
+    let targets: Vec<Target<'_>> = items.iter().map(|item| (item.as_str(), None)).collect();
+    fuzzy_filter(&targets, search).iter().map(result_to_js).collect()
+}
+
+/// Fuzzy match a single string, exported to JS as `fuzzyMatch`.
+///
+/// This is the WebAssembly form of [`fuzzy_match`].
+///
+/// This is only available with the `wasm` feature.
+///
+/// # Returns
+///
+/// An object of the form `{item, score, highlights}` (see [`fuzzy_filter_js`]) if `search`
+/// matches `item`, or `null` otherwise.
+#[wasm_bindgen(js_name = fuzzyMatch)]
+pub fn fuzzy_match_js(item: &str, search: &str) -> JsValue {
+    fuzzy_match(item, Some(search)).map_or(JsValue::NULL, |result| result_to_js(&result))
+}
@@ -0,0 +1,202 @@
+//! Corpora that track a source file and reindex it as it changes.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::{Corpus, ItemId, SharedCorpus};
+
+/// The changes applied to a [`WatchedCorpus`] by a single reindex.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CorpusChange {
+    /// The items created for newly added lines.
+    pub inserted: Vec<ItemId>,
+    /// The items whose line text changed.
+    pub updated: Vec<ItemId>,
+    /// The items whose lines were removed.
+    pub removed: Vec<ItemId>,
+    /// The version of the corpus after the changes were applied.
+    pub version: u64,
+}
+
+impl CorpusChange {
+    /// Whether the reindex didn't change any items.
+    pub fn is_empty(&self) -> bool {
+        self.inserted.is_empty() && self.updated.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// The state shared between a [`WatchedCorpus`] and its file watcher.
+#[derive(Debug)]
+struct WatchState {
+    path: PathBuf,
+    corpus: Arc<SharedCorpus>,
+    /// The ID of the item for each non-empty line of the file, in order.
+    line_ids: Mutex<Vec<ItemId>>,
+    events: Sender<CorpusChange>,
+}
+
+impl WatchState {
+    /// Re-read the source file, and apply the changed lines to the corpus.
+    fn reindex(&self) -> io::Result<CorpusChange> {
+        let lines = read_lines(&self.path)?;
+        let mut line_ids = self.line_ids.lock().unwrap_or_else(|err| err.into_inner());
+        let mut change = CorpusChange::default();
+
+        change.version = self.corpus.write(|corpus| {
+            // Match lines by their text, so inserting or removing a line doesn't shift the IDs after it
+            let mut existing: HashMap<String, VecDeque<ItemId>> = HashMap::new();
+            for &id in line_ids.iter() {
+                if let Some(text) = corpus.get(id) {
+                    existing.entry(text.to_string()).or_default().push_back(id);
+                }
+            }
+
+            let mut matched: Vec<Option<ItemId>> =
+                lines.iter().map(|line| existing.get_mut(line).and_then(VecDeque::pop_front)).collect();
+
+            // The IDs whose text no longer exists are reused in order for the new lines, as edits
+            let kept: HashSet<ItemId> = matched.iter().flatten().copied().collect();
+            let mut unmatched = line_ids.iter().copied().filter(|id| !kept.contains(id)).collect::<Vec<_>>().into_iter();
+
+            for (line, id) in lines.into_iter().zip(matched.iter_mut()) {
+                if id.is_some() {
+                    continue
+                }
+                *id = Some(match unmatched.next() {
+                    Some(old_id) => {
+                        corpus.update(old_id, line);
+                        change.updated.push(old_id);
+                        old_id
+                    },
+                    None => {
+                        let new_id = corpus.insert(line);
+                        change.inserted.push(new_id);
+                        new_id
+                    },
+                });
+            }
+
+            for id in unmatched {
+                corpus.remove(id);
+                change.removed.push(id);
+            }
+
+            *line_ids = matched.into_iter().flatten().collect();
+            corpus.version()
+        });
+
+        Ok(change)
+    }
+}
+
+/// Read the trimmed, non-empty lines of a file.
+fn read_lines(path: &Path) -> io::Result<Vec<String>> {
+    let reader = BufReader::new(File::open(path)?);
+    let mut lines = vec![];
+    for line in reader.lines() {
+        let line = line?;
+        let text = line.trim();
+        if !text.is_empty() {
+            lines.push(text.to_string());
+        }
+    }
+    Ok(lines)
+}
+
+/// A corpus that tracks a source file with one item per line, and automatically reindexes
+/// changed and added lines whenever the file changes.
+///
+/// Lines are processed the same as [`Corpus::from_lines_file`], and each non-empty line keeps
+/// the same [`ItemId`] for as long as it exists, even when lines are added or removed around it.
+/// Edited lines reuse the IDs of removed lines, in order. Every reindex that changes the corpus sends a
+/// [`CorpusChange`] event, so UIs know to refresh stale result lists.
+///
+/// This is only available with the `notify` feature.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use fuzzbunny_rs::WatchedCorpus;
+///
+/// let (watched, changes) = WatchedCorpus::watch("items.txt")?;
+///
+/// for change in changes {
+///     let results = watched.snapshot().search("app");
+///     // ...refresh the displayed results
+/// }
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug)]
+pub struct WatchedCorpus {
+    state: Arc<WatchState>,
+    _watcher: RecommendedWatcher,
+}
+
+impl WatchedCorpus {
+    /// Index the file at `path`, and start watching it for changes.
+    ///
+    /// # Returns
+    ///
+    /// The watched corpus, and the receiver for its change events.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`io::Error`] if the file can't be read, or watching it fails.
+    pub fn watch(path: impl AsRef<Path>) -> io::Result<(Self, Receiver<CorpusChange>)> {
+        let path = path.as_ref().canonicalize()?;
+        let (sender, receiver) = channel();
+
+        let state = Arc::new(WatchState {
+            path,
+            corpus: Arc::new(SharedCorpus::new(Corpus::new())),
+            line_ids: Mutex::new(vec![]),
+            events: sender,
+        });
+        state.reindex()?;
+
+        let handler_state = Arc::clone(&state);
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            let Ok(event) = event else { return };
+            let is_change = matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_));
+            if !is_change || !event.paths.iter().any(|path| path == &handler_state.path) {
+                return
+            }
+
+            // The file may be mid-replacement, in which case the next event reindexes it
+            if let Ok(change) = handler_state.reindex() {
+                if !change.is_empty() {
+                    let _ = handler_state.events.send(change);
+                }
+            }
+        }).map_err(io::Error::other)?;
+
+        // Watch the parent directory, so editors that replace the file on save are tracked too
+        let parent = state.path.parent().unwrap_or(Path::new("/"));
+        watcher.watch(parent, RecursiveMode::NonRecursive).map_err(io::Error::other)?;
+
+        Ok((WatchedCorpus { state, _watcher: watcher }, receiver))
+    }
+
+    /// Take a snapshot of the current corpus. See [`SharedCorpus::snapshot`].
+    pub fn snapshot(&self) -> Arc<Corpus> {
+        self.state.corpus.snapshot()
+    }
+
+    /// The shared corpus that is kept up to date with the file.
+    pub fn shared(&self) -> &Arc<SharedCorpus> {
+        &self.state.corpus
+    }
+
+    /// Reindex the file immediately, rather than waiting for a change notification.
+    ///
+    /// The change is returned rather than sent as an event.
+    pub fn reindex(&self) -> io::Result<CorpusChange> {
+        self.state.reindex()
+    }
+}
@@ -0,0 +1,24 @@
+#![cfg(feature = "async")]
+
+use futures::{executor::block_on, StreamExt};
+use fuzzbunny_rs::{fuzzy_filter, fuzzy_filter_stream, precompute_skips_for_items};
+
+#[test]
+fn stream_yields_all_matches() {
+  let items: Vec<String> = (0..5000).map(|i| format!("item number {}", i)).collect();
+  let targets = precompute_skips_for_items(items.iter().map(|s| s.as_str()));
+
+  let mut streamed: Vec<&str> = block_on(fuzzy_filter_stream(&targets, "number 12").map(|res| res.item).collect());
+  let mut expected: Vec<&str> = fuzzy_filter(&targets, "number 12").into_iter().map(|res| res.item).collect();
+  streamed.sort();
+  expected.sort();
+  assert_eq!(streamed, expected);
+}
+
+#[test]
+fn stream_stops_early() {
+  let targets = precompute_skips_for_items(vec!["apple", "application", "appendix"]);
+
+  let first: Vec<&str> = block_on(fuzzy_filter_stream(&targets, "app").take(2).map(|res| res.item).collect());
+  assert_eq!(first, vec!["apple", "application"]);
+}
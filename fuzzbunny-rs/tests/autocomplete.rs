@@ -0,0 +1,56 @@
+#![cfg(feature = "std")]
+
+use fuzzbunny_rs::{fuzzy_filter, precompute_skips_for_items, AutocompleteIndex};
+
+#[test]
+fn autocomplete_matches_fuzzy_filter() {
+  let lines: Vec<&str> = include_str!("gutenberg-catalog.txt").lines().collect();
+  let targets = precompute_skips_for_items(lines.iter().copied());
+  let index = AutocompleteIndex::new(lines.iter().copied());
+  assert_eq!(index.len(), lines.len());
+
+  let searches = ["t", "the", "The", "a", "al", "Alice", "sh", "p p", "\"the", "", "  the ", "zzz", "(", "19"];
+  for search in searches {
+    let expected = fuzzy_filter(&targets, search);
+    for limit in [0, 1, 5, 20, 100] {
+      let results = index.search(search, limit);
+      assert_eq!(results, expected[..limit.min(expected.len())], "{search:?} {limit}");
+    }
+  }
+}
+
+#[test]
+fn autocomplete_ranks_prefix_matches() {
+  let index = AutocompleteIndex::new(["cart", "Card", "car", "carbon", "Scar", "car"]);
+
+  let results = index.search("CAR", 4);
+  let items: Vec<&str> = results.iter().map(|res| res.item).collect();
+  assert_eq!(items, vec!["Card", "car", "car", "carbon"]);
+  assert!(results.iter().all(|res| res.score == 3700));
+  assert_eq!(results[0].highlights, Some(vec!["", "Car", "d"]));
+
+  // Only five items start with "car", so the last result comes from fuzzy matching
+  let results = index.search("car", 6);
+  assert_eq!(results.last().unwrap().item, "Scar");
+
+  let empty = AutocompleteIndex::new([]);
+  assert!(empty.is_empty());
+  assert!(empty.search("car", 5).is_empty());
+}
+
+#[test]
+fn autocomplete_highlights_items_that_change_length_when_lower_cased() {
+  let items = ["\u{212A}elvin", "\u{212A}ettle", "İstanbul", "İzmir", "Kiel"];
+  let targets = precompute_skips_for_items(items);
+  let index = AutocompleteIndex::new(items);
+
+  for (search, limit) in [("ke", 2), ("i", 2), ("i̇s", 1), ("ke", 3)] {
+    let expected = fuzzy_filter(&targets, search);
+    assert_eq!(index.search(search, limit), expected[..limit.min(expected.len())], "{search:?} {limit}");
+  }
+
+  let results = index.search("ke", 2);
+  assert_eq!(results[0].highlights, Some(vec!["", "\u{212A}e", "lvin"]));
+  let results = index.search("i̇s", 1);
+  assert_eq!(results[0].highlights, Some(vec!["", "İs", "tanbul"]));
+}
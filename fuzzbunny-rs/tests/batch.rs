@@ -0,0 +1,33 @@
+use fuzzbunny_rs::{fuzzy_filter, fuzzy_filter_batch, precompute_skips_for_items, SkipRules, Target};
+
+#[test]
+fn batch_matches_fuzzy_filter_per_search() {
+  let lines: Vec<&str> = include_str!("gutenberg-catalog.txt").lines().chain(["İstanbul", "Café Müller"]).collect();
+  let precomputed = precompute_skips_for_items(lines.iter().copied());
+  let on_the_fly: Vec<Target<'_>> = lines.iter().map(|&line| (line, None)).collect();
+  let searches = ["oliver", "alice", "mayflo", "declofusa", "\"of the", "", "  ", "is", "mü", "zzzz"];
+
+  for targets in [precomputed, on_the_fly] {
+    let batch = fuzzy_filter_batch(&targets, &searches);
+    assert_eq!(batch.len(), searches.len());
+    for (results, search) in batch.iter().zip(searches) {
+      let expected = fuzzy_filter(&targets, search);
+      assert_eq!(results, &expected);
+      let highlights: Vec<_> = results.iter().map(|res| (&res.highlights, res.index)).collect();
+      let expected: Vec<_> = expected.iter().map(|res| (&res.highlights, res.index)).collect();
+      assert_eq!(highlights, expected);
+    }
+  }
+}
+
+#[test]
+fn batch_keeps_precomputed_skips() {
+  let rules = SkipRules::new().with_boundaries(['/']);
+  let targets = rules.precompute_skips_for_items(vec!["src/my-file.rs", "src/main.rs"]);
+
+  let batch = fuzzy_filter_batch(&targets, &["sf", "sma"]);
+  assert!(batch[0].is_empty());
+  assert_eq!(batch[1].len(), 1);
+  assert!(fuzzy_filter_batch(&targets, &[]).is_empty());
+  assert_eq!(fuzzy_filter_batch(&[], &["a"]), vec![vec![]]);
+}
@@ -0,0 +1,53 @@
+use fuzzbunny_rs::{fuzzy_filter, fuzzy_filter_boosted, precompute_skips_for_items};
+
+#[test]
+fn zero_boost_matches_fuzzy_filter() {
+  let targets = precompute_skips_for_items(include_str!("gutenberg-catalog.txt").lines().take(20_000));
+
+  for search in ["the", "p p", "", "zzzz"] {
+    assert_eq!(fuzzy_filter_boosted(&targets, search, |_, _| 0), fuzzy_filter(&targets, search));
+  }
+}
+
+#[test]
+fn boosts_reorder_without_changing_matches() {
+  let targets = precompute_skips_for_items(vec!["Open File", "Open Folder", "Open Recent", "Close File"]);
+  let boosts = [0, 300, -10_000, 1_000_000];
+  let results = fuzzy_filter_boosted(&targets, "open", |index, item| {
+    assert_eq!(targets[index].0, item);
+    boosts[index]
+  });
+
+  let items: Vec<(&str, usize)> = results.iter().map(|res| (res.item, res.index.unwrap())).collect();
+  assert_eq!(items, vec![("Open Folder", 1), ("Open File", 0), ("Open Recent", 2)]);
+
+  // Scores include the boost, and negative boosts saturate at 0 rather than unmatching
+  let unboosted = fuzzy_filter(&targets, "open");
+  let score = |item| unboosted.iter().find(|res| res.item == item).unwrap().score;
+  assert_eq!(results[0].score, score("Open Folder") + 300);
+  assert_eq!(results[1].score, score("Open File"));
+  assert_eq!(results[2].score, 0);
+
+  // An empty search keeps the order of the items
+  let results = fuzzy_filter_boosted(&targets, "", |index, _| boosts[index]);
+  let items: Vec<&str> = results.iter().map(|res| res.item).collect();
+  assert_eq!(items, vec!["Open File", "Open Folder", "Open Recent", "Close File"]);
+  assert_eq!(results[3].score, 1_000_000);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn index_search_boosted() {
+  let mut index = fuzzbunny_rs::FuzzyIndex::new();
+  let file = index.insert("Open File");
+  let folder = index.insert("Open Folder");
+  index.insert("Close File");
+
+  let ids = |results: Vec<fuzzbunny_rs::IndexMatch<'_>>| -> Vec<_> { results.iter().map(|res| res.id).collect() };
+  assert_eq!(ids(index.search("open f")), vec![file, folder]);
+  assert_eq!(ids(index.search_boosted("open f", |id, _| if id == folder { 500 } else { 0 })), vec![folder, file]);
+  assert_eq!(index.search_boosted("open f", |_, _| 0), index.search("open f"));
+
+  let index = index.with_ngram_index();
+  assert_eq!(ids(index.search_boosted("open f", |id, _| if id == folder { 500 } else { 0 })), vec![folder, file]);
+}
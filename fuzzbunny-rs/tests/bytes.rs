@@ -0,0 +1,44 @@
+use fuzzbunny_rs::{fuzzy_filter, fuzzy_filter_bytes, precompute_skips_for_items};
+
+#[test]
+fn bytes_match_the_same_as_text() {
+  let items = vec!["Earth", "Mars", "Jupiter", "Saturn", "Uranus", "Neptune"];
+  let targets = precompute_skips_for_items(items.clone());
+  let lines: Vec<&[u8]> = items.iter().map(|item| item.as_bytes()).collect();
+
+  for search in ["ar", "u", "\"tu", "s n", "", "xyz"] {
+    let results = fuzzy_filter(&targets, search);
+    let byte_results = fuzzy_filter_bytes(&lines, search);
+    assert_eq!(byte_results.len(), results.len(), "{search}");
+    for (byte_result, result) in byte_results.iter().zip(&results) {
+      assert_eq!(byte_result.item, result.item.as_bytes());
+      assert_eq!(byte_result.score, result.score);
+      let highlights: Vec<&[u8]> = result.highlights.as_ref().unwrap().iter().map(|part| part.as_bytes()).collect();
+      assert_eq!(byte_result.highlights(), highlights);
+    }
+  }
+}
+
+#[test]
+fn bytes_match_latin1_and_invalid_utf8() {
+  // "Café Ölmühle" in Latin-1, followed by bytes that aren't valid UTF-8 in any position
+  let latin1: &[u8] = b"Caf\xe9 \xd6lm\xfchle";
+  let binary: &[u8] = b"\x00\xff\xfeheader\x80";
+  let lines = [latin1, binary];
+
+  let results = fuzzy_filter_bytes(&lines, "header");
+  assert_eq!(results.len(), 1);
+  assert_eq!(results[0].item, binary);
+  assert_eq!(results[0].ranges, vec![3..9]);
+  assert_eq!(results[0].highlights(), vec![&b"\x00\xff\xfe"[..], b"header", b"\x80"]);
+
+  // Latin-1 letters lower case, and ranges are offsets into the line rather than decoded text
+  let results = fuzzy_filter_bytes(&lines, "ölm");
+  assert_eq!(results.len(), 1);
+  assert_eq!(results[0].ranges, vec![5..8]);
+  let results = fuzzy_filter_bytes(&lines, "c ö");
+  assert_eq!(results[0].ranges, vec![0..1, 5..6]);
+
+  // Characters outside of Latin-1 never match
+  assert!(fuzzy_filter_bytes(&lines, "€").is_empty());
+}
@@ -0,0 +1,68 @@
+#![cfg(feature = "cabi")]
+
+use std::ffi::{c_char, CString};
+use std::ptr;
+
+use fuzzbunny_rs::cabi::*;
+use fuzzbunny_rs::{fuzzy_filter, precompute_skips_for_items};
+
+#[test]
+fn search_matches_fuzzy_filter() {
+  let items = vec!["apple", "banana", "application", "Maya Herrera", "pineapple"];
+  let owned: Vec<CString> = items.iter().map(|item| CString::new(*item).unwrap()).collect();
+  let pointers: Vec<*const c_char> = owned.iter().map(|item| item.as_ptr()).collect();
+  let search = CString::new("app").unwrap();
+
+  let expected = fuzzy_filter(&precompute_skips_for_items(items.clone()), "app");
+  unsafe {
+    let index = fuzzbunny_index_new(pointers.as_ptr(), pointers.len());
+    assert!(!index.is_null());
+    // The index copies the items
+    drop(owned);
+
+    let results = fuzzbunny_index_search(index, search.as_ptr());
+    fuzzbunny_index_free(index);
+    assert_eq!(fuzzbunny_results_len(results), expected.len());
+
+    for (i, expected) in expected.iter().enumerate() {
+      let found = &*fuzzbunny_results_get(results, i);
+      assert_eq!(items[found.item_index], expected.item);
+      assert_eq!(found.score, expected.score);
+
+      let ranges = std::slice::from_raw_parts(found.ranges, found.ranges_len);
+      let expected_ranges: Vec<FuzzbunnyRange> =
+        expected.ranges.as_ref().unwrap().iter().map(|range| FuzzbunnyRange { start: range.0, len: range.1 }).collect();
+      assert_eq!(ranges, expected_ranges);
+    }
+    assert!(fuzzbunny_results_get(results, expected.len()).is_null());
+    fuzzbunny_results_free(results);
+  }
+}
+
+#[test]
+fn invalid_input_returns_null() {
+  let invalid = [0xffu8, 0];
+  let items = [invalid.as_ptr() as *const c_char];
+  let search = CString::new("a").unwrap();
+
+  unsafe {
+    assert!(fuzzbunny_index_new(items.as_ptr(), 1).is_null());
+    assert!(fuzzbunny_index_new([ptr::null()].as_ptr(), 1).is_null());
+    assert!(fuzzbunny_index_new(ptr::null(), 1).is_null());
+
+    let index = fuzzbunny_index_new(ptr::null(), 0);
+    assert!(!index.is_null());
+    assert!(fuzzbunny_index_search(index, ptr::null()).is_null());
+    assert!(fuzzbunny_index_search(ptr::null(), search.as_ptr()).is_null());
+
+    let results = fuzzbunny_index_search(index, search.as_ptr());
+    assert_eq!(fuzzbunny_results_len(results), 0);
+    assert!(fuzzbunny_results_get(results, 0).is_null());
+    fuzzbunny_results_free(results);
+    fuzzbunny_index_free(index);
+
+    assert_eq!(fuzzbunny_results_len(ptr::null()), 0);
+    fuzzbunny_results_free(ptr::null_mut());
+    fuzzbunny_index_free(ptr::null_mut());
+  }
+}
@@ -0,0 +1,47 @@
+#![cfg(feature = "std")]
+
+use fuzzbunny_rs::{fuzzy_filter, precompute_skips_for_items, SearchHandle};
+
+#[test]
+fn uncancelled_search_matches_fuzzy_filter() {
+  let items: Vec<String> = (0..5000).map(|i| format!("item number {}", i)).collect();
+  let targets = precompute_skips_for_items(items.iter().map(|s| s.as_str()));
+  let handle = SearchHandle::new();
+
+  for search in ["", "number 12", "i n 4", "missing"] {
+    let results = handle.fuzzy_filter(&targets, search).unwrap();
+    assert_eq!(results, fuzzy_filter(&targets, search));
+    // Results carry their index in `targets`, rather than in the chunk they were scored in
+    assert!(results.iter().all(|res| targets[res.index.unwrap()].0 == res.item));
+  }
+  assert!(!handle.is_cancelled());
+}
+
+#[test]
+fn cancelled_search_returns_none() {
+  let targets = precompute_skips_for_items(vec!["apple", "banana", "application"]);
+  let handle = SearchHandle::new();
+  let clone = handle.clone();
+
+  clone.cancel();
+  assert!(handle.is_cancelled());
+  assert_eq!(handle.fuzzy_filter(&targets, "app"), None);
+  assert_eq!(handle.fuzzy_filter(&[], "app"), None);
+
+  // A new handle isn't affected by the cancelled one
+  assert_eq!(SearchHandle::new().fuzzy_filter(&targets, "app").unwrap().len(), 2);
+}
+
+#[test]
+fn search_cancelled_from_another_thread_stops_early() {
+  let items: Vec<String> = (0..200_000).map(|i| format!("a rather long item name, number {}", i)).collect();
+  let targets = precompute_skips_for_items(items.iter().map(|s| s.as_str()));
+  let handle = SearchHandle::new();
+
+  let result = std::thread::scope(|scope| {
+    let worker = scope.spawn(|| handle.fuzzy_filter(&targets, "ranum"));
+    handle.cancel();
+    worker.join().unwrap()
+  });
+  assert_eq!(result, None);
+}
@@ -0,0 +1,91 @@
+#![cfg(feature = "cli")]
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+fn fuzzbunny(args: &[&str], input: &str) -> Output {
+  let mut child = Command::new(env!("CARGO_BIN_EXE_fuzzbunny"))
+    .args(args)
+    .stdin(Stdio::piped())
+    .stdout(Stdio::piped())
+    .stderr(Stdio::piped())
+    .spawn()
+    .unwrap();
+  // Invalid arguments exit without reading stdin
+  let _ = child.stdin.take().unwrap().write_all(input.as_bytes());
+  child.wait_with_output().unwrap()
+}
+
+const INPUT: &str = "apple\nbanana\napplication\n";
+
+#[test]
+fn cli_prints_sorted_matches() {
+  let output = fuzzbunny(&["app", "--color", "never"], INPUT);
+  assert!(output.status.success());
+  assert_eq!(String::from_utf8(output.stdout).unwrap(), "3700\tapple\n3700\tapplication\n");
+
+  let output = fuzzbunny(&["-s", "-n", "1", "app", "--color", "never"], INPUT);
+  assert_eq!(String::from_utf8(output.stdout).unwrap(), "apple\n");
+}
+
+#[test]
+fn cli_highlights_matches() {
+  let output = fuzzbunny(&["--no-score", "--color", "always", "nan"], INPUT);
+  assert_eq!(String::from_utf8(output.stdout).unwrap(), "ba\x1b[1;31mnan\x1b[0ma\n");
+}
+
+#[test]
+fn cli_reads_files() {
+  let path = std::env::temp_dir().join(format!("fuzzbunny-cli-{}.txt", std::process::id()));
+  std::fs::write(&path, "cherry\ncheckout\n").unwrap();
+
+  // The file is read instead of stdin
+  let output = fuzzbunny(&["-s", "--color", "never", "--file", path.to_str().unwrap(), "che"], INPUT);
+  assert_eq!(String::from_utf8(output.stdout).unwrap(), "checkout\ncherry\n");
+  std::fs::remove_file(&path).unwrap();
+
+  // A file of "-" is stdin
+  let output = fuzzbunny(&["-s", "--color", "never", "-f", "-", "nan"], INPUT);
+  assert_eq!(String::from_utf8(output.stdout).unwrap(), "banana\n");
+
+  let output = fuzzbunny(&["-f", path.to_str().unwrap(), "che"], INPUT);
+  assert_eq!(output.status.code(), Some(2));
+  assert!(String::from_utf8(output.stderr).unwrap().contains(path.to_str().unwrap()));
+}
+
+#[test]
+fn cli_reads_lines_that_are_not_utf8() {
+  let path = std::env::temp_dir().join(format!("fuzzbunny-cli-latin1-{}.txt", std::process::id()));
+  std::fs::write(&path, b"caf\xe9 latte\r\ncafeteria\n").unwrap();
+
+  // Invalid UTF-8 is replaced, so the rest of the line can still be searched
+  let output = fuzzbunny(&["-s", "--color", "never", "-f", path.to_str().unwrap(), "caf"], INPUT);
+  assert!(output.status.success());
+  assert_eq!(String::from_utf8(output.stdout).unwrap(), "cafeteria\ncaf\u{fffd} latte\n");
+  std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn cli_exit_codes() {
+  assert_eq!(fuzzbunny(&["zzz"], INPUT).status.code(), Some(1));
+  assert_eq!(fuzzbunny(&[], INPUT).status.code(), Some(2));
+  assert_eq!(fuzzbunny(&["--bogus", "a"], INPUT).status.code(), Some(2));
+  assert_eq!(fuzzbunny(&["--file"], INPUT).status.code(), Some(2));
+  assert!(fuzzbunny(&["--help"], INPUT).status.success());
+}
+
+#[test]
+fn bench_reports_each_mode() {
+  let output = Command::new(env!("CARGO_BIN_EXE_fuzzbunny-bench"))
+    .args(["tests/gutenberg-catalog.txt", "-i", "1", "-m", "filter", "-m", "corpus"])
+    .output()
+    .unwrap();
+  assert!(output.status.success());
+
+  let stdout = String::from_utf8(output.stdout).unwrap();
+  let modes: Vec<_> = stdout.lines().skip(3).filter_map(|line| line.split_whitespace().next()).collect();
+  assert_eq!(modes, vec!["filter", "corpus"]);
+
+  let output = Command::new(env!("CARGO_BIN_EXE_fuzzbunny-bench")).args(["-m", "bogus", "x"]).output().unwrap();
+  assert_eq!(output.status.code(), Some(2));
+}
@@ -0,0 +1,53 @@
+#![cfg(feature = "std")]
+
+use fuzzbunny_rs::cluster_near_duplicates;
+
+#[test]
+fn cluster_groups_near_duplicates() {
+  let names = [
+    "iPhone 15 Pro",
+    "Galaxy S24",
+    "iphone 15 pro",
+    "IPHONE 15-PRO",
+    "galaxy s24 ",
+    "Pixel 8",
+    "iPhone 15",
+  ];
+
+  let clusters = cluster_near_duplicates(names, 0.6);
+  assert_eq!(
+    clusters,
+    vec![
+      vec!["iPhone 15 Pro", "iphone 15 pro", "IPHONE 15-PRO", "iPhone 15"],
+      vec!["Galaxy S24", "galaxy s24 "],
+      vec!["Pixel 8"],
+    ]
+  );
+
+  // A stricter threshold only groups strings that are the same ignoring case
+  let clusters = cluster_near_duplicates(names, 1.0);
+  assert_eq!(clusters.len(), 5);
+  assert_eq!(clusters[0], vec!["iPhone 15 Pro", "iphone 15 pro"]);
+}
+
+#[test]
+fn cluster_keeps_every_string() {
+  let lines: Vec<&str> = include_str!("gutenberg-catalog.txt").lines().take(500).collect();
+  let clusters = cluster_near_duplicates(lines.iter().copied(), 0.8);
+
+  let mut clustered: Vec<&str> = clusters.iter().flatten().copied().collect();
+  let mut expected = lines.clone();
+  clustered.sort_unstable();
+  expected.sort_unstable();
+  assert_eq!(clustered, expected);
+  assert!(clusters.iter().all(|cluster| !cluster.is_empty()));
+
+  assert!(cluster_near_duplicates([], 0.5).is_empty());
+  assert_eq!(cluster_near_duplicates(["", " ", "a"], 0.5), vec![vec!["", " "], vec!["a"]]);
+}
+
+#[test]
+#[should_panic(expected = "threshold must be in (0, 1]")]
+fn cluster_rejects_invalid_threshold() {
+  cluster_near_duplicates(["a"], 0.0);
+}
@@ -0,0 +1,61 @@
+use fuzzbunny_rs::{fuzzy_filter, fuzzy_filter_composite, precompute_skips_for_items, CompositeTarget, FieldRange};
+
+#[test]
+fn composite_matches_haystack() {
+  let targets = vec![
+    CompositeTarget::new(["Claire Bennet", "Cheerleader"]),
+    CompositeTarget::new(["Elle Bishop", "Agent"]),
+    CompositeTarget::new(["Maya Herrera", "Healer"]),
+  ];
+  let haystacks = precompute_skips_for_items(targets.iter().map(CompositeTarget::haystack));
+
+  for search in ["el", "he", "c c", ""] {
+    let results: Vec<_> = fuzzy_filter_composite(&targets, search).into_iter().map(|res| res.result).collect();
+    assert_eq!(results, fuzzy_filter(&haystacks, search));
+  }
+}
+
+#[test]
+fn composite_attributes_ranges_to_fields() {
+  let target = CompositeTarget::new(["John", "Smith", "", "Engineering"]);
+  assert_eq!(target.haystack(), "John Smith  Engineering");
+  assert_eq!(target.field_count(), 4);
+  assert_eq!(target.field(1), Some("Smith"));
+  assert_eq!(target.field(2), Some(""));
+  assert_eq!(target.field(4), None);
+  let targets = vec![target];
+
+  let results = fuzzy_filter_composite(&targets, "smith");
+  assert_eq!(results[0].fields, vec![FieldRange { field: 1, start: 0, len: 5 }]);
+
+  // A fuzzy match across fields
+  let results = fuzzy_filter_composite(&targets, "jo sm en");
+  assert_eq!(results[0].result.highlights, Some(vec!["", "Jo", "hn ", "Sm", "ith  ", "En", "gineering"]));
+  assert_eq!(
+    results[0].fields,
+    vec![
+      FieldRange { field: 0, start: 0, len: 2 },
+      FieldRange { field: 1, start: 0, len: 2 },
+      FieldRange { field: 3, start: 0, len: 2 },
+    ]
+  );
+
+  // A substring match spanning a field boundary is split, without the separator
+  let results = fuzzy_filter_composite(&targets, "hn smi");
+  assert_eq!(
+    results[0].fields,
+    vec![FieldRange { field: 0, start: 2, len: 2 }, FieldRange { field: 1, start: 0, len: 3 }]
+  );
+}
+
+#[test]
+fn composite_maps_ranges_of_fields_that_change_length_when_lower_cased() {
+  let targets = vec![CompositeTarget::new(["İİstan", "Capital"])];
+
+  let results = fuzzy_filter_composite(&targets, "stan");
+  assert_eq!(results[0].result.highlights, Some(vec!["İİ", "stan", " Capital"]));
+  assert_eq!(results[0].fields, vec![FieldRange { field: 0, start: 4, len: 4 }]);
+
+  let results = fuzzy_filter_composite(&targets, "stan cap");
+  assert_eq!(results[0].fields, vec![FieldRange { field: 0, start: 4, len: 4 }, FieldRange { field: 1, start: 0, len: 3 }]);
+}
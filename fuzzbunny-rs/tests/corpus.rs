@@ -0,0 +1,463 @@
+#![cfg(feature = "std")]
+
+use fuzzbunny_rs::{fuzzy_filter, precompute_skips_for_items, Corpus, Dictionaries, Normalization, RecentSelection, UsageBoost};
+
+#[test]
+fn corpus_search_matches_fuzzy_filter() {
+  let items = vec!["Claire Bennet", "Elle Bishop", "Monica Dawson", "EL Hawkins", "Maya Herrera"];
+  let corpus: Corpus = items.iter().copied().collect();
+  let targets = precompute_skips_for_items(items);
+
+  for search in ["el", "m d", "ha", ""] {
+    let corpus_results: Vec<_> = corpus.search(search).into_iter().map(|res| res.result).collect();
+    assert_eq!(corpus_results, fuzzy_filter(&targets, search));
+  }
+}
+
+#[test]
+fn corpus_insert_remove_update() {
+  let mut corpus = Corpus::new();
+  let apple = corpus.insert("apple");
+  let banana = corpus.insert("banana");
+  let cherry = corpus.insert("cherry");
+
+  assert_eq!(corpus.remove(banana), Some("banana".to_string()));
+  assert_eq!(corpus.remove(banana), None);
+  assert_eq!(corpus.update(cherry, "cherry apple"), Some("cherry".to_string()));
+  assert_eq!(corpus.get(cherry), Some("cherry apple"));
+  assert_eq!(corpus.len(), 2);
+
+  let ids: Vec<_> = corpus.search("apple").into_iter().map(|res| res.id).collect();
+  assert_eq!(ids, vec![apple, cherry]);
+
+  // IDs are never reused
+  let date = corpus.insert("date");
+  assert!(date != banana);
+  assert_eq!(corpus.iter().map(|(_, text)| text).collect::<Vec<_>>(), vec!["apple", "cherry apple", "date"]);
+}
+
+#[test]
+fn corpus_from_lines_skips_empty() {
+  let input = "  apple \n\n\t\nbanana\r\ncherry";
+  let corpus = Corpus::from_lines(std::io::Cursor::new(input)).unwrap();
+
+  assert_eq!(corpus.iter().map(|(_, text)| text).collect::<Vec<_>>(), vec!["apple", "banana", "cherry"]);
+}
+
+#[test]
+fn corpus_from_lines_file() {
+  let corpus = Corpus::from_lines_file("tests/gutenberg-catalog.txt").unwrap();
+  assert!(corpus.len() > 50_000);
+  assert!(!corpus.search("alice").is_empty());
+
+  assert!(Corpus::from_lines_file("tests/does-not-exist.txt").is_err());
+}
+
+#[test]
+fn corpus_memory_budget_limits_derived_data() {
+  let items: Vec<String> = (0..2000).map(|i| format!("Item{} in the corpus", i)).collect();
+  let unbudgeted: Corpus = items.iter().cloned().collect();
+  let mut corpus = unbudgeted.clone();
+
+  let full_usage = corpus.derived_memory();
+  corpus.set_memory_budget(Some(full_usage / 4));
+  assert!(corpus.derived_memory() <= full_usage / 4);
+
+  for search in ["item1", "i t c", "corpus"] {
+    assert_eq!(corpus.search(search), unbudgeted.search(search));
+    assert!(corpus.derived_memory() <= full_usage / 4);
+  }
+
+  corpus.insert("Another item");
+  assert!(corpus.derived_memory() <= full_usage / 4);
+
+  corpus.set_memory_budget(None);
+  corpus.search("");
+  assert!(corpus.derived_memory() > full_usage);
+}
+
+#[test]
+fn corpus_memory_budget_follows_the_matched_items() {
+  let mut corpus = Corpus::new();
+  corpus.extend((0..100).map(|i| format!("banana {}", i)));
+  let apples = corpus.extend((0..100).map(|i| format!("apple {}", i)));
+  let full_usage = corpus.derived_memory();
+  corpus.set_memory_budget(Some(full_usage * 6 / 10));
+
+  // The first search recomputes the data that doesn't fit, and makes room for it to be cached by the next
+  for search in ["apple", "apple", "banana", "banana"] {
+    assert_eq!(corpus.search(search).len(), 100);
+    assert!(corpus.derived_memory() <= full_usage * 6 / 10);
+  }
+
+  // Most of the cached data is now for the bananas
+  let cached = corpus.derived_memory();
+  for id in apples {
+    corpus.remove(id);
+  }
+  assert!(cached - corpus.derived_memory() < corpus.derived_memory() / 4);
+}
+
+#[test]
+fn corpus_deduplicates_identical_text() {
+  let mut corpus = Corpus::new();
+  let first = corpus.insert("connection reset");
+  let second = corpus.insert("connection reset");
+  let other = corpus.insert("connection refused");
+  assert_eq!(corpus.len(), 3);
+  assert_eq!(corpus.unique_len(), 2);
+
+  let ids: Vec<_> = corpus.search("reset").into_iter().map(|res| res.id).collect();
+  assert_eq!(ids, vec![first, second]);
+
+  corpus.remove(first);
+  assert_eq!(corpus.unique_len(), 2);
+  assert_eq!(corpus.get(second), Some("connection reset"));
+
+  corpus.update(other, "connection reset");
+  assert_eq!(corpus.unique_len(), 1);
+  let ids: Vec<_> = corpus.search("reset").into_iter().map(|res| res.id).collect();
+  assert_eq!(ids, vec![second, other]);
+}
+
+#[test]
+fn corpus_extend_matches_insert() {
+  let items: Vec<String> = (0..10_000).map(|i| format!("Item{} in the corpus", i % 7000)).collect();
+
+  let mut inserted = Corpus::new();
+  inserted.insert("existing item");
+  for item in &items {
+    inserted.insert(item.as_str());
+  }
+
+  let mut extended = Corpus::new();
+  extended.insert("existing item");
+  let ids = extended.extend(items.iter().map(|s| s.as_str()));
+
+  assert_eq!(ids.len(), items.len());
+  assert_eq!(extended.len(), inserted.len());
+  assert_eq!(extended.unique_len(), 7001);
+  assert_eq!(extended.get(ids[9999]), Some(items[9999].as_str()));
+  assert_eq!(extended.iter().collect::<Vec<_>>(), inserted.iter().collect::<Vec<_>>());
+  assert_eq!(extended.search("item69"), inserted.search("item69"));
+}
+
+#[test]
+fn corpus_selection_boosts_score() {
+  let mut corpus = Corpus::new();
+  let commit = corpus.insert("git commit");
+  let checkout = corpus.insert("git checkout");
+
+  let ids: Vec<_> = corpus.search("git c").into_iter().map(|res| res.id).collect();
+  assert_eq!(ids, vec![checkout, commit]);
+
+  corpus.record_selection(commit);
+  let results = corpus.search("git c");
+  assert_eq!(results[0].id, commit);
+  assert_eq!(results[0].result.score, results[1].result.score + 100);
+
+  // Boosts decay as other items are selected
+  for _ in 0..50 {
+    corpus.record_selection(checkout);
+  }
+  assert!((corpus.selection_count(commit) - 0.5).abs() < 0.01);
+  assert_eq!(corpus.search("git c")[0].id, checkout);
+  assert!(corpus.search("xyz").is_empty());
+}
+
+#[test]
+fn corpus_recent_selections_boost_score() {
+  let mut corpus = Corpus::new();
+  let commit = corpus.insert("git commit");
+  let checkout = corpus.insert("git checkout");
+  let base: Vec<u32> = corpus.search("git c").into_iter().map(|res| res.result.score).collect();
+
+  let recent = [RecentSelection { id: commit, weight: 0.5 }, RecentSelection { id: commit, weight: 0.25 }];
+  let results = corpus.search_with_recency("git c", &recent);
+  assert_eq!(results[0].id, commit);
+  assert_eq!(results[0].result.score, base[1] + 75);
+  assert_eq!(results[1].result.score, base[0]);
+
+  // Recent selections add to recorded ones, within the same maximum boost
+  corpus.record_selection(checkout);
+  corpus.set_usage_boost(UsageBoost { score_per_selection: 100, half_life: 50, max_boost: 150 });
+  let recent = [RecentSelection { id: checkout, weight: 1.0 }, RecentSelection { id: commit, weight: -1.0 }];
+  let results = corpus.search_with_recency("git c", &recent);
+  assert_eq!(results[0].result.score, base[0] + 150);
+  assert_eq!(results[1].result.score, base[1]);
+
+  // The corpus itself isn't changed
+  assert_eq!(corpus.search("git c")[0].result.score, base[0] + 100);
+  assert_eq!(corpus.search_with_recency("git c", &[]), corpus.search("git c"));
+}
+
+#[test]
+fn corpus_pinned_items_rank_first() {
+  let mut corpus = Corpus::new();
+  let settings = corpus.insert("Open Settings");
+  let set = corpus.insert("set");
+  let reset = corpus.insert("Reset Settings");
+  corpus.insert("Quit");
+
+  assert_eq!(corpus.search("set")[0].id, set);
+
+  corpus.set_pinned(reset, true);
+  corpus.set_pinned(settings, true);
+  let ids: Vec<_> = corpus.search("set").into_iter().map(|res| res.id).collect();
+  assert_eq!(ids, vec![settings, reset, set]);
+
+  let ids: Vec<_> = corpus.search("").into_iter().map(|res| res.id).collect();
+  assert_eq!(&ids[..2], &[settings, reset]);
+
+  // Pinned items that don't match aren't returned
+  assert_eq!(corpus.search("quit").len(), 1);
+  corpus.set_pinned(reset, false);
+  assert!(!corpus.is_pinned(reset));
+}
+
+#[test]
+fn corpus_search_restricted_to_tags() {
+  let mut corpus = Corpus::new();
+  let quest = corpus.insert("Slay the dragon");
+  let setting = corpus.insert("Dragon graphics quality");
+  let item = corpus.insert("Dragon scale");
+  let duplicate = corpus.insert("Dragon scale");
+  corpus.add_tag(quest, "quest");
+  corpus.add_tag(setting, "setting");
+  corpus.add_tag(duplicate, "item");
+
+  let ids = |results: Vec<fuzzbunny_rs::CorpusMatch>| results.into_iter().map(|res| res.id).collect::<Vec<_>>();
+  assert_eq!(ids(corpus.search_tagged("dragon", &["quest"])), vec![quest]);
+  assert_eq!(ids(corpus.search_tagged("dragon", &["quest", "setting"])), vec![setting, quest]);
+  assert_eq!(ids(corpus.search_tagged("dragon", &["item"])), vec![duplicate]);
+  assert!(corpus.search_tagged("dragon", &["unknown"]).is_empty());
+  assert_eq!(corpus.search("dragon").len(), 4);
+
+  assert_eq!(corpus.tags(quest), vec!["quest"]);
+  assert!(corpus.remove_tag(quest, "quest"));
+  assert!(corpus.search_tagged("dragon", &["quest"]).is_empty());
+  assert!(corpus.tags(item).is_empty());
+}
+
+#[test]
+fn corpus_compact_reclaims_removed_items() {
+  let mut corpus: Corpus = ["apple", "banana", "application", "cherry", "apple"].into_iter().collect();
+  let mut ids: Vec<_> = corpus.iter().map(|(id, _)| id).collect();
+  ids.sort();
+
+  assert_eq!(corpus.remove(ids[1]), Some("banana".to_string()));
+  assert_eq!(corpus.remove(ids[0]), Some("apple".to_string()));
+  assert_eq!(corpus.tombstone_count(), 1);
+  assert_eq!(corpus.unique_len(), 3);
+  assert!(corpus.search("ban").is_empty());
+
+  let before: Vec<_> = corpus.search("ap").into_iter().map(|res| (res.id, res.result.score)).collect();
+  corpus.compact();
+  let after: Vec<_> = corpus.search("ap").into_iter().map(|res| (res.id, res.result.score)).collect();
+  assert_eq!(before, after);
+  assert_eq!(corpus.tombstone_count(), 0);
+  assert_eq!(corpus.unique_len(), 3);
+  assert_eq!(corpus.get(ids[3]), Some("cherry"));
+
+  // Removed texts can be inserted again after compaction
+  let banana = corpus.insert("banana");
+  assert_eq!(corpus.search("ban")[0].id, banana);
+  assert!(corpus.update(ids[3], "date").is_some());
+  assert_eq!(corpus.tombstone_count(), 1);
+  assert_eq!(corpus.search("dat")[0].id, ids[3]);
+}
+
+#[test]
+fn corpus_stats_summarise_contents() {
+  let mut corpus: Corpus = ["Save game", "Load game", "Save game", "Audio settings"].into_iter().collect();
+  let stats = corpus.stats();
+  assert_eq!(stats.items, 4);
+  assert_eq!(stats.unique_texts, 3);
+  assert_eq!(stats.text_bytes, 9 + 9 + 14);
+  assert_eq!(stats.cached_texts, 3);
+  assert_eq!(stats.derived_bytes, corpus.derived_memory());
+  assert_eq!(stats.average_skips, 2.0);
+  assert_eq!(stats.longest, Some((corpus.search("audio")[0].id, 14)));
+
+  // Evicted search data is still counted in the skip average
+  corpus.set_memory_budget(Some(0));
+  let evicted = corpus.stats();
+  assert_eq!(evicted.cached_texts, 0);
+  assert_eq!(evicted.derived_bytes, 0);
+  assert_eq!(evicted.average_skips, stats.average_skips);
+
+  assert_eq!(Corpus::new().stats(), Default::default());
+}
+
+#[test]
+fn corpus_ids_stable_across_mutations() {
+  let mut corpus = Corpus::new();
+  let ids = corpus.extend(["Slay the dragon", "Sound volume", "Slay the dragon", "Dragon graphics", "Music volume"]);
+  corpus.set_memory_budget(Some(64));
+
+  let text_by_id = |corpus: &Corpus, search: &str| -> Vec<_> {
+    let mut results: Vec<_> = corpus.search(search).into_iter().map(|res| (res.id, res.result.item.to_string())).collect();
+    results.sort();
+    results
+  };
+
+  let before = text_by_id(&corpus, "dragon");
+  corpus.remove(ids[1]);
+  corpus.update(ids[4], "Music level");
+  corpus.compact();
+  corpus.insert("Dragon egg");
+  let after = text_by_id(&corpus, "dragon");
+
+  assert_eq!(after[..3], before[..]);
+  for (id, text) in &after {
+    assert_eq!(corpus.get(*id), Some(text.as_str()));
+  }
+  assert_eq!(corpus.get(ids[4]), Some("Music level"));
+  assert_eq!(corpus.search("level")[0].id, ids[4]);
+  assert!(corpus.get(ids[1]).is_none());
+}
+
+#[test]
+fn corpus_clones_share_storage_until_mutated() {
+  let mut original = Corpus::new();
+  let ids = original.extend(["Slay the dragon", "Sound volume", "Dragon graphics"]);
+  let mut tab = original.clone();
+
+  let shares_text = |a: &Corpus, b: &Corpus, id| a.get(id).unwrap().as_ptr() == b.get(id).unwrap().as_ptr();
+  assert!(shares_text(&original, &tab, ids[0]));
+
+  tab.remove(ids[1]);
+  tab.update(ids[2], "Dragon quality");
+  tab.set_pinned(ids[0], true);
+  tab.add_tag(ids[0], "quest");
+  tab.record_selection(ids[0]);
+  tab.insert("Music volume");
+  tab.set_memory_budget(Some(0));
+
+  // Mutating a clone never affects the original, and unchanged texts are still shared
+  assert!(shares_text(&original, &tab, ids[0]));
+  assert_eq!(original.len(), 3);
+  assert_eq!(original.get(ids[2]), Some("Dragon graphics"));
+  assert!(!original.is_pinned(ids[0]));
+  assert!(original.tags(ids[0]).is_empty());
+  assert_eq!(original.selection_count(ids[0]), 0.0);
+  assert!(original.derived_memory() > 0);
+  assert_eq!(tab.derived_memory(), 0);
+
+  let items = |corpus: &Corpus| -> Vec<_> { corpus.search("vol").into_iter().map(|res| res.id).collect() };
+  assert_eq!(items(&original), vec![ids[1]]);
+  assert_eq!(items(&tab).len(), 1);
+  assert_ne!(items(&tab), vec![ids[1]]);
+}
+
+#[test]
+fn corpus_aliases_return_canonical_item() {
+  let mut corpus = Corpus::new();
+  let settings = corpus.insert("Settings");
+  let options = corpus.insert("Options menu");
+  assert!(corpus.add_alias(settings, "prefs"));
+  assert!(corpus.add_alias(settings, "options"));
+  assert!(!corpus.add_alias(settings, "options"));
+  assert_eq!(corpus.aliases(settings), vec!["prefs", "options"]);
+
+  let results = corpus.search("pref");
+  assert_eq!(results.len(), 1);
+  assert_eq!(results[0].id, settings);
+  assert_eq!(results[0].result.item, "Settings");
+  assert_eq!(results[0].alias.as_ref().map(|alias| alias.item), Some("prefs"));
+  assert!(results[0].result.highlights.is_none());
+
+  let results = corpus.search("opt");
+  let ids: Vec<_> = results.iter().map(|res| (res.id, res.alias.is_some())).collect();
+  assert_eq!(ids, vec![(options, false), (settings, true)]);
+
+  // An item matching through its own text and an alias is returned once, with the better match
+  corpus.add_alias(settings, "setup");
+  let results = corpus.search("set");
+  assert_eq!(results.len(), 1);
+  assert!(results[0].alias.is_none());
+  assert!(results[0].result.highlights.is_some());
+
+  assert!(corpus.remove_alias(settings, "prefs"));
+  assert!(corpus.search("pref").is_empty());
+  corpus.remove(settings);
+  corpus.compact();
+  assert_eq!(corpus.search("opt").len(), 1);
+}
+
+#[test]
+fn corpus_items_use_their_normalization_profile() {
+  let mut corpus = Corpus::new();
+  let street = corpus.insert_normalized("Straße", Normalization::German);
+  let city = corpus.insert_normalized("München", Normalization::German);
+  let plain = corpus.insert("Straße");
+  let istanbul = corpus.insert_normalized("İstanbul", Normalization::Turkish);
+  let river = corpus.insert_normalized("Irmak", Normalization::Turkish);
+  assert_eq!(corpus.normalization(street), Some(Normalization::German));
+  assert_eq!(corpus.normalization(plain), Some(Normalization::Unicode));
+  assert_eq!(corpus.unique_len(), 5);
+
+  let results = corpus.search("strasse");
+  assert_eq!(results.len(), 1);
+  assert_eq!(results[0].id, street);
+  assert_eq!(results[0].result.highlights, Some(vec!["", "Straße"]));
+
+  let results = corpus.search("straße");
+  let ids: Vec<_> = results.iter().map(|res| res.id).collect();
+  assert_eq!(ids.len(), 2);
+  assert!(ids.contains(&street) && ids.contains(&plain));
+
+  let results = corpus.search("muenchen");
+  assert_eq!(results[0].id, city);
+  assert_eq!(results[0].result.highlights, Some(vec!["", "München"]));
+  // A match ending part way through a folded letter highlights the whole letter
+  assert_eq!(corpus.search("mu")[0].result.highlights, Some(vec!["", "Mü", "nchen"]));
+
+  let results = corpus.search("istanbul");
+  assert_eq!(results.len(), 1);
+  assert_eq!(results[0].id, istanbul);
+  assert_eq!(results[0].result.highlights, Some(vec!["", "İstanbul"]));
+  assert!(corpus.search("irmak").is_empty());
+  assert_eq!(corpus.search("ırmak")[0].id, river);
+
+  // Updates keep the profile, and removals leave other items with the same text intact
+  corpus.update(street, "Gäßchen");
+  assert_eq!(corpus.search("gaesschen")[0].id, street);
+  corpus.remove(plain);
+  corpus.compact();
+  assert_eq!(corpus.search("gaesschen")[0].result.highlights, Some(vec!["", "Gäßchen"]));
+}
+
+#[test]
+fn corpus_search_applies_dictionaries() {
+  let mut corpus = Corpus::new();
+  let colour = corpus.insert("Colour settings");
+  let theme = corpus.insert("The dark theme");
+  let plain = corpus.search("the dark").into_iter().map(|res| res.result.score).collect::<Vec<_>>();
+
+  let mut dictionaries = Dictionaries::new();
+  dictionaries.add_synonyms(["color", "colour", "hue"]);
+  dictionaries.add_stop_word("the");
+  let version = corpus.version();
+  assert_eq!(corpus.set_dictionaries(dictionaries), 1);
+  assert!(corpus.version() > version);
+  assert_eq!(corpus.dictionaries().synonyms("Color"), ["colour", "hue"]);
+
+  let results = corpus.search("color");
+  assert_eq!(results.len(), 1);
+  assert_eq!(results[0].id, colour);
+  assert_eq!(results[0].result.highlights, Some(vec!["", "Colour", " settings"]));
+
+  // Stop-words are dropped, unless the search is only stop-words
+  let results = corpus.search("the dark");
+  assert_eq!(results[0].id, theme);
+  assert_ne!(results.iter().map(|res| res.result.score).collect::<Vec<_>>(), plain);
+  assert_eq!(corpus.search("the")[0].id, theme);
+
+  // Quoted searches aren't rewritten
+  assert!(corpus.search("\"color").is_empty());
+
+  corpus.set_dictionaries(Dictionaries::new());
+  assert!(corpus.search("color").is_empty());
+  assert_eq!(corpus.dictionary_generation(), 2);
+}
@@ -0,0 +1,49 @@
+use fuzzbunny_rs::{explain_pair, fuzzy_filter, precompute_skips_for_items};
+
+#[test]
+fn explain_pair_follows_ranking() {
+  let pairs = [("app", "Pineapple", "Apple"), ("g", "Grape", "Guava"), ("w p", "Watermelon Pie", "White Peach")];
+  for (query, a, b) in pairs {
+    let targets = precompute_skips_for_items([a, b]);
+    let results = fuzzy_filter(&targets, query);
+    let (above, below) = (results[0].item, results[1].item);
+
+    let explanation = explain_pair(query, a, b);
+    assert!(explanation.starts_with(&format!("{above:?} ranks above {below:?} for {query:?}")), "{explanation}");
+    // The order of the targets doesn't change the explanation
+    assert_eq!(explain_pair(query, b, a), explanation);
+  }
+}
+
+#[test]
+fn explain_pair_lists_differences() {
+  let explanation = explain_pair("app", "Pineapple", "Apple");
+  let mut lines = explanation.lines();
+  assert_eq!(lines.next(), Some("\"Apple\" ranks above \"Pineapple\" for \"app\" (3700 vs 2700)"));
+  assert_eq!(
+    lines.next(),
+    Some("  \"Apple\": substring match on [\"App\"], score 3700 (contiguity 2700 + start 1000 + word prefix 0)")
+  );
+  assert_eq!(
+    lines.next(),
+    Some("  \"Pineapple\": substring match on [\"app\"], score 2700 (contiguity 2700 + start 0 + word prefix 0)")
+  );
+  assert_eq!(lines.next(), Some("Differences:"));
+  assert_eq!(lines.next(), Some("  start: +1000 (1000 vs 0)"));
+  assert_eq!(lines.next(), None);
+
+  let explanation = explain_pair("w p", "White Peach", "Watermelon Pie");
+  assert!(explanation.contains("fuzzy match on [\"W\", \"P\"]"), "{explanation}");
+  assert!(explanation.contains("  word prefix: +5 (194 vs 189)"), "{explanation}");
+}
+
+#[test]
+fn explain_pair_ties_and_non_matches() {
+  let explanation = explain_pair("g", "Guava", "Grape");
+  assert!(explanation.starts_with("\"Grape\" ranks above \"Guava\""), "{explanation}");
+  assert!(explanation.ends_with("The scores are equal, so \"Grape\" ranks first as it sorts first alphabetically"));
+
+  assert_eq!(explain_pair("kiw", "Kiwi", "Lime"), "\"Kiwi\" ranks above \"Lime\" for \"kiw\", as \"Lime\" doesn't match");
+  assert_eq!(explain_pair("kiw", "Lime", "Kiwi"), "\"Kiwi\" ranks above \"Lime\" for \"kiw\", as \"Lime\" doesn't match");
+  assert_eq!(explain_pair("kiw", "Lime", "Plum"), "neither \"Lime\" nor \"Plum\" match \"kiw\"");
+}
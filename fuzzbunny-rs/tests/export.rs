@@ -0,0 +1,92 @@
+#![cfg(feature = "serde")]
+
+use fuzzbunny_rs::{Corpus, CorpusExport, ItemId, Normalization, UsageBoost};
+
+fn example_corpus() -> (Corpus, Vec<ItemId>) {
+  let mut corpus = Corpus::new();
+  corpus.set_usage_boost(UsageBoost { score_per_selection: 10, half_life: 5, max_boost: 100 });
+  let ids = corpus.extend(["Slay the dragon", "Dragon graphics", "Slay the dragon", "Sound volume"]);
+  corpus.add_tag(ids[0], "quest");
+  corpus.add_tag(ids[0], "combat");
+  corpus.add_tag(ids[1], "setting");
+  corpus.set_pinned(ids[3], true);
+  corpus.add_alias(ids[3], "Audio level");
+  corpus.record_selection(ids[1]);
+  corpus.record_selection(ids[1]);
+  corpus.remove(ids[2]);
+  (corpus, ids)
+}
+
+fn assert_same_corpus(imported: &Corpus, original: &Corpus) {
+  assert_eq!(imported.export(), original.export());
+  for search in ["drag", "s", ""] {
+    let scores = |corpus: &Corpus| -> Vec<_> {
+      corpus.search(search).into_iter().map(|res| (res.id, res.result.score, res.pinned)).collect()
+    };
+    assert_eq!(scores(imported), scores(original));
+  }
+}
+
+#[test]
+fn export_round_trips() {
+  let (corpus, ids) = example_corpus();
+  let data = corpus.export();
+  assert_eq!(data.next_id, 4);
+  assert_eq!(data.items.len(), 3);
+  assert_eq!(data.items[0].tags, vec!["combat", "quest"]);
+
+  let mut imported = Corpus::import(data).unwrap();
+  assert_same_corpus(&imported, &corpus);
+  assert_eq!(imported.search_tagged("s", &["setting"])[0].id, ids[1]);
+
+  // Removed IDs are never reused
+  assert_eq!(imported.insert("new"), ItemId(4));
+
+  // Normalization profiles are kept
+  let cave = imported.insert_normalized("Drachenhöhle", Normalization::German);
+  let reimported = Corpus::import(imported.export()).unwrap();
+  assert_eq!(reimported.normalization(cave), Some(Normalization::German));
+  assert_eq!(reimported.search("hoehle")[0].id, cave);
+}
+
+#[test]
+fn import_rejects_invalid_data() {
+  let (corpus, _) = example_corpus();
+
+  let mut data: CorpusExport = corpus.export();
+  data.items.push(data.items[0].clone());
+  assert!(Corpus::import(data).is_err());
+
+  let mut data: CorpusExport = corpus.export();
+  data.format = 99;
+  assert!(Corpus::import(data).is_err());
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn json_round_trips() {
+  let (corpus, _) = example_corpus();
+  let mut json = vec![];
+  corpus.to_json(&mut json).unwrap();
+  assert_same_corpus(&Corpus::from_json(json.as_slice()).unwrap(), &corpus);
+
+  let written = r#"{"format": 1, "next_id": 0, "usage_boost": {"score_per_selection": 100, "half_life": 50, "max_boost": 1000},
+    "items": [{"id": 7, "text": "apple", "tags": ["fruit"]}, {"id": 3, "text": "banana"}]}"#;
+  let imported = Corpus::from_json(written.as_bytes()).unwrap();
+  assert_eq!(imported.get(ItemId(7)), Some("apple"));
+  assert_eq!(imported.tags(ItemId(7)), vec!["fruit"]);
+  assert_eq!(imported.export().next_id, 8);
+
+  assert!(Corpus::from_json("{}".as_bytes()).is_err());
+}
+
+#[cfg(feature = "msgpack")]
+#[test]
+fn msgpack_round_trips() {
+  let (corpus, _) = example_corpus();
+  let mut bytes = vec![];
+  corpus.to_msgpack(&mut bytes).unwrap();
+  assert_same_corpus(&Corpus::from_msgpack(bytes.as_slice()).unwrap(), &corpus);
+
+  assert!(Corpus::from_msgpack([0xc1].as_slice()).is_err());
+}
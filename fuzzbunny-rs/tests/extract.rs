@@ -0,0 +1,28 @@
+use fuzzbunny_rs::{extract_one, fuzzy_filter, precompute_skips_for_items};
+
+#[test]
+fn extract_one_matches_first_filter_result() {
+  let lines: Vec<&str> = include_str!("gutenberg-catalog.txt").lines().collect();
+  let targets = precompute_skips_for_items(lines.iter().copied());
+
+  for search in ["alice", "p p", "The", "\"of the", "zzzzzz", ""] {
+    let results = fuzzy_filter(&targets, search);
+    assert_eq!(extract_one(search, lines.iter().copied(), 0).as_ref(), results.first(), "{search}");
+  }
+}
+
+#[test]
+fn extract_one_applies_cutoff() {
+  let colours = ["Red", "Dark Red", "Reddish Brown", "Green"];
+
+  let best = extract_one("red", colours, 0).unwrap();
+  assert_eq!((best.item, best.score), ("Red", 3700));
+  assert_eq!(best.highlights, Some(vec!["", "Red"]));
+  assert_eq!(extract_one("red", colours, 3700).unwrap().item, "Red");
+  assert!(extract_one("red", colours, 3701).is_none());
+
+  // Equal scores rank the smaller choice first
+  assert_eq!(extract_one("d", ["Dune", "Dracula"], 0).unwrap().item, "Dracula");
+  assert_eq!(extract_one("", colours, 0).unwrap().item, "Red");
+  assert!(extract_one("blue", colours, 0).is_none());
+}
@@ -0,0 +1,39 @@
+#![cfg(feature = "std")]
+
+use fuzzbunny_rs::{Corpus, FederatedSearch};
+
+#[test]
+fn federated_search_weights_and_attributes() {
+  let commands: Corpus = ["Teleport home", "Trade"].into_iter().collect();
+  let players: Corpus = ["Trader Joe", "Tess"].into_iter().collect();
+  let unweighted_commands = commands.search("tr");
+  let unweighted_players = players.search("tr");
+
+  let results = FederatedSearch::new()
+    .with_corpus("commands", &commands, 1.0)
+    .with_corpus("players", &players, 0.5)
+    .search("tr");
+  let attributed: Vec<_> = results.iter().map(|res| (res.source, res.matched.result.item, res.matched.result.score)).collect();
+  assert_eq!(attributed, vec![
+    ("commands", "Trade", unweighted_commands[0].result.score),
+    ("players", "Trader Joe", unweighted_players[0].result.score / 2),
+  ]);
+
+  // Reweighting reorders the merged results
+  let results = FederatedSearch::new()
+    .with_corpus("commands", &commands, 1.0)
+    .with_corpus("players", &players, 3.0)
+    .search("tr");
+  assert_eq!(results[0].source, "players");
+  assert_eq!(results[0].matched.id, unweighted_players[0].id);
+}
+
+#[test]
+fn federated_empty_search_keeps_corpus_order() {
+  let a: Corpus = ["b", "a"].into_iter().collect();
+  let b: Corpus = ["c"].into_iter().collect();
+  let search = FederatedSearch::new().with_corpus("a", &a, 1.0).with_corpus("b", &b, 1.0);
+  let items: Vec<_> = search.search("").into_iter().map(|res| (res.source, res.matched.result.item)).collect();
+  assert_eq!(items, vec![("a", "b"), ("a", "a"), ("b", "c")]);
+  assert!(FederatedSearch::new().search("x").is_empty());
+}
@@ -0,0 +1,50 @@
+use std::cmp::Reverse;
+
+use fuzzbunny_rs::{fuzzy_filter, fuzzy_filter_fields, precompute_skips_for_items, FieldMatch, MultiFieldTarget};
+
+#[test]
+fn fields_rank_by_weight() {
+  let targets = vec![
+    MultiFieldTarget::weighted([("Claire Bennet", 3.0), ("Cheerleader who can regenerate", 1.0), ("healing", 0.5)]),
+    MultiFieldTarget::weighted([("Adam Monroe", 3.0), ("Immortal, regenerates", 1.0), ("healing immortal", 0.5)]),
+    MultiFieldTarget::weighted([("Regenerator", 3.0), ("A fictional device", 1.0), ("", 0.5)]),
+  ];
+
+  let results = fuzzy_filter_fields(&targets, "regen");
+  let names: Vec<_> = results.iter().map(|res| res.target.field(0).unwrap()).collect();
+  assert_eq!(names, vec!["Regenerator", "Adam Monroe", "Claire Bennet"]);
+  assert_eq!(results[0].score, 3 * 8500);
+  assert_eq!(results[0].fields, vec![FieldMatch { field: 0, score: 25500, highlights: vec!["", "Regen", "erator"] }]);
+
+  // Every matching field is highlighted, while the best one gives the score
+  let results = fuzzy_filter_fields(&targets, "imm");
+  assert_eq!(results.len(), 1);
+  let fields: Vec<_> = results[0].fields.iter().map(|field| (field.field, field.highlights.clone())).collect();
+  assert_eq!(fields, vec![(1, vec!["", "Imm", "ortal, regenerates"]), (2, vec!["healing ", "imm", "ortal"])]);
+  assert_eq!(results[0].score, results[0].fields[0].score);
+}
+
+#[test]
+fn fields_match_like_fuzzy_filter() {
+  let target = MultiFieldTarget::new(["Hiro Nakamura", "Time travel"]);
+  assert_eq!(target.field_count(), 2);
+  assert_eq!(target.field(1), Some("Time travel"));
+  assert_eq!(target.weight(1), Some(1.0));
+  assert_eq!(target.field(2), None);
+  let targets = vec![target];
+  let items = precompute_skips_for_items(vec!["Hiro Nakamura", "Time travel"]);
+
+  for search in ["hn", "tt", "ro", "\"hn"] {
+    let expected: Vec<_> = fuzzy_filter(&items, search).into_iter().map(|res| (res.score, res.highlights.unwrap())).collect();
+    let results = fuzzy_filter_fields(&targets, search);
+    let mut fields: Vec<_> = results.iter().flat_map(|res| &res.fields).map(|field| (field.score, field.highlights.clone())).collect();
+    fields.sort_by_key(|field| Reverse(field.0));
+    assert_eq!(fields, expected);
+  }
+
+  // An empty search matches every field, without highlights
+  let results = fuzzy_filter_fields(&targets, "");
+  assert_eq!(results[0].score, 0);
+  assert_eq!(results[0].fields.len(), 2);
+  assert!(fuzzy_filter_fields(&targets, "zz").is_empty());
+}
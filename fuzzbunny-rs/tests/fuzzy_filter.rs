@@ -1,6 +1,12 @@
-use fuzzbunny_rs::{Highlights, fuzzy_filter, precompute_skips_for_items};
+use std::cmp::Ordering;
+
+use fuzzbunny_rs::{
+    FuzzyFilterResult, Highlights, Range, fuzzy_filter, fuzzy_filter_by, fuzzy_filter_with_min_score, fuzzy_score_item, get_target_skips,
+    precompute_skips_for_items,
+};
 
 // from https://en.wikipedia.org/wiki/List_of_Heroes_characters#Main_characters
+#[allow(clippy::redundant_static_lifetimes)]
 const HEROES_CSV: &'static str = "Claire Bennet, Rapid cellular regeneration
 Elle Bishop, Electrokinesis
 Monica Dawson, Adaptive muscle memory
@@ -24,6 +30,7 @@ fn make_heroes() -> Vec<&'static str> {
     HEROES_CSV.trim().split('\n').collect()
 }
 
+#[allow(clippy::option_filter_map)]
 fn get_highlights(search: &str) -> Vec<Highlights<'static>>{
     let heroes = precompute_skips_for_items(make_heroes());
     let results = fuzzy_filter(&heroes, search);
@@ -78,3 +85,107 @@ fn filter_matches_string_exact() {
     ];
     assert_eq!(highlights, expected);
 }
+
+#[test]
+fn precompute_large_corpus_preserves_order() {
+    let items: Vec<String> = (0..10_000).map(|i| format!("Item{} of corpus", i)).collect();
+    let targets = precompute_skips_for_items(items.iter().map(|s| s.as_str()));
+
+    assert_eq!(targets.len(), items.len());
+    for (target, item) in targets.iter().zip(&items) {
+        assert_eq!(target.0, item);
+        assert_eq!(target.1.as_ref().unwrap(), &get_target_skips(item));
+    }
+}
+
+#[test]
+fn fuzzy_filter_by_uses_comparator() {
+    let targets = precompute_skips_for_items(vec!["src/main.rs", "src/", "docs/", "README.md", "tests/"]);
+    let is_dir = |item: &str| item.ends_with('/');
+
+    let results = fuzzy_filter_by(&targets, "s", |a, b| is_dir(b.item).cmp(&is_dir(a.item)));
+    let items: Vec<&str> = results.iter().map(|res| res.item).collect();
+    assert_eq!(items, vec!["src/", "docs/", "tests/", "src/main.rs"]);
+
+    // A comparator that never decides gives the same order as fuzzy_filter
+    let results = fuzzy_filter_by(&targets, "s", |_, _| Ordering::Equal);
+    assert_eq!(results, fuzzy_filter(&targets, "s"));
+
+    // An empty search isn't sorted
+    let results = fuzzy_filter_by(&targets, "", |a, b| a.item.cmp(b.item));
+    let items: Vec<&str> = results.iter().map(|res| res.item).collect();
+    assert_eq!(items, vec!["src/main.rs", "src/", "docs/", "README.md", "tests/"]);
+}
+
+#[test]
+fn filter_multilingual_items() {
+    // Skips are byte offsets, so they always fall on character boundaries
+    assert_eq!(get_target_skips("Café Müller"), vec![0, 6, 13]);
+
+    let targets = precompute_skips_for_items(vec!["Café Müller", "Crème Brûlée", "İstanbul Airport", "東京 タワー"]);
+    let results = fuzzy_filter(&targets, "cm");
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].highlights, Some(vec!["", "C", "afé ", "M", "üller"]));
+
+    let results = fuzzy_filter(&targets, "ia");
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].highlights, Some(vec!["", "İ", "stanbul ", "A", "irport"]));
+
+    let results = fuzzy_filter(&targets, "東タ");
+    assert_eq!(results[0].highlights, Some(vec!["", "東", "京 ", "タ", "ワー"]));
+
+    // Every highlight of every result slices the item on character boundaries
+    for search in ["c", "é", "ü", "b", "ül", "r", "京", "ワ"] {
+        for result in fuzzy_filter(&targets, search) {
+            assert_eq!(result.highlights.unwrap().concat(), result.item);
+        }
+    }
+}
+
+#[test]
+fn filter_results_include_ranges() {
+    let heroes = precompute_skips_for_items(make_heroes());
+    let results = fuzzy_filter(&heroes, "mp");
+    assert_eq!(results[0].ranges, Some(vec![Range(0, 1), Range(5, 1)]));
+
+    // The ranges cover the same sections as the matched highlights
+    for result in fuzzy_filter(&heroes, "pe") {
+        let ranges = result.ranges.unwrap();
+        let matched: Vec<&str> = ranges.iter().map(|range| &result.item[range.0..range.0 + range.1]).collect();
+        let highlighted: Vec<&str> = result.highlights.unwrap().into_iter().skip(1).step_by(2).collect();
+        assert_eq!(matched, highlighted);
+    }
+}
+
+#[test]
+fn normalized_scores_are_comparable() {
+    let heroes = precompute_skips_for_items(make_heroes());
+    for search in ["", "p", "pe", "mp", "nathan", "hiro nakamura"] {
+        for result in fuzzy_filter(&heroes, search) {
+            let normalized = result.normalized_score();
+            assert!((0.0..=1.0).contains(&normalized), "{} for {:?}", normalized, result.item);
+            let string_score = fuzzy_score_item(&(result.item, None), &search.to_lowercase());
+            if let Some(string_score) = string_score {
+                assert_eq!(string_score.normalized_score(result.item), normalized);
+            }
+        }
+    }
+
+    // An exact match is fully relevant, and shorter targets are more relevant
+    let targets = precompute_skips_for_items(vec!["apple", "apples", "apple pie"]);
+    let normalized: Vec<f32> = targets.iter().map(|target| fuzzy_score_item(target, "apple").unwrap().normalized_score(target.0)).collect();
+    assert_eq!(normalized[0], 1.0);
+    assert!(normalized[0] > normalized[1] && normalized[1] > normalized[2]);
+}
+
+#[test]
+fn fuzzy_filter_with_min_score_drops_weak_matches() {
+    let heroes = precompute_skips_for_items(make_heroes());
+    for search in ["", "p", "pe", "mp"] {
+        let expected: Vec<FuzzyFilterResult> =
+            fuzzy_filter(&heroes, search).into_iter().filter(|res| search.is_empty() || res.normalized_score() >= 0.6).collect();
+        assert_eq!(fuzzy_filter_with_min_score(&heroes, search, 0.6), expected);
+        assert_eq!(fuzzy_filter_with_min_score(&heroes, search, 0.0), fuzzy_filter(&heroes, search));
+    }
+    assert!(fuzzy_filter_with_min_score(&heroes, "p", 1.0).is_empty());
+}
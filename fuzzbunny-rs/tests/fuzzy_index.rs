@@ -0,0 +1,177 @@
+#![cfg(feature = "std")]
+
+use fuzzbunny_rs::{fuzzy_filter, precompute_skips_for_items, FuzzyIndex, ItemId};
+
+#[test]
+fn index_matches_fuzzy_filter() {
+  let items = ["Claire Bennet", "Elle Bishop", "Maya Herrera", "Mohinder Suresh", "Café Müller", "Elle Bishop"];
+  let index: FuzzyIndex = items.iter().copied().collect();
+  let targets = precompute_skips_for_items(items.iter().copied());
+
+  for search in ["el", "mh", "\"he", "mü", "zz", ""] {
+    let results: Vec<_> = index.search(search).into_iter().map(|res| res.result).collect();
+    assert_eq!(results, fuzzy_filter(&targets, search));
+  }
+}
+
+#[test]
+fn index_updates_incrementally() {
+  let mut index = FuzzyIndex::new();
+  assert!(index.is_empty());
+  let hiro = index.insert("Hiro Nakamura");
+  let ando = index.insert("Ando Masahashi");
+  let peter = index.insert("Peter Petrelli");
+  assert_eq!(index.len(), 3);
+
+  assert_eq!(index.update(ando, "Ando Nakamura"), Some("Ando Masahashi".to_string()));
+  let ids: Vec<_> = index.search("nakamura").iter().map(|res| res.id).collect();
+  assert_eq!(ids, vec![ando, hiro]);
+
+  assert_eq!(index.remove(hiro), Some("Hiro Nakamura".to_string()));
+  assert_eq!(index.remove(hiro), None);
+  assert_eq!(index.update(hiro, "Hiro"), None);
+  assert!(!index.contains(hiro));
+  assert_eq!(index.get(ando), Some("Ando Nakamura"));
+
+  // IDs aren't reused after removal
+  let claude = index.insert("Claude Rains");
+  assert_ne!(claude, hiro);
+  assert_eq!(index.iter().collect::<Vec<_>>(), vec![(ando, "Ando Nakamura"), (peter, "Peter Petrelli"), (claude, "Claude Rains")]);
+  assert_eq!(index.get(ItemId(100)), None);
+
+  // Identical items rank in insertion order
+  let copy = index.insert("Claude Rains");
+  let ids: Vec<_> = index.search("claude").iter().map(|res| res.id).collect();
+  assert_eq!(ids, vec![claude, copy]);
+}
+
+#[test]
+fn ngram_index_matches_full_scan() {
+  let file = std::fs::read_to_string("tests/gutenberg-catalog.txt").unwrap();
+  let mut index: FuzzyIndex = file.lines().skip(1).collect();
+  index.extend(["United States of America", "Café Müller", "İstanbul", "tab\tseparated", "C++ / Rust"]);
+  let mut ngram_index = index.clone().with_ngram_index();
+  assert!(ngram_index.has_ngram_index() && !index.has_ngram_index());
+
+  let searches = [
+    "alice", "alice wonder", "aw", "oliver twist", "otwist", "usam", "\"the ad", "\"of the\"", "c++", "/ r", "mü",
+    "ist", "i", "b\ts", "zzzz", "", "  ",
+  ];
+  let check = |index: &FuzzyIndex, ngram_index: &FuzzyIndex| {
+    for search in searches {
+      assert_eq!(ngram_index.search(search), index.search(search), "search {search:?}");
+    }
+  };
+  check(&index, &ngram_index);
+
+  // The n-gram index is kept up to date as items change
+  let ids: Vec<ItemId> = index.iter().map(|(id, _)| id).step_by(3).collect();
+  for (i, &id) in ids.iter().enumerate() {
+    if i % 2 == 0 {
+      index.remove(id);
+      ngram_index.remove(id);
+    } else {
+      index.update(id, "Alice Twist");
+      ngram_index.update(id, "Alice Twist");
+    }
+  }
+  check(&index, &ngram_index);
+}
+
+fn results<'i>(index: &'i FuzzyIndex, search: &str) -> Vec<(ItemId, String, u32, Option<Vec<&'i str>>)> {
+  index.search(search).into_iter().map(|res| (res.id, res.result.item.to_string(), res.result.score, res.result.highlights)).collect()
+}
+
+/// Modify the body of a saved index, replacing its checksum so only the modification is invalid.
+fn modified(bytes: &[u8], modify: impl FnOnce(&mut Vec<u8>)) -> Vec<u8> {
+  let mut body = bytes[..bytes.len() - 8].to_vec();
+  modify(&mut body);
+  let checksum = body.iter().fold(0xcbf2_9ce4_8422_2325_u64, |hash, &byte| (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3));
+  body.extend_from_slice(&checksum.to_le_bytes());
+  body
+}
+
+#[test]
+fn saved_index_round_trips() {
+  let mut index: FuzzyIndex = ["Claire Bennet", "Elle Bishop", "İstanbul", "Café Müller", "Elle Bishop"].into_iter().collect();
+  let removed = index.insert("Sylar");
+  index.remove(removed);
+  // IDs far apart are stored compactly as deltas
+  for _ in 0..300 {
+    let id = index.insert("placeholder");
+    index.remove(id);
+  }
+  let peter = index.insert("Peter Petrelli");
+
+  for index in [index.clone(), index.with_ngram_index()] {
+    let mut bytes = vec![];
+    index.save(&mut bytes).unwrap();
+    let mut loaded = FuzzyIndex::load(bytes.as_slice()).unwrap();
+
+    assert_eq!(loaded.len(), 6);
+    assert_eq!(loaded.has_ngram_index(), index.has_ngram_index());
+    assert_eq!(loaded.get(peter), Some("Peter Petrelli"));
+    for search in ["el", "ist", "\"mü", "pp", "zz", ""] {
+      assert_eq!(results(&loaded, search), results(&index, search));
+    }
+
+    // The next ID isn't reused
+    assert!(loaded.insert("Sylar") > peter);
+  }
+}
+
+#[test]
+fn saved_index_rejects_corruption_and_other_versions() {
+  let index: FuzzyIndex = ["apple", "banana"].into_iter().collect();
+  let mut bytes = vec![];
+  index.save(&mut bytes).unwrap();
+  assert!(FuzzyIndex::load(bytes.as_slice()).is_ok());
+
+  let mut corrupt = bytes.clone();
+  corrupt[14] ^= 1;
+  assert!(FuzzyIndex::load(corrupt.as_slice()).is_err());
+  assert!(FuzzyIndex::load(&bytes[..bytes.len() - 3]).is_err());
+  assert!(FuzzyIndex::load(&[][..]).is_err());
+
+  // A different scoring version or unknown flags with a valid checksum are still rejected
+  assert!(FuzzyIndex::load(modified(&bytes, |body| body[5] += 1).as_slice()).is_err());
+  assert!(FuzzyIndex::load(modified(&bytes, |body| body[9] = 2).as_slice()).is_err());
+  let with_ngrams = FuzzyIndex::load(modified(&bytes, |body| body[9] = 1).as_slice()).unwrap();
+  assert!(with_ngrams.has_ngram_index());
+
+  // A skip index past the end of an item is rejected rather than panicking when searched
+  let past_end = modified(&bytes, |body| {
+    let last_skip = body.len() - 2;
+    body[last_skip] += 1;
+  });
+  assert!(FuzzyIndex::load(past_end.as_slice()).is_err());
+}
+
+#[test]
+fn saved_index_rejects_offsets_inside_characters() {
+  let save = |items: [&str; 1]| {
+    let mut bytes = vec![];
+    FuzzyIndex::from_iter(items).save(&mut bytes).unwrap();
+    bytes
+  };
+
+  // The skips of "é" are [0, 2], stored as the deltas [0, 2] before an empty list of offsets
+  let bytes = save(["é"]);
+  assert!(FuzzyIndex::load(bytes.as_slice()).is_ok());
+  let skip_inside = modified(&bytes, |body| {
+    let len = body.len();
+    assert_eq!(body[len - 4..], [2, 0, 2, 0]);
+    body[len - 3..len - 1].copy_from_slice(&[1, 1]);
+  });
+  assert!(FuzzyIndex::load(skip_inside.as_slice()).is_err());
+
+  // The offsets of "İ" (lower cased to 3 bytes) are [0, 0, 0, 2], stored as the deltas [0, 0, 0, 2]
+  let bytes = save(["İ"]);
+  assert!(FuzzyIndex::load(bytes.as_slice()).is_ok());
+  let offset_inside = modified(&bytes, |body| {
+    let len = body.len();
+    assert_eq!(body[len - 5..], [4, 0, 0, 0, 2]);
+    body[len - 4..].copy_from_slice(&[0, 1, 0, 1]);
+  });
+  assert!(FuzzyIndex::load(offset_inside.as_slice()).is_err());
+}
@@ -1,4 +1,4 @@
-use fuzzbunny_rs::fuzzy_match;
+use fuzzbunny_rs::{fuzzy_match, Range};
 
 fn check_highlights(target: &str, search: &str, expected: Vec<&str>) {
   let highlights = fuzzy_match(target, Some(search)).unwrap().highlights.unwrap();
@@ -73,6 +73,15 @@ fn matches_quotes_substrings() {
   assert!(fuzzy_match("Los Angeles", Some("\"LA")).is_none());
 }
 
+#[test]
+fn matches_empty_quotes_as_empty_search() {
+  for search in ["\"", "\"\"", " \" "] {
+    let result = fuzzy_match("Las Vegas", Some(search)).unwrap();
+    assert_eq!(result.score, 0);
+    assert_eq!(result.highlights, Some(vec!["Las Vegas"]));
+  }
+}
+
 #[test]
 fn matches_normal_with_quotes_in_middle() {
   check_highlights("abc \"def\"", "a\"def\"", vec!["", "a", "bc ", "\"def\""]);
@@ -86,3 +95,30 @@ fn matches_camel_title_initials() {
   check_highlights("fuzzBunny.ts", "fb", vec!["", "f", "uzz", "B", "unny.ts"]);
   check_highlights("fuzzBunnyIsAwesome", "bia", vec!["fuzz", "B", "unny", "I", "s", "A", "wesome"]);
 }
+
+#[test]
+fn matches_non_ascii_word_prefixes() {
+  check_highlights("Café Müller", "cm", vec!["", "C", "afé ", "M", "üller"]);
+  check_highlights("Café Müller", "café mü", vec!["", "Café Mü", "ller"]);
+  check_highlights("Ölmühle Über", "öl üb", vec!["", "Öl", "mühle ", "Üb", "er"]);
+  check_highlights("東京 タワー", "東タ", vec!["", "東", "京 ", "タ", "ワー"]);
+}
+
+#[test]
+fn matches_length_changing_lower_case() {
+  // 'İ' lower cases to two characters, which are longer than it in UTF-8
+  check_highlights("İstanbul Airport", "ia", vec!["", "İ", "stanbul ", "A", "irport"]);
+  check_highlights("Ωmega İstanbul", "stan", vec!["Ωmega İ", "stan", "bul"]);
+  check_highlights("Ωmega İstanbul", "ωm", vec!["", "Ωm", "ega İstanbul"]);
+}
+
+#[test]
+fn matches_past_spaces_inside_words() {
+  // Symbols don't start words, so the space before one is skipped inside the word and splits the range
+  let result = fuzzy_match("a 🌀 b", Some("a🌀")).unwrap();
+  assert_eq!(result.ranges, Some(vec![Range(0, 1), Range(2, 4)]));
+  assert_eq!(result.highlights, Some(vec!["", "a", " ", "🌀", " b"]));
+
+  let result = fuzzy_match("\u{9bc}\u{fffc}a 🌀 ꠰ ¡ A", Some("a🌀")).unwrap();
+  assert_eq!(result.highlights, Some(vec!["\u{9bc}\u{fffc}", "a", " ", "🌀", " ꠰ ¡ A"]));
+}
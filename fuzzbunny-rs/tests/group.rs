@@ -0,0 +1,47 @@
+#![cfg(feature = "std")]
+
+use fuzzbunny_rs::{fuzzy_filter, fuzzy_filter_grouped, precompute_skips_for_items};
+
+#[test]
+fn grouped_results_match_deduplicated_fuzzy_filter() {
+  let items: Vec<String> = (0..3000).map(|i| format!("product {} in category {}", i % 700, i % 3)).collect();
+  let names: Vec<&str> = items.iter().map(|item| item.split(" in ").next().unwrap()).collect();
+  let targets = precompute_skips_for_items(names.iter().copied());
+
+  for search in ["product 12", "p 7", "", "missing"] {
+    let grouped = fuzzy_filter_grouped(&targets, search);
+    let mut expected = fuzzy_filter(&targets, search);
+    // Only the first of each run of identical results is kept, as ties are ordered by item
+    expected.dedup_by(|a, b| a.item == b.item);
+    if search.is_empty() {
+      expected.truncate(700);
+    }
+
+    let items: Vec<(&str, u32)> = grouped.iter().map(|res| (res.result.item, res.result.score)).collect();
+    let expected: Vec<(&str, u32)> = expected.iter().map(|res| (res.item, res.score)).collect();
+    assert_eq!(items, expected);
+
+    for res in &grouped {
+      assert_eq!(res.result.index, Some(res.indices[0]));
+      assert!(res.indices.windows(2).all(|pair| pair[0] < pair[1]));
+      assert!(res.indices.iter().all(|&index| names[index] == res.result.item));
+    }
+    let grouped_count: usize = grouped.iter().map(|res| res.indices.len()).sum();
+    assert_eq!(grouped_count, fuzzy_filter(&targets, search).len());
+  }
+}
+
+#[test]
+fn groups_are_byte_identical_strings() {
+  let targets = precompute_skips_for_items(vec!["Apple", "apple", "Apple ", "Apple"]);
+
+  let results = fuzzy_filter_grouped(&targets, "apple");
+  let groups: Vec<(&str, Vec<usize>)> = results.iter().map(|res| (res.result.item, res.indices.clone())).collect();
+  assert_eq!(groups, vec![("Apple", vec![0, 3]), ("Apple ", vec![2]), ("apple", vec![1])]);
+  assert_eq!(results[0].result.highlights, Some(vec!["", "Apple"]));
+
+  // An empty search keeps the order of the first targets of the groups
+  let results = fuzzy_filter_grouped(&targets, "");
+  let firsts: Vec<usize> = results.iter().map(|res| res.indices[0]).collect();
+  assert_eq!(firsts, vec![0, 1, 2]);
+}
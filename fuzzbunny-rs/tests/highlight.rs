@@ -0,0 +1,62 @@
+use fuzzbunny_rs::highlight::{self, Formatter};
+use fuzzbunny_rs::{fuzzy_filter, fuzzy_match, precompute_skips_for_items, FuzzyFilterResult, Range};
+
+#[test]
+fn html_escapes_every_section() {
+  let result = fuzzy_match("<b>Tom & \"Jerry's\"</b>", Some("je")).unwrap();
+  assert_eq!(
+    highlight::to_html(&result, "mark", None),
+    "&lt;b&gt;Tom &amp; &quot;<mark>Je</mark>rry&#39;s&quot;&lt;/b&gt;"
+  );
+  assert_eq!(
+    highlight::to_html(&result, "span", Some("hit \"x\"")),
+    "&lt;b&gt;Tom &amp; &quot;<span class=\"hit &quot;x&quot;\">Je</span>rry&#39;s&quot;&lt;/b&gt;"
+  );
+
+  let result = fuzzy_match("a<b", Some("<")).unwrap();
+  assert_eq!(highlight::to_html(&result, "mark", None), "a<mark>&lt;</mark>b");
+}
+
+#[test]
+fn ansi_and_markdown() {
+  let highlights = vec!["the ", "u", "nited ", "s", "tates of ", "am", "erica"];
+  assert_eq!(highlight::to_ansi(&highlights, "1;31"), "the \x1b[1;31mu\x1b[0mnited \x1b[1;31ms\x1b[0mtates of \x1b[1;31mam\x1b[0merica");
+  assert_eq!(highlight::to_markdown(&highlights), "the **u**nited **s**tates of **am**erica");
+
+  // Markdown syntax in the text is escaped
+  let result = fuzzy_match("snake_case *name*", Some("case")).unwrap();
+  assert_eq!(highlight::to_markdown(&result), "snake\\_**case** \\*name\\*");
+}
+
+#[test]
+fn every_source_renders_the_same() {
+  let targets = precompute_skips_for_items(vec!["Maya Herrera", "Matt Parkman", "Mohinder Suresh"]);
+  for result in fuzzy_filter(&targets, "mh") {
+    let ranges = result.ranges.clone().unwrap();
+    let highlights = result.highlights.clone().unwrap();
+    let expected = highlight::to_markdown(&result);
+    assert_eq!(highlight::to_markdown(&highlights), expected);
+    assert_eq!(highlight::to_markdown(highlights.as_slice()), expected);
+    assert_eq!(highlight::to_markdown((result.item, ranges.as_slice())), expected);
+  }
+
+  // A result without highlights renders its item unmatched
+  let result = FuzzyFilterResult { item: "a_b", score: 0, highlights: None, ranges: None, index: None };
+  assert_eq!(highlight::to_markdown(&result), "a\\_b");
+  assert_eq!(highlight::to_markdown(("a_b", &[Range(1, 1)][..])), "a**\\_**b");
+}
+
+#[test]
+fn custom_formatter() {
+  struct Brackets;
+  impl Formatter for Brackets {
+    fn matched(&self, section: &str, out: &mut String) {
+      out.push('[');
+      out.push_str(section);
+      out.push(']');
+    }
+  }
+
+  let result = fuzzy_match("United States of America", Some("usam")).unwrap();
+  assert_eq!(highlight::render(&result, &Brackets), "[U]nited [S]tates of [Am]erica");
+}
@@ -0,0 +1,83 @@
+#![cfg(feature = "std")]
+
+use std::path::PathBuf;
+
+use fuzzbunny_rs::{Corpus, Normalization};
+
+fn temp_path(name: &str) -> PathBuf {
+  let path = std::env::temp_dir().join(format!("fuzzbunny-{}-{}", name, std::process::id()));
+  let _ = std::fs::remove_file(&path);
+  path
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+  bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3))
+}
+
+fn results(corpus: &Corpus, search: &str) -> Vec<(u64, String, u32)> {
+  corpus.search(search).into_iter().map(|res| (res.id.0, res.result.item.to_string(), res.result.score)).collect()
+}
+
+#[test]
+fn index_round_trips() {
+  let mut corpus: Corpus = ["Slay the dragon", "Sound volume", "Slay the dragon", "Dragon graphics"].into_iter().collect();
+  let sound = corpus.search("sound")[0].id;
+  corpus.remove(sound);
+  corpus.insert_normalized("Drachenhöhle", Normalization::German);
+  corpus.set_memory_budget(Some(0));
+
+  let mut index = vec![];
+  corpus.write_index(&mut index).unwrap();
+  let loaded = Corpus::read_index(index.as_slice()).unwrap();
+
+  for search in ["drag", "s", "hoehle", ""] {
+    assert_eq!(results(&loaded, search), results(&corpus, search));
+  }
+  assert_eq!(loaded.unique_len(), 3);
+  assert!(loaded.derived_memory() > 0);
+}
+
+#[test]
+fn index_rejects_corruption_and_other_versions() {
+  let corpus: Corpus = ["apple", "banana"].into_iter().collect();
+  let mut index = vec![];
+  corpus.write_index(&mut index).unwrap();
+
+  let mut corrupt = index.clone();
+  corrupt[20] ^= 1;
+  assert!(Corpus::read_index(corrupt.as_slice()).is_err());
+  assert!(Corpus::read_index(&index[..index.len() - 3]).is_err());
+
+  // A different scoring version with a valid checksum is still rejected
+  let mut other_version = index[..index.len() - 8].to_vec();
+  other_version[5] += 1;
+  let checksum = fnv1a(&other_version);
+  other_version.extend_from_slice(&checksum.to_le_bytes());
+  assert!(Corpus::read_index(other_version.as_slice()).is_err());
+}
+
+#[test]
+fn load_falls_back_to_rebuilding() {
+  let source = temp_path("source.txt");
+  let index = temp_path("source.idx");
+  std::fs::write(&source, "apple\nbanana\n\napplication\n").unwrap();
+
+  // Missing index is built from the source and saved
+  let built = Corpus::load(&index, &source).unwrap();
+  assert_eq!(built.len(), 3);
+  assert!(index.exists());
+  let loaded = Corpus::load(&index, "/nonexistent/source.txt").unwrap();
+  assert_eq!(results(&loaded, "app"), results(&built, "app"));
+
+  // Corrupt index is rebuilt
+  let mut bytes = std::fs::read(&index).unwrap();
+  let last = bytes.len() - 1;
+  bytes[last] ^= 0xff;
+  std::fs::write(&index, bytes).unwrap();
+  assert!(Corpus::load(&index, "/nonexistent/source.txt").is_err());
+  assert_eq!(results(&Corpus::load(&index, &source).unwrap(), "app"), results(&built, "app"));
+  assert!(Corpus::read_index(std::fs::File::open(&index).unwrap()).is_ok());
+
+  let _ = std::fs::remove_file(&source);
+  let _ = std::fs::remove_file(&index);
+}
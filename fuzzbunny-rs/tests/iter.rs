@@ -0,0 +1,43 @@
+use fuzzbunny_rs::{fuzzy_filter, fuzzy_filter_iter};
+
+#[test]
+fn iter_yields_matches_in_order() {
+  let file = std::fs::read_to_string("tests/gutenberg-catalog.txt").unwrap();
+  let targets: Vec<_> = file.lines().skip(1).map(|line| (line, None)).collect();
+
+  for search in ["alice", "oliver twist", "\"the", "zzzz", ""] {
+    let mut expected = fuzzy_filter(&targets, search);
+    let mut results: Vec<_> = fuzzy_filter_iter(file.lines().skip(1), search).collect();
+    // The iterator yields matches in the order of the lines
+    let positions: Vec<_> = results.iter().map(|res| res.item.as_ptr()).collect();
+    assert!(positions.is_sorted(), "search {search:?}");
+
+    results.sort_by(|a, b| b.cmp(a));
+    expected.sort_by(|a, b| b.cmp(a));
+    assert_eq!(results, expected, "search {search:?}");
+  }
+}
+
+#[test]
+fn iter_scores_lazily() {
+  let mut scored = 0;
+  let items = ["apple", "banana", "application", "grape"].into_iter().inspect(|_| scored += 1);
+
+  let results: Vec<_> = fuzzy_filter_iter(items, "app").take(1).collect();
+  assert_eq!(results[0].highlights, Some(vec!["", "app", "le"]));
+  assert_eq!(scored, 1);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn par_iter_matches_iter() {
+  use fuzzbunny_rs::fuzzy_filter_par_iter;
+  use rayon::prelude::*;
+
+  let file = std::fs::read_to_string("tests/gutenberg-catalog.txt").unwrap();
+  let lines: Vec<&str> = file.lines().collect();
+
+  let results: Vec<_> = fuzzy_filter_par_iter(lines.par_iter().copied(), "alice").collect();
+  let expected: Vec<_> = fuzzy_filter_iter(lines.iter().copied(), "alice").collect();
+  assert_eq!(results, expected);
+}
@@ -0,0 +1,99 @@
+#![cfg(feature = "std")]
+
+use fuzzbunny_rs::{read_journal, JournalEntry, JournalWriter, JournaledCorpus, ItemId};
+use std::path::PathBuf;
+
+fn journal_path(name: &str) -> PathBuf {
+  let path = std::env::temp_dir().join(format!("fuzzbunny-{}-{}.journal", name, std::process::id()));
+  let _ = std::fs::remove_file(&path);
+  path
+}
+
+fn items(corpus: &JournaledCorpus) -> Vec<(ItemId, String)> {
+  corpus.corpus().iter().map(|(id, text)| (id, text.to_string())).collect()
+}
+
+#[test]
+fn journal_replays_mutations() {
+  let path = journal_path("replay");
+
+  let mut corpus = JournaledCorpus::open(&path).unwrap();
+  let apple = corpus.insert("apple").unwrap();
+  let banana = corpus.insert("banana").unwrap();
+  corpus.insert("cherry").unwrap();
+  corpus.update(apple, "apple pie").unwrap();
+  corpus.remove(banana).unwrap();
+  let expected = items(&corpus);
+  drop(corpus);
+
+  let mut corpus = JournaledCorpus::open(&path).unwrap();
+  assert_eq!(items(&corpus), expected);
+  assert_eq!(corpus.insert("date").unwrap(), ItemId(3));
+
+  std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn journal_compaction_keeps_items_and_ids() {
+  let path = journal_path("compact");
+
+  let mut corpus = JournaledCorpus::open(&path).unwrap();
+  for i in 0..100 {
+    let id = corpus.insert(format!("item {}", i)).unwrap();
+    if i % 2 == 0 {
+      corpus.remove(id).unwrap();
+    }
+  }
+  corpus.flush().unwrap();
+  let len_before = std::fs::metadata(&path).unwrap().len();
+  corpus.compact().unwrap();
+  corpus.insert("after compaction").unwrap();
+  let expected = items(&corpus);
+  drop(corpus);
+
+  assert!(std::fs::metadata(&path).unwrap().len() < len_before);
+  let corpus = JournaledCorpus::open(&path).unwrap();
+  assert_eq!(items(&corpus), expected);
+  assert_eq!(expected.last().unwrap().0, ItemId(100));
+
+  std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn journal_ignores_truncated_entry() {
+  let mut journal = JournalWriter::new(vec![]).unwrap();
+  journal.record(&JournalEntry::Insert { id: ItemId(0), text: "apple".to_string() }).unwrap();
+  journal.record(&JournalEntry::Insert { id: ItemId(1), text: "banana".to_string() }).unwrap();
+  let mut bytes = journal.into_inner();
+  bytes.truncate(bytes.len() - 3);
+
+  let entries = read_journal(bytes.as_slice()).unwrap();
+  assert_eq!(entries, vec![JournalEntry::Insert { id: ItemId(0), text: "apple".to_string() }]);
+  assert!(read_journal(&b"nope"[..]).is_err());
+}
+
+#[test]
+fn journal_ignores_truncated_text_with_a_huge_length() {
+  let mut bytes = JournalWriter::new(vec![]).unwrap().into_inner();
+  bytes.push(1);
+  bytes.extend(0u64.to_le_bytes());
+  bytes.extend(u32::MAX.to_le_bytes());
+  bytes.extend(b"apple");
+
+  assert_eq!(read_journal(bytes.as_slice()).unwrap(), vec![]);
+}
+
+#[test]
+fn journal_opens_truncated_header_as_empty() {
+  let path = journal_path("truncated-header");
+  std::fs::write(&path, b"FZ").unwrap();
+
+  let mut corpus = JournaledCorpus::open(&path).unwrap();
+  assert!(items(&corpus).is_empty());
+  let id = corpus.insert("apple").unwrap();
+  corpus.flush().unwrap();
+
+  let corpus = JournaledCorpus::open(&path).unwrap();
+  assert_eq!(items(&corpus), vec![(id, "apple".to_string())]);
+  std::fs::remove_file(&path).unwrap();
+}
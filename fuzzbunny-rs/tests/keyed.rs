@@ -0,0 +1,43 @@
+use fuzzbunny_rs::{fuzzy_filter, fuzzy_filter_map, precompute_skips_for_items};
+
+#[derive(Debug)]
+struct Hero {
+  name: String,
+  id: u32,
+}
+
+fn heroes() -> Vec<Hero> {
+  ["Claire Bennet", "Elle Bishop", "Maya Herrera", "Elle Bishop", "Café Müller"]
+    .iter()
+    .zip(1..)
+    .map(|(name, id)| Hero { name: name.to_string(), id })
+    .collect()
+}
+
+#[test]
+fn map_matches_fuzzy_filter() {
+  let heroes = heroes();
+  let names: Vec<&str> = heroes.iter().map(|hero| hero.name.as_str()).collect();
+  let targets = precompute_skips_for_items(names);
+
+  for search in ["el", "mh", "\"he", "mü", "zz", ""] {
+    let results = fuzzy_filter_map(&heroes, search, |hero| &hero.name);
+    let expected = fuzzy_filter(&targets, search);
+    assert_eq!(results.len(), expected.len());
+    for (result, expected) in results.iter().zip(expected) {
+      assert_eq!((result.key, result.score, Some(result.highlights.clone())), (expected.item, expected.score, expected.highlights));
+      assert_eq!(result.item.name, result.key);
+    }
+  }
+}
+
+#[test]
+fn map_references_items() {
+  let heroes = heroes();
+
+  // Identical keys are ranked in slice order
+  let results = fuzzy_filter_map(&heroes, "elle", |hero| &hero.name);
+  let ids: Vec<_> = results.iter().map(|res| (res.index, res.item.id)).collect();
+  assert_eq!(ids, vec![(1, 2), (3, 4)]);
+  assert_eq!(results[0].clone(), results[0]);
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "std")]
+
+use std::cell::Cell;
+
+use fuzzbunny_rs::{Corpus, ItemId, LazyCorpus};
+
+#[test]
+fn lazy_search_pulls_only_needed_batches() {
+  let pulled = Cell::new(0);
+  let source = (0..10_000).map(|i| {
+    pulled.set(pulled.get() + 1);
+    if i % 10 == 0 { format!("quest {i}") } else { format!("setting {i}") }
+  });
+  let mut corpus = LazyCorpus::new(source).with_batch_size(50);
+  assert_eq!(pulled.get(), 0);
+
+  let results = corpus.search("quest", 12);
+  assert_eq!(results.len(), 15);
+  assert_eq!(pulled.get(), 150);
+
+  // Already materialized items satisfy later searches without pulling
+  assert_eq!(corpus.search("quest", 10).len(), 15);
+  assert_eq!(pulled.get(), 150);
+  assert!(!corpus.is_exhausted());
+}
+
+#[test]
+fn lazy_corpus_matches_eager_corpus() {
+  let items = ["apple", "banana", "application", "cherry", "grape"];
+  let eager: Corpus = items.into_iter().collect();
+  let mut lazy = LazyCorpus::new(items).with_batch_size(2);
+
+  assert_eq!(lazy.get(ItemId(2)), Some("application"));
+  assert_eq!(lazy.materialized().len(), 3);
+  assert_eq!(lazy.get(ItemId(10)), None);
+  assert!(lazy.is_exhausted());
+
+  let lazy_results: Vec<_> = lazy.search("ap", usize::MAX).into_iter().map(|res| (res.id, res.result)).collect();
+  let eager_results: Vec<_> = eager.search("ap").into_iter().map(|res| (res.id, res.result)).collect();
+  assert_eq!(lazy_results, eager_results);
+  assert_eq!(lazy.pull(10), vec![]);
+}
@@ -0,0 +1,67 @@
+#![cfg(feature = "std")]
+
+use fuzzbunny_rs::{
+  fuzzy_filter, fuzzy_filter_with_options, precompute_skips_for_items, Corpus, FuzzyMatcher, MatchMode, MatchOptions, Matcher, Range,
+  StringScore, Target,
+};
+
+/// An alternate engine that only matches items starting with the search.
+struct StartsWith;
+
+impl Matcher for StartsWith {
+  fn name(&self) -> &str {
+    "starts-with"
+  }
+
+  fn score(&self, target: &Target<'_>, search: &str) -> Option<StringScore> {
+    target.0.to_lowercase().starts_with(search).then(|| StringScore { score: 1000 - target.0.len() as u32, ranges: vec![Range(0, search.len())] })
+  }
+}
+
+#[test]
+fn built_in_engines_match_their_functions() {
+  let targets = precompute_skips_for_items(vec!["Git: Commit", "Git: Checkout", "gcc", "Go to Config", ""]);
+  let initials = MatchOptions::default().with_mode(MatchMode::Initials);
+
+  for search in ["gc", "g c", "\"gc", "", "commit"] {
+    assert_eq!(FuzzyMatcher.filter(&targets, search), fuzzy_filter(&targets, search));
+    assert_eq!(initials.filter(&targets, search), fuzzy_filter_with_options(&targets, search, &initials));
+  }
+}
+
+#[test]
+fn engines_are_swappable_at_runtime() {
+  let targets = precompute_skips_for_items(vec!["Settings", "Open settings", "Set theme"]);
+  let engines: Vec<Box<dyn Matcher>> = vec![Box::new(FuzzyMatcher), Box::new(StartsWith)];
+
+  let items: Vec<Vec<&str>> = engines
+    .iter()
+    .map(|engine| engine.filter(&targets, "SET").iter().map(|res| res.item).collect())
+    .collect();
+  assert_eq!(items, vec![vec!["Set theme", "Settings", "Open settings"], vec!["Settings", "Set theme"]]);
+
+  let results = StartsWith.filter(&targets, "set");
+  assert_eq!(results[0].highlights, Some(vec!["", "Set", "tings"]));
+}
+
+#[test]
+fn engines_search_corpora() {
+  let mut corpus = Corpus::new();
+  let ids = corpus.extend(["Settings", "Open settings", "Set theme"]);
+  corpus.set_pinned(ids[1], true);
+
+  let fuzzy: Vec<_> = FuzzyMatcher.search_corpus(&corpus, "set").iter().map(|found| found.id).collect();
+  let expected: Vec<_> = corpus.search("set").iter().map(|found| found.id).collect();
+  assert_eq!(fuzzy, expected);
+
+  let matches = StartsWith.search_corpus(&corpus, "set");
+  let found: Vec<_> = matches.iter().map(|found| found.id).collect();
+  assert_eq!(found, vec![ids[0], ids[2]]);
+
+  // Pinned items still rank first
+  let matches = StartsWith.search_corpus(&corpus, "");
+  assert_eq!(matches.len(), 3);
+  let matches = MatchOptions::default().search_corpus(&corpus, "set");
+  assert_eq!(matches[0].id, ids[1]);
+  assert!(matches[0].pinned);
+}
@@ -0,0 +1,42 @@
+use fuzzbunny_rs::{fuzzy_match, precompute_skips_for_items, score_matrix};
+
+#[test]
+fn matrix_matches_fuzzy_match() {
+  let lines: Vec<&str> = include_str!("gutenberg-catalog.txt").lines().take(2000).collect();
+  let targets = precompute_skips_for_items(lines.iter().copied());
+  let queries = ["the", "alice", "p p", "\"of", "", "zzz"];
+
+  let matrix = score_matrix(&queries, &targets, 0);
+  assert_eq!((matrix.query_count(), matrix.target_count()), (queries.len(), lines.len()));
+
+  let dense = matrix.to_dense();
+  for (query, row) in queries.iter().zip(&dense) {
+    let expected: Vec<Option<u32>> = lines.iter().map(|line| fuzzy_match(line, Some(query)).map(|res| res.score)).collect();
+    assert_eq!(row, &expected, "{query}");
+  }
+  assert_eq!(matrix.iter().count(), matrix.match_count());
+  assert!(matrix.iter().all(|(query, target, score)| dense[query][target] == Some(score)));
+}
+
+#[test]
+fn matrix_applies_cutoff() {
+  let targets = precompute_skips_for_items(vec!["Mercury", "Venus", "Earth", "Mars"]);
+  let queries = vec!["m".to_string(), "ar".to_string(), "pluto".to_string()];
+
+  let matrix = score_matrix(&queries, &targets, 0);
+  assert_eq!(matrix.row(0), &[(0, 1300), (3, 1300)]);
+  assert_eq!(matrix.row(1), &[(2, 1200), (3, 1200)]);
+  assert!(matrix.row(2).is_empty());
+  assert_eq!(matrix.get(1, 2), Some(1200));
+  assert_eq!(matrix.get(1, 0), None);
+  assert_eq!(matrix.get(5, 0), None);
+
+  let sparse = score_matrix(&queries, &targets, 1250);
+  assert_eq!(sparse.match_count(), 2);
+  assert_eq!(sparse.row(0), matrix.row(0));
+  assert_eq!(sparse.row(1), &[]);
+
+  let empty = score_matrix::<&str>(&[], &targets, 0);
+  assert_eq!((empty.query_count(), empty.match_count()), (0, 0));
+  assert_eq!(score_matrix(&queries, &[], 0).to_dense(), vec![Vec::<Option<u32>>::new(); 3]);
+}
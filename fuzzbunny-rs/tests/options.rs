@@ -0,0 +1,345 @@
+use fuzzbunny_rs::{
+  fuzzy_filter, fuzzy_filter_with_options, fuzzy_score_item_with_options, precompute_skips_for_items, Anchor, MatchMode, MatchOptions,
+  Preset, Range, ScoringConfig,
+};
+
+#[test]
+fn default_options_match_fuzzy_filter() {
+  let targets = precompute_skips_for_items(include_str!("gutenberg-catalog.txt").lines());
+  for search in ["the", "p p", "\"of the", "", "zzz"] {
+    assert_eq!(fuzzy_filter_with_options(&targets, search, &MatchOptions::default()), fuzzy_filter(&targets, search));
+  }
+}
+
+#[test]
+fn initials_match_distinct_words() {
+  let targets = precompute_skips_for_items(vec![
+    "Git: Commit",
+    "gcc",
+    "View: Toggle Word Wrap",
+    "openFileInNewWindow",
+    "snake_case_name",
+    "g",
+  ]);
+  let options = MatchOptions::default().with_mode(MatchMode::Initials);
+  let items = |search| -> Vec<&str> {
+    fuzzy_filter_with_options(&targets, search, &options).into_iter().map(|res| res.item).collect()
+  };
+
+  assert_eq!(items("gc"), vec!["Git: Commit"]);
+  assert_eq!(items("G C"), vec!["Git: Commit"]);
+  assert_eq!(items("vtww"), vec!["View: Toggle Word Wrap"]);
+  assert_eq!(items("vww"), vec!["View: Toggle Word Wrap"]);
+  // Every character needs its own word
+  assert!(items("vwww").is_empty());
+  assert_eq!(items("ofinw"), vec!["openFileInNewWindow"]);
+  assert_eq!(items("scn"), vec!["snake_case_name"]);
+  // The order of the words matters
+  assert!(items("cg").is_empty());
+  assert!(items("\"gc").is_empty());
+  assert_eq!(items("").len(), 6);
+
+  let results = fuzzy_filter_with_options(&targets, "tw", &options);
+  assert_eq!(results[0].highlights, Some(vec!["View: ", "T", "oggle ", "W", "ord Wrap"]));
+}
+
+#[test]
+fn substring_mode_matches_quoted_searches() {
+  let targets = precompute_skips_for_items(include_str!("gutenberg-catalog.txt").lines());
+  let options = MatchOptions::default().with_mode(MatchMode::Substring);
+  for search in ["of the", "p p", "Ton", "zzz"] {
+    let quoted = fuzzy_filter(&targets, &format!("\"{}\"", search));
+    assert_eq!(fuzzy_filter_with_options(&targets, search, &options), quoted);
+    assert_eq!(fuzzy_filter_with_options(&targets, &format!("\"{}", search), &options), quoted);
+  }
+
+  // There is no fallback to matching the prefixes of words
+  let targets = precompute_skips_for_items(vec!["Washington", "the Toronto news"]);
+  let results = fuzzy_filter_with_options(&targets, "ton", &options);
+  assert_eq!(results.len(), 1);
+  assert_eq!(results[0].highlights, Some(vec!["Washing", "ton"]));
+  assert_eq!(fuzzy_filter(&targets, "ton").len(), 2);
+}
+
+#[test]
+fn word_prefix_mode_matches_the_start_of_words() {
+  let targets = precompute_skips_for_items(vec![
+    "New York",
+    "Yorkshire Pudding Cookbook",
+    "Porkyorkie",
+    "newYorker",
+    "İstanbul to York",
+  ]);
+  let options = MatchOptions::default().with_mode(MatchMode::WordPrefix);
+  let results = |search| fuzzy_filter_with_options(&targets, search, &options);
+  let items = |search| -> Vec<&str> { results(search).into_iter().map(|res| res.item).collect() };
+
+  assert_eq!(items("york"), vec!["Yorkshire Pudding Cookbook", "newYorker", "New York", "İstanbul to York"]);
+  assert_eq!(items("new y"), vec!["New York"]);
+  assert_eq!(items("\"pud"), vec!["Yorkshire Pudding Cookbook"]);
+  // Prefixes aren't matched fuzzily
+  assert!(items("ny").is_empty());
+  assert!(items("ork").is_empty());
+  assert_eq!(items("").len(), 5);
+
+  assert_eq!(results("york")[1].highlights, Some(vec!["new", "York", "er"]));
+  // Ranges are offsets into the original text, even where lower casing changes its length
+  assert_eq!(results("york")[3].highlights, Some(vec!["İstanbul to ", "York"]));
+}
+
+#[test]
+fn exact_mode_matches_whole_targets() {
+  let targets = precompute_skips_for_items(vec!["Apple", "apple pie", "  APPLE ", "Pineapple"]);
+  let options = MatchOptions::default().with_mode(MatchMode::Exact);
+  let results = fuzzy_filter_with_options(&targets, " apple", &options);
+  let items: Vec<&str> = results.iter().map(|res| res.item).collect();
+
+  assert_eq!(items, vec!["Apple", "  APPLE "]);
+  assert_eq!(results[1].highlights, Some(vec!["  ", "APPLE", " "]));
+  assert_eq!(fuzzy_filter_with_options(&targets, "\"apple pie\"", &options).len(), 1);
+  assert!(fuzzy_filter_with_options(&targets, "appl", &options).is_empty());
+}
+
+#[test]
+fn anchors_constrain_where_matches_start() {
+  let targets = precompute_skips_for_items(vec!["Snapshot Apply", "Git: Commit gcc", "Capybara", "apple pie"]);
+  let highlights = |search, options: &MatchOptions| -> Vec<(&str, Vec<&str>)> {
+    fuzzy_filter_with_options(&targets, search, options).into_iter().map(|res| (res.item, res.highlights.unwrap())).collect()
+  };
+  let start = MatchOptions::default().with_anchor(Anchor::StringStart);
+  let word = MatchOptions::default().with_anchor(Anchor::WordStart);
+
+  assert_eq!(highlights("ap", &start), vec![("apple pie", vec!["", "ap", "ple pie"])]);
+  assert_eq!(
+    highlights("ap", &word),
+    vec![("apple pie", vec!["", "ap", "ple pie"]), ("Snapshot Apply", vec!["Snapshot ", "Ap", "ply"])]
+  );
+  assert_eq!(fuzzy_filter_with_options(&targets, "ap", &MatchOptions::default()).len(), 3);
+
+  // A substring elsewhere doesn't stop a match of word prefixes at the anchor
+  assert_eq!(highlights("gc", &start), vec![("Git: Commit gcc", vec!["", "G", "it: ", "C", "ommit gcc"])]);
+  assert_eq!(highlights("gc", &MatchOptions::default()), vec![("Git: Commit gcc", vec!["Git: Commit ", "gc", "c"])]);
+  // Quoted searches only match substrings at the anchor
+  assert!(highlights("\"gc", &start).is_empty());
+  assert_eq!(highlights("\"pie", &word), vec![("apple pie", vec!["apple ", "pie"])]);
+  assert_eq!(fuzzy_filter_with_options(&targets, "", &start).len(), 4);
+
+  // Anchors apply to the other match modes
+  let substring = start.clone().with_mode(MatchMode::Substring);
+  assert_eq!(highlights("snap", &substring).len(), 1);
+  assert!(highlights("shot", &substring).is_empty());
+  assert!(highlights("shot", &word.clone().with_mode(MatchMode::Substring)).is_empty());
+  assert!(highlights("pie", &start.clone().with_mode(MatchMode::WordPrefix)).is_empty());
+  assert_eq!(highlights("sa", &start.clone().with_mode(MatchMode::Initials)).len(), 1);
+  // Typos aren't tolerated for anchored matches
+  assert!(highlights("aple", &start.with_max_typos(1)).is_empty());
+}
+
+#[test]
+fn search_range_restricts_matches() {
+  let paths = vec!["src/options/mod.rs", "options/src/lib.rs", "docs/Options.md"];
+  let targets = precompute_skips_for_items(paths.clone());
+  let basename = |path: &str| path.rfind('/').map_or(0, |slash| slash + 1)..path.len();
+
+  let results: Vec<(&str, Vec<Range>)> = targets
+    .iter()
+    .filter_map(|target| {
+      let options = MatchOptions::default().with_search_range(basename(target.0));
+      let string_score = fuzzy_score_item_with_options(target, "o", &options)?;
+      Some((target.0, string_score.ranges))
+    })
+    .collect();
+  assert_eq!(results, vec![("src/options/mod.rs", vec![Range(13, 1)]), ("docs/Options.md", vec![Range(5, 1)])]);
+
+  // The range is scored as if it were the whole target, with anchors relative to it
+  let options = MatchOptions::default().with_search_range(4..14).with_anchor(Anchor::StringStart);
+  let string_score = fuzzy_score_item_with_options(&targets[0], "om", &options).unwrap();
+  assert_eq!(string_score.ranges, vec![Range(4, 1), Range(12, 1)]);
+  assert!(fuzzy_score_item_with_options(&(paths[0], None), "om", &options).is_some());
+  assert!(fuzzy_score_item_with_options(&targets[0], "src", &options).is_none());
+
+  // Ranges are clipped to the target, and must fall on character boundaries
+  assert!(fuzzy_score_item_with_options(&("docs", None), "d", &options).is_none());
+  let clipped = MatchOptions::default().with_search_range(2..100);
+  assert_eq!(fuzzy_score_item_with_options(&("docs", None), "cs", &clipped).unwrap().ranges, vec![Range(2, 2)]);
+  let mid_char = MatchOptions::default().with_search_range(1..3);
+  assert!(fuzzy_score_item_with_options(&("éa", None), "a", &mid_char).is_none());
+}
+
+#[test]
+fn best_alignment_takes_the_highest_scoring_alignment() {
+  let best = MatchOptions::default().with_best_alignment(true);
+  let score = |target, search, options: &MatchOptions| fuzzy_score_item_with_options(&(target, None), search, options);
+
+  let first = score("Fun Big Apple Zoo, Foo Baz", "fbaz", &MatchOptions::default()).unwrap();
+  let aligned = score("Fun Big Apple Zoo, Foo Baz", "fbaz", &best).unwrap();
+  assert_eq!(first.ranges, vec![Range(0, 1), Range(4, 1), Range(8, 1), Range(14, 1)]);
+  assert_eq!(aligned.ranges, vec![Range(19, 1), Range(23, 3)]);
+  assert!(aligned.score > first.score);
+
+  // Matches are never worse, and substring matches are unchanged
+  let targets = precompute_skips_for_items(include_str!("gutenberg-catalog.txt").lines().take(5000));
+  for search in ["the", "p p", "alwo", "\"of the", "tadv"] {
+    let first = fuzzy_filter_with_options(&targets, search, &MatchOptions::default());
+    let aligned = fuzzy_filter_with_options(&targets, search, &best);
+    assert_eq!(aligned.len(), first.len());
+    for target in &targets {
+      let first = fuzzy_score_item_with_options(target, search, &MatchOptions::default());
+      let aligned = fuzzy_score_item_with_options(target, search, &best);
+      assert_eq!(aligned.is_some(), first.is_some());
+      if let (Some(first), Some(aligned)) = (first, aligned) {
+        assert!(aligned.score >= first.score);
+        if first.ranges.len() == 1 {
+          assert_eq!((aligned.score, aligned.ranges), (first.score, first.ranges));
+        }
+      }
+    }
+  }
+}
+
+#[test]
+fn presets_configure_options() {
+  let targets = precompute_skips_for_items(vec!["View: Toggle Word Wrap", "Preferences: Open Keyboard Shortcuts", "Toggle"]);
+
+  let palette = fuzzy_filter_with_options(&targets, "pok", &Preset::CommandPalette.into());
+  assert_eq!(palette.len(), 1);
+  assert_eq!(palette[0].item, "Preferences: Open Keyboard Shortcuts");
+  // Loose matches inside words are left out of the palette
+  assert!(fuzzy_filter_with_options(&targets, "ggle", &Preset::CommandPalette.into()).is_empty());
+
+  for preset in [Preset::FilePaths, Preset::PersonNames, Preset::CodeIdentifiers] {
+    assert_eq!(preset.options().mode, MatchMode::Fuzzy);
+    assert_eq!(fuzzy_filter_with_options(&targets, "ggle", &preset.into()).len(), 2);
+  }
+  assert_eq!(Preset::ALL.len(), 4);
+
+  // Shorter paths rank higher for the same match
+  let paths = precompute_skips_for_items(vec!["src/lib/util/mod.rs", "src/lib.rs"]);
+  let results = fuzzy_filter_with_options(&paths, "lib", &Preset::FilePaths.into());
+  assert_eq!(results[0].item, "src/lib.rs");
+  assert!(results[0].score > results[1].score);
+}
+
+#[test]
+fn numeric_equivalence_matches_by_value() {
+  let targets = precompute_skips_for_items(vec!["Release v1.00", "Release v1.5", "Item 02", "Item 20", "lib-1.02.3"]);
+  let options = MatchOptions::default().with_numeric_equivalence(true);
+  let items = |search: &str| -> Vec<&str> {
+    let mut items: Vec<&str> = fuzzy_filter_with_options(&targets, search, &options).iter().map(|res| res.item).collect();
+    items.sort_unstable();
+    items
+  };
+
+  // "1.0" is "1", which is also a prefix of "1.5"
+  assert_eq!(items("v1.0"), vec!["Release v1.00", "Release v1.5"]);
+  assert_eq!(items("v1.50"), vec!["Release v1.5"]);
+  assert_eq!(items("item 2"), vec!["Item 02", "Item 20"]);
+  assert_eq!(items("item 002"), vec!["Item 02", "Item 20"]);
+  assert_eq!(items("1.2.3"), vec!["lib-1.02.3"]);
+
+  // Without the option, numbers only match as text
+  assert!(fuzzy_filter_with_options(&targets, "v1.50", &MatchOptions::default()).is_empty());
+  assert!(fuzzy_filter_with_options(&targets, "1.2.3", &MatchOptions::default()).is_empty());
+
+  // Highlights reference the original text, including the ignored zeros
+  let results = fuzzy_filter_with_options(&targets, "v1.0", &options);
+  assert_eq!(results[0].item, "Release v1.00");
+  assert_eq!(results[0].highlights, Some(vec!["Release ", "v1.00"]));
+  let results = fuzzy_filter_with_options(&targets, "item 2", &options);
+  assert_eq!(results[0].item, "Item 02");
+  assert_eq!(results[0].highlights, Some(vec!["", "Item 02"]));
+}
+
+#[test]
+fn scoring_config_weights_matches() {
+  let targets = precompute_skips_for_items(vec!["Carpet", "Red Car", "Scarf", "Cat Art Room"]);
+  let scores = |config: ScoringConfig| -> Vec<(&str, u32)> {
+    let options = MatchOptions::default().with_scoring_config(config);
+    fuzzy_filter_with_options(&targets, "car", &options).iter().map(|res| (res.item, res.score)).collect()
+  };
+
+  // The default config scores the same as fuzzy_filter
+  let default_scores: Vec<(&str, u32)> = fuzzy_filter(&targets, "car").iter().map(|res| (res.item, res.score)).collect();
+  assert_eq!(scores(ScoringConfig::default()), default_scores);
+  assert_eq!(default_scores, vec![("Carpet", 3700), ("Red Car", 2896), ("Scarf", 2700), ("Cat Art Room", 2692)]);
+
+  let config = ScoringConfig::default().with_start_bonus(0).with_prefix_bonus(2000).with_contiguous_weight(100);
+  assert_eq!(scores(config), vec![("Red Car", 2896), ("Cat Art Room", 2492), ("Carpet", 900), ("Scarf", 900)]);
+
+  // Only the second range of "Cat Art Room" decays
+  let decayed = scores(ScoringConfig::default().with_range_decay_percent(50));
+  assert_eq!(decayed[3], ("Cat Art Room", 2446));
+
+  let normalized = scores(ScoringConfig::default().with_length_normalization(true));
+  assert_eq!(normalized, vec![("Carpet", 2775), ("Scarf", 2160), ("Red Car", 2068), ("Cat Art Room", 1682)]);
+}
+
+#[test]
+fn max_typos_tolerates_misspellings() {
+  let targets = precompute_skips_for_items(vec!["Application Settings", "Appetizers", "Replication", "Café Müller"]);
+  let options = MatchOptions::default().with_max_typos(1);
+  let results = |search: &str, options: &MatchOptions| -> Vec<(&str, u32, Vec<&str>)> {
+    fuzzy_filter_with_options(&targets, search, options).into_iter().map(|res| (res.item, res.score, res.highlights.unwrap())).collect()
+  };
+
+  assert!(fuzzy_filter(&targets, "aplication").is_empty());
+  // A deleted character, a transposition and a substitution
+  assert_eq!(results("aplication", &options)[0].2, vec!["", "A", "p", "plication", " Settings"]);
+  assert_eq!(results("aplpication", &options)[0].2, vec!["", "Application", " Settings"]);
+  assert_eq!(results("cafe", &options), vec![("Café Müller", 2700 + 1000 - 1000, vec!["", "Caf", "é Müller"])]);
+  assert_eq!(results("mülelr", &options)[0].2, vec!["Café ", "Müller"]);
+
+  // Typo matches rank below exact matches, and exact matches are scored as usual
+  let misspelt = precompute_skips_for_items(vec!["Setings", "Settings"]);
+  let items: Vec<_> = fuzzy_filter_with_options(&misspelt, "settings", &options).into_iter().map(|res| res.item).collect();
+  assert_eq!(items, vec!["Settings", "Setings"]);
+  assert_eq!(results("app", &options), results("app", &MatchOptions::default()));
+
+  // Too many typos, or too short a search, don't match
+  assert!(results("aplicaton", &options).is_empty());
+  assert_eq!(results("aplicaton", &options.clone().with_max_typos(2)).len(), 2);
+  assert!(results("xp", &options).is_empty());
+  assert!(results("\"aplication", &options).is_empty());
+}
+
+#[test]
+fn acronym_bonus_ranks_initials_first() {
+  let targets = precompute_skips_for_items(vec![
+    "Unused Samples",
+    "United States of America",
+    "FuzzBunny",
+    "Offbeat",
+    "Foo Bar Baz",
+  ]);
+  let results = |search: &str, config: ScoringConfig| -> Vec<(&str, u32)> {
+    let options = MatchOptions::default().with_scoring_config(config);
+    fuzzy_filter_with_options(&targets, search, &options).into_iter().map(|res| (res.item, res.score)).collect()
+  };
+
+  let default = results("usam", ScoringConfig::default());
+  let boosted = results("usam", ScoringConfig::default().with_acronym_bonus(5000));
+  assert_eq!(default[0].0, "Unused Samples");
+  assert_eq!(boosted[0], ("United States of America", default[1].1 + 5000));
+  // "Unused Samples" matches "sam" as a whole word, so it isn't an acronym
+  assert_eq!(boosted[1], default[0]);
+
+  // camelCase humps are initials, but substring matches aren't
+  let boosted = results("fb", ScoringConfig::default().with_acronym_bonus(5000));
+  let default = results("fb", ScoringConfig::default());
+  let bonus = |item: &str| boosted.iter().find(|res| res.0 == item).unwrap().1 - default.iter().find(|res| res.0 == item).unwrap().1;
+  assert_eq!(bonus("FuzzBunny"), 5000);
+  assert_eq!(bonus("Foo Bar Baz"), 5000);
+  assert_eq!(bonus("Offbeat"), 0);
+}
+
+#[test]
+fn acronym_bonus_counts_characters() {
+  let targets = precompute_skips_for_items(vec!["Übung Öffnen"]);
+  let score = |config: ScoringConfig| {
+    let options = MatchOptions::default().with_scoring_config(config);
+    fuzzy_filter_with_options(&targets, "übö", &options)[0].score
+  };
+
+  // "Üb" is two characters, despite being three bytes
+  assert_eq!(score(ScoringConfig::default().with_acronym_bonus(5000)), score(ScoringConfig::default()) + 5000);
+}
@@ -0,0 +1,66 @@
+use fuzzbunny_rs::{fuzzy_filter, fuzzy_filter_owned, highlight, precompute_skips_for_items, FuzzyFilterResult, FuzzyFilterResultOwned};
+
+#[test]
+fn owned_results_match_fuzzy_filter() {
+  let targets = precompute_skips_for_items(vec!["pineapple", "apple", "banana", "application", "Apple pie"]);
+
+  for search in ["", "app", "ap pi", "missing"] {
+    let results = fuzzy_filter(&targets, search);
+    let owned = fuzzy_filter_owned(&targets, search);
+    assert_eq!(owned.len(), results.len());
+
+    for (owned, result) in owned.iter().zip(&results) {
+      assert_eq!(owned, &result.to_owned());
+      assert_eq!(owned.item, result.item);
+      assert_eq!(owned.ranges, result.ranges);
+      assert_eq!(owned.index, result.index);
+      assert_eq!(owned.highlights(), result.highlights);
+      assert_eq!(owned.normalized_score(), result.normalized_score());
+
+      let borrowed = owned.as_result();
+      assert_eq!(borrowed, *result);
+      assert_eq!(borrowed.highlights, result.highlights);
+    }
+  }
+}
+
+#[test]
+fn owned_results_outlive_their_targets() {
+  let results = {
+    let items: Vec<String> = (0..100).map(|i| format!("item {}", i)).collect();
+    let targets = precompute_skips_for_items(items.iter().map(|s| s.as_str()));
+    fuzzy_filter_owned(&targets, "item 42")
+  };
+
+  let worker = std::thread::spawn(move || results);
+  let results = worker.join().unwrap();
+  assert_eq!(results[0].item, "item 42");
+  assert_eq!(results[0].index, Some(42));
+  assert_eq!(highlight::to_markdown(&results[0]), "**item 42**");
+}
+
+#[test]
+fn to_owned_keeps_highlights_without_ranges() {
+  let result = FuzzyFilterResult { item: "abcdef", score: 1, highlights: Some(vec!["a", "bc", "d", "ef"]), ranges: None, index: None };
+  let owned: FuzzyFilterResultOwned = result.clone().into();
+  assert_eq!(owned.highlights(), Some(vec!["a", "bc", "d", "ef"]));
+  assert_eq!(owned.as_result().ranges, Some(vec![fuzzbunny_rs::Range(1, 2), fuzzbunny_rs::Range(4, 2)]));
+
+  let unmatched = FuzzyFilterResult { highlights: None, ..result };
+  assert_eq!(unmatched.to_owned().highlights(), None);
+  assert_eq!(highlight::to_markdown(unmatched.to_owned()), "abcdef");
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn owned_results_deserialize_escaped_items() {
+  let targets = precompute_skips_for_items(vec!["say \"cheese\""]);
+  let results = fuzzy_filter_owned(&targets, "chee");
+
+  let json = serde_json::to_string(&results).unwrap();
+  let expected = format!(r#"[{{"item":"say \"cheese\"","score":{},"ranges":[[5,4]],"index":0}}]"#, results[0].score);
+  assert_eq!(json, expected);
+  let decoded: Vec<FuzzyFilterResultOwned> = serde_json::from_str(&json).unwrap();
+  assert_eq!(decoded, results);
+  assert_eq!(decoded[0].ranges, results[0].ranges);
+}
@@ -0,0 +1,38 @@
+use fuzzbunny_rs::{fuzzy_filter, fuzzy_filter_page, precompute_skips_for_items, FilterOptions};
+
+#[test]
+fn pages_match_fuzzy_filter() {
+  let targets = precompute_skips_for_items(include_str!("gutenberg-catalog.txt").lines());
+  for search in ["the", "p p", "\"of the", "", "zzz"] {
+    let expected = fuzzy_filter(&targets, search);
+    for (offset, limit) in [(0, None), (0, Some(0)), (0, Some(10)), (25, Some(10)), (expected.len().saturating_sub(3), Some(10)), (expected.len() + 5, Some(10)), (7, None)] {
+      let options = FilterOptions { limit, offset };
+      let page = fuzzy_filter_page(&targets, search, &options);
+      let window: Vec<_> = expected.iter().skip(offset).take(limit.unwrap_or(usize::MAX)).cloned().collect();
+      assert_eq!(page.total, expected.len());
+      assert_eq!(page.results, window, "{:?} {:?}", search, options);
+
+      for (result, expected) in page.results.iter().zip(&window) {
+        assert_eq!(result.highlights, expected.highlights);
+        assert_eq!(targets[result.index.unwrap()].0, result.item);
+      }
+    }
+  }
+}
+
+#[test]
+fn consecutive_pages_cover_every_result() {
+  let targets = precompute_skips_for_items(include_str!("gutenberg-catalog.txt").lines());
+  let expected = fuzzy_filter(&targets, "the");
+
+  let mut results = vec![];
+  for page in 0.. {
+    let page = fuzzy_filter_page(&targets, "the", &FilterOptions::page(page, 50));
+    if page.results.is_empty() {
+      break
+    }
+    results.extend(page.results);
+  }
+  assert_eq!(results, expected);
+  assert_eq!(FilterOptions::page(3, 20), FilterOptions::default().with_offset(60).with_limit(20));
+}
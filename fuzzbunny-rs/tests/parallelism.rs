@@ -0,0 +1,38 @@
+#![cfg(feature = "std")]
+
+use fuzzbunny_rs::{fuzzy_filter, fuzzy_filter_with_parallelism, precompute_skips_for_items, Parallelism};
+
+#[test]
+fn parallelism_matches_fuzzy_filter() {
+  let items: Vec<String> = (0..20_000).map(|i| format!("item number {}", i)).collect();
+  let targets = precompute_skips_for_items(items.iter().map(|s| s.as_str()));
+
+  let configs = [
+    Parallelism::new(),
+    Parallelism::sequential(),
+    Parallelism::new().with_min_items(0),
+    #[cfg(feature = "rayon")]
+    Parallelism::with_num_threads(2).unwrap().with_min_items(100),
+  ];
+
+  for parallelism in &configs {
+    for search in ["", "number 12", "i n 4", "missing"] {
+      assert_eq!(fuzzy_filter_with_parallelism(&targets, search, parallelism), fuzzy_filter(&targets, search));
+    }
+  }
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn searches_can_share_a_pool() {
+  use std::sync::Arc;
+
+  let pool = Arc::new(rayon::ThreadPoolBuilder::new().num_threads(2).build().unwrap());
+  let items: Vec<String> = (0..10_000).map(|i| format!("item number {}", i)).collect();
+  let targets = precompute_skips_for_items(items.iter().map(|s| s.as_str()));
+  let parallelism = Parallelism::new().with_thread_pool(pool.clone()).with_min_items(0);
+
+  // Searching from a thread of the same pool doesn't deadlock
+  let results = pool.install(|| fuzzy_filter_with_parallelism(&targets, "number 99", &parallelism));
+  assert_eq!(results, fuzzy_filter(&targets, "number 99"));
+}
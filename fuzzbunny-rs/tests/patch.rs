@@ -0,0 +1,57 @@
+#![cfg(feature = "std")]
+
+use fuzzbunny_rs::{fuzzy_filter, patch_filter_results, precompute_skips_for_items, FuzzyFilterResult};
+
+#[test]
+fn patch_matches_full_refilter() {
+  let old_items: Vec<String> = (0..500).map(|i| format!("item number {}", i)).collect();
+  let old_targets = precompute_skips_for_items(old_items.iter().map(|s| s.as_str()));
+  let mut patched = fuzzy_filter(&old_targets, "num 1");
+
+  // Remove one item, rename another and add a new one, with the fresh items at the end
+  let mut new_items = old_items.clone();
+  new_items.remove(100);
+  new_items.remove(10);
+  new_items.push("numeric 1 renamed".to_string());
+  new_items.push("number 1 added".to_string());
+  let new_targets = precompute_skips_for_items(new_items.iter().map(|s| s.as_str()));
+
+  let fresh = precompute_skips_for_items(vec!["numeric 1 renamed", "number 1 added"]);
+  patch_filter_results(&mut patched, "num 1", &[10, 100], &fresh, 498);
+
+  assert_eq!(patched, fuzzy_filter(&new_targets, "num 1"));
+}
+
+#[test]
+fn patch_removes_single_duplicate() {
+  let targets = precompute_skips_for_items(vec!["apple", "apple", "application"]);
+  let mut results = fuzzy_filter(&targets, "app");
+  patch_filter_results(&mut results, "app", &[0], &[], 2);
+
+  let items: Vec<&str> = results.iter().map(|res| res.item).collect();
+  assert_eq!(items, vec!["apple", "application"]);
+}
+
+#[test]
+fn patch_renumbers_indices_after_removal() {
+  let old_items = vec!["banana", "apple", "cherry", "application", "grape", "appendix"];
+  let old_targets = precompute_skips_for_items(old_items.clone());
+  let mut patched = fuzzy_filter(&old_targets, "app");
+
+  // Remove "banana", which doesn't match, and "application", then add "apple pie"
+  let new_items = vec!["apple", "cherry", "grape", "appendix", "apple pie"];
+  let new_targets = precompute_skips_for_items(new_items);
+  let fresh = precompute_skips_for_items(vec!["apple pie"]);
+  patch_filter_results(&mut patched, "app", &[3, 0], &fresh, 4);
+
+  let indexed = |results: &[FuzzyFilterResult<'_>]| -> Vec<(String, Option<usize>)> {
+    results.iter().map(|res| (res.item.to_string(), res.index)).collect()
+  };
+  let expected = fuzzy_filter(&new_targets, "app");
+  assert_eq!(indexed(&patched), indexed(&expected));
+  assert_eq!(indexed(&patched), vec![
+    ("appendix".to_string(), Some(3)),
+    ("apple".to_string(), Some(0)),
+    ("apple pie".to_string(), Some(4)),
+  ]);
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "std")]
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use fuzzbunny_rs::{fuzzy_filter, fuzzy_filter_os, precompute_skips_for_items};
+
+#[test]
+fn os_strings_match_the_same_as_text() {
+  let items = vec!["src/main.rs", "src/lib.rs", "tests/lib_test.rs", "README.md", "Über/Straße.txt"];
+  let targets = precompute_skips_for_items(items.clone());
+  let paths: Vec<PathBuf> = items.iter().map(PathBuf::from).collect();
+
+  for search in ["lib", "s l", "\"rs", "", "md"] {
+    let results = fuzzy_filter(&targets, search);
+    let os_results = fuzzy_filter_os(&paths, search);
+    assert_eq!(os_results.len(), results.len(), "{search}");
+    for (os_result, result) in os_results.iter().zip(&results) {
+      assert_eq!(os_result.item, Path::new(result.item).as_os_str());
+      assert_eq!(os_result.score, result.score);
+      assert_eq!(&os_result.highlights(), result.highlights.as_ref().unwrap());
+    }
+  }
+
+  let results = fuzzy_filter_os(&paths, "straße");
+  assert_eq!(results[0].highlights(), vec!["Über/", "Straße", ".txt"]);
+}
+
+#[cfg(unix)]
+#[test]
+fn os_strings_match_invalid_utf8() {
+  use std::os::unix::ffi::OsStringExt;
+
+  let invalid = OsString::from_vec(b"photos/caf\xe9 \xff menu.txt".to_vec());
+  let items = vec![invalid.clone(), OsString::from("photos/menu.txt")];
+
+  let results = fuzzy_filter_os(&items, "menu");
+  assert_eq!(results.len(), 2);
+  assert_eq!(results[0].item, items[1]);
+  // The original item is kept, while highlights use its lossy text
+  assert_eq!(results[1].item, invalid);
+  assert_eq!(results[1].text, "photos/caf\u{fffd} \u{fffd} menu.txt");
+  assert_eq!(results[1].highlights(), vec!["photos/caf\u{fffd} \u{fffd} ", "menu", ".txt"]);
+
+  let results = fuzzy_filter_os(&items, "p c m");
+  assert_eq!(results.len(), 1);
+  assert_eq!(results[0].highlights(), vec!["", "p", "hotos/", "c", "af\u{fffd} \u{fffd} ", "m", "enu.txt"]);
+}
@@ -0,0 +1,67 @@
+#![cfg(feature = "serde")]
+
+use fuzzbunny_rs::PayloadCorpus;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Quest {
+  level: u32,
+  zone: String,
+}
+
+fn quest(level: u32, zone: &str) -> Quest {
+  Quest { level, zone: zone.to_string() }
+}
+
+#[test]
+fn payloads_returned_with_matches() {
+  let mut corpus = PayloadCorpus::new();
+  let dragon = corpus.insert("Slay the dragon", quest(40, "Mountains"));
+  let sword = corpus.insert("Find the sword", quest(5, "Village"));
+  corpus.insert("Dragon egg hunt", quest(20, "Mountains"));
+
+  let results = corpus.search("drag");
+  assert_eq!(results.len(), 2);
+  assert_eq!(results[1].matched.id, dragon);
+  assert_eq!(results[1].payload.level, 40);
+
+  corpus.update(sword, "Forge the sword");
+  corpus.payload_mut(sword).unwrap().level = 6;
+  assert_eq!(corpus.search("forge")[0].payload, &quest(6, "Village"));
+  assert_eq!(corpus.set_payload(dragon, quest(45, "Peak")), Some(quest(40, "Mountains")));
+
+  assert_eq!(corpus.remove(dragon), Some(("Slay the dragon".to_string(), quest(45, "Peak"))));
+  assert!(corpus.payload(dragon).is_none());
+  assert_eq!(corpus.len(), 2);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn payloads_round_trip_as_json() {
+  let mut corpus = PayloadCorpus::new();
+  let dragon = corpus.insert("Slay the dragon", quest(40, "Mountains"));
+  corpus.insert("Find the sword", quest(5, "Village"));
+  corpus.add_tag(dragon, "combat");
+
+  let mut json = vec![];
+  corpus.to_json(&mut json).unwrap();
+  let loaded = PayloadCorpus::<Quest>::from_json(json.as_slice()).unwrap();
+  assert_eq!(loaded.payload(dragon), Some(&quest(40, "Mountains")));
+  assert_eq!(loaded.search_tagged("s", &["combat"])[0].payload.zone, "Mountains");
+
+  let missing = r#"{"corpus": {"format": 1, "next_id": 1, "usage_boost": {"score_per_selection": 100, "half_life": 50, "max_boost": 1000},
+    "items": [{"id": 0, "text": "apple"}]}, "payloads": []}"#;
+  assert!(PayloadCorpus::<Quest>::from_json(missing.as_bytes()).is_err());
+}
+
+#[cfg(feature = "msgpack")]
+#[test]
+fn payloads_round_trip_as_msgpack() {
+  let mut corpus = PayloadCorpus::new();
+  corpus.insert("Slay the dragon", quest(40, "Mountains"));
+
+  let mut bytes = vec![];
+  corpus.to_msgpack(&mut bytes).unwrap();
+  let loaded = PayloadCorpus::<Quest>::from_msgpack(bytes.as_slice()).unwrap();
+  assert_eq!(loaded.search("slay")[0].payload, &quest(40, "Mountains"));
+}
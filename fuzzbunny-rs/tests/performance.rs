@@ -2,7 +2,11 @@ use fuzzbunny_rs::{fuzzy_filter, get_target_skips};
 use std::fs::File;
 use std::io::{self, BufRead};
 
+// Timing depends on the machine, so this only runs on request. See `benches/` for the
+// per-function benchmarks.
 #[test]
+#[ignore = "timing dependent, run with --ignored"]
+#[allow(clippy::unnecessary_cast)]
 fn fuzzy_score_item_bench() {
   let file = File::open("tests/gutenberg-catalog.txt").unwrap();
   let lines: Result<Vec<String>, _> = io::BufReader::new(file).lines().skip(1).collect();
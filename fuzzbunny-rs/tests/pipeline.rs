@@ -0,0 +1,100 @@
+use fuzzbunny_rs::{fuzzy_filter, fuzzy_filter_with_options, precompute_skips_for_items, MatchOptions, Normalization, NormalizationPipeline};
+
+#[test]
+fn pipeline_applies_steps_in_order() {
+  assert_eq!(NormalizationPipeline::new().normalize("Crème"), "Crème");
+  assert_eq!(NormalizationPipeline::default().normalize("Crème"), "crème");
+  assert_eq!(NormalizationPipeline::new().fold_diacritics().normalize("Crème ŒUVRE"), "Creme OEUVRE");
+  // Decomposed accents are combining marks, which are removed
+  assert_eq!(NormalizationPipeline::default().fold_diacritics().normalize("Cre\u{300}me"), "creme");
+  assert_eq!(NormalizationPipeline::new().profile(Normalization::German).normalize("Müller"), "mueller");
+
+  let pipeline = NormalizationPipeline::new().map(|char, out| match char {
+    '&' => out.push_str("and"),
+    _ => out.push(char),
+  });
+  assert_eq!(pipeline.normalize("R&D"), "RandD");
+  // The order of the steps matters
+  let strip_then_map = NormalizationPipeline::new().strip(|char| char == 'a').map(|char, out| out.extend([char, 'a']));
+  assert_eq!(strip_then_map.normalize("ab"), "ba");
+}
+
+#[test]
+fn pipeline_equality() {
+  let pipeline = NormalizationPipeline::default().strip(char::is_whitespace);
+  assert_eq!(pipeline, pipeline.clone());
+  assert_ne!(pipeline, NormalizationPipeline::default().strip(char::is_whitespace));
+  assert_eq!(NormalizationPipeline::default(), NormalizationPipeline::new().lowercase());
+  assert_ne!(NormalizationPipeline::default(), NormalizationPipeline::new());
+}
+
+#[test]
+fn normalized_text_maps_ranges_to_original() {
+  let pipeline = NormalizationPipeline::default().fold_diacritics().strip(|char| char == '-');
+  let normalized = pipeline.normalize_with_offsets("Straße-Ärger");
+  assert_eq!(normalized.text(), "strassearger");
+  assert_eq!(normalized.original_range(0..4), 0..4);
+  // Part of an expanded character covers the whole character
+  assert_eq!(normalized.original_range(4..5), 4..6);
+  // Removed characters directly after a range are covered by it
+  assert_eq!(normalized.original_range(5..7), 4..8);
+  assert_eq!(normalized.original_range(5..6), 4..6);
+  assert_eq!(normalized.original_range(7..12), 8..14);
+  assert_eq!(normalized.original_range(12..12), 14..14);
+
+  let unchanged = NormalizationPipeline::default().normalize_with_offsets("Plain");
+  assert_eq!(unchanged.original_range(1..3), 1..3);
+}
+
+#[test]
+fn pipeline_options_match_normalized_text() {
+  let targets = precompute_skips_for_items(vec!["Crème Brûlée", "Creme Caramel", "Café-Bar", "CafeBar"]);
+  let options = MatchOptions::default().with_pipeline(NormalizationPipeline::default().fold_diacritics());
+
+  let items = |search: &str| -> Vec<&str> {
+    let mut items: Vec<&str> = fuzzy_filter_with_options(&targets, search, &options).iter().map(|res| res.item).collect();
+    items.sort_unstable();
+    items
+  };
+  assert_eq!(items("creme"), vec!["Creme Caramel", "Crème Brûlée"]);
+  assert_eq!(items("CRÈME"), vec!["Creme Caramel", "Crème Brûlée"]);
+  assert_eq!(items("cafe bar"), vec!["CafeBar", "Café-Bar"]);
+  assert_eq!(fuzzy_filter(&targets, "creme").len(), 1);
+
+  // Highlights reference the original text
+  let results = fuzzy_filter_with_options(&targets, "brulee", &options);
+  assert_eq!(results[0].highlights, Some(vec!["Crème ", "Brûlée"]));
+  // Word boundaries come from the original text
+  let results = fuzzy_filter_with_options(&targets, "c b", &options);
+  assert_eq!(results.len(), 3);
+
+  // The default pipeline matches the same as without one
+  let targets = precompute_skips_for_items(vec!["Creme Caramel", "CafeBar", "cafe-bar", "Bar"]);
+  let default = MatchOptions::default().with_pipeline(NormalizationPipeline::default());
+  for search in ["cafe", "c b", "\"bar", "eme", "b"] {
+    assert_eq!(fuzzy_filter_with_options(&targets, search, &default), fuzzy_filter(&targets, search));
+  }
+}
+
+#[test]
+fn pipeline_decomposes_and_case_folds() {
+  let decompose = NormalizationPipeline::new().decompose();
+  assert_eq!(decompose.normalize("Café"), "Cafe\u{301}");
+  assert_eq!(decompose.normalize("Ｒｕｓｔ ﬁle①⑫ x²\u{a0}Ⅻ½…"), "Rust file112 x2 XII1⁄2...");
+  assert_eq!(NormalizationPipeline::new().case_fold().normalize("STRAẞE Straße ΣΊΣΥΦΟΣ σίσυφος"), "strasse strasse σίσυφοσ σίσυφοσ");
+
+  // Decomposed letters fold to the same base letters as precomposed ones, other than those that
+  // decompose to a separate middle dot or apostrophe
+  let fold = NormalizationPipeline::new().fold_diacritics();
+  let decompose_fold = NormalizationPipeline::new().decompose().fold_diacritics();
+  for char in ('\u{c0}'..='\u{17f}').filter(|char| char.is_alphabetic() && !"Ŀŀŉ".contains(*char)) {
+    let text = char.to_string();
+    assert_eq!(decompose_fold.normalize(&text), fold.normalize(&text), "{char:?}");
+  }
+
+  let folded = NormalizationPipeline::folded();
+  assert_eq!(folded.normalize("Crème Brûlée"), folded.normalize("CRE\u{300}ME BRU\u{302}LE\u{301}E"));
+  assert_eq!(folded.normalize("Oﬃce Straße Ærø"), "office strasse aero");
+  let normalized = folded.normalize_with_offsets("Oﬃce");
+  assert_eq!(normalized.original_range(1..4), 1..4);
+}
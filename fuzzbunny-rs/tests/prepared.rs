@@ -0,0 +1,51 @@
+use fuzzbunny_rs::{
+  fuzzy_filter, fuzzy_filter_prepared, fuzzy_filter_prepared_normalized, fuzzy_filter_with_options,
+  precompute_skips_for_items, prepare_targets, prepare_targets_normalized, MatchOptions, NormalizationPipeline,
+  PreparedTarget,
+};
+
+#[test]
+fn prepared_matches_fuzzy_filter() {
+  let items = ["Claire Bennet", "Elle Bishop", "Maya Herrera", "İstanbul Airport", "Café Müller", ""];
+  let prepared = prepare_targets(items);
+  let targets = precompute_skips_for_items(items);
+
+  for search in ["el", "EB", "\"he", "ia", "mü", "zz", "", "  "] {
+    assert_eq!(fuzzy_filter_prepared(&prepared, search), fuzzy_filter(&targets, search));
+  }
+}
+
+#[test]
+fn prepared_target_caches_lower_case() {
+  let target = PreparedTarget::new("Hiro NAKAMURA");
+  assert_eq!(target.text(), "Hiro NAKAMURA");
+  assert_eq!(target.lower(), "hiro nakamura");
+
+  let score = target.score("naka").unwrap();
+  assert_eq!(score.score, 4800 + 195);
+  assert!(target.score("zz").is_none());
+}
+
+#[test]
+fn normalized_targets_match_options_pipeline() {
+  let items = ["Café Müller", "Crème Brûlée", "Straße", "Oﬃce Space", "İstanbul", "cafe\u{301}", ""];
+  let pipeline = NormalizationPipeline::folded();
+  let prepared = prepare_targets_normalized(items, &pipeline);
+  let targets = precompute_skips_for_items(items);
+  let options = MatchOptions::default().with_pipeline(pipeline.clone());
+
+  for search in ["cafe", "CAFÉ", "creme brulee", "strasse", "office", "istanbul", "\"e mu", "zz", ""] {
+    let results = fuzzy_filter_prepared_normalized(&prepared, search, &pipeline);
+    let expected = fuzzy_filter_with_options(&targets, search, &options);
+    assert_eq!(results, expected, "search {search:?}");
+    for (result, expected) in results.iter().zip(&expected) {
+      assert_eq!(result.highlights, expected.highlights, "search {search:?}");
+    }
+  }
+
+  let results = fuzzy_filter_prepared_normalized(&prepared, "cafe", &pipeline);
+  let items: Vec<_> = results.iter().map(|res| res.item).collect();
+  assert_eq!(items, vec!["Café Müller", "cafe\u{301}"]);
+  assert_eq!(results[1].highlights, Some(vec!["", "cafe\u{301}"]));
+  assert_eq!(PreparedTarget::normalized("Oﬃce", &pipeline).lower(), "office");
+}
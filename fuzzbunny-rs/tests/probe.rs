@@ -0,0 +1,48 @@
+use fuzzbunny_rs::{fuzzy_match, highlights_from_ranges, match_at};
+
+fn highlights_at<'a>(target: &'a str, search: &str, offset: usize) -> Option<Vec<&'a str>> {
+  match_at(target, search, offset).map(|string_score| highlights_from_ranges(target, string_score.ranges))
+}
+
+#[test]
+fn match_at_only_matches_at_offset() {
+  let target = "Las Vegas, Los Angeles";
+  assert_eq!(highlights_at(target, "las", 0), Some(vec!["", "Las", " Vegas, Los Angeles"]));
+  assert_eq!(highlights_at(target, "las", 1), None);
+  assert_eq!(highlights_at(target, "ve", 4), Some(vec!["Las ", "Ve", "gas, Los Angeles"]));
+  // Word prefixes only match from the word at the offset
+  assert_eq!(highlights_at(target, "l a", 11), Some(vec!["Las Vegas, ", "L", "os ", "A", "ngeles"]));
+  assert_eq!(highlights_at(target, "l a", 0), Some(vec!["", "La", "s Vegas, Los Angeles"]));
+  assert_eq!(highlights_at(target, "v a", 0), None);
+  // Quoted searches only match substrings
+  assert_eq!(highlights_at(target, "\"los a", 11), Some(vec!["Las Vegas, ", "Los A", "ngeles"]));
+  assert_eq!(highlights_at(target, "\"l a\"", 11), None);
+
+  // The offsets of every match, in order
+  let offsets: Vec<usize> = (0..=target.len()).filter(|&offset| match_at(target, "s", offset).is_some()).collect();
+  assert_eq!(offsets, vec![2, 8, 13, 21]);
+}
+
+#[test]
+fn match_at_scores_like_fuzzy_match() {
+  let target = "Las Vegas, Los Angeles";
+  for search in ["las", "l v", "\"las"] {
+    let best = fuzzy_match(target, Some(search)).unwrap();
+    assert_eq!(match_at(target, search, 0).unwrap().score, best.score, "{search}");
+  }
+  let vegas = match_at(target, "ve", 4).unwrap();
+  assert_eq!(vegas.score, fuzzy_match(target, Some("ve")).unwrap().score);
+}
+
+#[test]
+fn match_at_handles_unicode_and_bounds() {
+  let target = "Café Über";
+  assert_eq!(highlights_at(target, "über", 6), Some(vec!["Café ", "Über"]));
+  assert_eq!(highlights_at(target, "é", 3), Some(vec!["Caf", "é", " Über"]));
+  // Not a character boundary
+  assert!(match_at(target, "über", 7).is_none());
+  assert!(match_at(target, "über", 100).is_none());
+  assert!(match_at(target, "", 100).is_none());
+  assert_eq!(match_at(target, "", 0).unwrap().ranges.len(), 0);
+  assert!(match_at("", "", 0).is_none());
+}
@@ -0,0 +1,130 @@
+// Invariants of the scorer that should hold for any target and search, checked on generated
+// inputs.
+
+use fuzzbunny_rs::{fuzzy_filter, fuzzy_match, fuzzy_score_item, precompute_skips_for_items, Range};
+use proptest::prelude::*;
+
+/// Targets mixing words, separators, case changes and multi-byte characters.
+fn target() -> impl Strategy<Value = String> {
+  prop_oneof![
+    "[a-zA-Z0-9 ._/-]{0,40}",
+    "[a-zA-Zäöüßİı東京 -]{0,20}",
+    "\\PC{0,20}",
+  ]
+}
+
+/// A search taken from a target, either a substring or its characters from across its words, so
+/// most searches match.
+fn target_and_search() -> impl Strategy<Value = (String, String)> {
+  (target(), any::<prop::sample::Index>(), any::<prop::sample::Index>(), 1..4usize).prop_map(|(target, start, len, stride)| {
+    let chars: Vec<char> = target.chars().collect();
+    if chars.is_empty() {
+      return (target, String::new())
+    }
+    let start = start.index(chars.len());
+    let len = len.index(chars.len() - start) + 1;
+    let search: String = chars[start..start + len].iter().step_by(stride).collect();
+    (target, search)
+  })
+}
+
+/// Lower case words of ASCII letters.
+fn words() -> impl Strategy<Value = Vec<String>> {
+  prop::collection::vec("[a-z]{2,8}", 2..6)
+}
+
+fn check_ranges(target: &str, ranges: &[Range]) -> Result<(), TestCaseError> {
+  for range in ranges {
+    prop_assert!(!range.is_empty(), "empty range {range:?}");
+    prop_assert!(range.end() <= target.len(), "range {range:?} past the end");
+    prop_assert!(target.is_char_boundary(range.start()) && target.is_char_boundary(range.end()), "range {range:?} splits a character");
+  }
+  for pair in ranges.windows(2) {
+    prop_assert!(pair[0].end() <= pair[1].start(), "ranges {:?} and {:?} overlap or are unsorted", pair[0], pair[1]);
+  }
+  Ok(())
+}
+
+proptest! {
+  #[test]
+  fn highlights_reassemble_the_target((target, search) in target_and_search()) {
+    if let Some(result) = fuzzy_match(&target, Some(&search)) {
+      let highlights = result.highlights.unwrap();
+      prop_assert_eq!(highlights.concat(), target.as_str());
+
+      // Every second section is a matched range
+      let ranges = result.ranges.unwrap();
+      let matched: Vec<&str> = highlights.iter().skip(1).step_by(2).copied().collect();
+      let sections: Vec<&str> = ranges.iter().map(|range| &target[range.start()..range.end()]).collect();
+      prop_assert_eq!(matched, sections);
+    }
+  }
+
+  #[test]
+  fn ranges_are_sorted_and_never_overlap((target, search) in target_and_search()) {
+    if let Some(score) = fuzzy_score_item(&(target.as_str(), None), &search.trim().to_lowercase()) {
+      check_ranges(&target, &score.ranges)?;
+    }
+  }
+
+  #[test]
+  fn any_search_is_handled(target in target(), search in "\\PC{0,10}") {
+    // Arbitrary searches mostly don't match, but must never panic or produce invalid ranges
+    if let Some(result) = fuzzy_match(&target, Some(&search)) {
+      check_ranges(&target, result.ranges.as_deref().unwrap())?;
+      prop_assert_eq!(result.highlights.unwrap().concat(), target.as_str());
+    }
+  }
+
+  #[test]
+  fn substrings_always_match((target, search) in target_and_search()) {
+    let search = search.trim();
+    let is_substring = target.to_lowercase().contains(&search.to_lowercase());
+    prop_assume!(is_substring && !target.is_empty() && !search.is_empty() && !search.starts_with('"'));
+    prop_assert!(fuzzy_match(&target, Some(search)).is_some());
+  }
+
+  #[test]
+  fn longer_contiguous_matches_score_higher(words in words(), len in 1..8usize) {
+    let target = words.join(" ");
+    let len = len.min(words[0].len() - 1);
+    let shorter = fuzzy_match(&target, Some(&target[..len])).unwrap();
+    let longer = fuzzy_match(&target, Some(&target[..len + 1])).unwrap();
+    prop_assert!(longer.score > shorter.score, "{} <= {}", longer.score, shorter.score);
+  }
+
+  #[test]
+  fn contiguous_matches_beat_scattered_matches(words in words()) {
+    // The initials of the words, matched across the words or as a run at the start of a target
+    // of the same length
+    let scattered = words.join(" ");
+    let initials: String = words.iter().map(|word| &word[..1]).collect();
+    let contiguous = format!("{initials}{}", &scattered[initials.len()..]);
+    prop_assume!(!scattered.contains(&initials));
+
+    let scattered = fuzzy_match(&scattered, Some(&initials)).unwrap();
+    let contiguous = fuzzy_match(&contiguous, Some(&initials)).unwrap();
+    prop_assert!(scattered.ranges.as_ref().unwrap().len() > 1);
+    prop_assert!(contiguous.score > scattered.score, "{} <= {}", contiguous.score, scattered.score);
+  }
+
+  #[test]
+  fn normalized_scores_are_fractions((target, search) in target_and_search()) {
+    if let Some(result) = fuzzy_match(&target, Some(&search)) {
+      let score = result.normalized_score();
+      prop_assert!((0.0..=1.0).contains(&score), "{score}");
+    }
+  }
+
+  #[test]
+  fn filter_results_are_sorted(targets in prop::collection::vec(target(), 0..20), search in "[a-z]{1,3}") {
+    let targets = precompute_skips_for_items(targets.iter().map(String::as_str));
+    let results = fuzzy_filter(&targets, &search);
+    for pair in results.windows(2) {
+      prop_assert!(pair[0] >= pair[1]);
+    }
+    // Every match is a result
+    let matches = targets.iter().filter(|target| fuzzy_match(target.0, Some(&search)).is_some()).count();
+    prop_assert_eq!(results.len(), matches);
+  }
+}
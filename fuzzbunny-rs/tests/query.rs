@@ -0,0 +1,75 @@
+use fuzzbunny_rs::{fuzzy_filter, fuzzy_filter_query, precompute_skips_for_items, Query, QueryToken};
+
+#[test]
+fn query_parses_tokens_and_phrases() {
+  let fuzzy = |word: &str| QueryToken::Fuzzy(word.to_string());
+  let phrase = |phrase: &str| QueryToken::Phrase(phrase.to_string());
+
+  assert_eq!(Query::parse("  \"Las Vegas\" Hotel  cheap").tokens(), [phrase("las vegas"), fuzzy("hotel"), fuzzy("cheap")]);
+  assert_eq!(Query::parse("hotel\"las ve").tokens(), [fuzzy("hotel"), phrase("las ve")]);
+  assert_eq!(Query::parse("a \"\" \" b \"c").tokens(), [fuzzy("a"), phrase("b"), fuzzy("c")]);
+  assert!(Query::parse("  \"  ").is_empty());
+
+  let exclude = |word: &str| QueryToken::Exclude(word.to_string());
+  assert_eq!(Query::parse("petrelli -Nathan -\"mr p\" - x-ray").tokens(), [fuzzy("petrelli"), exclude("nathan"), exclude("mr p"), fuzzy("-"), fuzzy("x-ray")]);
+  assert_eq!(Query::parse("-\"\" -").tokens(), [fuzzy("-")]);
+}
+
+#[test]
+fn single_token_matches_fuzzy_filter() {
+  let file = std::fs::read_to_string("tests/gutenberg-catalog.txt").unwrap();
+  let targets = precompute_skips_for_items(file.lines().skip(1));
+
+  for search in ["alice", "tw", "\"the", "\"of the\"", "zzzz", ""] {
+    let results = fuzzy_filter_query(&targets, search);
+    let expected = fuzzy_filter(&targets, search);
+    assert_eq!(results, expected, "search {search:?}");
+    for (result, expected) in results.iter().zip(&expected) {
+      assert_eq!(result.highlights, expected.highlights, "search {search:?}");
+    }
+  }
+}
+
+#[test]
+fn tokens_match_independently() {
+  let targets = precompute_skips_for_items(vec![
+    "Peter Petrelli",
+    "Nathan Petrelli",
+    "Angela Petrelli",
+    "Café Müller",
+    "Petrol station",
+  ]);
+
+  // Every token has to match, in any order
+  let items: Vec<_> = fuzzy_filter_query(&targets, "petrelli nat").iter().map(|res| res.item).collect();
+  assert_eq!(items, vec!["Nathan Petrelli"]);
+
+  // Overlapping tokens merge their highlights, and scores add up
+  let results = fuzzy_filter_query(&targets, "petr petrel");
+  assert_eq!(results.len(), 3);
+  assert_eq!(results[0].item, "Peter Petrelli");
+  assert_eq!(results[0].highlights, Some(vec!["Peter ", "Petrel", "li"]));
+  let single = fuzzy_filter(&targets, "petr");
+  assert_eq!(single[0].item, "Petrol station");
+  assert!(results[0].score > single[1].score);
+
+  // Phrases only match substrings
+  assert!(fuzzy_filter_query(&targets, "\"pp").is_empty());
+  assert_eq!(fuzzy_filter_query(&targets, "\"é mü\" caf")[0].highlights, Some(vec!["", "Café Mü", "ller"]));
+}
+
+#[test]
+fn exclusions_filter_matches() {
+  let targets = precompute_skips_for_items(vec!["Peter Petrelli", "Nathan Petrelli", "Angela Petrelli", "Mr. Petrelli", ""]);
+
+  let items: Vec<_> = fuzzy_filter_query(&targets, "petrelli -nathan -\"mr.\"").iter().map(|res| res.item).collect();
+  assert_eq!(items, vec!["Peter Petrelli", "Angela Petrelli"]);
+
+  // Exclusions are only matched as substrings, and don't change scores
+  let results = fuzzy_filter_query(&targets, "petrelli -np");
+  assert_eq!(results, fuzzy_filter(&targets, "petrelli"));
+
+  // A query of only exclusions matches every other target
+  let items: Vec<_> = fuzzy_filter_query(&targets, "-ang -peter").iter().map(|res| (res.item, res.score)).collect();
+  assert_eq!(items, vec![("Mr. Petrelli", 0), ("Nathan Petrelli", 0)]);
+}
@@ -0,0 +1,65 @@
+use fuzzbunny_rs::{clip_ranges, fuzzy_match, merge_ranges, segments, Range, Segment};
+
+#[test]
+fn ranges_have_accessors_and_convert_to_std_ranges() {
+  let range = Range(3, 4);
+  assert_eq!((range.start(), range.len(), range.end()), (3, 4, 7));
+  assert!(!range.is_empty());
+  assert!(Range(5, 0).is_empty());
+
+  assert_eq!(std::ops::Range::from(range), 3..7);
+  assert_eq!(Range::from(3..7), range);
+  // Reversed ranges become empty
+  #[allow(clippy::reversed_empty_ranges)]
+  let reversed = Range::from(7..3);
+  assert_eq!(reversed, Range(7, 0));
+}
+
+#[test]
+fn ranges_are_clipped_to_windows() {
+  assert_eq!(Range(3, 4).clip(0..10), Some(Range(3, 4)));
+  assert_eq!(Range(3, 4).clip(5..10), Some(Range(5, 2)));
+  assert_eq!(Range(3, 4).clip(0..5), Some(Range(3, 2)));
+  assert_eq!(Range(3, 4).clip(7..10), None);
+  assert_eq!(Range(3, 4).clip(0..3), None);
+
+  let ranges = [Range(0, 2), Range(4, 4), Range(10, 1)];
+  assert_eq!(clip_ranges(&ranges, 1..6), vec![Range(1, 1), Range(4, 2)]);
+  assert_eq!(clip_ranges(&ranges, 12..20), vec![]);
+}
+
+#[test]
+fn overlapping_and_adjacent_ranges_are_merged() {
+  assert_eq!(merge_ranges(vec![]), vec![]);
+  assert_eq!(merge_ranges(vec![Range(0, 2), Range(2, 2)]), vec![Range(0, 4)]);
+  assert_eq!(merge_ranges(vec![Range(0, 2), Range(3, 2)]), vec![Range(0, 2), Range(3, 2)]);
+  // Contained ranges don't shorten the merged range
+  assert_eq!(merge_ranges(vec![Range(0, 10), Range(2, 2)]), vec![Range(0, 10)]);
+  // Unsorted input and empty ranges
+  assert_eq!(merge_ranges(vec![Range(8, 2), Range(4, 0), Range(1, 3), Range(9, 4)]), vec![Range(1, 3), Range(8, 5)]);
+}
+
+#[test]
+fn segments_alternate_between_unmatched_and_matched_sections() {
+  let segment = |range: std::ops::Range<usize>, matched| Segment { range, matched };
+
+  assert_eq!(
+    segments(10, &[Range(2, 3), Range(7, 1)]).collect::<Vec<_>>(),
+    vec![segment(0..2, false), segment(2..5, true), segment(5..7, false), segment(7..8, true), segment(8..10, false)],
+  );
+  assert_eq!(segments(4, &[Range(0, 4)]).collect::<Vec<_>>(), vec![segment(0..4, true)]);
+  assert_eq!(segments(4, &[]).collect::<Vec<_>>(), vec![segment(0..4, false)]);
+  assert_eq!(segments(0, &[]).count(), 0);
+  // Ranges past the end of the target are clipped
+  assert_eq!(segments(4, &[Range(2, 5), Range(8, 1)]).collect::<Vec<_>>(), vec![segment(0..2, false), segment(2..4, true)]);
+}
+
+#[test]
+fn segments_cover_highlights_of_matches() {
+  let result = fuzzy_match("Git: Checkout branch", Some("gcb")).unwrap();
+  let sections: Vec<&str> = segments(result.item.len(), result.ranges.as_deref().unwrap())
+    .map(|segment| &result.item[segment.range])
+    .collect();
+  let highlights: Vec<&str> = result.highlights.unwrap().into_iter().filter(|section| !section.is_empty()).collect();
+  assert_eq!(sections, highlights);
+}
@@ -0,0 +1,70 @@
+#![cfg(feature = "std")]
+
+use std::io::{self, Read};
+
+use fuzzbunny_rs::{fuzzy_filter, fuzzy_filter_reader, precompute_skips_for_items};
+
+const CATALOG: &str = include_str!("gutenberg-catalog.txt");
+
+#[test]
+fn reader_matches_fuzzy_filter() {
+  let targets = precompute_skips_for_items(CATALOG.lines().filter(|line| !line.is_empty()));
+
+  for (search, k) in [("the", 10), ("p p", 25), ("\"and", 3), ("sherlock", 1000), ("", 5)] {
+    let results = fuzzy_filter_reader(CATALOG.as_bytes(), search, k).unwrap();
+    let expected = fuzzy_filter(&targets, search);
+    assert_eq!(results.len(), expected.len().min(k));
+
+    for (result, expected) in results.iter().zip(&expected) {
+      assert_eq!((result.line.as_str(), result.score), (expected.item, expected.score));
+      assert_eq!(Some(result.highlights()), expected.highlights);
+    }
+  }
+}
+
+#[test]
+fn reader_keeps_line_numbers() {
+  let reader = "Bergamot\r\n\nblood orange\nOrange\n".as_bytes();
+  let results = fuzzy_filter_reader(reader, "orange", 10).unwrap();
+
+  let lines: Vec<(usize, &str)> = results.iter().map(|res| (res.line_number, res.line.as_str())).collect();
+  assert_eq!(lines, vec![(3, "Orange"), (2, "blood orange")]);
+
+  let results = fuzzy_filter_reader("Bergamot\r\nbergamot\n".as_bytes(), "berg", 10).unwrap();
+  assert_eq!(results[0].line, "Bergamot");
+  assert_eq!(results[0].highlights(), vec!["", "Berg", "amot"]);
+  assert!(fuzzy_filter_reader(reader, "orange", 0).unwrap().is_empty());
+}
+
+/// A reader that fails once it is read past a given number of bytes.
+struct Truncated<'a> {
+  reader: &'a [u8],
+  remaining: usize,
+}
+
+impl Read for Truncated<'_> {
+  fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    if self.remaining == 0 {
+      return Err(io::Error::other("read past the end"));
+    }
+    let len = buf.len().min(self.remaining);
+    self.remaining -= len;
+    self.reader.read(&mut buf[..len])
+  }
+}
+
+#[test]
+fn reader_stops_early_for_empty_search() {
+  let reader = io::BufReader::with_capacity(4, Truncated { reader: b"one\ntwo\nthree\n", remaining: 8 });
+  let lines: Vec<String> = fuzzy_filter_reader(reader, " ", 2).unwrap().into_iter().map(|res| res.line).collect();
+  assert_eq!(lines, vec!["one", "two"]);
+
+  let reader = io::BufReader::with_capacity(4, Truncated { reader: b"one\ntwo\nthree\n", remaining: 8 });
+  assert_eq!(fuzzy_filter_reader(reader, "t", 2).unwrap_err().kind(), io::ErrorKind::Other);
+}
+
+#[test]
+fn reader_rejects_invalid_utf8() {
+  let err = fuzzy_filter_reader(&b"valid\n\xff\xfe\n"[..], "v", 5).unwrap_err();
+  assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
@@ -0,0 +1,134 @@
+// Differential tests against the reference fuzzbunny JS library.
+//
+// These replay a fixture of search, target and score triples exported from the JS library with
+// `tests/reference/export.js`, and fail if [`scoring_v1`] (the original algorithm) matches
+// different targets than the JS library, or ranks any two targets of a search in the opposite
+// order. Differences in the scores themselves are reported but don't fail, as long as the
+// rankings agree.
+//
+// The fixture is read from the path in `FUZZBUNNY_JS_FIXTURE`, or from
+// `tests/reference/fuzzbunny-js.tsv` by default, and the replay is skipped if it doesn't exist.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use fuzzbunny_rs::{precompute_skips_for_items, scoring_v1};
+
+/// The number of divergences listed when the replay fails.
+const MAX_REPORTED: usize = 20;
+
+/// A line of the fixture: the score of a target for a search, or [`None`] if it didn't match.
+struct Case {
+  search: String,
+  target: String,
+  score: Option<u32>,
+}
+
+/// Parse a fixture of tab separated `search`, `target` and `score` columns, where an empty score
+/// means the target didn't match. Blank lines and lines starting with `#` are ignored.
+fn parse_fixture(text: &str) -> Vec<Case> {
+  text
+    .lines()
+    .enumerate()
+    .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+    .map(|(i, line)| {
+      let columns: Vec<&str> = line.split('\t').collect();
+      let [search, target, score] = columns[..] else { panic!("line {}: expected 3 columns, found {}", i + 1, columns.len()) };
+      let score = (!score.is_empty()).then(|| score.parse().unwrap_or_else(|_| panic!("line {}: invalid score '{score}'", i + 1)));
+      Case { search: search.to_string(), target: target.to_string(), score }
+    })
+    .collect()
+}
+
+/// Replay the cases of a fixture.
+///
+/// # Returns
+///
+/// The divergences in matches and rankings, and the number of matches scored differently.
+fn replay(cases: &[Case]) -> (Vec<String>, usize) {
+  let mut searches: Vec<&str> = vec![];
+  let mut by_search: HashMap<&str, Vec<&Case>> = HashMap::new();
+  for case in cases {
+    by_search.entry(&case.search).or_insert_with(|| {
+      searches.push(&case.search);
+      vec![]
+    }).push(case);
+  }
+
+  let mut divergences = vec![];
+  let mut score_differences = 0;
+  for search in searches {
+    let cases = &by_search[search];
+    let targets = precompute_skips_for_items(cases.iter().map(|case| case.target.as_str()));
+    let scores: HashMap<&str, u32> = scoring_v1::fuzzy_filter(&targets, search).iter().map(|result| (result.item, result.score)).collect();
+
+    let mut matched = vec![];
+    for case in cases {
+      let score = scores.get(case.target.as_str()).copied();
+      match (case.score, score) {
+        (Some(expected), Some(score)) => {
+          score_differences += usize::from(expected != score);
+          matched.push((case, expected, score));
+        },
+        (None, None) => {},
+        (expected, score) => divergences.push(format!("{search:?} on {:?}: JS score {expected:?}, but scored {score:?}", case.target)),
+      }
+    }
+
+    for (i, &(a, expected_a, score_a)) in matched.iter().enumerate() {
+      for &(b, expected_b, score_b) in &matched[i + 1..] {
+        if expected_a.cmp(&expected_b) == score_b.cmp(&score_a) && expected_a != expected_b {
+          divergences.push(format!(
+            "{search:?} ranks {:?} ({score_a}) and {:?} ({score_b}) in the opposite order to JS ({expected_a} and {expected_b})",
+            a.target, b.target,
+          ));
+        }
+      }
+    }
+  }
+  (divergences, score_differences)
+}
+
+#[test]
+fn replay_detects_divergences() {
+  let fixture = "\
+# search\ttarget\tscore
+app\tapple\t3700
+app\tpineapple\t2700
+app\tbanana\t
+ban\tbanana\t1
+ban\tapple\t1
+";
+  let (divergences, score_differences) = replay(&parse_fixture(fixture));
+  assert_eq!(divergences, vec![
+    "\"ban\" on \"apple\": JS score Some(1), but scored None".to_string(),
+  ]);
+  assert_eq!(score_differences, 1);
+
+  // Swapped scores are a ranking divergence
+  let (divergences, _) = replay(&parse_fixture("app\tapple\t2700\napp\tpineapple\t3700\n"));
+  assert_eq!(divergences.len(), 1);
+}
+
+#[test]
+fn matches_js_reference() {
+  let path = std::env::var_os("FUZZBUNNY_JS_FIXTURE")
+    .map_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/reference/fuzzbunny-js.tsv"), PathBuf::from);
+  let Ok(fixture) = std::fs::read_to_string(&path) else {
+    eprintln!("skipping the JS reference replay, as {} doesn't exist", path.display());
+    return
+  };
+
+  let cases = parse_fixture(&fixture);
+  let (divergences, score_differences) = replay(&cases);
+  if score_differences > 0 {
+    eprintln!("{score_differences} of the {} cases are scored differently to JS, in the same order", cases.len());
+  }
+  assert!(
+    divergences.is_empty(),
+    "{} divergences from the JS reference in {}:\n{}",
+    divergences.len(),
+    path.display(),
+    divergences.iter().take(MAX_REPORTED).cloned().collect::<Vec<_>>().join("\n"),
+  );
+}
@@ -0,0 +1,40 @@
+// Export the scores of the reference fuzzbunny JS library as a fixture for `tests/reference.rs`.
+//
+// Every search is matched against every target, writing tab separated search, target and score
+// columns (with an empty score for targets that don't match):
+//
+//   npm install fuzzbunny
+//   node tests/reference/export.js tests/gutenberg-catalog.txt > tests/reference/fuzzbunny-js.tsv
+//
+// Targets are read one per line from the given file, and searches from a second file if given,
+// or the built-in list otherwise. Lines containing tabs can't be represented, so are skipped.
+
+const fs = require('fs');
+const {fuzzyMatch} = require('fuzzbunny');
+const {version} = require('fuzzbunny/package.json');
+
+const DEFAULT_SEARCHES = [
+  'a', 'the', 'adv', 'alice', 'sherlock', 'holmes', 'tale two', 'pride', 'war peace', 'moby',
+  'frank', 'dr', 'jekyll', 'tom', 'twain', 'dickens', 'ss', 'grimm', 'odyssey', 'ulysses',
+  'hist', 'life', 'complete works', 'vol 1', 'cw', '"the adv', 'sonnets', 'poems', 'ch', 'xyz',
+];
+
+const readLines = (path) =>
+  fs.readFileSync(path, 'utf8').split('\n').filter((line) => line.trim() !== '' && !line.includes('\t'));
+
+const [targetsPath, searchesPath] = process.argv.slice(2);
+if (!targetsPath) {
+  console.error('usage: node tests/reference/export.js <TARGETS> [SEARCHES]');
+  process.exit(2);
+}
+const targets = readLines(targetsPath);
+const searches = searchesPath ? readLines(searchesPath) : DEFAULT_SEARCHES;
+
+const out = [`# exported from fuzzbunny ${version}`];
+for (const search of searches) {
+  for (const target of targets) {
+    const match = fuzzyMatch(target, search);
+    out.push(`${search}\t${target}\t${match ? match.score : ''}`);
+  }
+}
+fs.writeFileSync(process.stdout.fd, out.join('\n') + '\n');
@@ -0,0 +1,37 @@
+#![cfg(feature = "std")]
+
+use fuzzbunny_rs::{fuzzy_filter, precompute_skips_for_items, ResumableSearch};
+use std::task::Poll;
+
+#[test]
+fn resumable_matches_fuzzy_filter() {
+  let items: Vec<String> = (0..1000).map(|i| format!("item number {}", i)).collect();
+  let targets = precompute_skips_for_items(items.iter().map(|s| s.as_str()));
+  let mut search = ResumableSearch::new(&targets, "number 12", 64);
+
+  let mut polls = 1;
+  let results = loop {
+    match search.poll() {
+      Poll::Ready(results) => break results,
+      Poll::Pending => polls += 1,
+    }
+  };
+
+  let expected = fuzzy_filter(&targets, "number 12");
+  assert_eq!(polls, 16);
+  assert_eq!(results, expected);
+  assert!(results.iter().all(|res| targets[res.index.unwrap()].0 == res.item));
+}
+
+#[test]
+fn resumable_reports_progress() {
+  let targets = precompute_skips_for_items(vec!["apple", "banana", "application", "pear"]);
+  let mut search = ResumableSearch::new(&targets, "app", 2);
+
+  assert_eq!(search.progress(), 0.0);
+  assert!(search.poll().is_pending());
+  assert_eq!(search.progress(), 0.5);
+  assert_eq!(search.partial_results().len(), 1);
+  assert!(search.poll().is_ready());
+  assert_eq!(search.progress(), 1.0);
+}
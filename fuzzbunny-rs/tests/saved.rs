@@ -0,0 +1,60 @@
+#![cfg(feature = "serde")]
+
+use fuzzbunny_rs::{fuzzy_filter, precompute_skips_for_items, SavedTarget, SavedTargets};
+
+#[test]
+fn saved_targets_round_trip() {
+  let mut targets = precompute_skips_for_items(vec!["Claire Bennet", "Elle Bishop", "Café Müller"]);
+  targets.push(("Noah Bennet", None));
+
+  let saved = SavedTargets::new(&targets);
+  assert_eq!(saved.targets(), targets);
+  assert_eq!(saved.targets[3].skips, None);
+  assert_eq!(fuzzy_filter(&saved.targets(), "cb"), fuzzy_filter(&targets, "cb"));
+}
+
+#[test]
+fn saved_targets_recompute_stale_skips() {
+  let skips = Some(vec![0, 5, 12]);
+  let target = |skips| SavedTarget { text: "Maya Herrera".to_string(), skips };
+  let saved = SavedTargets {
+    format: 1,
+    scoring_version: 2,
+    targets: vec![target(skips.clone()), target(Some(vec![5, 0, 12])), target(Some(vec![0, 40])), target(None)],
+  };
+  assert_eq!(saved.targets(), vec![("Maya Herrera", skips.clone()), ("Maya Herrera", skips.clone()), ("Maya Herrera", skips.clone()), ("Maya Herrera", None)]);
+
+  let stale = SavedTargets { scoring_version: 1, targets: vec![target(Some(vec![0, 12]))], ..saved };
+  assert_eq!(stale.targets(), vec![("Maya Herrera", skips)]);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn results_serialize_as_json() {
+  use fuzzbunny_rs::{fuzzy_score_item, FuzzyFilterResult, StringScore};
+
+  let targets = precompute_skips_for_items(vec!["apple"]);
+  let results = fuzzy_filter(&targets, "app");
+  let json = serde_json::to_string(&results).unwrap();
+  assert_eq!(json, r#"[{"item":"apple","score":3700,"highlights":["","app","le"],"ranges":[[0,3]],"index":0}]"#);
+  let parsed: Vec<FuzzyFilterResult> = serde_json::from_str(&json).unwrap();
+  assert_eq!(parsed, results);
+  assert_eq!(parsed[0].highlights, results[0].highlights);
+  assert_eq!(parsed[0].ranges, results[0].ranges);
+  assert_eq!(parsed[0].index, Some(0));
+  // Results serialized before ranges were added still deserialize
+  let parsed: FuzzyFilterResult = serde_json::from_str(r#"{"item":"apple","score":3700,"highlights":null}"#).unwrap();
+  assert_eq!(parsed.ranges, None);
+  assert_eq!(parsed.index, None);
+
+  let score = fuzzy_score_item(&targets[0], "ple").unwrap();
+  let json = serde_json::to_string(&score).unwrap();
+  assert_eq!(json, r#"{"score":2700,"ranges":[[2,3]]}"#);
+  let parsed: StringScore = serde_json::from_str(&json).unwrap();
+  assert_eq!((parsed.score, parsed.ranges[0].0, parsed.ranges[0].1), (2700, 2, 3));
+
+  let saved = SavedTargets::new(&precompute_skips_for_items(vec!["Maya Herrera"]));
+  let json = serde_json::to_string(&saved).unwrap();
+  assert_eq!(json, r#"{"format":1,"scoring_version":2,"targets":[{"text":"Maya Herrera","skips":[0,5,12]}]}"#);
+  assert_eq!(serde_json::from_str::<SavedTargets>(&json).unwrap(), saved);
+}
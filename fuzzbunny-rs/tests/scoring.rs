@@ -0,0 +1,41 @@
+use fuzzbunny_rs::{fuzzy_filter, fuzzy_filter_with_options, precompute_skips_for_items, scoring_v1, MatchOptions, ScoringVersion};
+
+// A snapshot of version 1 rankings, which must never change
+#[test]
+fn scoring_v1_is_frozen() {
+  let targets = precompute_skips_for_items(vec!["apple", "pineapple", "Application Settings", "Las Vegas", "Los Angeles", "banana", "Earth"]);
+  let expected = [
+    ("app", "Application Settings", 3700, vec!["", "App", "lication Settings"]),
+    ("app", "apple", 3700, vec!["", "app", "le"]),
+    ("app", "pineapple", 2700, vec!["pine", "app", "le"]),
+    ("a s", "Application Settings", 1788, vec!["", "A", "pplication ", "S", "ettings"]),
+    ("l a", "Las Vegas", 2200, vec!["", "La", "s Vegas"]),
+    ("l a", "Los Angeles", 1796, vec!["", "L", "os ", "A", "ngeles"]),
+    ("\"la", "Las Vegas", 2200, vec!["", "La", "s Vegas"]),
+    ("nana", "banana", 4800, vec!["ba", "nana"]),
+    ("ar", "Earth", 1200, vec!["E", "ar", "th"]),
+    ("lv", "Las Vegas", 1796, vec!["", "L", "as ", "V", "egas"]),
+  ];
+
+  let searches = ["app", "a s", "l a", "\"la", "nana", "ar", "lv"];
+  let actual: Vec<(&str, &str, u32, Vec<&str>)> = searches
+    .iter()
+    .flat_map(|&search| scoring_v1::fuzzy_filter(&targets, search).into_iter().map(move |res| (search, res.item, res.score, res.highlights.unwrap())))
+    .collect();
+  assert_eq!(actual, expected);
+}
+
+#[test]
+fn scoring_versions_are_selectable() {
+  let targets = precompute_skips_for_items(vec!["apple", "pineapple", "Las Vegas", "Los Angeles"]);
+  assert_eq!(ScoringVersion::default(), ScoringVersion::LATEST);
+  assert_eq!(ScoringVersion::V1.number(), 1);
+
+  let pinned = MatchOptions::default().with_scoring(ScoringVersion::V1);
+  for search in ["app", "l a", "\"la", ""] {
+    assert_eq!(fuzzy_filter_with_options(&targets, search, &pinned), scoring_v1::fuzzy_filter(&targets, search));
+    // The latest version is currently the same as version 1
+    assert_eq!(fuzzy_filter(&targets, search), scoring_v1::fuzzy_filter(&targets, search));
+  }
+  assert_eq!(scoring_v1::score(&targets[0], "app").unwrap().score, 3700);
+}
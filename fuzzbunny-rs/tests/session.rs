@@ -0,0 +1,39 @@
+#![cfg(feature = "std")]
+
+use fuzzbunny_rs::{fuzzy_filter, precompute_skips_for_items, SearchSession};
+
+#[test]
+fn session_matches_fuzzy_filter() {
+  let file = std::fs::read_to_string("tests/gutenberg-catalog.txt").unwrap();
+  let targets = precompute_skips_for_items(file.lines().skip(1));
+  let mut session = SearchSession::new(&targets);
+
+  // Typing, deleting and retyping a search, including quoted and multi-word searches
+  let searches = [
+    "", "a", "al", "ali", "alic", "alice", "alice ", "alice w", "alice wo", "alic", "Alice", "\"al", "\"alice\"",
+    "o", "ol", "oliver twist", "zz", "zzz", "",
+  ];
+  for search in searches {
+    assert_eq!(session.search(search), fuzzy_filter(&targets, search), "search {search:?}");
+  }
+}
+
+#[test]
+fn session_narrows_previous_matches() {
+  let targets = precompute_skips_for_items(vec!["Claire Bennet", "Elle Bishop", "Maya Herrera", "Mohinder Suresh"]);
+  let mut session = SearchSession::new(&targets);
+  assert_eq!(session.query(), None);
+
+  assert_eq!(session.search("  E ").len(), 4);
+  assert_eq!(session.query(), Some("e"));
+  let items: Vec<_> = session.search("eb").iter().map(|res| res.item).collect();
+  assert_eq!(items, vec!["Elle Bishop"]);
+
+  // Searches that don't extend the previous one score every target again
+  let items: Vec<_> = session.search("mh").iter().map(|res| res.item).collect();
+  assert_eq!(items, vec!["Maya Herrera"]);
+
+  session.reset();
+  assert_eq!(session.query(), None);
+  assert_eq!(session.search("ebz"), vec![]);
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "std")]
+
+use fuzzbunny_rs::{Corpus, ShardedCorpus};
+
+#[test]
+fn sharded_search_merges_by_score() {
+  let quests: Corpus = ["Slay the dragon", "Find the lost sword"].into_iter().collect();
+  let settings: Corpus = ["Dragon graphics", "Sound volume"].into_iter().collect();
+  let combined: Corpus = ["Slay the dragon", "Find the lost sword", "Dragon graphics", "Sound volume"].into_iter().collect();
+  let sharded: ShardedCorpus = [("quests", quests), ("settings", settings)].into_iter().collect();
+
+  assert_eq!(sharded.len(), 4);
+  for search in ["drag", "s", "o v", "zzz"] {
+    let sharded_results: Vec<_> = sharded.search(search).into_iter().map(|res| res.matched.result).collect();
+    let combined_results: Vec<_> = combined.search(search).into_iter().map(|res| res.result).collect();
+    assert_eq!(sharded_results, combined_results);
+  }
+
+  let results = sharded.search("drag");
+  assert_eq!(results[0].shard, "settings");
+  assert_eq!(results[1].shard, "quests");
+}
+
+#[test]
+fn sharded_reload_replaces_single_shard() {
+  let mut sharded = ShardedCorpus::new();
+  sharded.set_shard("a", Corpus::from_iter(["apple"]));
+  sharded.set_shard("b", Corpus::from_iter(["banana"]));
+
+  let old = sharded.set_shard("a", Corpus::from_iter(["apricot", "avocado"]));
+  assert_eq!(old.map(|corpus| corpus.len()), Some(1));
+  assert_eq!(sharded.shard_names().collect::<Vec<_>>(), vec!["a", "b"]);
+  assert!(sharded.search("apple").is_empty());
+
+  sharded.shard_mut("b").unwrap().insert("blueberry");
+  let items: Vec<_> = sharded.search("").into_iter().map(|res| res.matched.result.item).collect();
+  assert_eq!(items, vec!["apricot", "avocado", "banana", "blueberry"]);
+
+  assert!(sharded.remove_shard("a").is_some());
+  assert!(sharded.shard("a").is_none());
+  assert_eq!(sharded.len(), 2);
+}
@@ -0,0 +1,28 @@
+#![cfg(feature = "std")]
+
+use fuzzbunny_rs::similarity;
+
+#[test]
+fn similarity_is_normalized() {
+  assert_eq!(similarity("Las Vegas", "las vegas"), 1.0);
+  assert_eq!(similarity("  Las Vegas\t", "LAS VEGAS"), 1.0);
+  assert_eq!(similarity("Las Vegas", "Los Angeles"), 0.0);
+  assert_eq!(similarity("", ""), 1.0);
+  assert_eq!(similarity("", "Las Vegas"), 0.0);
+
+  let pairs = [("Vegas", "Las Vegas"), ("l v", "Las Vegas"), ("Las Vegas Strip", "Las Vegas"), ("LV", "Las Vegas")];
+  for (a, b) in pairs {
+    let score = similarity(a, b);
+    assert!(score > 0.0 && score < 1.0, "{a} {b}: {score}");
+    assert_eq!(score, similarity(b, a));
+  }
+}
+
+#[test]
+fn similarity_prefers_closer_strings() {
+  let target = "Acme Corporation Ltd";
+  let candidates = ["acme corporation ltd.", "Acme Corporation", "Acme Corp", "Acme", "A C L"];
+
+  let scores: Vec<f64> = candidates.iter().map(|candidate| similarity(candidate, target)).collect();
+  assert!(scores.windows(2).all(|pair| pair[0] > pair[1]), "{scores:?}");
+}
@@ -0,0 +1,66 @@
+use fuzzbunny_rs::{
+  fuzzy_filter, fuzzy_filter_with_options, get_target_skips, precompute_skips_for_items, MatchMode, MatchOptions, SkipRules,
+};
+
+#[test]
+fn default_rules_match_get_target_skips() {
+  let rules = SkipRules::default();
+  assert_eq!(rules, SkipRules::DEFAULT);
+  for target in include_str!("gutenberg-catalog.txt").lines().chain(["openFileInNewWindow", "snake_case_name", "Café Müller", "東京 タワー"]) {
+    assert_eq!(rules.skips(target), get_target_skips(target));
+  }
+}
+
+#[test]
+fn custom_boundaries() {
+  let rules = SkipRules::new().with_boundaries(['_']);
+  assert_eq!(rules.skips("snake_case-name"), vec![0, 5, 6, 15]);
+
+  // Whitespace still separates words
+  assert_eq!(rules.skips("a-b c"), vec![0, 4, 5]);
+
+  // Without camelCase splitting, only the first hump of a word starts it
+  let rules = SkipRules::new().with_camel_case(false);
+  assert_eq!(rules.skips("openFileInNewWindow"), vec![0, 19]);
+  assert_eq!(get_target_skips("openFileInNewWindow"), vec![0, 4, 8, 10, 13, 19]);
+}
+
+#[test]
+fn options_use_skip_rules_on_the_fly() {
+  let items = vec!["src/my-file.rs", "src/main.rs", "docs/file.md"];
+  let rules = SkipRules::new().with_boundaries(['/', '.']);
+  let options = MatchOptions::default().with_skip_rules(rules.clone());
+  let unprepared: Vec<(&str, Option<Vec<usize>>)> = items.iter().map(|&item| (item, None)).collect();
+
+  for search in ["sf", "df", "mf", "main", ""] {
+    let expected = fuzzy_filter(&rules.precompute_skips_for_items(items.clone()), search);
+    assert_eq!(fuzzy_filter_with_options(&unprepared, search, &options), expected);
+  }
+  let results = fuzzy_filter_with_options(&unprepared, "sf", &options);
+  assert!(results.is_empty());
+  assert_eq!(fuzzy_filter(&precompute_skips_for_items(items.clone()), "sf")[0].item, "src/my-file.rs");
+
+  // Initials only match the words of the rules
+  let initials = options.with_mode(MatchMode::Initials);
+  let results: Vec<&str> = fuzzy_filter_with_options(&unprepared, "sm", &initials).iter().map(|res| res.item).collect();
+  assert_eq!(results, vec!["src/main.rs", "src/my-file.rs"]);
+  assert!(fuzzy_filter_with_options(&unprepared, "smf", &initials).is_empty());
+}
+
+#[test]
+fn skip_rules_apply_to_targets_that_change_length_when_lower_cased() {
+  let rules = SkipRules::new().with_boundaries(['/', '.']);
+  let options = MatchOptions::default().with_skip_rules(rules.clone());
+
+  for target in ["x/my-file.rs", "İ/my-file.rs"] {
+    let prepared = rules.precompute_skips_for_items(vec![target]);
+    assert!(fuzzy_filter(&prepared, "mf").is_empty());
+    assert!(fuzzy_filter_with_options(&[(target, None)], "if", &options).is_empty());
+    assert!(fuzzy_filter_with_options(&[(target, None)], "if", &options.clone().with_mode(MatchMode::Initials)).is_empty());
+
+    // The words of the rules still match
+    assert_eq!(fuzzy_filter(&prepared, "mr")[0].item, target);
+  }
+  let results = fuzzy_filter(&rules.precompute_skips_for_items(vec!["İ/my-file.rs"]), "i/m");
+  assert_eq!(results[0].highlights, Some(vec!["", "İ/m", "y-file.rs"]));
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "std")]
+
+use fuzzbunny_rs::{Corpus, Dictionaries, SharedCorpus};
+use std::sync::Arc;
+
+#[test]
+fn snapshot_is_consistent_during_inserts() {
+  let shared = SharedCorpus::new((0..1000).map(|i| format!("item {}", i)).collect());
+  let snapshot = shared.snapshot();
+  let version = snapshot.version();
+
+  std::thread::scope(|scope| {
+    scope.spawn(|| {
+      for i in 0..1000 {
+        shared.insert(format!("new item {}", i));
+      }
+    });
+
+    for _ in 0..10 {
+      assert_eq!(snapshot.search("item").len(), 1000);
+      assert_eq!(snapshot.version(), version);
+    }
+  });
+
+  assert_eq!(shared.snapshot().len(), 2000);
+  assert!(shared.version() > version);
+}
+
+#[test]
+fn snapshot_reclaimed_when_dropped() {
+  let shared = SharedCorpus::new(Corpus::from_iter(["apple"]));
+  let snapshot = shared.snapshot();
+  let weak = Arc::downgrade(&snapshot);
+
+  shared.insert("banana");
+  assert!(weak.upgrade().is_some());
+
+  drop(snapshot);
+  assert!(weak.upgrade().is_none());
+}
+
+#[test]
+fn snapshot_keeps_dictionaries_during_swap() {
+  let shared = SharedCorpus::new(Corpus::from_iter(["Television", "Radio"]));
+  let mut dictionaries = Dictionaries::new();
+  dictionaries.add_synonyms(["tv", "television"]);
+  assert_eq!(shared.set_dictionaries(dictionaries), 1);
+
+  let snapshot = shared.snapshot();
+  assert_eq!(shared.set_dictionaries(Dictionaries::new()), 2);
+
+  // The in-flight query keeps the generation it started with
+  assert_eq!(snapshot.dictionary_generation(), 1);
+  assert_eq!(snapshot.search("tv").len(), 1);
+  assert_eq!(shared.dictionary_generation(), 2);
+  assert!(shared.snapshot().search("tv").is_empty());
+}
+
+#[test]
+fn snapshot_panics_after_a_mutation_panicked() {
+  let shared = SharedCorpus::new(Corpus::from_iter(["apple", "banana"]));
+  let mutation = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+    shared.write(|corpus| {
+      corpus.insert("application");
+      panic!("failed part-way through the mutation");
+    })
+  }));
+  assert!(mutation.is_err());
+
+  // The corpus may be half-mutated, so it isn't queried
+  assert!(std::panic::catch_unwind(|| shared.snapshot()).is_err());
+}
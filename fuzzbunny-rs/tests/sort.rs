@@ -0,0 +1,46 @@
+use std::cmp::Ordering;
+
+use fuzzbunny_rs::{fuzzy_filter, fuzzy_filter_sorted, precompute_skips_for_items, SortPolicy};
+
+fn items<'a>(targets: &[(&'a str, Option<Vec<usize>>)], search: &str, policy: &SortPolicy) -> Vec<&'a str> {
+  fuzzy_filter_sorted(targets, search, policy).iter().map(|res| res.item).collect()
+}
+
+#[test]
+fn score_policy_matches_fuzzy_filter() {
+  let targets = precompute_skips_for_items(include_str!("gutenberg-catalog.txt").lines());
+  for search in ["the", "p p", "\"of the", "", "zzz"] {
+    assert_eq!(fuzzy_filter_sorted(&targets, search, &SortPolicy::default()), fuzzy_filter(&targets, search));
+  }
+}
+
+#[test]
+fn ties_are_ordered_by_policy() {
+  let targets = precompute_skips_for_items(vec!["Zeta x", "beta  x", "alfa x", "Zeta x", "beta x"]);
+
+  assert_eq!(items(&targets, "x", &SortPolicy::OriginalOrder), vec!["Zeta x", "alfa x", "Zeta x", "beta x", "beta  x"]);
+  assert_eq!(items(&targets, "x", &SortPolicy::Alphabetical), vec!["alfa x", "beta x", "Zeta x", "Zeta x", "beta  x"]);
+  assert_eq!(items(&targets, "x", &SortPolicy::ShortestFirst), vec!["Zeta x", "alfa x", "Zeta x", "beta x", "beta  x"]);
+
+  // A custom comparator only orders ties, and still falls back to the order of the items
+  let by_length = SortPolicy::custom(|a, b| a.item.len().cmp(&b.item.len()).then(Ordering::Equal));
+  assert_eq!(items(&targets, "be", &by_length), vec!["beta x", "beta  x"]);
+  assert_eq!(items(&targets, "zeta", &by_length), vec!["Zeta x", "Zeta x"]);
+  let reversed = SortPolicy::custom(|a, b| b.item.cmp(a.item));
+  assert_eq!(items(&targets, "x", &reversed), vec!["beta x", "alfa x", "Zeta x", "Zeta x", "beta  x"]);
+  assert_eq!(format!("{:?}", reversed), "Custom(..)");
+}
+
+#[test]
+fn results_carry_original_index() {
+  let targets = precompute_skips_for_items(vec!["Zeta x", "beta  x", "alfa x", "Zeta x", "beta x"]);
+  for policy in [SortPolicy::Score, SortPolicy::OriginalOrder, SortPolicy::Alphabetical, SortPolicy::ShortestFirst] {
+    for result in fuzzy_filter_sorted(&targets, "x", &policy) {
+      assert_eq!(targets[result.index.unwrap()].0, result.item);
+    }
+  }
+
+  // Duplicate items are kept in their original order
+  let indices: Vec<usize> = fuzzy_filter(&targets, "zeta").iter().map(|res| res.index.unwrap()).collect();
+  assert_eq!(indices, vec![0, 3]);
+}
@@ -0,0 +1,37 @@
+#![cfg(feature = "std")]
+
+use fuzzbunny_rs::{fuzzy_filter, fuzzy_filter_to_channel, precompute_skips_for_items};
+use std::sync::mpsc::sync_channel;
+
+fn make_items() -> Vec<String> {
+  (0..5000).map(|i| format!("item number {}", i)).collect()
+}
+
+#[test]
+fn channel_receives_all_matches() {
+  let items = make_items();
+  let targets = precompute_skips_for_items(items.iter().map(|s| s.as_str()));
+  let (sender, receiver) = sync_channel(4);
+
+  let targets = &targets;
+  let mut streamed: Vec<&str> = std::thread::scope(|scope| {
+    scope.spawn(move || fuzzy_filter_to_channel(targets, "number 12", &sender).unwrap());
+    receiver.iter().map(|res| res.item).collect()
+  });
+
+  let mut expected: Vec<&str> = fuzzy_filter(targets, "number 12").into_iter().map(|res| res.item).collect();
+  streamed.sort();
+  expected.sort();
+  assert_eq!(streamed.len(), 111);
+  assert_eq!(streamed, expected);
+}
+
+#[test]
+fn channel_stops_when_receiver_dropped() {
+  let items = make_items();
+  let targets = precompute_skips_for_items(items.iter().map(|s| s.as_str()));
+  let (sender, receiver) = sync_channel(1);
+  drop(receiver);
+
+  assert!(fuzzy_filter_to_channel(&targets, "item", &sender).is_err());
+}
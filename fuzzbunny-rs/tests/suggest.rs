@@ -0,0 +1,41 @@
+use fuzzbunny_rs::suggest;
+
+const COMMANDS: [&str; 10] =
+  ["add", "branch", "checkout", "cherry-pick", "commit", "config", "remote", "reset", "status", "stash"];
+
+#[test]
+fn suggest_corrects_typos() {
+  assert_eq!(suggest("comit", COMMANDS, 5), vec!["commit"]);
+  assert_eq!(suggest("chekcout", COMMANDS, 5), vec!["checkout"]);
+  assert_eq!(suggest("brnach", COMMANDS, 5), vec!["branch"]);
+  assert_eq!(suggest("Stauts", COMMANDS, 5), vec!["status", "stash"]);
+  // Short queries don't tolerate typos
+  assert!(suggest("ad", ["dd", "xd", "da"], 5).is_empty());
+}
+
+#[test]
+fn suggest_completes_prefixes() {
+  // Equally good prefix matches rank the closest to the query first
+  assert_eq!(suggest("re", COMMANDS, 5), vec!["reset", "remote"]);
+  assert_eq!(suggest("sta", COMMANDS, 5), vec!["stash", "status"]);
+  assert_eq!(suggest("cherry p", COMMANDS, 5), vec!["cherry-pick"]);
+  // A misspelt prefix is still completed
+  assert_eq!(suggest("conf", COMMANDS, 1), vec!["config"]);
+  assert_eq!(suggest("cnofig", COMMANDS, 1), vec!["config"]);
+}
+
+#[test]
+fn suggest_ranks_and_limits() {
+  let suggestions = suggest("co", COMMANDS, 10);
+  assert_eq!(suggestions, vec!["commit", "config"]);
+  assert_eq!(suggest("co", COMMANDS, 1), vec!["commit"]);
+  assert_eq!(suggest("status", COMMANDS, 10)[0], "status");
+  assert_eq!(suggest("stash", ["stash", "stash", "stas"], 10), vec!["stash", "stas"]);
+  assert!(suggest("  ", COMMANDS, 5).is_empty());
+  assert!(suggest("xyzzy", COMMANDS, 5).is_empty());
+}
+
+#[test]
+fn suggest_matches_candidates_that_change_length_when_lower_cased() {
+  assert_eq!(suggest("is", ["İİ Sehir"], 5), vec!["İİ Sehir"]);
+}
@@ -0,0 +1,41 @@
+#![cfg(feature = "std")]
+
+use std::sync::{Arc, Mutex};
+use std::thread::{self, ThreadId};
+
+use fuzzbunny_rs::{clear_telemetry_hook, fuzzy_filter, precompute_skips_for_items, set_telemetry_hook, Corpus, QueryMetrics};
+
+// Hooks are process wide, so this is the only test in this binary
+#[test]
+fn telemetry_hook_receives_query_metrics() {
+  let received: Arc<Mutex<Vec<(ThreadId, QueryMetrics)>>> = Arc::default();
+  let sink = received.clone();
+  set_telemetry_hook(move |metrics: &QueryMetrics| sink.lock().unwrap().push((thread::current().id(), metrics.clone())));
+
+  let targets = precompute_skips_for_items(vec!["Earth", "Mars", "Jupiter", "Saturn"]);
+  let results = fuzzy_filter(&targets, "  AR ");
+  assert_eq!(results.len(), 2);
+
+  let mut corpus = Corpus::new();
+  corpus.extend(["Earth", "Mars"]);
+  corpus.search("mars");
+
+  clear_telemetry_hook();
+  fuzzy_filter(&targets, "ar");
+
+  let received = received.lock().unwrap();
+  let metrics: Vec<&QueryMetrics> = received.iter().filter(|(thread, _)| *thread == thread::current().id()).map(|(_, metrics)| metrics).collect();
+  assert_eq!(metrics.len(), 2);
+
+  let filter = metrics[0];
+  assert_eq!(filter.operation, "fuzzy_filter");
+  assert_eq!((filter.query_len, filter.corpus_size, filter.match_count), (2, 4, 2));
+  assert!(!filter.parallel);
+  let stages: Vec<(&str, usize, usize)> = filter.stages.iter().map(|stage| (stage.name, stage.input, stage.output)).collect();
+  assert_eq!(stages, vec![("score", 4, 2), ("sort", 2, 2)]);
+  assert!(filter.stages.iter().all(|stage| stage.elapsed <= filter.elapsed));
+
+  let search = metrics[1];
+  assert_eq!(search.operation, "corpus_search");
+  assert_eq!((search.query_len, search.corpus_size, search.match_count), (4, 2, 1));
+}
@@ -0,0 +1,30 @@
+use fuzzbunny_rs::{fuzzy_filter, fuzzy_filter_top_k, precompute_skips_for_items};
+
+#[test]
+fn top_k_matches_truncated_filter() {
+  let file = std::fs::read_to_string("tests/gutenberg-catalog.txt").unwrap();
+  let targets = precompute_skips_for_items(file.lines().skip(1));
+
+  for search in ["alice", "oliver twist", "a", "zzzz", "", "  "] {
+    for k in [0, 1, 20, 1000] {
+      let mut expected = fuzzy_filter(&targets, search);
+      expected.truncate(k);
+      assert_eq!(fuzzy_filter_top_k(&targets, search, k), expected, "search {search:?}, k {k}");
+    }
+  }
+}
+
+#[test]
+fn top_k_keeps_highlights() {
+  let targets = precompute_skips_for_items(vec!["", "pineapple", "apple", "banana", "application", "apple"]);
+
+  let results = fuzzy_filter_top_k(&targets, "app", 3);
+  let items: Vec<_> = results.iter().map(|res| (res.item, res.score)).collect();
+  assert_eq!(items, vec![("apple", 3700), ("apple", 3700), ("application", 3700)]);
+  assert_eq!(results[2].highlights, Some(vec!["", "app", "lication"]));
+
+  // Empty targets never match
+  let items: Vec<_> = fuzzy_filter_top_k(&targets, "", 2).iter().map(|res| res.item).collect();
+  assert_eq!(items, vec!["pineapple", "apple"]);
+  assert_eq!(fuzzy_filter_top_k(&targets, "app", 10).len(), 4);
+}
@@ -0,0 +1,59 @@
+use fuzzbunny_rs::{fuzzy_match, trace_match, Alignment, MatchPath, MatchTrace};
+
+#[test]
+fn trace_agrees_with_fuzzy_match() {
+  let targets = ["The United States of America", "Las Vegas", "Los Angeles", "Apple Pie Banana", "", "Straße"];
+  let searches = ["usam", "\"la", "la", "ab", "apb", "l a", "", "sse", "xyz"];
+  for target in targets {
+    for search in searches {
+      let trace = trace_match(target, search);
+      assert_eq!(trace.score, fuzzy_match(target, Some(search)).map(|res| res.score), "{trace:?}");
+      assert_eq!(trace.path.is_some(), trace.score.is_some());
+      assert_eq!(trace.path == Some(MatchPath::Fuzzy), trace.matched_alignment().is_some());
+    }
+  }
+}
+
+#[test]
+fn trace_records_alignments() {
+  let trace = trace_match("Apple Pie Banana", "apx");
+  assert_eq!(
+    trace,
+    MatchTrace {
+      target: "Apple Pie Banana".to_string(),
+      search: "apx".to_string(),
+      quoted: false,
+      substring: None,
+      alignments: vec![Alignment { skip: 0, start: 0, matched: 2, broke_at: Some(2) }],
+      path: None,
+      score: None,
+    }
+  );
+
+  // The first alignment runs out of words, so matching is retried from a later word
+  let trace = trace_match("ant bee apple ice", "api");
+  assert_eq!(trace.substring, None);
+  assert_eq!(
+    trace.alignments,
+    vec![
+      Alignment { skip: 0, start: 0, matched: 1, broke_at: Some(1) },
+      Alignment { skip: 2, start: 8, matched: 3, broke_at: Some(10) },
+    ]
+  );
+  assert_eq!(trace.matched_alignment(), trace.alignments.last());
+  assert_eq!(trace.path, Some(MatchPath::Fuzzy));
+}
+
+#[test]
+fn trace_records_substring_path() {
+  let trace = trace_match("Las Vegas", "  VEG ");
+  assert_eq!((trace.search.as_str(), trace.quoted), ("veg", false));
+  assert_eq!(trace.substring, Some(4));
+  assert!(trace.alignments.is_empty());
+  assert_eq!(trace.path, Some(MatchPath::Substring));
+
+  // Quoted searches never fall back to fuzzy matching
+  let trace = trace_match("Los Angeles", "\"la");
+  assert!(trace.quoted);
+  assert_eq!((trace.substring, trace.alignments.len(), trace.path), (None, 0, None));
+}
@@ -0,0 +1,108 @@
+use fuzzbunny_rs::{char_width, display_width, highlights_with_context, truncate_highlights, Range};
+
+fn widths(sections: &[impl AsRef<str>]) -> usize {
+  sections.iter().map(|section| display_width(section.as_ref())).sum()
+}
+
+#[test]
+fn display_width_counts_cells() {
+  assert_eq!(char_width('a'), 1);
+  assert_eq!(char_width('東'), 2);
+  assert_eq!(char_width('한'), 2);
+  assert_eq!(char_width('Ａ'), 2);
+  assert_eq!(char_width('\u{301}'), 0);
+  assert_eq!(char_width('\u{200d}'), 0);
+  assert_eq!(display_width(""), 0);
+  assert_eq!(display_width("日本語 text"), 11);
+}
+
+#[test]
+fn truncate_keeps_matches_visible() {
+  let highlights = ["a long prefix before the ", "match", " and a long suffix after it"];
+  assert_eq!(truncate_highlights(&highlights, 100), highlights);
+
+  let truncated = truncate_highlights(&highlights, 15);
+  assert_eq!(truncated, vec!["…the ", "match", " and…"]);
+  assert_eq!(widths(&truncated), 15);
+
+  // Context before the match is kept in preference to context after it
+  let truncated = truncate_highlights(&highlights, 8);
+  assert_eq!(truncated, vec!["… ", "match", "…"]);
+
+  // A match at the end keeps all the context before it
+  let truncated = truncate_highlights(&["some text then ", "match"], 10);
+  assert_eq!(truncated, vec!["…hen ", "match"]);
+
+  // A match at the start only needs an ellipsis at the end
+  let truncated = truncate_highlights(&["", "match", " at the start of the text"], 10);
+  assert_eq!(truncated, vec!["", "match", " at …"]);
+}
+
+#[test]
+fn truncate_cuts_long_matches() {
+  let highlights = ["prefix ", "first", " middle ", "second", " suffix"];
+  // The matches fit together
+  assert_eq!(truncate_highlights(&highlights, 21), vec!["…", "first", " middle ", "second", "…"]);
+  // The matches don't fit together, so the text from the first is kept
+  assert_eq!(truncate_highlights(&highlights, 12), vec!["…", "first", " midd…"]);
+  assert_eq!(truncate_highlights(&highlights, 5), vec!["…", "fir", "…"]);
+  assert_eq!(truncate_highlights(&highlights, 2), vec!["…"]);
+  assert!(truncate_highlights(&highlights, 0).is_empty());
+}
+
+#[test]
+fn truncate_uses_display_width() {
+  let highlights = ["東京都", "渋谷", "区道玄坂"];
+  let truncated = truncate_highlights(&highlights, 9);
+  assert_eq!(truncated, vec!["…都", "渋谷", "…"]);
+  assert!(widths(&truncated) <= 9);
+
+  // Wide characters are never split, even if a cell is left over
+  let truncated = truncate_highlights(&highlights, 7);
+  assert_eq!(truncated, vec!["…", "渋谷", "…"]);
+}
+
+#[test]
+fn context_windows_surround_each_match() {
+  let target = "the quick brown fox jumps over the lazy dog";
+  // Short targets are returned whole
+  assert_eq!(highlights_with_context(target, &[Range(4, 5)], 100, 2), vec!["the ", "quick", " brown fox jumps over the lazy dog"]);
+
+  assert_eq!(highlights_with_context(target, &[Range(16, 3)], 20, 3), vec!["…wn ", "fox", " ju…"]);
+  // Separate windows are joined by an ellipsis
+  assert_eq!(highlights_with_context(target, &[Range(4, 5), Range(35, 4)], 30, 2), vec!["…e ", "quick", " b…e ", "lazy", " d…"]);
+  // Overlapping windows are merged
+  assert_eq!(highlights_with_context(target, &[Range(10, 5), Range(16, 3)], 30, 2), vec!["…k ", "brown", " ", "fox", " j…"]);
+  // Windows at either end of the target don't need an ellipsis there
+  assert_eq!(highlights_with_context(target, &[Range(0, 3), Range(40, 3)], 30, 2), vec!["", "the", " q…y ", "dog"]);
+}
+
+#[test]
+fn context_windows_fit_in_max_len() {
+  let target = "the quick brown fox jumps over the lazy dog";
+  let len = |sections: &[std::borrow::Cow<'_, str>]| sections.iter().map(|section| section.chars().count()).sum::<usize>();
+
+  // Later windows are cut off
+  let preview = highlights_with_context(target, &[Range(4, 5), Range(35, 4)], 12, 2);
+  assert_eq!(preview, vec!["…e ", "quick", " b…"]);
+  assert_eq!(len(&preview), 11);
+  let preview = highlights_with_context(target, &[Range(4, 5), Range(35, 4)], 17, 2);
+  assert_eq!(preview, vec!["…e ", "quick", " b…e ", "laz", "…"]);
+  assert_eq!(len(&preview), 17);
+
+  // Long matches are cut too
+  assert_eq!(highlights_with_context(target, &[Range(0, 43)], 6, 2), vec!["", "the q", "…"]);
+  assert_eq!(highlights_with_context(target, &[Range(16, 3)], 2, 2), vec!["…"]);
+  assert!(highlights_with_context(target, &[Range(16, 3)], 0, 2).is_empty());
+}
+
+#[test]
+fn context_windows_handle_unsorted_ranges_and_multibyte_text() {
+  let target = "日本語のテキストの中の検索語を含む長い文章";
+  // "検索" is at bytes 33..39
+  let preview = highlights_with_context(target, &[Range(36, 3), Range(33, 3)], 8, 2);
+  assert_eq!(preview, vec!["…中の", "検索", "語を…"]);
+
+  // Targets without matches show their start
+  assert_eq!(highlights_with_context(target, &[], 6, 2), vec!["日本語のテ…"]);
+}
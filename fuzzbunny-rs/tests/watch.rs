@@ -0,0 +1,68 @@
+#![cfg(feature = "notify")]
+
+use fuzzbunny_rs::WatchedCorpus;
+use std::time::Duration;
+
+// Replace the file atomically, like an editor saving it, so no half-written file is indexed
+fn replace_file(path: &std::path::Path, contents: &str) {
+  let tmp_path = path.with_extension("tmp");
+  std::fs::write(&tmp_path, contents).unwrap();
+  std::fs::rename(&tmp_path, path).unwrap();
+}
+
+fn items(watched: &WatchedCorpus) -> Vec<String> {
+  watched.snapshot().iter().map(|(_, text)| text.to_string()).collect()
+}
+
+#[test]
+fn watch_reindexes_changed_lines() {
+  let path = std::env::temp_dir().join(format!("fuzzbunny-watch-{}.txt", std::process::id()));
+  std::fs::write(&path, "apple\nbanana\ncherry\n").unwrap();
+
+  let (watched, changes) = WatchedCorpus::watch(&path).unwrap();
+  assert_eq!(items(&watched), vec!["apple", "banana", "cherry"]);
+  let ids: Vec<_> = watched.snapshot().iter().map(|(id, _)| id).collect();
+
+  replace_file(&path, "apple\nblueberry\n");
+  let change = changes.recv_timeout(Duration::from_secs(10)).unwrap();
+  assert_eq!(change.updated, vec![ids[1]]);
+  assert_eq!(change.removed, vec![ids[2]]);
+  assert_eq!(items(&watched), vec!["apple", "blueberry"]);
+
+  replace_file(&path, "apple\nblueberry\ndate\n");
+  let change = changes.recv_timeout(Duration::from_secs(10)).unwrap();
+  assert_eq!(change.inserted.len(), 1);
+  assert_eq!(watched.snapshot().search("date").len(), 1);
+
+  std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn watch_keeps_ids_when_a_line_is_inserted_at_the_top() {
+  let path = std::env::temp_dir().join(format!("fuzzbunny-watch-top-{}.txt", std::process::id()));
+  std::fs::write(&path, "apple\nbanana\napple\n").unwrap();
+
+  let (watched, changes) = WatchedCorpus::watch(&path).unwrap();
+  let items: Vec<_> = watched.snapshot().iter().map(|(id, text)| (id, text.to_string())).collect();
+
+  replace_file(&path, "cherry\napple\nbanana\napple\n");
+  let change = changes.recv_timeout(Duration::from_secs(10)).unwrap();
+  assert_eq!(change.inserted.len(), 1);
+  assert!(change.updated.is_empty());
+  assert!(change.removed.is_empty());
+
+  let snapshot = watched.snapshot();
+  for (id, text) in &items {
+    assert_eq!(snapshot.get(*id), Some(text.as_str()));
+  }
+  let cherry = change.inserted[0];
+  assert_eq!(snapshot.get(cherry), Some("cherry"));
+
+  // Removing the first line keeps the IDs of the rest too
+  replace_file(&path, "apple\nbanana\napple\n");
+  let change = changes.recv_timeout(Duration::from_secs(10)).unwrap();
+  assert_eq!(change.removed, vec![cherry]);
+  assert!(change.inserted.is_empty() && change.updated.is_empty());
+
+  std::fs::remove_file(&path).unwrap();
+}